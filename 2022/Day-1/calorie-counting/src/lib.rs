@@ -0,0 +1,107 @@
+use aoc_common::registry::aoc;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+/// The Calories of the food items one Elf carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf(pub Vec<u64>);
+
+impl Elf {
+    pub fn total(&self) -> u64 {
+        self.0.iter().sum()
+    }
+}
+
+/// Every Elf in the expedition, in the input's order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elves(pub Vec<Elf>);
+
+impl Elves {
+    /// The Calories carried by the Elf carrying the most.
+    pub fn most(&self) -> Result<u64> {
+        self.0
+            .iter()
+            .map(Elf::total)
+            .max()
+            .ok_or_else(|| eyre!("no Elves in the input"))
+    }
+
+    /// The Calories carried by the three Elves carrying the most, together.
+    pub fn top_three(&self) -> Result<u64> {
+        let mut totals: Vec<u64> = self.0.iter().map(Elf::total).collect();
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(totals.iter().take(3).sum())
+    }
+}
+
+#[aoc(year = 2022, day = 1, part = 1)]
+pub fn part1(lines: Vec<String>) -> Result<u64> {
+    parse_data(lines)?.most()
+}
+
+#[aoc(year = 2022, day = 1, part = 2)]
+pub fn part2(lines: Vec<String>) -> Result<u64> {
+    parse_data(lines)?.top_three()
+}
+
+/// One Elf per blank-line-separated group of lines, each line an item's Calories.
+pub fn parse_data(lines: Vec<String>) -> Result<Elves> {
+    let mut elves = Vec::new();
+    let mut items = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !items.is_empty() {
+                elves.push(Elf(std::mem::take(&mut items)));
+            }
+            continue;
+        }
+        let calories = line
+            .parse()
+            .wrap_err_with(|| format!("line {}: {:?} is not a number of Calories", i + 1, line))?;
+        items.push(calories);
+    }
+    if !items.is_empty() {
+        elves.push(Elf(items));
+    }
+    Ok(Elves(elves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn read(path: &str) -> Vec<String> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let elves = parse_data(read("../test-1.txt")).unwrap();
+        assert_eq!(elves.0.len(), 5);
+        assert_eq!(elves.0[0], Elf(vec![1000, 2000, 3000]));
+        assert_eq!(elves.0[3].total(), 24000);
+
+        let err = parse_data(vec![String::from("1000"), String::from("ten")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 2: \"ten\" is not a number of Calories"));
+    }
+
+    #[test_case("../test-1.txt", 24000, 45000; "example")]
+    fn test_parts(path: &str, most: u64, top_three: u64) {
+        assert_eq!(part1(read(path)).unwrap(), most);
+        assert_eq!(part2(read(path)).unwrap(), top_three);
+    }
+
+    #[test]
+    fn test_no_elves() {
+        let elves = parse_data(vec![String::new()]).unwrap();
+        assert!(elves.most().is_err());
+        assert_eq!(elves.top_three().unwrap(), 0);
+    }
+}
@@ -0,0 +1,7 @@
+aoc_common::aoc_main!(
+    year = 2022,
+    day = 1,
+    parse = calorie_counting::parse_data,
+    part1 = calorie_counting::Elves::most,
+    part2 = calorie_counting::Elves::top_three
+);
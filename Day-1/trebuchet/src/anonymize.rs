@@ -0,0 +1,96 @@
+use crate::{keep_digits, replace_strings, Digits};
+use aoc_common::shuffle::Shuffler;
+
+// letters no number word uses, so filler never spells or completes one
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The lines rewritten from scratch and shuffled: each keeps its calibration
+/// values under both parts' rules, and nothing else of the original.
+pub fn anonymize(lines: &[String], seed: u64) -> Vec<String> {
+    let mut rng = Shuffler::new(seed);
+    let mut lines: Vec<String> = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| disguise(line, &mut rng))
+        .collect();
+    rng.shuffle(&mut lines);
+    lines
+}
+
+// A fresh line with the same first and last digit as `line`, both with and
+// without the spelled-out ones; a spelled-out digit that differs from the
+// literal one next to it is first (or last) only because it's spelled out
+// before (or after) every literal digit, so that's where it goes
+fn disguise(line: &str, rng: &mut Shuffler) -> String {
+    let literal: Vec<char> = keep_digits(line, Digits::Ascii).chars().collect();
+    let spelled: Vec<char> = replace_strings(line).chars().collect();
+    let mut out = filler(rng);
+    let (Some(&first), Some(&last)) = (spelled.first(), spelled.last()) else {
+        return out;
+    };
+    let mut tokens = Vec::new();
+    match (literal.first(), literal.last()) {
+        (Some(&a), Some(&b)) => {
+            if first != a {
+                tokens.push(word(first));
+            }
+            tokens.push(a.to_string());
+            if literal.len() > 1 {
+                tokens.push(b.to_string());
+            }
+            if last != b {
+                tokens.push(word(last));
+            }
+        }
+        _ => {
+            tokens.push(word(first));
+            tokens.push(word(last));
+        }
+    }
+    for token in tokens {
+        out.push_str(&token);
+        out.push(FILLER[rng.below(FILLER.len())] as char);
+        out.push_str(&filler(rng));
+    }
+    out
+}
+
+fn word(digit: char) -> String {
+    let digit = digit.to_digit(10).unwrap_or(0) as usize;
+    String::from(WORDS[digit])
+}
+
+// up to three filler letters
+fn filler(rng: &mut Shuffler) -> String {
+    (0..rng.below(4))
+        .map(|_| FILLER[rng.below(FILLER.len())] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, read_input};
+    use test_case::test_case;
+
+    #[test_case("../test-1.txt"; "digits")]
+    #[test_case("../test-2.txt"; "words")]
+    fn test_anonymize(path: &str) {
+        let lines = read_input(path).unwrap();
+        let anonymized = anonymize(&lines, 42);
+        assert_eq!(anonymized.len(), lines.len());
+        assert!(anonymized.iter().all(|line| !lines.contains(line)));
+        // part 1 only reads lines that have a digit
+        let input = lines.join("\n");
+        let shared = anonymized.join("\n");
+        if let Ok(answer) = part1(&input) {
+            assert_eq!(part1(&shared).unwrap(), answer);
+        }
+        assert_eq!(part2(&shared).unwrap(), part2(&input).unwrap());
+        assert_eq!(anonymize(&lines, 42), anonymized);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{keep_digits, replace_strings, Digits};
+use aoc_common::explain::Narrative;
+
+/// Every line's digits and calibration value under each part's rules, then
+/// the totals.
+pub fn explain(lines: &[String]) -> Narrative {
+    let mut narrative = Narrative::new();
+    narrative.section("Calibration values");
+    let (mut part1, mut part2) = (0, 0);
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let literal = keep_digits(line, Digits::Ascii);
+        let spelled = replace_strings(line);
+        part1 += value(&literal).unwrap_or(0);
+        part2 += value(&spelled).unwrap_or(0);
+        narrative.line(format!(
+            "line {} {:?}: part 1 reads {}; part 2 reads {}",
+            i + 1,
+            line,
+            reading(&literal),
+            reading(&spelled)
+        ));
+    }
+    narrative
+        .section("Answers")
+        .line(format!("part 1: the values add up to {}", part1))
+        .line(format!("part 2: the values add up to {}", part2));
+    narrative
+}
+
+// The first digit and the last, or None for a line without any
+fn value(digits: &str) -> Option<usize> {
+    let first = digits.chars().next()?.to_digit(10)?;
+    let last = digits.chars().next_back()?.to_digit(10)?;
+    Some((first * 10 + last) as usize)
+}
+
+// e.g. "2 1 9 -> 29"
+fn reading(digits: &str) -> String {
+    match value(digits) {
+        Some(value) => {
+            let digits: Vec<String> = digits.chars().map(String::from).collect();
+            format!("{} -> {}", digits.join(" "), value)
+        }
+        None => String::from("no digit"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn test_explain() {
+        let lines = read_input("../test-2.txt").unwrap();
+        let narrative = explain(&lines);
+        let [values, answers] = narrative.sections() else {
+            panic!("expected two sections");
+        };
+        assert_eq!(values.lines.len(), 7);
+        assert_eq!(
+            values.lines[0],
+            "line 1 \"two1nine\": part 1 reads 1 -> 11; part 2 reads 2 1 9 -> 29"
+        );
+        assert_eq!(
+            values.lines[1],
+            "line 2 \"eightwothree\": part 1 reads no digit; part 2 reads 8 2 3 -> 83"
+        );
+        assert_eq!(answers.lines[0], "part 1: the values add up to 209");
+        assert_eq!(answers.lines[1], "part 2: the values add up to 281");
+    }
+}
@@ -0,0 +1,568 @@
+use aoc_common::input::read_lines;
+use aoc_common::parse::{parse_lines, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::path::Path;
+use tracing::warn;
+
+pub mod anonymize;
+pub mod explain;
+
+#[tracing::instrument]
+pub fn read_input(filename: &str) -> Result<Vec<String>> {
+    Ok(read_lines(filename)?)
+}
+
+// Zero of every run of Unicode decimal digits (category Nd, Unicode 15.0).
+// Each run holds the ten digits 0-9 in order.
+const DIGIT_ZEROS: &[u32] = &[
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+    0x1E950, 0x1FBF0,
+];
+
+// the puzzle's own number words, tried in this order
+const ENGLISH: &[(&str, u32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Which characters the scanner treats as digits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Digits {
+    /// Only `0`-`9`
+    #[default]
+    Ascii,
+    /// Any Unicode decimal digit, e.g. `٣` or `３`
+    Unicode,
+}
+
+impl Digits {
+    pub fn value(self, c: char) -> Option<u32> {
+        if c.is_ascii_digit() {
+            return c.to_digit(10);
+        }
+        if self == Digits::Ascii || !c.is_numeric() {
+            return None;
+        }
+
+        // is_numeric also covers letter-like numerals (Ⅻ, ½, ²), so look the
+        // character up in the decimal digit runs
+        let c = c as u32;
+        let zero = match DIGIT_ZEROS.binary_search(&c) {
+            Ok(i) => DIGIT_ZEROS[i],
+            Err(0) => return None,
+            Err(i) => DIGIT_ZEROS[i - 1],
+        };
+        (c - zero < 10).then_some(c - zero)
+    }
+}
+
+/// Whether spelled-out digits ("one" .. "nine") count.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Only literal digits, as in part 1
+    DigitsOnly,
+    /// Literal and spelled-out digits, as in part 2
+    #[default]
+    Words,
+}
+
+/// Number words and the digits they stand for, for [`Mode::Words`]: the
+/// puzzle's "one" to "nine" by default, plus any loaded from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    // longest first, so "sixth" is found rather than the "six" it starts with
+    words: Vec<(String, u32)>,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary::english()
+    }
+}
+
+impl Dictionary {
+    /// The puzzle's words, "one" to "nine".
+    pub fn english() -> Self {
+        let mut dictionary = Dictionary { words: Vec::new() };
+        dictionary.extend(
+            ENGLISH
+                .iter()
+                .map(|&(word, digit)| (word.to_string(), digit)),
+        );
+        dictionary
+    }
+
+    /// Words from `text`, a `word digit` pair per line, e.g. `deux 2`; blank
+    /// lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut words = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, digit) = line.split_once(char::is_whitespace).ok_or_else(|| {
+                eyre!("line {}: expected a word and a digit in {:?}", i + 1, line)
+            })?;
+            let digit = match digit.trim().parse::<u32>() {
+                Ok(digit) if digit <= 9 => digit,
+                _ => bail!("line {}: {:?} is not a digit 0-9", i + 1, digit.trim()),
+            };
+            words.push((word.to_string(), digit));
+        }
+        let mut dictionary = Dictionary { words: Vec::new() };
+        dictionary.extend(words);
+        Ok(dictionary)
+    }
+
+    /// [`Dictionary::parse`] on the contents of a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("cannot read {}", path.display()))?;
+        Dictionary::parse(&text).wrap_err_with(|| format!("invalid dictionary {}", path.display()))
+    }
+
+    /// Add `other`'s words, its digit winning for a word both have.
+    pub fn merge(&mut self, other: Dictionary) {
+        self.extend(other.words);
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn extend(&mut self, words: impl IntoIterator<Item = (String, u32)>) {
+        for (word, digit) in words {
+            match self.words.iter_mut().find(|(known, _)| *known == word) {
+                Some(known) => known.1 = digit,
+                None => self.words.push((word, digit)),
+            }
+        }
+        // stable, so words of one length keep the order they came in
+        self.words
+            .sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub mode: Mode,
+    pub digits: Digits,
+    pub parse: ParseMode,
+}
+
+#[tracing::instrument]
+pub fn parse_data(data: Vec<String>) -> Result<Vec<usize>> {
+    parse_data_with(data, Options::default())
+}
+
+#[tracing::instrument]
+pub fn parse_data_with(data: Vec<String>, options: Options) -> Result<Vec<usize>> {
+    parse_data_with_dictionary(data, options, &Dictionary::english())
+}
+
+/// [`parse_data_with`], spelled-out digits being the words of `dictionary`.
+#[tracing::instrument(skip(dictionary))]
+pub fn parse_data_with_dictionary(
+    data: Vec<String>,
+    options: Options,
+    dictionary: &Dictionary,
+) -> Result<Vec<usize>> {
+    let parsed = parse_lines(&data, options.parse, |line| {
+        let new_line = match options.mode {
+            Mode::DigitsOnly => keep_digits(line, options.digits),
+            Mode::Words => replace_words(line, options.digits, &dictionary.words),
+        };
+        let mut found = new_line.chars().filter(|c| c.is_ascii_digit());
+        let first_digit = found
+            .next()
+            .ok_or_else(|| format!("no digit in {:?}", line))?;
+        let last_digit = found.next_back().unwrap_or(first_digit);
+        // concatenate first and last digits as a usize
+        format!("{}{}", first_digit, last_digit)
+            .parse::<usize>()
+            .map_err(|_| String::from("bad calibration value"))
+    })?;
+
+    // blank lines (e.g. a trailing newline) carry no calibration value
+    if parsed.blank > 0 {
+        warn!("Skipped {} blank lines", parsed.blank);
+    }
+    if let Some(summary) = parsed.summary() {
+        warn!("{}", summary);
+    }
+
+    Ok(parsed.items)
+}
+
+// Sum of calibration values using literal digits only (part 1)
+pub fn part1(input: &str) -> Result<usize> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 1, part = 1)]
+pub fn part1_with(input: &str, parse: ParseMode) -> Result<usize> {
+    let options = Options {
+        mode: Mode::DigitsOnly,
+        parse,
+        ..Options::default()
+    };
+    let data = parse_data_with(input.lines().map(String::from).collect(), options)?;
+    Ok(data.iter().sum())
+}
+
+// Sum of calibration values with spelled-out digits counted (part 2)
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 1, part = 2)]
+pub fn part2_with(input: &str, parse: ParseMode) -> Result<usize> {
+    let options = Options {
+        parse,
+        ..Options::default()
+    };
+    let data = parse_data_with(input.lines().map(String::from).collect(), options)?;
+    Ok(data.iter().sum())
+}
+
+/// Day 1's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Trebuchet;
+
+impl Solver for Trebuchet {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+// The literal digits of a line, as ASCII
+#[tracing::instrument]
+pub fn keep_digits(line: &str, digits: Digits) -> String {
+    line.chars()
+        .filter_map(|c| digits.value(c))
+        .filter_map(|d| char::from_digit(d, 10))
+        .collect()
+}
+
+#[tracing::instrument]
+pub fn replace_strings(line: &str) -> String {
+    replace_strings_with(line, Digits::Ascii)
+}
+
+// Digits in the result are always ASCII, whatever script they were written in
+#[tracing::instrument]
+pub fn replace_strings_with(line: &str, digits: Digits) -> String {
+    replace_words(line, digits, ENGLISH)
+}
+
+/// [`replace_strings_with`] for the words of `dictionary` rather than the
+/// puzzle's, e.g. to pull the first and last number out of French text.
+pub fn replace_strings_in(line: &str, digits: Digits, dictionary: &Dictionary) -> String {
+    replace_words(line, digits, &dictionary.words)
+}
+
+// The first of `words` found wins, so a word longer than one it starts with
+// has to come before it
+fn replace_words(line: &str, digits: Digits, words: &[(impl AsRef<str>, u32)]) -> String {
+    let mut result = String::new();
+
+    let mut i = 0;
+
+    // i is a byte offset, so step over whole characters
+    while let Some(current) = line[i..].chars().next() {
+        // check if the current character is a digit - if so, add it to the result
+        if let Some(digit) = digits.value(current).and_then(|d| char::from_digit(d, 10)) {
+            result.push(digit);
+            i += current.len_utf8();
+            continue;
+        }
+
+        // check if the current character is the beginning of a string to be replaced
+        // if so, replace it and move the index forward to the last character of the replacement
+        let mut replaced = false;
+        for (key, value) in words {
+            let key = key.as_ref();
+            let Some(last) = key.chars().next_back() else {
+                continue;
+            };
+            if line[i..].starts_with(key) {
+                result.extend(char::from_digit(*value, 10));
+                // a one-character key has no last character to share with the next word
+                i += if key.len() > last.len_utf8() {
+                    key.len() - last.len_utf8()
+                } else {
+                    key.len()
+                };
+                replaced = true;
+                break;
+            }
+        }
+
+        // if the current character is not a digit and not the beginning of a string to be replaced
+        // move the index forward by one
+        if !replaced {
+            i += current.len_utf8();
+        }
+    }
+
+    result
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong): non-blank lines with no digit at all, written or spelled out, read
+// with the same digits as the solver, so a line it would fail on is flagged
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let digits = Options::default().digits;
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter(|(_, line)| replace_strings_with(line, digits).is_empty())
+        .map(|(i, _)| (i + 1, String::from("no digit, written or spelled out")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    // test file reader:
+    // 1abc2
+    // pqr3stu8vwx
+    // a1b2c3d4e5f
+    // treb7uchet
+    fn test_read_input() {
+        let result = read_input("../test-1.txt").unwrap();
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], "1abc2");
+        assert_eq!(result[1], "pqr3stu8vwx");
+        assert_eq!(result[2], "a1b2c3d4e5f");
+        assert_eq!(result[3], "treb7uchet");
+    }
+
+    #[test]
+    // test parse data
+    // 1abc2
+    // pqr3stu8vwx
+    // a1b2c3d4e5f
+    // treb7uchet
+    fn test_parse_data() {
+        let data = read_input("../test-1.txt").unwrap();
+        let result = parse_data(data).unwrap();
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], 12);
+        assert_eq!(result[1], 38);
+        assert_eq!(result[2], 15);
+        assert_eq!(result[3], 77);
+    }
+
+    #[test_case("two1nine", "219" ; "two1nine")]
+    #[test_case("eightwothree", "823" ; "eightwothree")]
+    #[test_case("abcone2threexyz", "123" ; "abcone2threexyz")]
+    #[test_case("xtwone3four", "2134" ; "xtwone3four")]
+    #[test_case("4nineeightseven2", "49872" ; "4nineeightseven2")]
+    #[test_case("zoneight234", "18234" ; "zoneight234")]
+    #[test_case("7pqrstsixteen", "76" ; "7pqrstsixteen")]
+    // f47ninexfqsbdrseventwo7twonep - overlapping string case from the data - super sucked
+    #[test_case("f47ninexfqsbdrseventwo7twonep", "47972721" ; "f47ninexfqsbdrseventwo7twonep")]
+    fn test_replace_strings(input: &str, expected: &str) {
+        let mut result = Vec::<String>::new();
+        let line = String::from(input);
+        result.push(replace_strings(&line));
+        assert_eq!(result[0], expected);
+    }
+
+    #[test]
+    fn test_parse_data_2() {
+        let data = read_input("../test-2.txt").unwrap();
+        let result = parse_data(data).unwrap();
+        assert_eq!(result.len(), 7);
+        assert_eq!(result[0], 29);
+        assert_eq!(result[1], 83);
+        assert_eq!(result[2], 13);
+        assert_eq!(result[3], 24);
+        assert_eq!(result[4], 42);
+        assert_eq!(result[5], 14);
+        assert_eq!(result[6], 76);
+        assert_eq!(result.iter().sum::<usize>(), 281);
+    }
+
+    #[test_case('7', Digits::Ascii, Some(7) ; "ascii")]
+    #[test_case('٣', Digits::Ascii, None ; "arabic_indic_ascii_only")]
+    #[test_case('٣', Digits::Unicode, Some(3) ; "arabic_indic")]
+    #[test_case('９', Digits::Unicode, Some(9) ; "fullwidth")]
+    #[test_case('𝟘', Digits::Unicode, Some(0) ; "math_double_struck")]
+    #[test_case('Ⅻ', Digits::Unicode, None ; "roman_numeral")]
+    #[test_case('²', Digits::Unicode, None ; "superscript")]
+    fn test_digit_value(c: char, digits: Digits, expected: Option<u32>) {
+        assert_eq!(digits.value(c), expected);
+    }
+
+    #[test]
+    fn test_parse_data_unicode() {
+        let data = vec![String::from("a٣bc９"), String::from("éoneŝ४")];
+        let options = Options {
+            digits: Digits::Unicode,
+            ..Options::default()
+        };
+        assert_eq!(
+            parse_data_with(data.clone(), options).unwrap(),
+            vec![39, 14]
+        );
+        assert_eq!(replace_strings(&data[1]), "1");
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 142);
+
+        // words are ignored, so "two1nine" is just 11
+        assert_eq!(part1("two1nine\neightwo3three").unwrap(), 11 + 33);
+    }
+
+    #[test_case("two1nine", Mode::DigitsOnly, 11 ; "digits_only")]
+    #[test_case("two1nine", Mode::Words, 29 ; "words")]
+    #[test_case("7pqrstsixteen", Mode::DigitsOnly, 77 ; "digits_only_single_digit")]
+    fn test_parse_data_mode(line: &str, mode: Mode, expected: usize) {
+        let options = Options {
+            mode,
+            ..Options::default()
+        };
+        let result = parse_data_with(vec![String::from(line)], options).unwrap();
+        assert_eq!(result, vec![expected]);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let data = vec![
+            String::from("1abc2"),
+            String::new(),
+            String::from("   "),
+            String::from("treb7uchet"),
+        ];
+        assert_eq!(parse_data(data).unwrap(), vec![12, 77]);
+        assert_eq!(part1("1abc2\n\npqr3stu8vwx\n").unwrap(), 12 + 38);
+    }
+
+    #[test]
+    fn test_line_without_digits() {
+        let data = vec![String::from("1abc2"), String::from("pqrstu")];
+        let err = parse_data(data).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: no digit in \"pqrstu\"");
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "1abc2\npqrstu\n\ntreb7uchet";
+        assert!(part1(input).is_err());
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 89);
+        assert_eq!(part2_with("two1nine\nxyz", ParseMode::Lenient).unwrap(), 29);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(parse_data(Vec::new()).unwrap().is_empty());
+        assert_eq!(part1("").unwrap(), 0);
+        assert_eq!(part2("\n\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = std::fs::read_to_string("../test-2.txt").unwrap();
+        assert_eq!(part2(&input).unwrap(), 281);
+    }
+
+    #[test_case("../dictionaries/fr.txt", "undeuxtrois", 13 ; "french")]
+    #[test_case("../dictionaries/de.txt", "xfünfzig3", 53 ; "german")]
+    #[test_case("../dictionaries/ordinals.txt", "the sixth of may, 1st", 61 ; "ordinals")]
+    #[test_case("../dictionaries/fr.txt", "two1nine", 29 ; "english_kept")]
+    fn test_dictionary(path: &str, line: &str, expected: usize) {
+        let mut dictionary = Dictionary::english();
+        dictionary.merge(Dictionary::load(path).unwrap());
+        let result =
+            parse_data_with_dictionary(vec![String::from(line)], Options::default(), &dictionary);
+        assert_eq!(result.unwrap(), vec![expected]);
+    }
+
+    #[test]
+    fn test_dictionary_parse() {
+        let dictionary = Dictionary::parse("# comment\n\nsixth 6\nsix 7\n").unwrap();
+        assert_eq!(dictionary.len(), 2);
+        // the longer word wins where both match
+        assert_eq!(replace_strings_in("sixth", Digits::Ascii, &dictionary), "6");
+        assert_eq!(replace_strings_in("sixt", Digits::Ascii, &dictionary), "7");
+
+        let mut english = Dictionary::english();
+        english.merge(Dictionary::parse("one 7\nx 5").unwrap());
+        assert_eq!(english.len(), 10);
+        assert_eq!(replace_strings_in("onexx", Digits::Ascii, &english), "755");
+
+        assert_eq!(
+            Dictionary::parse("deux").unwrap_err().to_string(),
+            "line 1: expected a word and a digit in \"deux\""
+        );
+        assert_eq!(
+            Dictionary::parse("un 1\ndix 10").unwrap_err().to_string(),
+            "line 2: \"10\" is not a digit 0-9"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = read_input("../test-2.txt").unwrap();
+        assert!(validate(&input).is_empty());
+
+        let input = vec![String::from("1abc2"), String::new(), String::from("abc")];
+        assert_eq!(
+            validate(&input),
+            vec![(3, String::from("no digit, written or spelled out"))]
+        );
+
+        // the solver reads only ASCII digits, so an Arabic-Indic ٣ is no digit
+        let input = vec![String::from("abc٣")];
+        assert_eq!(
+            validate(&input),
+            vec![(1, String::from("no digit, written or spelled out"))]
+        );
+        assert!(part2(&input[0]).is_err());
+    }
+
+    #[test]
+    fn test_solver() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(Trebuchet.part1(&input).unwrap(), "142");
+        let input = std::fs::read_to_string("../test-2.txt").unwrap();
+        assert_eq!(Trebuchet.part2(&input).unwrap(), "281");
+    }
+}
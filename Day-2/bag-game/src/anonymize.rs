@@ -0,0 +1,50 @@
+use crate::{is_feasible, parse_data, Game};
+use aoc_common::shuffle::Shuffler;
+use color_eyre::eyre::Result;
+
+/// The games in a new order, each with its rounds and their cube counts
+/// shuffled and with another game's id. Ids only move between games that are
+/// both possible or both impossible, so the possible ids, and both answers,
+/// add up as before.
+pub fn anonymize(lines: &[String], seed: u64) -> Result<Vec<String>> {
+    let mut rng = Shuffler::new(seed);
+    let mut games = parse_data(lines.to_vec())?;
+
+    let (possible, impossible): (Vec<usize>, Vec<usize>) =
+        (0..games.len()).partition(|&i| is_feasible(&games[i]));
+    for group in [possible, impossible] {
+        let mut ids: Vec<usize> = group.iter().map(|&i| games[i].id).collect();
+        rng.shuffle(&mut ids);
+        for (&i, id) in group.iter().zip(ids) {
+            games[i].id = id;
+        }
+    }
+
+    for game in &mut games {
+        rng.shuffle(&mut game.rounds);
+        for round in &mut game.rounds {
+            rng.shuffle(&mut round.0);
+        }
+    }
+    rng.shuffle(&mut games);
+    Ok(games.iter().map(Game::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, read_input};
+
+    #[test]
+    fn test_anonymize() {
+        let lines = read_input("../test-1.txt").unwrap();
+        let anonymized = anonymize(&lines, 42).unwrap();
+        assert_eq!(anonymized.len(), lines.len());
+        assert_ne!(anonymized, lines);
+        let (input, shared) = (lines.join("\n"), anonymized.join("\n"));
+        assert_eq!(part1(&shared).unwrap(), part1(&input).unwrap());
+        assert_eq!(part2(&shared).unwrap(), part2(&input).unwrap());
+        assert_eq!(anonymize(&lines, 42).unwrap(), anonymized);
+        assert!(anonymize(&[String::from("Game one")], 42).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{get_power, is_feasible, minimum_bag, total_feasible, total_power, Game};
+use aoc_common::explain::Narrative;
+
+/// Every game's minimal bag, whether 12 red, 13 green and 14 blue cubes could
+/// have played it and its power, then the totals.
+pub fn explain(games: &[Game]) -> Narrative {
+    let mut narrative = Narrative::new();
+    narrative.section("Minimal bags");
+    for game in games {
+        let bag = minimum_bag(game);
+        let verdict = if is_feasible(game) {
+            String::from("possible")
+        } else {
+            // one round over the limit is enough, so the reasons come from
+            // the bag, which holds each color's largest round
+            let over: Vec<String> = [
+                (bag.red, 12, "red"),
+                (bag.green, 13, "green"),
+                (bag.blue, 14, "blue"),
+            ]
+            .into_iter()
+            .filter(|&(count, limit, _)| count > limit)
+            .map(|(count, limit, color)| format!("{} {} > {}", count, color, limit))
+            .collect();
+            format!("impossible ({})", over.join(", "))
+        };
+        let power = get_power(game).map_or(String::from("too large"), |power| power.to_string());
+        narrative.line(format!(
+            "Game {}: {} red, {} green, {} blue; {}, power {}",
+            game.id, bag.red, bag.green, bag.blue, verdict, power
+        ));
+    }
+
+    let possible: Vec<String> = games
+        .iter()
+        .filter(|game| is_feasible(game))
+        .map(|game| game.id.to_string())
+        .collect();
+    let total = |total: color_eyre::Result<u64>| {
+        total.map_or_else(|err| err.to_string(), |total| total.to_string())
+    };
+    narrative
+        .section("Answers")
+        .line(format!(
+            "part 1: the possible games {} add up to {}",
+            possible.join(", "),
+            total(total_feasible(games))
+        ))
+        .line(format!(
+            "part 2: the powers add up to {}",
+            total(total_power(games))
+        ));
+    narrative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_data, read_input};
+
+    #[test]
+    fn test_explain() {
+        let games = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let narrative = explain(&games);
+        let [bags, answers] = narrative.sections() else {
+            panic!("expected two sections");
+        };
+        assert_eq!(
+            bags.lines[0],
+            "Game 1: 4 red, 2 green, 6 blue; possible, power 48"
+        );
+        assert_eq!(
+            bags.lines[3],
+            "Game 4: 14 red, 3 green, 15 blue; impossible (14 red > 12, 15 blue > 14), power 630"
+        );
+        assert_eq!(
+            answers.lines,
+            [
+                "part 1: the possible games 1, 2, 5 add up to 8",
+                "part 2: the powers add up to 2286"
+            ]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{minimum_bag, Bag, Color, Game};
+
+// One possible bag and how well it explains the observed rounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candidate {
+    pub bag: Bag,
+    pub log_likelihood: f64,
+    // likelihood normalized over all candidates that were considered
+    pub probability: f64,
+}
+
+// Log-likelihood of seeing every round of `game` when drawing from `bag`.
+//
+// Each round is modelled as one handful drawn without replacement from the
+// full bag (multivariate hypergeometric), with the cubes put back between
+// rounds. Returns negative infinity for bags that can't produce a round.
+pub fn log_likelihood(game: &Game, bag: &Bag) -> f64 {
+    let total = bag.red + bag.green + bag.blue;
+
+    game.rounds
+        .iter()
+        .map(|round| {
+            let (mut red, mut green, mut blue) = (0, 0, 0);
+            round
+                .0
+                .iter()
+                .for_each(|color_count| match color_count.color {
+                    Color::Red => red += color_count.count,
+                    Color::Green => green += color_count.count,
+                    Color::Blue => blue += color_count.count,
+                });
+
+            ln_choose(bag.red, red) + ln_choose(bag.green, green) + ln_choose(bag.blue, blue)
+                - ln_choose(total, red + green + blue)
+        })
+        .sum()
+}
+
+// Rank every bag from the minimum consistent bag up to `extra` more cubes of
+// each color, most likely first. The probabilities assume a uniform prior
+// over that window, so they only mean something relative to each other.
+#[tracing::instrument(skip(game))]
+pub fn rank_bags(game: &Game, extra: usize) -> Vec<Candidate> {
+    let min = minimum_bag(game);
+
+    let mut candidates = Vec::new();
+    for red in min.red..=min.red + extra {
+        for green in min.green..=min.green + extra {
+            for blue in min.blue..=min.blue + extra {
+                let bag = Bag { red, green, blue };
+                candidates.push(Candidate {
+                    bag,
+                    log_likelihood: log_likelihood(game, &bag),
+                    probability: 0.0,
+                });
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.log_likelihood.total_cmp(&a.log_likelihood));
+
+    // normalize relative to the best candidate to stay clear of underflow
+    let best = candidates[0].log_likelihood;
+    let total: f64 = candidates
+        .iter()
+        .map(|c| (c.log_likelihood - best).exp())
+        .sum();
+    candidates
+        .iter_mut()
+        .for_each(|c| c.probability = (c.log_likelihood - best).exp() / total);
+
+    candidates
+}
+
+// ln(n choose k), or negative infinity when k > n
+fn ln_choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(5, 0), 0.0);
+        assert!((ln_choose(5, 2) - 10f64.ln()).abs() < 1e-12);
+        assert!((ln_choose(20, 17) - 1140f64.ln()).abs() < 1e-9);
+        assert_eq!(ln_choose(2, 3), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_log_likelihood() {
+        let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse()
+            .unwrap();
+
+        let too_small = Bag {
+            red: 3,
+            green: 2,
+            blue: 6,
+        };
+        assert_eq!(log_likelihood(&game, &too_small), f64::NEG_INFINITY);
+        assert!(log_likelihood(&game, &minimum_bag(&game)).is_finite());
+    }
+
+    #[test]
+    fn test_rank_bags() {
+        let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse()
+            .unwrap();
+        let ranking = rank_bags(&game, 5);
+
+        assert_eq!(ranking.len(), 6 * 6 * 6);
+        assert_eq!(
+            ranking[0].bag,
+            Bag {
+                red: 7,
+                green: 5,
+                blue: 11
+            }
+        );
+        assert!((ranking[0].probability - 0.013211).abs() < 1e-6);
+        assert!(ranking
+            .windows(2)
+            .all(|w| w[0].log_likelihood >= w[1].log_likelihood));
+        let total: f64 = ranking.iter().map(|c| c.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // with no room to grow, the minimum bag is the only explanation
+        let ranking = rank_bags(&game, 0);
+        assert_eq!(ranking.len(), 1);
+        assert_eq!(ranking[0].bag, minimum_bag(&game));
+        assert_eq!(ranking[0].probability, 1.0);
+    }
+}
@@ -0,0 +1,963 @@
+use aoc_common::input::read_lines;
+use aoc_common::parse::{parse_lines, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{bail, eyre, Result};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, space1},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+pub mod anonymize;
+pub mod explain;
+pub mod infer;
+pub mod stats;
+#[cfg(feature = "visualize")]
+pub mod visualize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    Blue,
+    Green,
+    Red,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorCount {
+    pub color: Color,
+    pub count: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Round(pub Vec<ColorCount>);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Game {
+    pub id: usize,
+    pub rounds: Vec<Round>,
+}
+
+// Displayed in the puzzle's own format, so a displayed game parses back to
+// the same game
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Color::Blue => "blue",
+            Color::Green => "green",
+            Color::Red => "red",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for ColorCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.color)
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts: Vec<String> = self.0.iter().map(ColorCount::to_string).collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(Round::to_string).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+impl FromStr for Game {
+    type Err = color_eyre::Report;
+
+    fn from_str(line: &str) -> Result<Self> {
+        match parse_game(line) {
+            Ok(("", game)) => Ok(game),
+            Ok((rest, _)) => Err(eyre!("unexpected trailing input {:?}", rest)),
+            Err(e) => Err(eyre!("invalid game {:?}: {}", line, e)),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub fn read_input(filename: &str) -> Result<Vec<String>> {
+    Ok(read_lines(filename)?)
+}
+
+// Sum of the ids of games possible with 12 red, 13 green and 14 blue cubes (part 1)
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 2, part = 1)]
+pub fn part1_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_feasible(&parse_data_with(input, mode)?)
+}
+
+// Sum of the power of the minimum cube set for each game (part 2)
+pub fn part2(input: &str) -> Result<u64> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 2, part = 2)]
+pub fn part2_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_power(&parse_data_with(input, mode)?)
+}
+
+/// Day 2's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BagGame;
+
+impl Solver for BagGame {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+// The totals are checked, so absurd ids or counts are reported rather than
+// wrapping around
+pub fn total_feasible(games: &[Game]) -> Result<u64> {
+    games
+        .iter()
+        .filter(|game| is_feasible(game))
+        .try_fold(0u64, |acc, game| {
+            acc.checked_add(game.id as u64)
+                .ok_or_else(|| eyre!("the sum of feasible game ids overflows at game {}", game.id))
+        })
+}
+
+pub fn total_power(games: &[Game]) -> Result<u64> {
+    games.iter().try_fold(0u64, |acc, game| {
+        let power =
+            get_power(game).ok_or_else(|| eyre!("the power of game {} overflows", game.id))?;
+        acc.checked_add(power)
+            .ok_or_else(|| eyre!("the total power overflows at game {}", game.id))
+    })
+}
+
+// Parse a single color
+pub fn parse_color(input: &str) -> IResult<&str, Color> {
+    let (input, color_str) = nom::branch::alt((tag("blue"), tag("green"), tag("red")))(input)?;
+    let color = match color_str {
+        "red" => Color::Red,
+        "blue" => Color::Blue,
+        "green" => Color::Green,
+        _ => unreachable!(),
+    };
+    Ok((input, color))
+}
+
+// Parse a color count pair
+pub fn parse_color_count(input: &str) -> IResult<&str, ColorCount> {
+    let (input, (count, _, color)) =
+        tuple((map_res(digit1, str::parse::<usize>), space1, parse_color))(input)?;
+    Ok((input, ColorCount { color, count }))
+}
+
+// Parse a round
+pub fn parse_round(input: &str) -> IResult<&str, Round> {
+    let (input, list) = separated_list1(tag(", "), parse_color_count)(input)?;
+    Ok((input, Round(list)))
+}
+
+// Parse a game
+#[tracing::instrument(skip_all)]
+pub fn parse_game(input: &str) -> IResult<&str, Game> {
+    let (input, _) = tag("Game ")(input)?;
+    let (input, id) = map_res(digit1, str::parse::<usize>)(input)?;
+    let (input, _) = tag(": ")(input)?;
+    let (input, rounds) = separated_list1(tag("; "), parse_round)(input)?;
+    Ok((input, Game { id, rounds }))
+}
+
+// Determine feasibility of a game
+#[tracing::instrument(skip_all)]
+pub fn is_feasible(game: &Game) -> bool {
+    game.rounds.iter().all(is_round_feasible)
+}
+
+// Whether one round could be drawn from a bag of 12 red, 13 green and 14 blue;
+// a duplicate color's counts are added, and a sum too big for a usize is
+// more than any bag holds
+pub fn is_round_feasible(round: &Round) -> bool {
+    let mut blue = Some(0usize);
+    let mut green = Some(0usize);
+    let mut red = Some(0usize);
+
+    round
+        .0
+        .iter()
+        .for_each(|color_count| match color_count.color {
+            Color::Blue => blue = blue.and_then(|sum| sum.checked_add(color_count.count)),
+            Color::Green => green = green.and_then(|sum| sum.checked_add(color_count.count)),
+            Color::Red => red = red.and_then(|sum| sum.checked_add(color_count.count)),
+        });
+
+    blue.is_some_and(|blue| blue <= 14)
+        && green.is_some_and(|green| green <= 13)
+        && red.is_some_and(|red| red <= 12)
+}
+
+// The fewest cubes of each color that would make a game possible
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bag {
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn minimum_bag(game: &Game) -> Bag {
+    let mut bag = Bag::default();
+
+    game.rounds.iter().for_each(|round| {
+        let mut blue = 0;
+        let mut green = 0;
+        let mut red = 0;
+
+        round
+            .0
+            .iter()
+            .for_each(|color_count| match color_count.color {
+                // duplicate colors are summed; saturating keeps an absurd
+                // sum too big rather than wrapping it small
+                Color::Blue => {
+                    blue = color_count.count.saturating_add(blue);
+                    bag.blue = bag.blue.max(blue);
+                }
+                Color::Green => {
+                    green = color_count.count.saturating_add(green);
+                    bag.green = bag.green.max(green);
+                }
+                Color::Red => {
+                    red = color_count.count.saturating_add(red);
+                    bag.red = bag.red.max(red);
+                }
+            });
+    });
+
+    bag
+}
+
+// The product of the minimum bag's counts, or None when it doesn't fit in a u64
+#[tracing::instrument(skip_all)]
+pub fn get_power(game: &Game) -> Option<u64> {
+    let bag = minimum_bag(game);
+    [bag.blue, bag.green, bag.red]
+        .into_iter()
+        .try_fold(1u64, |acc, count| {
+            acc.checked_mul(u64::try_from(count).ok()?)
+        })
+}
+
+// A color named more than once in a round (rounds are 1-based)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateColor {
+    pub game: usize,
+    pub round: usize,
+    pub color: Color,
+}
+
+pub fn duplicate_colors(game: &Game) -> Vec<DuplicateColor> {
+    let mut duplicates = Vec::new();
+    game.rounds.iter().enumerate().for_each(|(i, round)| {
+        let mut seen = Vec::new();
+        round.0.iter().for_each(|color_count| {
+            if seen.contains(&color_count.color) {
+                duplicates.push(DuplicateColor {
+                    game: game.id,
+                    round: i + 1,
+                    color: color_count.color,
+                });
+            } else {
+                seen.push(color_count.color);
+            }
+        });
+    });
+    duplicates
+}
+
+// parse a vector of games
+#[tracing::instrument]
+pub fn parse_data(input: Vec<String>) -> Result<Vec<Game>> {
+    parse_data_with(input, ParseMode::Strict)
+}
+
+#[tracing::instrument]
+pub fn parse_data_with(input: Vec<String>, mode: ParseMode) -> Result<Vec<Game>> {
+    // the whole line has to be a game, so nothing after it is silently dropped
+    let parsed = parse_lines(&input, mode, |line| line.parse::<Game>())?;
+    // as on Day 1, blank lines (e.g. a trailing newline) are skipped
+    if parsed.blank > 0 {
+        warn!("Skipped {} blank lines", parsed.blank);
+    }
+    if let Some(summary) = parsed.summary() {
+        warn!("{}", summary);
+    }
+    let result = parsed.items;
+
+    // a color named twice in a round is rejected with the malformed lines,
+    // or its counts added when those are skipped
+    for duplicate in result.iter().flat_map(duplicate_colors) {
+        match mode {
+            ParseMode::Lenient => warn!(
+                "Game {} round {}: {:?} appears more than once, counts are added",
+                duplicate.game, duplicate.round, duplicate.color
+            ),
+            ParseMode::Strict => bail!(
+                "game {} round {}: {:?} appears more than once",
+                duplicate.game,
+                duplicate.round,
+                duplicate.color
+            ),
+        }
+    }
+
+    Ok(result)
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong): lines that aren't games, ids that don't increase, and empty rounds
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let mut findings = Vec::new();
+    let mut last_id = None;
+    for (i, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((id, rounds)) = line
+            .strip_prefix("Game ")
+            .and_then(|rest| rest.split_once(':'))
+            .and_then(|(id, rounds)| Some((id.parse::<usize>().ok()?, rounds)))
+        else {
+            findings.push((i + 1, String::from("expected \"Game <id>: <rounds>\"")));
+            continue;
+        };
+
+        if let Some(last) = last_id.filter(|&last| id <= last) {
+            findings.push((i + 1, format!("game {} comes after game {}", id, last)));
+        }
+        last_id = Some(id);
+
+        for (n, round) in rounds.split(';').enumerate() {
+            if round.trim().is_empty() {
+                findings.push((i + 1, format!("round {} is empty", n + 1)));
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::conformance::round_trip;
+
+    #[test]
+    // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    // Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    // Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    // Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    // Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    fn test_read_input() {
+        let result = read_input("../test-1.txt").unwrap();
+        assert_eq!(result.len(), 5);
+        assert_eq!(
+            result[0],
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+        );
+        assert_eq!(
+            result[1],
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"
+        );
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("blue"), Ok(("", Color::Blue)));
+        assert_eq!(parse_color("green"), Ok(("", Color::Green)));
+        assert_eq!(parse_color("red"), Ok(("", Color::Red)));
+        assert!(parse_color("invalid").is_err());
+    }
+
+    #[test]
+    fn test_parse_color_count() {
+        assert_eq!(
+            parse_color_count("3 blue"),
+            Ok((
+                "",
+                ColorCount {
+                    color: Color::Blue,
+                    count: 3
+                }
+            ))
+        );
+        assert_eq!(
+            parse_color_count("4 red"),
+            Ok((
+                "",
+                ColorCount {
+                    color: Color::Red,
+                    count: 4
+                }
+            ))
+        );
+        assert_eq!(
+            parse_color_count("2 green"),
+            Ok((
+                "",
+                ColorCount {
+                    color: Color::Green,
+                    count: 2
+                }
+            ))
+        );
+        assert!(parse_color_count("invalid").is_err());
+    }
+
+    #[test]
+    fn test_parse_round() {
+        let round = Round(vec![
+            ColorCount {
+                color: Color::Blue,
+                count: 3,
+            },
+            ColorCount {
+                color: Color::Red,
+                count: 4,
+            },
+        ]);
+        assert_eq!(parse_round("3 blue, 4 red"), Ok(("", round)));
+        assert!(parse_round("invalid").is_err());
+    }
+
+    #[test]
+    fn test_parse_game() {
+        let game_str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let expected_game = Game {
+            id: 1,
+            rounds: vec![
+                Round(vec![
+                    ColorCount {
+                        color: Color::Blue,
+                        count: 3,
+                    },
+                    ColorCount {
+                        color: Color::Red,
+                        count: 4,
+                    },
+                ]),
+                Round(vec![
+                    ColorCount {
+                        color: Color::Red,
+                        count: 1,
+                    },
+                    ColorCount {
+                        color: Color::Green,
+                        count: 2,
+                    },
+                    ColorCount {
+                        color: Color::Blue,
+                        count: 6,
+                    },
+                ]),
+                Round(vec![ColorCount {
+                    color: Color::Green,
+                    count: 2,
+                }]),
+            ],
+        };
+        assert_eq!(parse_game(game_str), Ok(("", expected_game)));
+
+        let game_str = "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue";
+        let expected_game = Game {
+            id: 2,
+            rounds: vec![
+                Round(vec![
+                    ColorCount {
+                        color: Color::Blue,
+                        count: 1,
+                    },
+                    ColorCount {
+                        color: Color::Green,
+                        count: 2,
+                    },
+                ]),
+                Round(vec![
+                    ColorCount {
+                        color: Color::Green,
+                        count: 3,
+                    },
+                    ColorCount {
+                        color: Color::Blue,
+                        count: 4,
+                    },
+                    ColorCount {
+                        color: Color::Red,
+                        count: 1,
+                    },
+                ]),
+                Round(vec![
+                    ColorCount {
+                        color: Color::Green,
+                        count: 1,
+                    },
+                    ColorCount {
+                        color: Color::Blue,
+                        count: 1,
+                    },
+                ]),
+            ],
+        };
+        assert_eq!(parse_game(game_str), Ok(("", expected_game)));
+
+        assert!(parse_game("invalid").is_err());
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let input = read_input("../test-1.txt").unwrap();
+
+        let expected = vec![
+            Game {
+                id: 1,
+                rounds: vec![
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 3,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 4,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Red,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Green,
+                            count: 2,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 6,
+                        },
+                    ]),
+                    Round(vec![ColorCount {
+                        color: Color::Green,
+                        count: 2,
+                    }]),
+                ],
+            },
+            Game {
+                id: 2,
+                rounds: vec![
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Green,
+                            count: 2,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 3,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 4,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 1,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 1,
+                        },
+                    ]),
+                ],
+            },
+            Game {
+                id: 3,
+                rounds: vec![
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 8,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 6,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 20,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 5,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 4,
+                        },
+                        ColorCount {
+                            color: Color::Green,
+                            count: 13,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 5,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 1,
+                        },
+                    ]),
+                ],
+            },
+            Game {
+                id: 4,
+                rounds: vec![
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 3,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 6,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 3,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 6,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Green,
+                            count: 3,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 15,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 14,
+                        },
+                    ]),
+                ],
+            },
+            Game {
+                id: 5,
+                rounds: vec![
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Red,
+                            count: 6,
+                        },
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Green,
+                            count: 3,
+                        },
+                    ]),
+                    Round(vec![
+                        ColorCount {
+                            color: Color::Blue,
+                            count: 2,
+                        },
+                        ColorCount {
+                            color: Color::Red,
+                            count: 1,
+                        },
+                        ColorCount {
+                            color: Color::Green,
+                            count: 2,
+                        },
+                    ]),
+                ],
+            },
+        ];
+
+        let result = parse_data(input).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_is_feasible() {
+        let input = read_input("../test-1.txt").unwrap();
+        let data = parse_data(input).unwrap();
+        assert!(is_feasible(&data[0]));
+        assert!(is_feasible(&data[1]));
+        assert!(!is_feasible(&data[2]));
+        assert!(!is_feasible(&data[3]));
+        assert!(is_feasible(&data[4]));
+    }
+
+    #[test]
+    fn test_sum() {
+        let input = read_input("../test-1.txt").unwrap();
+        let data = parse_data(input).unwrap();
+        let total = data.iter().fold(0, |acc, game| {
+            if is_feasible(game) {
+                acc + game.id
+            } else {
+                acc
+            }
+        });
+        assert_eq!(total, 8);
+    }
+
+    #[test]
+    fn test_minimum_bag() {
+        let input = read_input("../test-1.txt").unwrap();
+        let data = parse_data(input).unwrap();
+        assert_eq!(
+            minimum_bag(&data[0]),
+            Bag {
+                red: 4,
+                green: 2,
+                blue: 6
+            }
+        );
+        assert_eq!(
+            minimum_bag(&data[2]),
+            Bag {
+                red: 20,
+                green: 13,
+                blue: 6
+            }
+        );
+        assert_eq!(get_power(&data[0]), Some(48));
+    }
+
+    #[test]
+    fn test_round_overflow() {
+        let line = format!("Game 1: {} red, 1 red", usize::MAX);
+        let game: Game = line.parse().unwrap();
+        assert!(!is_round_feasible(&game.rounds[0]));
+        assert_eq!(minimum_bag(&game).red, usize::MAX);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part2(&input).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // well past u32, still inside u64
+        assert_eq!(
+            part2("Game 1: 100000 red, 100000 green, 1000 blue").unwrap(),
+            10u64.pow(13)
+        );
+
+        let huge = "Game 1: 4294967296 red, 4294967296 green, 4294967296 blue";
+        let games = parse_data(vec![String::from(huge)]).unwrap();
+        assert_eq!(get_power(&games[0]), None);
+        assert_eq!(
+            total_power(&games).unwrap_err().to_string(),
+            "the power of game 1 overflows"
+        );
+
+        let ids = vec![
+            format!("Game {}: 1 red", u64::MAX),
+            format!("Game {}: 1 red", u64::MAX),
+        ];
+        assert!(total_feasible(&parse_data(ids).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_game_from_str() {
+        let game: Game = "Game 5: 6 red, 1 blue".parse().unwrap();
+        assert_eq!(game.id, 5);
+        assert_eq!(game.rounds.len(), 1);
+        assert_eq!(game.rounds[0].0[1].color, Color::Blue);
+
+        assert!(Game::from_str("Game 5: 6 red, 1 blue; ").is_err());
+        assert!(Game::from_str("Game five").is_err());
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let input = vec![
+            String::from("Game 1: 3 blue"),
+            String::new(),
+            String::from("Game 2: 1 red"),
+        ];
+        assert_eq!(parse_data(input.clone()).unwrap().len(), 2);
+        assert!(validate(&input).is_empty());
+        assert_eq!(part1("\n\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let input = vec![
+            String::from("Game 1: 3 blue"),
+            String::from("Game two: 1 red"),
+        ];
+        let err = parse_data(input).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 2: invalid game \"Game two: 1 red\""));
+
+        let err = parse_data(vec![String::from("Game 1: 3 blue; 4 red;")]).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: unexpected trailing input \";\"");
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "Game 1: 3 blue, 1 red, 2 green\nGame two: 1 red\nGame 3: 20 red, 1 green, 1 blue\n7 red";
+        assert!(part1(input).is_err());
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 1);
+        assert_eq!(part2_with(input, ParseMode::Lenient).unwrap(), 26);
+
+        let input = "Game 1: 3 red, 4 red";
+        assert_eq!(
+            part1(input).unwrap_err().to_string(),
+            "game 1 round 1: Red appears more than once"
+        );
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 1);
+        assert_eq!(part2_with(input, ParseMode::Lenient).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_colors() {
+        let input = read_input("../test-1.txt").unwrap();
+        let data = parse_data_with(input, ParseMode::Strict).unwrap();
+        assert!(data.iter().all(|game| duplicate_colors(game).is_empty()));
+
+        let input = vec![String::from("Game 3: 1 blue; 3 red, 2 green, 4 red, 1 red")];
+        let data = parse_data_with(input.clone(), ParseMode::Lenient).unwrap();
+        let duplicate = DuplicateColor {
+            game: 3,
+            round: 2,
+            color: Color::Red,
+        };
+        assert_eq!(duplicate_colors(&data[0]), vec![duplicate, duplicate]);
+        // lenient mode keeps summing them
+        assert_eq!(minimum_bag(&data[0]).red, 8);
+
+        let err = parse_data_with(input, ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "game 3 round 2: Red appears more than once"
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        // the example is already canonical
+        for line in read_input("../test-1.txt").unwrap() {
+            assert_eq!(round_trip::<Game>(&line), line);
+        }
+        assert_eq!(
+            round_trip::<Game>("Game 007: 03 blue, 4 red; 2 green"),
+            "Game 7: 3 blue, 4 red; 2 green"
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input = read_input("../test-1.txt").unwrap();
+        let data = parse_data(input).unwrap();
+
+        let json = serde_json::to_string(&data).unwrap();
+        let result: Vec<Game> = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, data);
+
+        let (_, game) = parse_game("Game 7: 3 blue, 4 red; 2 green").unwrap();
+        assert_eq!(
+            serde_json::to_string(&game).unwrap(),
+            r#"{"id":7,"rounds":[[{"color":"blue","count":3},{"color":"red","count":4}],[{"color":"green","count":2}]]}"#
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert!(validate(&input).is_empty());
+
+        let input = vec![
+            String::from("Game 2: 3 blue; ; 1 red"),
+            String::from("Game 1: 2 green;"),
+            String::from("3 blue, 4 red"),
+        ];
+        assert_eq!(
+            validate(&input),
+            vec![
+                (1, String::from("round 2 is empty")),
+                (2, String::from("game 1 comes after game 2")),
+                (2, String::from("round 2 is empty")),
+                (3, String::from("expected \"Game <id>: <rounds>\"")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solver() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(BagGame.part1(&input).unwrap(), "8");
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(BagGame.part2(&input).unwrap(), "2286");
+    }
+}
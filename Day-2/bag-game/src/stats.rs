@@ -0,0 +1,179 @@
+use crate::{Color, Game};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Cubes of one color shown per round; rounds that don't mention the color count as 0
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ColorStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+// The single biggest handful pulled out of the bag (rounds are 1-based)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Draw {
+    pub game: usize,
+    pub round: usize,
+    pub color: Color,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: usize,
+    pub rounds: usize,
+    pub red: ColorStats,
+    pub green: ColorStats,
+    pub blue: ColorStats,
+    // number of rounds -> number of games with that many rounds
+    pub rounds_per_game: BTreeMap<usize, usize>,
+    pub largest_draw: Option<Draw>,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn stats(games: &[Game]) -> Stats {
+    let mut rounds_per_game = BTreeMap::new();
+    let mut largest_draw: Option<Draw> = None;
+    let mut shown: [Vec<usize>; 3] = Default::default();
+
+    games.iter().for_each(|game| {
+        *rounds_per_game.entry(game.rounds.len()).or_insert(0) += 1;
+
+        game.rounds.iter().enumerate().for_each(|(i, round)| {
+            let mut counts = [0; 3];
+            round.0.iter().for_each(|color_count| {
+                counts[index(color_count.color)] += color_count.count;
+
+                if largest_draw.map(|draw| draw.count) < Some(color_count.count) {
+                    largest_draw = Some(Draw {
+                        game: game.id,
+                        round: i + 1,
+                        color: color_count.color,
+                        count: color_count.count,
+                    });
+                }
+            });
+            shown
+                .iter_mut()
+                .zip(counts)
+                .for_each(|(shown, count)| shown.push(count));
+        });
+    });
+
+    let [red, green, blue] = shown.map(|counts| color_stats(&counts));
+    Stats {
+        games: games.len(),
+        rounds: rounds_per_game
+            .iter()
+            .map(|(rounds, games)| rounds * games)
+            .sum(),
+        red,
+        green,
+        blue,
+        rounds_per_game,
+        largest_draw,
+    }
+}
+
+fn index(color: Color) -> usize {
+    match color {
+        Color::Red => 0,
+        Color::Green => 1,
+        Color::Blue => 2,
+    }
+}
+
+fn color_stats(counts: &[usize]) -> ColorStats {
+    if counts.is_empty() {
+        return ColorStats::default();
+    }
+
+    ColorStats {
+        min: counts.iter().copied().min().unwrap_or(0),
+        max: counts.iter().copied().max().unwrap_or(0),
+        mean: counts.iter().sum::<usize>() as f64 / counts.len() as f64,
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "games   {:>6}", self.games)?;
+        writeln!(f, "rounds  {:>6}", self.rounds)?;
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<6}  {:>6}  {:>6}  {:>6}",
+            "color", "min", "max", "mean"
+        )?;
+        for (name, color) in [
+            ("red", self.red),
+            ("green", self.green),
+            ("blue", self.blue),
+        ] {
+            writeln!(
+                f,
+                "{:<6}  {:>6}  {:>6}  {:>6.2}",
+                name, color.min, color.max, color.mean
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:>6}  {:>6}", "rounds", "games")?;
+        for (rounds, games) in &self.rounds_per_game {
+            writeln!(f, "{:>6}  {:>6}", rounds, games)?;
+        }
+
+        if let Some(draw) = self.largest_draw {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "largest draw: {} {:?} (game {}, round {})",
+                draw.count, draw.color, draw.game, draw.round
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_data, read_input};
+
+    #[test]
+    fn test_stats() {
+        let games = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let stats = stats(&games);
+
+        assert_eq!(stats.games, 5);
+        assert_eq!(stats.rounds, 14);
+        assert_eq!((stats.red.min, stats.red.max), (0, 20));
+        assert_eq!((stats.green.min, stats.green.max), (0, 13));
+        assert_eq!((stats.blue.min, stats.blue.max), (0, 15));
+        assert!((stats.red.mean - 61.0 / 14.0).abs() < 1e-9);
+        assert_eq!(stats.rounds_per_game, BTreeMap::from([(2, 1), (3, 4)]));
+        assert_eq!(
+            stats.largest_draw,
+            Some(Draw {
+                game: 3,
+                round: 1,
+                color: Color::Red,
+                count: 20
+            })
+        );
+        assert!(stats
+            .to_string()
+            .ends_with("largest draw: 20 Red (game 3, round 1)\n"));
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let stats = stats(&[]);
+        assert_eq!(stats.rounds, 0);
+        assert_eq!(stats.red, ColorStats::default());
+        assert_eq!(stats.largest_draw, None);
+    }
+}
@@ -0,0 +1,133 @@
+use aoc_common::visualize::{Checkpoint, Item, Scene, Stepper, Style, Visualize};
+use color_eyre::eyre::{eyre, Result};
+
+use crate::{
+    get_power, is_feasible, is_round_feasible, minimum_bag, parse_data, Color, Game, Round,
+};
+
+// One line per round; rounds drawing more cubes than the part 1 bag holds are
+// flagged, and the rest of a feasible game is highlighted.
+impl Visualize for Game {
+    fn visualize(&self) -> Scene {
+        let feasible = is_feasible(self);
+        Scene::List(
+            self.rounds
+                .iter()
+                .map(|round| {
+                    let style = if !is_round_feasible(round) {
+                        Style::Alert
+                    } else if feasible {
+                        Style::Highlight
+                    } else {
+                        Style::Plain
+                    };
+                    Item::new(describe(round), style)
+                })
+                .collect(),
+        )
+    }
+}
+
+// Part 1, stopping after each game with the running total
+pub fn part1_stepped(input: &str, stepper: &mut impl Stepper) -> Result<u64> {
+    let games = parse_data(input.lines().map(String::from).collect())?;
+    let mut total = 0u64;
+    for game in &games {
+        let feasible = is_feasible(game);
+        if feasible {
+            total = total.checked_add(game.id as u64).ok_or_else(|| {
+                eyre!("the sum of feasible game ids overflows at game {}", game.id)
+            })?;
+        }
+        stepper.checkpoint(
+            Checkpoint::new(format!("Game {}", game.id))
+                .scene(game.visualize())
+                .var("feasible", feasible)
+                .var("total", total),
+        );
+    }
+    Ok(total)
+}
+
+// Part 2, stopping after each game with its minimum bag and the running total
+pub fn part2_stepped(input: &str, stepper: &mut impl Stepper) -> Result<u64> {
+    let games = parse_data(input.lines().map(String::from).collect())?;
+    let mut total = 0u64;
+    for game in &games {
+        let bag = minimum_bag(game);
+        let power =
+            get_power(game).ok_or_else(|| eyre!("the power of game {} overflows", game.id))?;
+        total = total
+            .checked_add(power)
+            .ok_or_else(|| eyre!("the total power overflows at game {}", game.id))?;
+        stepper.checkpoint(
+            Checkpoint::new(format!("Game {}", game.id))
+                .scene(game.visualize())
+                .var(
+                    "minimum bag",
+                    format!("{} red, {} green, {} blue", bag.red, bag.green, bag.blue),
+                )
+                .var("power", power)
+                .var("total", total),
+        );
+    }
+    Ok(total)
+}
+
+fn describe(round: &Round) -> String {
+    round
+        .0
+        .iter()
+        .map(|c| {
+            let color = match c.color {
+                Color::Blue => "blue",
+                Color::Green => "green",
+                Color::Red => "red",
+            };
+            format!("{} {}", c.count, color)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize() {
+        let input = crate::read_input("../test-1.txt").unwrap();
+        let games = crate::parse_data(input).unwrap();
+
+        let Scene::List(items) = games[0].visualize() else {
+            panic!("expected a list scene");
+        };
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0], Item::new("3 blue, 4 red", Style::Highlight));
+
+        // game 3 draws 20 red in its first round
+        let Scene::List(items) = games[2].visualize() else {
+            panic!("expected a list scene");
+        };
+        assert_eq!(items[0].style, Style::Alert);
+        assert_eq!(items[2].style, Style::Plain);
+    }
+
+    #[test]
+    fn test_stepped() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+
+        let mut steps = Vec::new();
+        assert_eq!(part1_stepped(&input, &mut steps).unwrap(), 8);
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[2].label, "Game 3");
+        assert_eq!(steps[2].variables[0].1, "false");
+        assert_eq!(steps[4].variables[1].1, "8");
+
+        let mut steps = Vec::new();
+        assert_eq!(part2_stepped(&input, &mut steps).unwrap(), 2286);
+        assert_eq!(steps[0].variables[0].1, "4 red, 2 green, 6 blue");
+        assert_eq!(steps[0].variables[1].1, "48");
+        assert!(steps[0].scene.is_some());
+    }
+}
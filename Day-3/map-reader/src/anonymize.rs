@@ -0,0 +1,57 @@
+use crate::is_symbol;
+use aoc_common::shuffle::Shuffler;
+
+/// The schematic upside down, with the symbols other than `*` swapped for
+/// one another. Flipping the rows keeps every number's neighbours, and only
+/// a gear's `*` matters beyond being a symbol, so both answers hold.
+pub fn anonymize(lines: &[String], seed: u64) -> Vec<String> {
+    let mut rng = Shuffler::new(seed);
+    let mut symbols: Vec<char> = lines
+        .iter()
+        .flat_map(|line| line.chars())
+        .filter(|&ch| is_symbol(ch) && ch != '*')
+        .collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+    let mut swapped = symbols.clone();
+    rng.shuffle(&mut swapped);
+
+    lines
+        .iter()
+        .rev()
+        .map(|line| {
+            line.chars()
+                .map(|ch| match symbols.binary_search(&ch) {
+                    Ok(i) => swapped[i],
+                    Err(_) => ch,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    fn answers(lines: &[String]) -> (u128, u128) {
+        let schematic: crate::Schematic = lines.join("\n").parse().unwrap();
+        let parts = schematic
+            .numbers
+            .iter()
+            .filter(|number| schematic.is_part_number(number))
+            .map(|number| u128::from(number.value()))
+            .sum();
+        (parts, schematic.query('*', 2).sum_of_products)
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let lines = read_input("../test-1.txt").unwrap();
+        let anonymized = anonymize(&lines, 42);
+        assert_eq!(anonymized.first(), lines.last());
+        assert_eq!(answers(&anonymized), answers(&lines));
+        assert_eq!(answers(&lines), (4361, 467835));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{grid, is_symbol, Position, Schematic, Symbol};
+use aoc_common::grid::Point;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// A number the solver left out of the part-number sum, with enough context to
+// see why: anything non-blank around it, and the closest recognized symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orphan {
+    pub value: u64,
+    pub position: Position,
+    pub digits: usize,
+    // non-digit, non-'.' characters in the 8-neighborhood of the number
+    pub neighbors: Vec<Symbol>,
+    // closest recognized symbol and its distance in cells (diagonals count as 1)
+    pub nearest: Option<(Symbol, usize)>,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn orphans(lines: &[String], schematic: &Schematic) -> Vec<Orphan> {
+    let grid = grid(lines);
+    schematic
+        .numbers
+        .iter()
+        .zip(schematic.part_flags())
+        .filter(|(_, is_part)| !is_part)
+        .map(|(number, _)| {
+            let first = &number.0[0].position;
+
+            let around: BTreeSet<Point> = number
+                .0
+                .iter()
+                .flat_map(|numeral| grid.neighbors8(Point::from(&numeral.position)))
+                .collect();
+            let neighbors: Vec<Symbol> = around
+                .into_iter()
+                .filter(|&point| grid[point] != '.' && !grid[point].is_ascii_digit())
+                .map(|point| Symbol {
+                    position: Position::from(point),
+                    symbol: grid[point],
+                })
+                .collect();
+
+            let nearest = schematic
+                .symbols
+                .iter()
+                .map(|symbol| {
+                    let distance = number
+                        .0
+                        .iter()
+                        .map(|numeral| {
+                            let p = &numeral.position;
+                            p.row
+                                .abs_diff(symbol.position.row)
+                                .max(p.col.abs_diff(symbol.position.col))
+                        })
+                        .min()
+                        .unwrap_or(usize::MAX);
+                    (symbol.clone(), distance)
+                })
+                .min_by_key(|(_, distance)| *distance);
+
+            Orphan {
+                value: number.value(),
+                position: first.clone(),
+                digits: number.0.len(),
+                neighbors,
+                nearest,
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Orphan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at ({}, {}):",
+            self.value, self.position.row, self.position.col
+        )?;
+
+        if self.neighbors.is_empty() {
+            write!(f, " nothing adjacent")?;
+            if let Some((symbol, distance)) = &self.nearest {
+                write!(
+                    f,
+                    "; nearest symbol {:?} at ({}, {}) is {} cells away",
+                    symbol.symbol, symbol.position.row, symbol.position.col, distance
+                )?;
+            }
+            return Ok(());
+        }
+
+        for (i, neighbor) in self.neighbors.iter().enumerate() {
+            let reason = if is_symbol(neighbor.symbol) {
+                "is a symbol but was not counted as adjacent"
+            } else {
+                "is not a recognized symbol"
+            };
+            write!(
+                f,
+                "{} {:?} at ({}, {}) {}",
+                if i == 0 { "" } else { ";" },
+                neighbor.symbol,
+                neighbor.position.row,
+                neighbor.position.col,
+                reason
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn test_orphans() {
+        let input = read_input("../test-1.txt").unwrap();
+        let schematic: Schematic = input.join("\n").parse().unwrap();
+        let orphans = orphans(&input, &schematic);
+
+        assert_eq!(orphans.len(), 2);
+        assert_eq!(orphans[0].value, 114);
+        assert_eq!(orphans[0].position, Position { row: 0, col: 5 });
+        assert!(orphans[0].neighbors.is_empty());
+        assert_eq!(
+            orphans[0].to_string(),
+            "114 at (0, 5): nothing adjacent; nearest symbol '*' at (1, 3) is 2 cells away"
+        );
+        assert_eq!(orphans[1].value, 58);
+        assert_eq!(orphans[1].nearest.as_ref().unwrap().1, 2);
+    }
+
+    #[test]
+    fn test_orphan_reasons() {
+        let input = vec![String::from("12 ..."), String::from("....7*")];
+        let schematic: Schematic = input.join("\n").parse().unwrap();
+        let orphans = orphans(&input, &schematic);
+
+        // the 7 touches the '*' to its right, so it's a part number
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(
+            orphans[0].to_string(),
+            "12 at (0, 0): ' ' at (0, 2) is not a recognized symbol"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Position, Schematic};
+use aoc_common::explain::Narrative;
+
+/// Every number with the symbols it touches, and so whether it's a part
+/// number, then every gear's ratio and the totals.
+pub fn explain(schematic: &Schematic) -> Narrative {
+    let mut narrative = Narrative::new();
+    narrative.section("Numbers");
+    let (mut parts, mut sum) = (0, 0u128);
+    for (number, symbols) in schematic.numbers.iter().zip(schematic.touching()) {
+        let Some(first) = number.0.first() else {
+            continue;
+        };
+        let touching: Vec<String> = symbols
+            .iter()
+            .map(|&id| &schematic.symbols[id])
+            .map(|symbol| format!("{} at {}", symbol.symbol, at(&symbol.position)))
+            .collect();
+        let verdict = if touching.is_empty() {
+            String::from("touches no symbol, not a part number")
+        } else {
+            parts += 1;
+            sum += u128::from(number.value());
+            format!("touches {}, a part number", touching.join(" and "))
+        };
+        narrative.line(format!(
+            "{} at {}: {}",
+            number.value(),
+            at(&first.position),
+            verdict
+        ));
+    }
+
+    narrative.section("Gears");
+    let gears = schematic.query('*', 2);
+    for gear in &gears.matches {
+        let numbers: Vec<String> = gear.numbers.iter().map(u64::to_string).collect();
+        narrative.line(format!(
+            "* at {}: {} = {}",
+            at(&gear.symbol.position),
+            numbers.join(" x "),
+            gear.product()
+        ));
+    }
+
+    narrative
+        .section("Answers")
+        .line(format!(
+            "part 1: the {} part numbers add up to {}",
+            parts, sum
+        ))
+        .line(format!(
+            "part 2: the {} gear ratios add up to {}",
+            gears.matches.len(),
+            gears.sum_of_products
+        ));
+    narrative
+}
+
+// 1-based, as in an editor
+fn at(position: &Position) -> String {
+    format!("row {}, col {}", position.row + 1, position.col + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+        let narrative = explain(&schematic);
+        let [numbers, gears, answers] = narrative.sections() else {
+            panic!("expected three sections");
+        };
+        assert_eq!(
+            numbers.lines[0],
+            "467 at row 1, col 1: touches * at row 2, col 4, a part number"
+        );
+        assert_eq!(
+            numbers.lines[1],
+            "114 at row 1, col 6: touches no symbol, not a part number"
+        );
+        assert_eq!(gears.lines[0], "* at row 2, col 4: 467 x 35 = 16345");
+        assert_eq!(
+            answers.lines,
+            [
+                "part 1: the 8 part numbers add up to 4361",
+                "part 2: the 2 gear ratios add up to 467835"
+            ]
+        );
+    }
+}
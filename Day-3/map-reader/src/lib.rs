@@ -0,0 +1,943 @@
+use aoc_common::grid::{Extent, Grid, Point};
+use aoc_common::input::read_lines;
+use aoc_common::parse::{Malformed, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{bail, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+use serde::{Deserialize, Serialize};
+
+pub mod anonymize;
+pub mod diagnostics;
+pub mod explain;
+pub mod stream;
+pub mod svg;
+#[cfg(feature = "visualize")]
+pub mod visualize;
+
+// Ordered row by row, in reading order
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl From<Point> for Position {
+    fn from(point: Point) -> Self {
+        Position {
+            row: point.row,
+            col: point.col,
+        }
+    }
+}
+
+impl From<&Position> for Point {
+    fn from(position: &Position) -> Self {
+        Point::new(position.row, position.col)
+    }
+}
+
+impl From<Position> for Point {
+    fn from(position: Position) -> Self {
+        Point::from(&position)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Symbol {
+    pub position: Position,
+    pub symbol: char,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Numeral {
+    pub position: Position,
+    pub value: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Number(pub Vec<Numeral>);
+
+impl Number {
+    // Cells around the number that are on a grid of `extent`, in reading
+    // order: every neighbor of a numeral, diagonals included, that isn't part
+    // of the number itself. Cells past an edge are left out, never wrapped.
+    #[tracing::instrument(skip_all)]
+    pub fn border(&self, extent: Extent) -> BTreeSet<Position> {
+        let own: BTreeSet<Point> = self.0.iter().map(|n| Point::from(&n.position)).collect();
+        own.iter()
+            .filter_map(|&point| extent.pos(point))
+            .flat_map(|pos| pos.neighbors8())
+            .map(Point::from)
+            .filter(|point| !own.contains(point))
+            .map(Position::from)
+            .collect()
+    }
+
+    // parse_numbers rejects numbers too long for a u64, so this only
+    // saturates for hand-built ones
+    #[tracing::instrument(skip_all)]
+    pub fn value(&self) -> u64 {
+        self.checked_value().unwrap_or(u64::MAX)
+    }
+
+    // None when the digits don't fit in a u64
+    pub fn checked_value(&self) -> Option<u64> {
+        self.0.iter().try_fold(0u64, |value, numeral| {
+            value.checked_mul(10)?.checked_add(numeral.value as u64)
+        })
+    }
+}
+
+/// Index of a symbol in [`Schematic::symbols`].
+pub type SymbolId = usize;
+
+// The parsed engine schematic: every symbol and every multi-digit number
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Schematic {
+    pub symbols: Vec<Symbol>,
+    pub numbers: Vec<Number>,
+}
+
+// A symbol and the values of the numbers touching it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adjacency {
+    pub symbol: Symbol,
+    pub numbers: Vec<u64>,
+}
+
+// Widened to u128 so two u64 numbers always multiply exactly; more than that
+// saturates instead of wrapping
+impl Adjacency {
+    pub fn product(&self) -> u128 {
+        self.numbers
+            .iter()
+            .fold(1u128, |product, &n| product.saturating_mul(n as u128))
+    }
+
+    pub fn sum(&self) -> u128 {
+        self.numbers.iter().map(|&n| n as u128).sum()
+    }
+}
+
+// Every matching symbol plus the totals over all of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjacencyQuery {
+    pub matches: Vec<Adjacency>,
+    // sum over matches of the product of their numbers (gear ratios for '*', 2)
+    pub sum_of_products: u128,
+    // sum of every number touching a match
+    pub sum: u128,
+}
+
+impl Schematic {
+    // The smallest grid holding every number and symbol. Nothing outside it
+    // can be a symbol, so it is as good as the input's size for adjacency.
+    pub fn extent(&self) -> Extent {
+        self.numbers
+            .iter()
+            .flat_map(|number| number.0.iter().map(|numeral| &numeral.position))
+            .chain(self.symbols.iter().map(|symbol| &symbol.position))
+            .fold(Extent::new(0, 0), |extent, position| {
+                Extent::new(
+                    extent.width.max(position.col + 1),
+                    extent.height.max(position.row + 1),
+                )
+            })
+    }
+
+    // Each symbol's position and its index in `symbols`, so what touches a
+    // cell is one lookup instead of a pass over every symbol
+    pub fn symbol_index(&self) -> HashMap<Position, SymbolId> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .map(|(id, symbol)| (symbol.position.clone(), id))
+            .collect()
+    }
+
+    // The symbols touching each number, in reading order, aligned with
+    // `numbers`: one index lookup per border cell
+    #[tracing::instrument(skip(self))]
+    pub fn touching(&self) -> Vec<Vec<SymbolId>> {
+        let index = self.symbol_index();
+        let extent = self.extent();
+        self.numbers
+            .iter()
+            .map(|number| {
+                number
+                    .border(extent)
+                    .iter()
+                    .filter_map(|position| index.get(position).copied())
+                    .collect()
+            })
+            .collect()
+    }
+
+    // A part number touches at least one symbol, diagonals included. This
+    // indexes the symbols on every call; use `part_flags` for all numbers.
+    pub fn is_part_number(&self, number: &Number) -> bool {
+        let index = self.symbol_index();
+        number
+            .border(self.extent())
+            .iter()
+            .any(|position| index.contains_key(position))
+    }
+
+    // Whether each number is a part number, aligned with `numbers`
+    pub fn part_flags(&self) -> Vec<bool> {
+        self.touching()
+            .iter()
+            .map(|symbols| !symbols.is_empty())
+            .collect()
+    }
+
+    // Sum of every part number (part 1)
+    #[tracing::instrument(skip(self))]
+    pub fn part_sum(&self) -> u128 {
+        self.numbers
+            .iter()
+            .zip(self.part_flags())
+            .filter(|(_, is_part)| *is_part)
+            .map(|(number, _)| number.value() as u128)
+            .sum()
+    }
+
+    // Every symbol with the numbers touching it, aligned with `symbols`
+    #[tracing::instrument(skip(self))]
+    pub fn adjacencies(&self) -> Vec<Adjacency> {
+        let mut numbers = vec![Vec::new(); self.symbols.len()];
+        for (number, symbols) in self.numbers.iter().zip(self.touching()) {
+            for id in symbols {
+                numbers[id].push(number.value());
+            }
+        }
+        self.symbols
+            .iter()
+            .zip(numbers)
+            .map(|(symbol, numbers)| Adjacency {
+                symbol: symbol.clone(),
+                numbers,
+            })
+            .collect()
+    }
+
+    // Every '*' with the numbers touching it; those touching exactly two are
+    // gears, and their product is the gear ratio
+    pub fn gears(&self) -> Vec<Adjacency> {
+        self.adjacencies()
+            .into_iter()
+            .filter(|adjacency| adjacency.symbol.symbol == '*')
+            .collect()
+    }
+
+    // Sum of the gear ratios, '*'s touching exactly two numbers (part 2)
+    pub fn gear_ratio_sum(&self) -> u128 {
+        self.gears()
+            .iter()
+            .filter(|gear| gear.numbers.len() == 2)
+            .map(Adjacency::product)
+            .sum()
+    }
+
+    // All `symbol` characters touching exactly `k` numbers, e.g. ('*', 2) for gears
+    #[tracing::instrument(skip(self))]
+    pub fn query(&self, symbol: char, k: usize) -> AdjacencyQuery {
+        let matches: Vec<Adjacency> = self
+            .adjacencies()
+            .into_iter()
+            .filter(|adjacency| adjacency.symbol.symbol == symbol)
+            .filter(|adjacency| adjacency.numbers.len() == k)
+            .collect();
+
+        AdjacencyQuery {
+            sum_of_products: matches.iter().map(Adjacency::product).sum(),
+            sum: matches.iter().map(Adjacency::sum).sum(),
+            matches,
+        }
+    }
+}
+
+// Drawn back as a grid of '.', digits and symbols. The schematic doesn't keep
+// the input's size, so empty rows and columns past the last number or symbol
+// are dropped.
+impl fmt::Display for Schematic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self
+            .symbols
+            .iter()
+            .map(|symbol| (&symbol.position, symbol.symbol))
+            .chain(self.numbers.iter().flat_map(|number| {
+                number.0.iter().map(|numeral| {
+                    let digit = char::from_digit(numeral.value, 10).unwrap_or('?');
+                    (&numeral.position, digit)
+                })
+            }));
+        let mut grid: Vec<Vec<char>> = Vec::new();
+        for (position, ch) in cells {
+            if grid.len() <= position.row {
+                grid.resize(position.row + 1, Vec::new());
+            }
+            let row = &mut grid[position.row];
+            if row.len() <= position.col {
+                row.resize(position.col + 1, '.');
+            }
+            row[position.col] = ch;
+        }
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let lines: Vec<String> = grid
+            .into_iter()
+            .map(|mut row| {
+                row.resize(width, '.');
+                row.into_iter().collect()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = color_eyre::Report;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let lines: Vec<String> = input.lines().map(String::from).collect();
+        Ok(Schematic {
+            symbols: parse_symbols(&lines)?,
+            numbers: parse_numbers(&lines)?,
+        })
+    }
+}
+
+pub fn part1(input: &str) -> Result<u128> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 3, part = 1)]
+pub fn part1_with(input: &str, mode: ParseMode) -> Result<u128> {
+    Ok(parse_schematic_with(input, mode)?.part_sum())
+}
+
+pub fn part2(input: &str) -> Result<u128> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 3, part = 2)]
+pub fn part2_with(input: &str, mode: ParseMode) -> Result<u128> {
+    Ok(parse_schematic_with(input, mode)?.gear_ratio_sum())
+}
+
+// like `input.parse::<Schematic>()`, but numbers too long to read follow `mode`
+pub fn parse_schematic_with(input: &str, mode: ParseMode) -> Result<Schematic> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    Ok(Schematic {
+        symbols: parse_symbols(&lines)?,
+        numbers: parse_numbers_with(&lines, mode)?,
+    })
+}
+
+/// Day 3's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MapReader;
+
+impl Solver for MapReader {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+#[tracing::instrument]
+pub fn read_input(filename: &str) -> Result<Vec<String>> {
+    Ok(read_lines(filename)?)
+}
+
+// The input as a grid of characters; short lines are padded with '.', which
+// is neither a digit nor a symbol
+pub fn grid(input: &[String]) -> Grid<char> {
+    Grid::from_lines(input, '.', |ch| ch)
+}
+
+// Anything but '.', a digit or whitespace marks a part
+pub fn is_symbol(ch: char) -> bool {
+    ch != '.' && !ch.is_ascii_digit() && !ch.is_whitespace()
+}
+
+#[tracing::instrument]
+pub fn parse_symbols(input: &[String]) -> Result<Vec<Symbol>> {
+    let grid = grid(input);
+    let symbols = grid
+        .find_all(|&ch| is_symbol(ch))
+        .map(|point| Symbol {
+            position: Position::from(point),
+            symbol: grid[point],
+        })
+        .collect();
+
+    Ok(symbols)
+}
+
+#[tracing::instrument]
+pub fn parse_numbers(input: &[String]) -> Result<Vec<Number>> {
+    parse_numbers_with(input, ParseMode::Strict)
+}
+
+// A number too long for a u64 stops parsing in strict mode; in lenient mode
+// it is left out and reported
+#[tracing::instrument]
+pub fn parse_numbers_with(input: &[String], mode: ParseMode) -> Result<Vec<Number>> {
+    let mut numbers = Vec::<Number>::new();
+    let mut current_number = Vec::<Numeral>::new();
+
+    grid(input).rows().enumerate().for_each(|(row, cells)| {
+        cells.iter().enumerate().for_each(|(col, ch)| {
+            if let Some(digit) = ch.to_digit(10) {
+                let numeral = Numeral {
+                    position: Position { row, col },
+                    value: digit,
+                };
+                current_number.push(numeral);
+            } else if !current_number.is_empty() {
+                numbers.push(Number(current_number.clone()));
+                current_number.clear();
+            }
+        });
+
+        if !current_number.is_empty() {
+            numbers.push(Number(current_number.clone()));
+            current_number.clear();
+        };
+    });
+
+    let (numbers, too_long): (Vec<_>, Vec<_>) = numbers
+        .into_iter()
+        .partition(|number| number.checked_value().is_some());
+    let skipped: Vec<Malformed> = too_long
+        .iter()
+        .map(|number| {
+            let start = &number.0[0].position;
+            Malformed {
+                line: start.row + 1,
+                message: format!(
+                    "the {}-digit number at column {} is too long",
+                    number.0.len(),
+                    start.col + 1
+                ),
+            }
+        })
+        .collect();
+    match (mode, skipped.first()) {
+        (_, None) => {}
+        (ParseMode::Strict, Some(first)) => bail!("{}", first),
+        (ParseMode::Lenient, Some(_)) => {
+            let skipped: Vec<String> = skipped.iter().map(Malformed::to_string).collect();
+            warn!(
+                "Skipped {} numbers too long to read: {}",
+                skipped.len(),
+                skipped.join("; ")
+            );
+        }
+    }
+
+    Ok(numbers)
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong): the schematic is a grid, so every line should be as long as the first
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let Some(width) = input.first().map(|line| line.chars().count()) else {
+        return Vec::new();
+    };
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.chars().count()))
+        .filter(|&(_, length)| length != width)
+        .map(|(line, length)| (line, format!("{} characters, expected {}", length, width)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::conformance::round_trip;
+
+    // 467..114..
+    // ...*......
+    // ..35..633.
+    // ......#...
+    // 617*......
+    // .....+.58.
+    // ..592.....
+    // ......755.
+    // ...$.*....
+    // .664.598..
+
+    #[test]
+    fn test_read_input() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert_eq!(input.len(), 10);
+        assert_eq!(input[0], "467..114..");
+        assert_eq!(input[9], ".664.598..");
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        let input = read_input("../test-1.txt").unwrap();
+        let symbols = parse_symbols(&input).unwrap();
+        assert_eq!(symbols.len(), 6);
+        assert_eq!(symbols[0].position.row, 1);
+        assert_eq!(symbols[0].position.col, 3);
+        assert_eq!(symbols[0].symbol, '*');
+        assert_eq!(symbols[1].position.row, 3);
+        assert_eq!(symbols[1].position.col, 6);
+        assert_eq!(symbols[1].symbol, '#');
+        assert_eq!(symbols[2].position.row, 4);
+        assert_eq!(symbols[2].position.col, 3);
+    }
+
+    #[test]
+    fn test_border() {
+        let number = Number(vec![
+            Numeral {
+                position: Position { row: 2, col: 3 },
+                value: 4,
+            },
+            Numeral {
+                position: Position { row: 2, col: 4 },
+                value: 6,
+            },
+            Numeral {
+                position: Position { row: 2, col: 5 },
+                value: 7,
+            },
+            Numeral {
+                position: Position { row: 2, col: 6 },
+                value: 8,
+            },
+            Numeral {
+                position: Position { row: 2, col: 7 },
+                value: 9,
+            },
+        ]);
+        let border = number.border(Extent::new(10, 10));
+        assert_eq!(border.len(), 16);
+        // the same order every run: above, the row itself, then below
+        let first = border.first().unwrap();
+        assert_eq!(*first, Position { row: 1, col: 2 });
+        let last = border.last().unwrap();
+        assert_eq!(*last, Position { row: 3, col: 8 });
+        let rows: Vec<usize> = border.iter().map(|position| position.row).collect();
+        assert!(rows.is_sorted());
+
+        // a grid ending at the number's last column cuts off its right side
+        assert_eq!(number.border(Extent::new(8, 4)).len(), 13);
+    }
+
+    #[test]
+    fn test_border_at_edges() {
+        // "12" in the top left corner
+        let number = Number(vec![
+            Numeral {
+                position: Position { row: 0, col: 0 },
+                value: 1,
+            },
+            Numeral {
+                position: Position { row: 0, col: 1 },
+                value: 2,
+            },
+        ]);
+        assert!(number.border(Extent::new(2, 1)).is_empty());
+        let border: Vec<_> = number.border(Extent::new(3, 2)).into_iter().collect();
+        assert_eq!(
+            border,
+            vec![
+                Position { row: 0, col: 2 },
+                Position { row: 1, col: 0 },
+                Position { row: 1, col: 1 },
+                Position { row: 1, col: 2 },
+            ]
+        );
+        // a number off the grid has no border on it
+        assert!(number.border(Extent::new(0, 0)).is_empty());
+
+        // "12" in the bottom right corner
+        let number = Number(vec![
+            Numeral {
+                position: Position { row: 2, col: 3 },
+                value: 1,
+            },
+            Numeral {
+                position: Position { row: 2, col: 4 },
+                value: 2,
+            },
+        ]);
+        let border: Vec<_> = number.border(Extent::new(5, 3)).into_iter().collect();
+        assert_eq!(
+            border,
+            vec![
+                Position { row: 1, col: 2 },
+                Position { row: 1, col: 3 },
+                Position { row: 1, col: 4 },
+                Position { row: 2, col: 2 },
+            ]
+        );
+        assert_eq!(number.border(Extent::new(5, 5)).len(), 7);
+        // the "1" alone borders the cell the "2" was in
+        let alone = Number(vec![number.0[0].clone()]);
+        assert_eq!(alone.border(Extent::new(5, 3)).len(), 5);
+    }
+
+    #[test]
+    fn test_border_single_digit() {
+        let digit = |row, col| {
+            Number(vec![Numeral {
+                position: Position { row, col },
+                value: 7,
+            }])
+        };
+        let extent = Extent::new(3, 3);
+        // every side, not only the left
+        assert_eq!(digit(1, 1).border(extent).len(), 8);
+        assert!(digit(1, 1)
+            .border(extent)
+            .contains(&Position { row: 1, col: 2 }));
+        // three cells in each corner, five along each edge
+        for (row, col) in [(0, 0), (0, 2), (2, 0), (2, 2)] {
+            assert_eq!(
+                digit(row, col).border(extent).len(),
+                3,
+                "({}, {})",
+                row,
+                col
+            );
+        }
+        for (row, col) in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+            assert_eq!(
+                digit(row, col).border(extent).len(),
+                5,
+                "({}, {})",
+                row,
+                col
+            );
+        }
+        assert!(digit(0, 0).border(Extent::new(1, 1)).is_empty());
+
+        let schematic: Schematic = "....7*\n#3....".parse().unwrap();
+        let parts: Vec<u64> = schematic
+            .numbers
+            .iter()
+            .filter(|number| schematic.is_part_number(number))
+            .map(Number::value)
+            .collect();
+        assert_eq!(parts, vec![7, 3]);
+    }
+
+    #[test]
+    fn test_value() {
+        let number = Number(vec![
+            Numeral {
+                position: Position { row: 2, col: 3 },
+                value: 4,
+            },
+            Numeral {
+                position: Position { row: 2, col: 4 },
+                value: 6,
+            },
+            Numeral {
+                position: Position { row: 2, col: 5 },
+                value: 7,
+            },
+            Numeral {
+                position: Position { row: 2, col: 6 },
+                value: 8,
+            },
+            Numeral {
+                position: Position { row: 2, col: 7 },
+                value: 9,
+            },
+        ]);
+        assert_eq!(number.value(), 46789);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        // both numbers overflow a u32 and their gear ratio overflows a u64
+        let schematic: Schematic = "12345678901*98765432109".parse().unwrap();
+        assert_eq!(schematic.numbers[0].value(), 12_345_678_901);
+        let gears = schematic.query('*', 2);
+        assert_eq!(gears.sum_of_products, 12_345_678_901u128 * 98_765_432_109);
+
+        let err = parse_numbers(&[String::from("..123456789012345678901")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: the 21-digit number at column 3 is too long"
+        );
+    }
+
+    #[test]
+    fn test_parse_numbers_lenient() {
+        let input = [
+            String::from("..123456789012345678901"),
+            String::from("7...*12"),
+        ];
+        let numbers = parse_numbers_with(&input, ParseMode::Lenient).unwrap();
+        let values: Vec<u64> = numbers.iter().map(Number::value).collect();
+        assert_eq!(values, vec![7, 12]);
+        // the numbers kept are where they were
+        assert_eq!(numbers[1].0[0].position, Position { row: 1, col: 5 });
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        // the example's last column is empty
+        let trimmed: Vec<&str> = input.lines().map(|line| &line[..9]).collect();
+        assert_eq!(round_trip::<Schematic>(&input), trimmed.join("\n"));
+
+        assert_eq!(round_trip::<Schematic>("..\n.7*\n..."), "...\n.7*");
+        assert_eq!(round_trip::<Schematic>(""), "");
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let long = format!("1*{}", "9".repeat(10_000));
+        let inputs = [
+            "\0",
+            "*",
+            "*\n\n*",
+            "1*\n*",
+            "..9\n.\n*..",
+            "٣*٣",
+            "\u{1f468}\u{200d}\u{1f469}*7",
+            "7\r\n*\r\n",
+            long.as_str(),
+        ];
+        for input in inputs {
+            let result = std::panic::catch_unwind(|| {
+                let lines: Vec<String> = input.lines().map(String::from).collect();
+                validate(&lines);
+                if let Ok(schematic) = input.parse::<Schematic>() {
+                    schematic.query('*', 2);
+                    diagnostics::orphans(&lines, &schematic);
+                    svg::render(&lines, &schematic);
+                }
+                let _ = stream::scan(input.as_bytes());
+            });
+            assert!(result.is_ok(), "panicked on {:?}", input);
+        }
+        assert!(long.parse::<Schematic>().is_err());
+        assert!(stream::scan(long.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        let input = read_input("../test-1.txt").unwrap();
+        let result = parse_numbers(&input).unwrap();
+
+        assert_eq!(result.len(), 10); // Check if the number of numbers parsed is correct
+
+        // Check the first number
+        assert_eq!(result[0].0.len(), 3); // Check if the number of numerals in the first number is correct
+        assert_eq!(result[0].0[0].value, 4); // Check the value of the first numeral of the first number
+        assert_eq!(result[0].0[0].position, Position { row: 0, col: 0 }); // Check the position of the first numeral of the first number
+
+        // Check the sixth number
+        assert_eq!(result[5].0.len(), 2); // Check if the number of numerals in the sixth number is correct
+        assert_eq!(result[5].0[0].value, 5); // Check the value of the first numeral of the sixth number
+        assert_eq!(result[5].0[0].position, Position { row: 5, col: 7 }); // Check the position of the first numeral of the sixth number
+
+        // Add more assertions as needed to check the other numbers and numerals
+        assert_eq!(result[0].value(), 467);
+        assert_eq!(result[1].value(), 114);
+        assert_eq!(result[2].value(), 35);
+        assert_eq!(result[3].value(), 633);
+        assert_eq!(result[4].value(), 617);
+        assert_eq!(result[5].value(), 58);
+        assert_eq!(result[6].value(), 592);
+        assert_eq!(result[7].value(), 755);
+        assert_eq!(result[8].value(), 664);
+        assert_eq!(result[9].value(), 598);
+    }
+
+    #[test]
+    fn test_schematic_from_str() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.numbers[0].value(), 467);
+
+        let schematic = Schematic::from_str("..12\n.#..").unwrap();
+        assert_eq!(
+            schematic.symbols,
+            vec![Symbol {
+                position: Position { row: 1, col: 1 },
+                symbol: '#'
+            }]
+        );
+        assert_eq!(schematic.numbers[0].value(), 12);
+    }
+
+    #[test]
+    fn test_is_part_number() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+        let parts: Vec<u64> = schematic
+            .numbers
+            .iter()
+            .filter(|number| schematic.is_part_number(number))
+            .map(|number| number.value())
+            .collect();
+        assert_eq!(parts, vec![467, 35, 633, 617, 592, 755, 664, 598]);
+    }
+
+    #[test]
+    fn test_query() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+
+        // gears: 467 * 35 and 755 * 598
+        let gears = schematic.query('*', 2);
+        assert_eq!(gears.matches.len(), 2);
+        assert_eq!(gears.matches[0].numbers, vec![467, 35]);
+        assert_eq!(
+            gears.matches[1].symbol.position,
+            Position { row: 8, col: 5 }
+        );
+        assert_eq!(gears.sum_of_products, 467835);
+        assert_eq!(gears.sum, 467 + 35 + 755 + 598);
+
+        let lonely = schematic.query('*', 1);
+        assert_eq!(lonely.matches.len(), 1);
+        assert_eq!(lonely.sum_of_products, 617);
+
+        assert!(schematic.query('#', 2).matches.is_empty());
+        assert!(schematic.query('@', 0).matches.is_empty());
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = vec![String::from("..9"), String::from("."), String::from("*..")];
+        let grid = grid(&input);
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Position { row: 0, col: 2 }], '9');
+        // the short line is padded, so its missing cells are empty
+        assert_eq!(grid[&Position { row: 1, col: 2 }], '.');
+        assert_eq!(
+            parse_symbols(&input).unwrap()[0].position,
+            Position { row: 2, col: 0 }
+        );
+    }
+
+    #[test]
+    fn test_is_symbol() {
+        ['*', '#', '+', '$', '@', '/', '=', '%', '&', '-']
+            .iter()
+            .for_each(|&ch| assert!(is_symbol(ch), "{:?}", ch));
+        ['.', '0', '9', ' ', '\r']
+            .iter()
+            .for_each(|&ch| assert!(!is_symbol(ch), "{:?}", ch));
+
+        let input = vec![String::from("1@2/3"), String::from(".=.%.")];
+        let symbols: String = parse_symbols(&input)
+            .unwrap()
+            .iter()
+            .map(|s| s.symbol)
+            .collect();
+        assert_eq!(symbols, "@/=%");
+    }
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 4361);
+        assert_eq!(part2(&input).unwrap(), 467835);
+        assert_eq!(MapReader.part1(&input).unwrap(), "4361");
+        assert_eq!(MapReader.part2(&input).unwrap(), "467835");
+
+        // '@' and '/' count as much as '*' does
+        let input = "12@..\n.....\n../7.";
+        assert_eq!(part1(input).unwrap(), 19);
+        assert_eq!(part2(input).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_symbol_index() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+        let index = schematic.symbol_index();
+        assert_eq!(index.len(), 6);
+        assert_eq!(index.get(&Position { row: 1, col: 3 }), Some(&0));
+        assert_eq!(index.get(&Position { row: 3, col: 6 }), Some(&1));
+        assert_eq!(index.get(&Position { row: 0, col: 0 }), None);
+
+        // 467 touches the first '*', 114 nothing, 617 the '*' beside it; 58 is
+        // the other number touching nothing
+        let touching = schematic.touching();
+        assert_eq!(touching.len(), schematic.numbers.len());
+        assert_eq!(touching[0], vec![0]);
+        assert!(touching[1].is_empty());
+        assert_eq!(touching[4], vec![2]);
+        assert_eq!(
+            schematic.part_flags(),
+            vec![true, false, true, true, true, false, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_gears() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let schematic: Schematic = input.parse().unwrap();
+        let gears = schematic.gears();
+        let numbers: Vec<&[u64]> = gears.iter().map(|gear| gear.numbers.as_slice()).collect();
+        assert_eq!(numbers, vec![&[467, 35][..], &[617], &[755, 598]]);
+        assert_eq!(gears[1].symbol.position, Position { row: 4, col: 3 });
+        assert_eq!(schematic.gear_ratio_sum(), 467835);
+
+        // a '*' touching three numbers is no gear
+        let schematic: Schematic = "1.2\n.*.\n..3".parse().unwrap();
+        assert_eq!(schematic.gears()[0].numbers, vec![1, 2, 3]);
+        assert_eq!(schematic.gear_ratio_sum(), 0);
+        assert_eq!(schematic.adjacencies().len(), 1);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input = read_input("../test-1.txt").unwrap();
+        let numbers = parse_numbers(&input).unwrap();
+
+        let json = serde_json::to_string(&numbers).unwrap();
+        let result: Vec<Number> = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, numbers);
+
+        let symbol = Symbol {
+            position: Position { row: 1, col: 3 },
+            symbol: '*',
+        };
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, r#"{"position":{"row":1,"col":3},"symbol":"*"}"#);
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert!(validate(&input).is_empty());
+
+        // a truncated last row
+        let input = vec![
+            String::from("467.."),
+            String::from("...*."),
+            String::from("35"),
+        ];
+        assert_eq!(
+            validate(&input),
+            vec![(3, String::from("2 characters, expected 5"))]
+        );
+    }
+}
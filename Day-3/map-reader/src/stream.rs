@@ -0,0 +1,176 @@
+use crate::is_symbol;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::io::BufRead;
+
+// Part 1 and part 2 answers computed without holding the whole schematic
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub rows: usize,
+    pub numbers: usize,
+    pub part_numbers: usize,
+    // u128 so that no u64 gear ratio can wrap
+    pub part_sum: u128,
+    pub gears: usize,
+    pub gear_ratio_sum: u128,
+}
+
+// a run of digits within one row, columns inclusive
+#[derive(Debug)]
+struct Span {
+    start: usize,
+    end: usize,
+    value: u64,
+}
+
+impl Span {
+    fn touches(&self, col: usize) -> bool {
+        col + 1 >= self.start && col <= self.end + 1
+    }
+}
+
+#[derive(Debug, Default)]
+struct Row {
+    numbers: Vec<Span>,
+    symbols: Vec<(usize, char)>,
+}
+
+impl Row {
+    fn parse(line: &str) -> Result<Self> {
+        let mut row = Row::default();
+        let mut current: Option<Span> = None;
+
+        for (col, ch) in line.chars().enumerate() {
+            if let Some(digit) = ch.to_digit(10) {
+                let span = current.get_or_insert(Span {
+                    start: col,
+                    end: col,
+                    value: 0,
+                });
+                span.end = col;
+                span.value = span
+                    .value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit as u64))
+                    .ok_or_else(|| eyre!("the number at column {} is too long", span.start + 1))?;
+            } else {
+                row.numbers.extend(current.take());
+                if is_symbol(ch) {
+                    row.symbols.push((col, ch));
+                }
+            }
+        }
+        row.numbers.extend(current);
+
+        Ok(row)
+    }
+}
+
+// Stream a schematic through a 3-row window (previous, current, next).
+//
+// Anything adjacent to a cell lives in those three rows, so each row is
+// resolved as soon as the row after it has been read and memory stays
+// proportional to the line length rather than the file size.
+#[tracing::instrument(skip_all)]
+pub fn scan<R: BufRead>(reader: R) -> Result<Totals> {
+    let mut totals = Totals::default();
+    let mut previous = Row::default();
+    let mut current: Option<Row> = None;
+
+    for (i, line) in reader.lines().enumerate() {
+        let next = Row::parse(&line?).wrap_err_with(|| format!("line {}", i + 1))?;
+        if let Some(row) = current.take() {
+            totals.add(&previous, &row, &next);
+            previous = row;
+        }
+        current = Some(next);
+    }
+    if let Some(row) = current {
+        totals.add(&previous, &row, &Row::default());
+    }
+
+    Ok(totals)
+}
+
+impl Totals {
+    fn add(&mut self, previous: &Row, current: &Row, next: &Row) {
+        let window = [previous, current, next];
+        self.rows += 1;
+
+        current.numbers.iter().for_each(|number| {
+            self.numbers += 1;
+            let is_part = window
+                .iter()
+                .flat_map(|row| row.symbols.iter())
+                .any(|&(col, _)| number.touches(col));
+            if is_part {
+                self.part_numbers += 1;
+                self.part_sum += number.value as u128;
+            }
+        });
+
+        current
+            .symbols
+            .iter()
+            .filter(|&&(_, symbol)| symbol == '*')
+            .for_each(|&(col, _)| {
+                let adjacent: Vec<u128> = window
+                    .iter()
+                    .flat_map(|row| row.numbers.iter())
+                    .filter(|number| number.touches(col))
+                    .map(|number| number.value as u128)
+                    .collect();
+                if let [a, b] = adjacent[..] {
+                    self.gears += 1;
+                    self.gear_ratio_sum += a * b;
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_scan() {
+        let file = File::open("../test-1.txt").unwrap();
+        let totals = scan(BufReader::new(file)).unwrap();
+        assert_eq!(
+            totals,
+            Totals {
+                rows: 10,
+                numbers: 10,
+                part_numbers: 8,
+                part_sum: 4361,
+                gears: 2,
+                gear_ratio_sum: 467835,
+            }
+        );
+    }
+
+    #[test]
+    fn test_scan_edges() {
+        // single digits, numbers at the end of a line, and the first/last rows
+        let input = "1.2*3\n....7\n*...\n9";
+        let totals = scan(input.as_bytes()).unwrap();
+        assert_eq!(totals.rows, 4);
+        assert_eq!(totals.numbers, 5);
+        assert_eq!(totals.part_sum, 2 + 3 + 7 + 9);
+        assert_eq!(totals.gear_ratio_sum, 0);
+
+        assert_eq!(scan("".as_bytes()).unwrap(), Totals::default());
+    }
+
+    #[test]
+    fn test_scan_large_numbers() {
+        let totals = scan("12345678901*98765432109".as_bytes()).unwrap();
+        assert_eq!(totals.part_sum, 12_345_678_901 + 98_765_432_109);
+        assert_eq!(totals.gear_ratio_sum, 12_345_678_901 * 98_765_432_109);
+
+        let err = scan("1.\n.123456789012345678901".as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("line 2"));
+        assert!(format!("{:?}", err).contains("the number at column 2 is too long"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{grid, Schematic, Symbol};
+
+// size of one grid cell in pixels
+const CELL: usize = 16;
+
+const BACKGROUND: &str = "#ffffff";
+const PART_FILL: &str = "#b7e4c7";
+const ORPHAN_FILL: &str = "#e9ecef";
+const SYMBOL_FILL: &str = "#ffd6a5";
+const GEAR_FILL: &str = "#ffadad";
+const GEAR_STROKE: &str = "#d00000";
+
+// Render the schematic as an SVG document.
+//
+// Numbers adjacent to a symbol (part numbers) are shaded green, the rest grey.
+// Symbols are shaded orange, and `*` symbols touching exactly two numbers
+// (gears) are circled in red. Every number and symbol carries a <title>
+// tooltip so the file can be inspected in a browser.
+pub fn render(lines: &[String], schematic: &Schematic) -> String {
+    let grid = grid(lines);
+    let (width, height) = (grid.width() * CELL, grid.height() * CELL);
+
+    let parts = schematic.part_flags();
+    let adjacencies = schematic.adjacencies();
+
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="monospace" font-size="{}">"#,
+        width,
+        height,
+        width,
+        height,
+        CELL * 3 / 4
+    ));
+    svg.push('\n');
+    svg.push_str(&format!(
+        r#"<rect width="{}" height="{}" fill="{}"/>"#,
+        width, height, BACKGROUND
+    ));
+    svg.push('\n');
+
+    // numbers
+    schematic
+        .numbers
+        .iter()
+        .zip(parts)
+        .for_each(|(number, is_part)| {
+            let first = &number.0[0].position;
+            let (class, fill, verdict) = if is_part {
+                ("part", PART_FILL, "part number")
+            } else {
+                ("orphan", ORPHAN_FILL, "not adjacent to a symbol")
+            };
+            svg.push_str(&format!(
+                r#"<rect class="{}" x="{}" y="{}" width="{}" height="{}" fill="{}"><title>{} at ({}, {}): {}</title></rect>"#,
+                class,
+                first.col * CELL,
+                first.row * CELL,
+                number.0.len() * CELL,
+                CELL,
+                fill,
+                number.value(),
+                first.row,
+                first.col,
+                verdict
+            ));
+            svg.push('\n');
+        });
+
+    // symbols and gears
+    adjacencies.iter().for_each(|adjacency| {
+        let Symbol { position, symbol } = &adjacency.symbol;
+        let symbol = *symbol;
+        let adjacent = adjacency.numbers.len();
+        let (x, y) = (position.col * CELL, position.row * CELL);
+
+        if symbol == '*' && adjacent == 2 {
+            svg.push_str(&format!(
+                r#"<circle class="gear" cx="{}" cy="{}" r="{}" fill="{}" stroke="{}" stroke-width="2"><title>gear at ({}, {})</title></circle>"#,
+                x + CELL / 2,
+                y + CELL / 2,
+                CELL / 2,
+                GEAR_FILL,
+                GEAR_STROKE,
+                position.row,
+                position.col
+            ));
+        } else {
+            svg.push_str(&format!(
+                r#"<rect class="symbol" x="{}" y="{}" width="{}" height="{}" fill="{}"><title>{} at ({}, {}): {} adjacent numbers</title></rect>"#,
+                x,
+                y,
+                CELL,
+                CELL,
+                SYMBOL_FILL,
+                escape(symbol),
+                position.row,
+                position.col,
+                adjacent
+            ));
+        }
+        svg.push('\n');
+    });
+
+    // the characters themselves, skipping empty '.' cells
+    grid.find_all(|&ch| ch != '.').for_each(|point| {
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            point.col * CELL + CELL / 2,
+            point.row * CELL + CELL / 2,
+            escape(grid[point])
+        ));
+        svg.push('\n');
+    });
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape(ch: char) -> String {
+    match ch {
+        '&' => String::from("&amp;"),
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        '"' => String::from("&quot;"),
+        _ => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn test_render() {
+        let input = read_input("../test-1.txt").unwrap();
+        let schematic: Schematic = input.join("\n").parse().unwrap();
+        let svg = render(&input, &schematic);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"width="160" height="160""#));
+        // 114 and 58 are the only numbers not touching a symbol
+        assert_eq!(svg.matches(r#"class="part""#).count(), 8);
+        assert_eq!(svg.matches(r#"class="orphan""#).count(), 2);
+        assert!(svg.contains("114 at (0, 5): not adjacent to a symbol"));
+        // 467 * 35 and 755 * 598
+        assert_eq!(svg.matches(r#"class="gear""#).count(), 2);
+        assert_eq!(svg.matches(r#"class="symbol""#).count(), 4);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape('&'), "&amp;");
+        assert_eq!(escape('<'), "&lt;");
+        assert_eq!(escape('#'), "#");
+    }
+}
@@ -0,0 +1,212 @@
+use aoc_common::graph::Graph;
+use aoc_common::grid::{Grid, Point};
+use aoc_common::visualize::{Animate, Glyph, Inspect, Scene, Style, Visualize};
+
+use crate::{Number, Position, Schematic};
+
+impl Schematic {
+    // The schematic as scanned down to (not including) row `scanned`: part
+    // numbers above it are highlighted, other numbers above it plain, and
+    // anything below dimmed. Symbols are always accented. The grid only
+    // reaches as far as the last number or symbol.
+    fn draw(&self, scanned: usize) -> Grid<Glyph> {
+        let extent = self.extent();
+        let mut grid = Grid::new(extent.width, extent.height, Glyph::new('.', Style::Dim));
+        for (number, is_part) in self.numbers.iter().zip(self.part_flags()) {
+            let style = if number.0[0].position.row >= scanned {
+                Style::Dim
+            } else if is_part {
+                Style::Highlight
+            } else {
+                Style::Plain
+            };
+            for numeral in &number.0 {
+                let ch = char::from_digit(numeral.value, 10).unwrap_or('?');
+                grid[&numeral.position] = Glyph::new(ch, style);
+            }
+        }
+        for symbol in &self.symbols {
+            grid[&symbol.position] = Glyph::new(symbol.symbol, Style::Accent);
+        }
+
+        grid
+    }
+}
+
+impl Visualize for Schematic {
+    fn visualize(&self) -> Scene {
+        Scene::Grid(self.draw(usize::MAX))
+    }
+}
+
+// One frame per row, judging each row's numbers as the scan passes it
+impl Animate for Schematic {
+    fn frames(&self) -> Vec<Scene> {
+        (1..=self.extent().height)
+            .map(|scanned| Scene::Grid(self.draw(scanned)))
+            .collect()
+    }
+}
+
+// What a cell holds and how it takes part in adjacency: a number with the
+// symbols on its border, a symbol with the numbers around it, or a blank cell
+// with the numbers whose border covers it
+impl Inspect for Schematic {
+    fn inspect(&self, point: Point) -> Vec<(String, String)> {
+        let position = Position::from(point);
+        let mut details = Vec::new();
+
+        if let Some(number) = self
+            .numbers
+            .iter()
+            .find(|number| number.0.iter().any(|numeral| numeral.position == position))
+        {
+            let border = number.border(self.extent());
+            let touching: Vec<String> = self
+                .symbols
+                .iter()
+                .filter(|symbol| border.contains(&symbol.position))
+                .map(|symbol| format!("{} {}", symbol.symbol, describe(&symbol.position)))
+                .collect();
+            details.push((String::from("number"), number.value().to_string()));
+            details.push((
+                String::from("part number"),
+                self.is_part_number(number).to_string(),
+            ));
+            details.push((String::from("border cells"), border.len().to_string()));
+            details.push((String::from("touches"), list(touching)));
+        } else if let Some(symbol) = self.symbols.iter().find(|s| s.position == position) {
+            details.push((String::from("symbol"), symbol.symbol.to_string()));
+            details.push((String::from("numbers"), list(self.bordering(&position))));
+        } else {
+            let bordering = self.bordering(&position);
+            if !bordering.is_empty() {
+                details.push((String::from("border of"), list(bordering)));
+            }
+        }
+
+        details
+    }
+}
+
+impl Schematic {
+    /// Numbers and symbols as nodes, joined where a symbol sits on a
+    /// number's border, with a label for each node.
+    pub fn adjacency_graph(&self) -> (Graph, Vec<String>) {
+        let mut graph = Graph::new(self.numbers.len() + self.symbols.len());
+        let mut labels: Vec<String> = self
+            .numbers
+            .iter()
+            .map(|number| format!("{} {}", number.value(), describe(&number.0[0].position)))
+            .collect();
+        labels.extend(
+            self.symbols
+                .iter()
+                .map(|symbol| format!("{} {}", symbol.symbol, describe(&symbol.position))),
+        );
+
+        for (i, symbols) in self.touching().into_iter().enumerate() {
+            for j in symbols {
+                graph.add_undirected(i, self.numbers.len() + j, 1);
+            }
+        }
+
+        (graph, labels)
+    }
+
+    // values of the numbers whose border includes `position`
+    fn bordering(&self, position: &Position) -> Vec<String> {
+        let extent = self.extent();
+        self.numbers
+            .iter()
+            .filter(|number| number.border(extent).contains(position))
+            .map(Number::value)
+            .map(|value| value.to_string())
+            .collect()
+    }
+}
+
+fn describe(position: &Position) -> String {
+    format!("({}, {})", position.row, position.col)
+}
+
+fn list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        String::from("-")
+    } else {
+        items.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Schematic {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_visualize() {
+        let Scene::Grid(grid) = example().visualize() else {
+            panic!("expected a grid scene");
+        };
+
+        // nothing sits in the last column of the example
+        assert_eq!((grid.width(), grid.height()), (9, 10));
+        // 467 touches the '*' below it, 114 touches nothing
+        assert_eq!(grid[Point::new(0, 0)], Glyph::new('4', Style::Highlight));
+        assert_eq!(grid[Point::new(0, 5)], Glyph::new('1', Style::Plain));
+        assert_eq!(grid[Point::new(1, 3)], Glyph::new('*', Style::Accent));
+        assert_eq!(grid[Point::new(0, 3)].style, Style::Dim);
+    }
+
+    #[test]
+    fn test_frames() {
+        let schematic = example();
+        let frames = schematic.frames();
+        assert_eq!(frames.len(), 10);
+        assert_eq!(frames.last(), Some(&schematic.visualize()));
+
+        // after the first row only 467 and 114 have been judged
+        let Scene::Grid(first) = &frames[0] else {
+            panic!("expected a grid scene");
+        };
+        assert_eq!(first[Point::new(0, 0)].style, Style::Highlight);
+        assert_eq!(first[Point::new(2, 2)], Glyph::new('3', Style::Dim));
+    }
+
+    #[test]
+    fn test_inspect() {
+        let schematic = example();
+        let detail = |row, col, name: &str| {
+            schematic
+                .inspect(Point::new(row, col))
+                .into_iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value)
+        };
+
+        assert_eq!(detail(0, 1, "number").as_deref(), Some("467"));
+        assert_eq!(detail(0, 1, "touches").as_deref(), Some("* (1, 3)"));
+        assert_eq!(detail(0, 5, "part number").as_deref(), Some("false"));
+        assert_eq!(detail(1, 3, "numbers").as_deref(), Some("467, 35"));
+        assert_eq!(detail(1, 2, "border of").as_deref(), Some("467, 35"));
+        assert!(schematic.inspect(Point::new(6, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_adjacency_graph() {
+        let (graph, labels) = example().adjacency_graph();
+        // ten numbers and six symbols; 114 and 58 touch nothing
+        assert_eq!(graph.len(), 16);
+        assert_eq!(labels[0], "467 (0, 0)");
+        assert_eq!(labels[10], "* (1, 3)");
+        assert_eq!(graph.edges().count(), 16);
+        assert!(graph.neighbors(1).is_empty());
+
+        let dot = graph.to_dot("day3", |i| labels[i].clone());
+        assert!(dot.contains("0 -- 10 [label=\"1\"];"));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{parse_data, Card};
+use aoc_common::shuffle::Shuffler;
+use color_eyre::eyre::Result;
+use std::collections::{BTreeSet, HashMap};
+
+/// The cards in their order, since copies are won by position, with every
+/// number swapped for another number of the input and each side's numbers
+/// shuffled. The swap is one-to-one, so each card keeps its matches, and
+/// both answers stay the same.
+pub fn anonymize(lines: &[String], seed: u64) -> Result<Vec<String>> {
+    let mut rng = Shuffler::new(seed);
+    let mut cards = parse_data(lines.to_vec())?;
+
+    let numbers: Vec<u32> = cards
+        .iter()
+        .flat_map(|card| card.winning.iter().chain(&card.have))
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut relabeled = numbers.clone();
+    rng.shuffle(&mut relabeled);
+    let relabel: HashMap<u32, u32> = numbers.into_iter().zip(relabeled).collect();
+
+    for card in &mut cards {
+        for numbers in [&mut card.winning, &mut card.have] {
+            for number in numbers.iter_mut() {
+                *number = relabel[number];
+            }
+            rng.shuffle(numbers);
+        }
+    }
+    Ok(cards.iter().map(Card::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, read_input};
+
+    #[test]
+    fn test_anonymize() {
+        let lines = read_input("../test-1.txt").unwrap();
+        let anonymized = anonymize(&lines, 42).unwrap();
+        assert_eq!(anonymized.len(), lines.len());
+        assert_ne!(anonymized, lines);
+        let (input, shared) = (lines.join("\n"), anonymized.join("\n"));
+        assert_eq!(part1(&shared).unwrap(), part1(&input).unwrap());
+        assert_eq!(part2(&shared).unwrap(), part2(&input).unwrap());
+        assert_eq!(anonymize(&lines, 42).unwrap(), anonymized);
+        assert!(anonymize(&[String::from("Card one")], 42).is_err());
+    }
+}
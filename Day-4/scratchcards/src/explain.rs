@@ -0,0 +1,75 @@
+use crate::{copies, total_cards, total_points, Card};
+use aoc_common::explain::Narrative;
+
+/// Every card's matching numbers, its points and how many copies of it end
+/// up won, then the totals.
+pub fn explain(cards: &[Card]) -> Narrative {
+    let mut narrative = Narrative::new();
+    narrative.section("Cards");
+    let counts = copies(cards).ok();
+    for (i, card) in cards.iter().enumerate() {
+        let matching: Vec<String> = card
+            .have
+            .iter()
+            .filter(|number| card.winning.contains(number))
+            .map(u32::to_string)
+            .collect();
+        let matching = if matching.is_empty() {
+            String::from("no matches")
+        } else {
+            format!("{} matching ({})", matching.len(), matching.join(", "))
+        };
+        let points = card
+            .points()
+            .map_or(String::from("too many"), |points| points.to_string());
+        let copies = counts
+            .as_ref()
+            .map_or(String::from("too many"), |counts| counts[i].to_string());
+        narrative.line(format!(
+            "Card {}: {}, {} points, {} copies",
+            card.id, matching, points, copies
+        ));
+    }
+
+    let total = |total: color_eyre::Result<u64>| {
+        total.map_or_else(|err| err.to_string(), |total| total.to_string())
+    };
+    narrative
+        .section("Answers")
+        .line(format!(
+            "part 1: the points add up to {}",
+            total(total_points(cards))
+        ))
+        .line(format!(
+            "part 2: the copies add up to {} cards",
+            total(total_cards(cards))
+        ));
+    narrative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_data, read_input};
+
+    #[test]
+    fn test_explain() {
+        let cards = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let narrative = explain(&cards);
+        let [lines, answers] = narrative.sections() else {
+            panic!("expected two sections");
+        };
+        assert_eq!(
+            lines.lines[0],
+            "Card 1: 4 matching (83, 86, 17, 48), 8 points, 1 copies"
+        );
+        assert_eq!(lines.lines[5], "Card 6: no matches, 0 points, 1 copies");
+        assert_eq!(
+            answers.lines,
+            [
+                "part 1: the points add up to 13",
+                "part 2: the copies add up to 30 cards"
+            ]
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use aoc_common::input::read_lines;
+use aoc_common::parse::{parse_lines, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+pub mod anonymize;
+pub mod explain;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, space1},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+// One scratchcard: the winning numbers left of the '|' and the numbers we
+// have right of it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Card {
+    pub id: usize,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Card {
+    // How many of the numbers we have are winning numbers
+    pub fn matches(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+
+    // 1 point for the first match, doubled for every match after it; None
+    // when that doesn't fit in a u64
+    pub fn points(&self) -> Option<u64> {
+        match self.matches() {
+            0 => Some(0),
+            n => u32::try_from(n - 1)
+                .ok()
+                .and_then(|shift| 1u64.checked_shl(shift)),
+        }
+    }
+}
+
+// Numbers right-aligned in two columns, as the puzzle prints them
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let numbers = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        write!(
+            f,
+            "Card {}: {} | {}",
+            self.id,
+            numbers(&self.winning),
+            numbers(&self.have)
+        )
+    }
+}
+
+impl FromStr for Card {
+    type Err = color_eyre::Report;
+
+    fn from_str(line: &str) -> Result<Self> {
+        match parse_card(line) {
+            Ok(("", card)) => Ok(card),
+            Ok((rest, _)) => Err(eyre!("unexpected trailing input {:?}", rest)),
+            Err(e) => Err(eyre!("invalid card {:?}: {}", line, e)),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub fn read_input(filename: &str) -> Result<Vec<String>> {
+    Ok(read_lines(filename)?)
+}
+
+// Sum of every card's points (part 1)
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 4, part = 1)]
+pub fn part1_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_points(&parse_data_with(input, mode)?)
+}
+
+// How many cards there are once every win has been copied (part 2)
+pub fn part2(input: &str) -> Result<u64> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 4, part = 2)]
+pub fn part2_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_cards(&parse_data_with(input, mode)?)
+}
+
+/// Day 4's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Scratchcards;
+
+impl Solver for Scratchcards {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+// The totals are checked, so a card with absurdly many matches is reported
+// rather than wrapping around
+pub fn total_points(cards: &[Card]) -> Result<u64> {
+    cards.iter().try_fold(0u64, |acc, card| {
+        let points = card
+            .points()
+            .ok_or_else(|| eyre!("the points of card {} overflow", card.id))?;
+        acc.checked_add(points)
+            .ok_or_else(|| eyre!("the total points overflow at card {}", card.id))
+    })
+}
+
+// Each card wins one copy of each of the next `matches` cards, for every copy
+// of it there is. Copies only ever go forward, so one pass in order settles
+// each card's count before it is needed; wins past the last card are dropped.
+pub fn copies(cards: &[Card]) -> Result<Vec<u64>> {
+    let mut copies = vec![1u64; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let end = cards.len().min(i + 1 + card.matches());
+        for j in i + 1..end {
+            copies[j] = copies[j]
+                .checked_add(copies[i])
+                .ok_or_else(|| eyre!("the copies of card {} overflow", cards[j].id))?;
+        }
+    }
+    Ok(copies)
+}
+
+// How many cards there are once every card's copies are counted
+pub fn total_cards(cards: &[Card]) -> Result<u64> {
+    copies(cards)?.iter().try_fold(0u64, |acc, &count| {
+        acc.checked_add(count)
+            .ok_or_else(|| eyre!("the total number of cards overflows"))
+    })
+}
+
+// Parse numbers separated by one or more spaces
+pub fn parse_numbers(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(space1, map_res(digit1, str::parse::<u32>))(input)
+}
+
+// Parse a card; ids and numbers are right-aligned, so any run of spaces
+// separates them
+#[tracing::instrument(skip_all)]
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, _) = tuple((tag("Card"), space1))(input)?;
+    let (input, id) = map_res(digit1, str::parse::<usize>)(input)?;
+    let (input, _) = tuple((tag(":"), space1))(input)?;
+    let (input, winning) = parse_numbers(input)?;
+    let (input, _) = tuple((space1, tag("|"), space1))(input)?;
+    let (input, have) = parse_numbers(input)?;
+    Ok((input, Card { id, winning, have }))
+}
+
+// parse a vector of cards
+#[tracing::instrument]
+pub fn parse_data(input: Vec<String>) -> Result<Vec<Card>> {
+    parse_data_with(input, ParseMode::Strict)
+}
+
+#[tracing::instrument]
+pub fn parse_data_with(input: Vec<String>, mode: ParseMode) -> Result<Vec<Card>> {
+    // the whole line has to be a card, so nothing after it is silently dropped
+    let parsed = parse_lines(&input, mode, |line| line.parse::<Card>())?;
+    if parsed.blank > 0 {
+        warn!("Skipped {} blank lines", parsed.blank);
+    }
+    if let Some(summary) = parsed.summary() {
+        warn!("{}", summary);
+    }
+    Ok(parsed.items)
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong): cards out of order, a number listed twice on one side, or fewer or
+// more numbers than the first card has, as a truncated line would
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let mut findings = Vec::new();
+    let mut first: Option<(usize, usize)> = None;
+    let mut last_id = None;
+    for (i, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(card) = line.parse::<Card>() else {
+            findings.push((
+                i + 1,
+                String::from("expected \"Card <id>: <winning> | <have>\""),
+            ));
+            continue;
+        };
+
+        if let Some(last) = last_id.filter(|&last| card.id <= last) {
+            findings.push((i + 1, format!("card {} comes after card {}", card.id, last)));
+        }
+        last_id = Some(card.id);
+
+        let counts = (card.winning.len(), card.have.len());
+        let (winning, have) = *first.get_or_insert(counts);
+        if counts != (winning, have) {
+            findings.push((
+                i + 1,
+                format!(
+                    "{} winning and {} held numbers, expected {} and {}",
+                    counts.0, counts.1, winning, have
+                ),
+            ));
+        }
+        for (side, numbers) in [("winning", &card.winning), ("held", &card.have)] {
+            let mut seen = std::collections::HashSet::new();
+            for number in numbers.iter().filter(|&&number| !seen.insert(number)) {
+                findings.push((
+                    i + 1,
+                    format!("{} is listed twice among the {} numbers", number, side),
+                ));
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_read_input() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert_eq!(input.len(), 6);
+        assert_eq!(input[2], "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1");
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let card: Card = "Card   3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+            .parse()
+            .unwrap();
+        assert_eq!(card.id, 3);
+        assert_eq!(card.winning, vec![1, 21, 53, 59, 44]);
+        assert_eq!(card.have, vec![69, 82, 63, 72, 16, 21, 14, 1]);
+
+        assert!("Card 1: 41 48 | 83 86 | 17".parse::<Card>().is_err());
+        assert!("Card 1: 41 48 |".parse::<Card>().is_err());
+        assert!("Card 1: | 83 86".parse::<Card>().is_err());
+        assert!("Card x: 41 | 83".parse::<Card>().is_err());
+        assert!("Game 1: 41 | 83".parse::<Card>().is_err());
+    }
+
+    #[test_case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 4, 8)]
+    #[test_case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", 2, 2)]
+    #[test_case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", 2, 2)]
+    #[test_case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1, 1)]
+    #[test_case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0, 0)]
+    fn test_points(line: &str, matches: usize, points: u64) {
+        let card: Card = line.parse().unwrap();
+        assert_eq!(card.matches(), matches);
+        assert_eq!(card.points(), Some(points));
+    }
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 13);
+        assert_eq!(part2(&input).unwrap(), 30);
+        assert_eq!(Scratchcards.part1(&input).unwrap(), "13");
+        assert_eq!(Scratchcards.part2(&input).unwrap(), "30");
+    }
+
+    #[test]
+    fn test_total_cards() {
+        let input = read_input("../test-1.txt").unwrap();
+        let cards = parse_data(input).unwrap();
+        assert_eq!(total_cards(&cards).unwrap(), 30);
+        // wins past the last card are dropped
+        assert_eq!(total_cards(&cards[..2]).unwrap(), 3);
+        assert_eq!(total_cards(&[]).unwrap(), 0);
+        assert_eq!(copies(&cards).unwrap(), vec![1, 2, 4, 8, 14, 1]);
+    }
+
+    #[test]
+    fn test_serde() {
+        let cards = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let json = serde_json::to_string(&cards).unwrap();
+        let result: Vec<Card> = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, cards);
+
+        let card: Card = "Card 7: 41 48 | 83 86".parse().unwrap();
+        assert_eq!(
+            serde_json::to_string(&card).unwrap(),
+            r#"{"id":7,"winning":[41,48],"have":[83,86]}"#
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let input = read_input("../test-1.txt").unwrap();
+        for line in &input {
+            assert_eq!(line.parse::<Card>().unwrap().to_string(), *line);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert!(validate(&input).is_empty());
+
+        let input = vec![
+            String::from("Card 2: 41 48 | 83 86"),
+            String::from("Card 1: 41 41 | 83"),
+            String::from("Card 3 41 | 83"),
+        ];
+        assert_eq!(
+            validate(&input),
+            vec![
+                (2, String::from("card 1 comes after card 2")),
+                (
+                    2,
+                    String::from("2 winning and 1 held numbers, expected 2 and 2")
+                ),
+                (
+                    2,
+                    String::from("41 is listed twice among the winning numbers")
+                ),
+                (
+                    3,
+                    String::from("expected \"Card <id>: <winning> | <have>\"")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let numbers: Vec<u32> = (1..=65).collect();
+        let card = Card {
+            id: 1,
+            winning: numbers.clone(),
+            have: numbers,
+        };
+        assert_eq!(card.points(), None);
+        let err = total_points(&[card]).unwrap_err();
+        assert_eq!(err.to_string(), "the points of card 1 overflow");
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "Card 1: 41 48 | 48 2\nCard two: 1 | 1\nCard 3: 7 | 7 8";
+        let err = part1(input).unwrap_err();
+        assert!(err.to_string().starts_with("line 2: invalid card"));
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 2);
+    }
+}
@@ -0,0 +1,6 @@
+aoc_common::aoc_main!(
+    day = 4,
+    parse = scratchcards::parse_data,
+    part1 = scratchcards::total_points,
+    part2 = scratchcards::total_cards
+);
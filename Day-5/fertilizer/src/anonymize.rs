@@ -0,0 +1,51 @@
+use crate::{parse_data, rule_line};
+use aoc_common::shuffle::Shuffler;
+use color_eyre::eyre::Result;
+
+/// The almanac with its seed ranges and each map's rules in a new order.
+/// Part 1 takes the lowest over all seeds and part 2 over all ranges, and a
+/// map's rules never overlap, so neither order changes an answer. An odd
+/// seeds line, which has no ranges, has its seeds shuffled one by one.
+pub fn anonymize(lines: &[String], seed: u64) -> Result<Vec<String>> {
+    let mut rng = Shuffler::new(seed);
+    let mut almanac = parse_data(lines.to_vec())?;
+
+    let seeds = if almanac.seeds.len().is_multiple_of(2) {
+        let mut pairs: Vec<&[i64]> = almanac.seeds.chunks(2).collect();
+        rng.shuffle(&mut pairs);
+        pairs.concat()
+    } else {
+        rng.shuffle(&mut almanac.seeds);
+        almanac.seeds.clone()
+    };
+    let seeds: Vec<String> = seeds.iter().map(i64::to_string).collect();
+
+    let mut out = vec![format!("seeds: {}", seeds.join(" "))];
+    for stage in &almanac.stages {
+        let mut rules: Vec<String> = stage.map.rules().iter().map(rule_line).collect();
+        rng.shuffle(&mut rules);
+        out.push(String::new());
+        out.push(format!("{}-to-{} map:", stage.from, stage.to));
+        out.extend(rules);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_anonymize() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        let lines: Vec<String> = input.lines().map(String::from).collect();
+        let anonymized = anonymize(&lines, 42).unwrap();
+        assert_ne!(anonymized, lines);
+        let shared = anonymized.join("\n");
+        assert_eq!(part1(&shared).unwrap(), part1(&input).unwrap());
+        assert_eq!(part2(&shared).unwrap(), part2(&input).unwrap());
+        assert_eq!(anonymize(&lines, 42).unwrap(), anonymized);
+        assert!(anonymize(&[String::from("seeds: one")], 42).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{lowest_location, lowest_range_location, Almanac};
+use aoc_common::explain::Narrative;
+use aoc_common::interval::IntervalSet;
+
+/// Every seed on the seeds line followed through each map, the lowest
+/// location each seed range reaches, then the answers.
+pub fn explain(almanac: &Almanac) -> Narrative {
+    let mut narrative = Narrative::new();
+    narrative.section("Seeds");
+    for &seed in &almanac.seeds {
+        let mut value = seed;
+        let steps: Vec<String> = almanac
+            .stages
+            .iter()
+            .map(|stage| {
+                value = stage.map.map(value);
+                format!("{} {}", stage.to, value)
+            })
+            .collect();
+        narrative.line(format!("Seed {}: {}", seed, steps.join(", ")));
+    }
+
+    narrative.section("Seed ranges");
+    match almanac.seed_ranges() {
+        Ok(ranges) => {
+            for &range in ranges.intervals() {
+                let lowest = almanac
+                    .locations(&IntervalSet::from_iter([range]))
+                    .min()
+                    .map_or(String::from("none"), |lowest| lowest.to_string());
+                narrative.line(format!(
+                    "Seeds {}..{}: lowest location {}",
+                    range.start, range.end, lowest
+                ));
+            }
+        }
+        Err(err) => {
+            narrative.line(err.to_string());
+        }
+    }
+
+    let answer = |answer: color_eyre::Result<i64>| {
+        answer.map_or_else(|err| err.to_string(), |answer| answer.to_string())
+    };
+    narrative
+        .section("Answers")
+        .line(format!(
+            "part 1: the lowest location of a seed is {}",
+            answer(lowest_location(almanac))
+        ))
+        .line(format!(
+            "part 2: the lowest location of a seed range is {}",
+            answer(lowest_range_location(almanac))
+        ));
+    narrative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let almanac: Almanac = std::fs::read_to_string("../test-1.txt")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let narrative = explain(&almanac);
+        let [seeds, ranges, answers] = narrative.sections() else {
+            panic!("expected three sections");
+        };
+        assert_eq!(
+            seeds.lines[0],
+            "Seed 79: soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82"
+        );
+        assert_eq!(
+            ranges.lines,
+            [
+                "Seeds 55..68: lowest location 56",
+                "Seeds 79..93: lowest location 46"
+            ]
+        );
+        assert_eq!(
+            answers.lines,
+            [
+                "part 1: the lowest location of a seed is 35",
+                "part 2: the lowest location of a seed range is 46"
+            ]
+        );
+    }
+}
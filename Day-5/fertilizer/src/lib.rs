@@ -0,0 +1,572 @@
+use aoc_common::interval::{Interval, IntervalMap, IntervalSet, Mapping};
+use aoc_common::parse::{Malformed, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{bail, eyre, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+use tracing::warn;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, space1},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+pub mod anonymize;
+pub mod explain;
+
+// One map of the almanac: how `from` numbers become `to` numbers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stage {
+    pub from: String,
+    pub to: String,
+    #[serde(
+        serialize_with = "serialize_rules",
+        deserialize_with = "deserialize_rules"
+    )]
+    pub map: IntervalMap,
+}
+
+// A map as the almanac writes it, [destination, source, length] per rule
+fn serialize_rules<S: Serializer>(map: &IntervalMap, serializer: S) -> Result<S::Ok, S::Error> {
+    let rules: Vec<[i64; 3]> = map.rules().iter().map(rule_numbers).collect();
+    rules.serialize(serializer)
+}
+
+// Rules read back through the parser's checks, so overlapping or overflowing
+// ones are an error rather than a panic
+fn deserialize_rules<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IntervalMap, D::Error> {
+    let mut map = IntervalMap::new();
+    for [destination, source, len] in Vec::<[i64; 3]>::deserialize(deserializer)? {
+        add_rule(&mut map, destination, source, len).map_err(serde::de::Error::custom)?;
+    }
+    Ok(map)
+}
+
+// The seeds line and the maps after it, chained from seed to location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub stages: Vec<Stage>,
+}
+
+impl Almanac {
+    // `seed` run through every map in turn
+    pub fn location(&self, seed: i64) -> i64 {
+        self.stages
+            .iter()
+            .fold(seed, |value, stage| stage.map.map(value))
+    }
+
+    // The seeds line read as (start, length) pairs
+    pub fn seed_ranges(&self) -> Result<IntervalSet> {
+        if !self.seeds.len().is_multiple_of(2) {
+            bail!(
+                "the seeds line has {} numbers, which don't pair up into ranges",
+                self.seeds.len()
+            );
+        }
+        self.seeds
+            .chunks(2)
+            .map(|pair| {
+                let end = pair[0]
+                    .checked_add(pair[1])
+                    .ok_or_else(|| eyre!("the seed range {} {} overflows", pair[0], pair[1]))?;
+                Ok(Interval::new(pair[0], end))
+            })
+            .collect()
+    }
+
+    // Every value of `seeds` run through every map at once. Each map splits
+    // the ranges where its rules begin and end, so the work grows with the
+    // number of ranges, not the billions of seeds in them.
+    pub fn locations(&self, seeds: &IntervalSet) -> IntervalSet {
+        self.stages
+            .iter()
+            .fold(seeds.clone(), |set, stage| stage.map.transform(&set))
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = color_eyre::Report;
+
+    fn from_str(input: &str) -> Result<Self> {
+        parse_almanac_with(input, ParseMode::Strict)
+    }
+}
+
+// Blank lines only separate the maps, so they're skipped; a header starts
+// each map and the rules after it belong to it. Errors name the line. A bad
+// header or rule stops a strict parse and is skipped in a lenient one; the
+// seeds line and a chain of maps reaching location are needed either way.
+// An input with nothing in it is an almanac with no seeds and no maps.
+pub fn parse_almanac_with(input: &str, mode: ParseMode) -> Result<Almanac> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty());
+    let Some((n, first)) = lines.next() else {
+        return Ok(Almanac {
+            seeds: Vec::new(),
+            stages: Vec::new(),
+        });
+    };
+    let seeds = whole(first, "seeds line", parse_seeds).map_err(|e| eyre!("line {}: {}", n, e))?;
+
+    let mut stages: Vec<Stage> = Vec::new();
+    let mut skipped = Vec::new();
+    for (n, line) in lines {
+        let Err(err) = parse_map_line(&mut stages, line) else {
+            continue;
+        };
+        let malformed = Malformed {
+            line: n,
+            message: err.to_string(),
+        };
+        match mode {
+            ParseMode::Strict => return Err(malformed.into()),
+            ParseMode::Lenient => skipped.push(malformed),
+        }
+    }
+    if !skipped.is_empty() {
+        let skipped: Vec<String> = skipped.iter().map(Malformed::to_string).collect();
+        warn!(
+            "Skipped {} malformed lines: {}",
+            skipped.len(),
+            skipped.join("; ")
+        );
+    }
+
+    let last = stages.last().map_or("seed", |stage| stage.to.as_str());
+    if last != "location" {
+        bail!("the maps stop at {} instead of reaching location", last);
+    }
+    Ok(Almanac { seeds, stages })
+}
+
+// One header or rule added to the maps read so far
+fn parse_map_line(stages: &mut Vec<Stage>, line: &str) -> Result<()> {
+    if line.ends_with(':') {
+        let (from, to) = whole(line, "map header", parse_header)?;
+        let expected = stages.last().map_or("seed", |stage| stage.to.as_str());
+        if from != expected {
+            bail!("the {}-to-{} map should start from {}", from, to, expected);
+        }
+        stages.push(Stage {
+            from: String::from(from),
+            to: String::from(to),
+            map: IntervalMap::new(),
+        });
+    } else {
+        let (destination, source, len) = whole(line, "rule", parse_rule)?;
+        let Some(stage) = stages.last_mut() else {
+            bail!("a rule before any map");
+        };
+        add_rule(&mut stage.map, destination, source, len)?;
+    }
+    Ok(())
+}
+
+// The lowest location of any seed on the seeds line (part 1)
+pub fn part1(input: &str) -> Result<i64> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 5, part = 1)]
+pub fn part1_with(input: &str, mode: ParseMode) -> Result<i64> {
+    lowest_location(&parse_almanac_with(input, mode)?)
+}
+
+// The lowest location of any seed in the seed ranges (part 2)
+pub fn part2(input: &str) -> Result<i64> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 5, part = 2)]
+pub fn part2_with(input: &str, mode: ParseMode) -> Result<i64> {
+    lowest_range_location(&parse_almanac_with(input, mode)?)
+}
+
+// parse the almanac from its lines
+pub fn parse_data(input: Vec<String>) -> Result<Almanac> {
+    input.join("\n").parse()
+}
+
+// 0 when there are no seeds, as for every day's empty input
+pub fn lowest_location(almanac: &Almanac) -> Result<i64> {
+    Ok(almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.location(seed))
+        .min()
+        .unwrap_or(0))
+}
+
+pub fn lowest_range_location(almanac: &Almanac) -> Result<i64> {
+    Ok(almanac
+        .locations(&almanac.seed_ranges()?)
+        .min()
+        .unwrap_or(0))
+}
+
+/// Day 5's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fertilizer;
+
+impl Solver for Fertilizer {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+// A rule added to `map`, reported instead of panicking when it overlaps
+// another rule or runs past the largest i64
+fn add_rule(map: &mut IntervalMap, destination: i64, source: i64, len: i64) -> Result<()> {
+    let (Some(end), Some(_)) = (source.checked_add(len), destination.checked_add(len)) else {
+        bail!("the rule {} {} {} overflows", destination, source, len);
+    };
+    let source = Interval::new(source, end);
+    if let Some(rule) = map
+        .rules()
+        .iter()
+        .find(|rule| rule.source.intersect(&source).is_some())
+    {
+        bail!(
+            "source range {}..{} overlaps {}..{}",
+            source.start,
+            source.end,
+            rule.source.start,
+            rule.source.end
+        );
+    }
+    map.insert(Mapping {
+        source,
+        offset: destination - source.start,
+    });
+    Ok(())
+}
+
+// A rule as the almanac writes it: destination start, source start, length
+fn rule_numbers(rule: &Mapping) -> [i64; 3] {
+    [
+        rule.source.start + rule.offset,
+        rule.source.start,
+        rule.source.len(),
+    ]
+}
+
+fn rule_line(rule: &Mapping) -> String {
+    let [destination, source, len] = rule_numbers(rule);
+    format!("{} {} {}", destination, source, len)
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong). Unlike parsing, which stops at the first, this carries on past a bad
+// line, and also flags an odd seeds line and a map without rules.
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let mut findings = Vec::new();
+    let mut lines = input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty());
+    let Some((n, first)) = lines.next() else {
+        return findings;
+    };
+    match whole(first, "seeds line", parse_seeds) {
+        Ok(seeds) if !seeds.len().is_multiple_of(2) => findings.push((
+            n,
+            format!(
+                "the seeds line has {} numbers, which don't pair up into ranges",
+                seeds.len()
+            ),
+        )),
+        Ok(_) => {}
+        Err(e) => findings.push((n, e.to_string())),
+    }
+
+    // each map's header line, its rules and where it leads
+    let mut stages: Vec<(usize, IntervalMap, String)> = Vec::new();
+    for (n, line) in lines {
+        if line.ends_with(':') {
+            match whole(line, "map header", parse_header) {
+                Ok((from, to)) => {
+                    let expected = stages.last().map_or("seed", |(_, _, to)| to.as_str());
+                    if from != expected {
+                        findings.push((
+                            n,
+                            format!("the {}-to-{} map should start from {}", from, to, expected),
+                        ));
+                    }
+                    stages.push((n, IntervalMap::new(), String::from(to)));
+                }
+                Err(e) => findings.push((n, e.to_string())),
+            }
+        } else {
+            match whole(line, "rule", parse_rule) {
+                Ok((destination, source, len)) => match stages.last_mut() {
+                    Some((_, map, _)) => {
+                        if let Err(e) = add_rule(map, destination, source, len) {
+                            findings.push((n, e.to_string()));
+                        }
+                    }
+                    None => findings.push((n, String::from("a rule before any map"))),
+                },
+                Err(e) => findings.push((n, e.to_string())),
+            }
+        }
+    }
+
+    for (n, map, _) in &stages {
+        if map.rules().is_empty() {
+            findings.push((*n, String::from("the map has no rules")));
+        }
+    }
+    let last = stages.last().map_or("seed", |(_, _, to)| to.as_str());
+    if last != "location" {
+        findings.push((
+            input.len(),
+            format!("the maps stop at {} instead of reaching location", last),
+        ));
+    }
+    findings.sort_by_key(|&(n, _)| n);
+    findings
+}
+
+// `parser` over the whole of `line`, so nothing after it is silently dropped
+fn whole<'a, O>(
+    line: &'a str,
+    what: &str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O> {
+    match parser(line) {
+        Ok(("", value)) => Ok(value),
+        Ok((rest, _)) => Err(eyre!("unexpected trailing input {:?}", rest)),
+        Err(e) => Err(eyre!("invalid {} {:?}: {}", what, line, e)),
+    }
+}
+
+// Parse a non-negative number
+pub fn parse_number(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, str::parse::<i64>)(input)
+}
+
+// Parse the seeds line
+pub fn parse_seeds(input: &str) -> IResult<&str, Vec<i64>> {
+    let (input, _) = tuple((tag("seeds:"), space1))(input)?;
+    separated_list1(space1, parse_number)(input)
+}
+
+// Parse a map header, e.g. "seed-to-soil map:"
+pub fn parse_header(input: &str) -> IResult<&str, (&str, &str)> {
+    let (input, (from, _, to)) = tuple((alpha1, tag("-to-"), alpha1))(input)?;
+    let (input, _) = tag(" map:")(input)?;
+    Ok((input, (from, to)))
+}
+
+// Parse a rule: destination start, source start and length
+pub fn parse_rule(input: &str) -> IResult<&str, (i64, i64, i64)> {
+    let (input, (destination, _, source)) = tuple((parse_number, space1, parse_number))(input)?;
+    let (input, (_, len)) = tuple((space1, parse_number))(input)?;
+    Ok((input, (destination, source, len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn example() -> Almanac {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let almanac = example();
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.stages.len(), 7);
+        assert_eq!(almanac.stages[0].from, "seed");
+        assert_eq!(almanac.stages[0].to, "soil");
+        assert_eq!(almanac.stages[0].map.rules().len(), 2);
+        assert_eq!(almanac.stages[6].to, "location");
+        assert_eq!(almanac.stages[0].map.map(98), 50);
+    }
+
+    #[test_case(79, 82)]
+    #[test_case(14, 43)]
+    #[test_case(55, 86)]
+    #[test_case(13, 35)]
+    fn test_location(seed: i64, location: i64) {
+        assert_eq!(example().location(seed), location);
+    }
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 35);
+        assert_eq!(part2(&input).unwrap(), 46);
+        assert_eq!(Fertilizer.part1(&input).unwrap(), "35");
+        assert_eq!(Fertilizer.part2(&input).unwrap(), "46");
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "seeds: 79 14\nseed-to-location map:\n50 98 2\n52 50\n0 60 30\n";
+        assert!(part1(input)
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 4: invalid rule \"52 50\""));
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 14);
+        assert_eq!(part2_with(input, ParseMode::Lenient).unwrap(), 19);
+        // the maps still have to reach location
+        assert!(part1_with("seeds: 1\nseed-to-soil map:", ParseMode::Lenient).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let almanac = example();
+        let json = serde_json::to_string(&almanac).unwrap();
+        let result: Almanac = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, almanac);
+
+        let almanac: Almanac = "seeds: 1\nseed-to-location map:\n50 98 2".parse().unwrap();
+        let json = r#"{"seeds":[1],"stages":[{"from":"seed","to":"location","map":[[50,98,2]]}]}"#;
+        assert_eq!(serde_json::to_string(&almanac).unwrap(), json);
+        let overlapping = json.replace("[[50,98,2]]", "[[50,98,2],[0,99,5]]");
+        assert!(serde_json::from_str::<Almanac>(&overlapping).is_err());
+    }
+
+    #[test]
+    fn test_parse_data() {
+        let lines: Vec<String> = std::fs::read_to_string("../test-1.txt")
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        let almanac = parse_data(lines).unwrap();
+        assert_eq!(almanac, example());
+        assert_eq!(lowest_location(&almanac).unwrap(), 35);
+        assert_eq!(lowest_range_location(&almanac).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_validate() {
+        let input: Vec<String> = std::fs::read_to_string("../test-1.txt")
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert!(validate(&input).is_empty());
+
+        let input: Vec<String> = [
+            "seeds: 1 2 3",
+            "",
+            "seed-to-soil map:",
+            "0 5 10",
+            "20 10 3",
+            "",
+            "water-to-location map:",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            validate(&input),
+            vec![
+                (
+                    1,
+                    String::from("the seeds line has 3 numbers, which don't pair up into ranges")
+                ),
+                (5, String::from("source range 10..13 overlaps 5..15")),
+                (
+                    7,
+                    String::from("the water-to-location map should start from soil")
+                ),
+                (7, String::from("the map has no rules")),
+            ]
+        );
+        assert_eq!(
+            validate(&[String::from("seeds: 1 2"), String::from("0 0 1")]),
+            vec![
+                (2, String::from("a rule before any map")),
+                (
+                    2,
+                    String::from("the maps stop at seed instead of reaching location")
+                ),
+            ]
+        );
+        assert!(validate(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_locations_match_every_seed() {
+        let almanac = example();
+        let ranges = almanac.seed_ranges().unwrap();
+        let brute: i64 = ranges
+            .intervals()
+            .iter()
+            .flat_map(|interval| interval.start..interval.end)
+            .map(|seed| almanac.location(seed))
+            .min()
+            .unwrap();
+        let locations = almanac.locations(&ranges);
+        assert_eq!(locations.min(), Some(brute));
+        assert_eq!(locations.measure(), ranges.measure());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let error = |input: &str| input.parse::<Almanac>().unwrap_err().to_string();
+        let maps = "seed-to-location map:\n1 2 3";
+
+        assert!(error("seeds:\n").starts_with("line 1: invalid seeds line"));
+        assert_eq!(
+            error("seeds: 1\nseed-to-soil map:\n0 0 1"),
+            "the maps stop at soil instead of reaching location"
+        );
+        assert_eq!(
+            error("seeds: 1\n0 0 1\nseed-to-location map:"),
+            "line 2: a rule before any map"
+        );
+        assert_eq!(
+            error("seeds: 1\nsoil-to-location map:"),
+            "line 2: the soil-to-location map should start from seed"
+        );
+        assert_eq!(
+            error("seeds: 1\nseed-to-location map:\n0 5 10\n\n20 10 3"),
+            "line 5: source range 10..13 overlaps 5..15"
+        );
+        assert_eq!(
+            error(&format!("seeds: 1\n{}\n{} 0 1", maps, i64::MAX)),
+            format!("line 4: the rule {} 0 1 overflows", i64::MAX)
+        );
+        assert!(error("seeds: 1\nseed-to-location map:\n1 2").starts_with("line 3: invalid rule"));
+        assert!(error("seeds: 99999999999999999999\nseed-to-location map:").starts_with("line 1:"));
+
+        let odd: Almanac = format!("seeds: 1 2 3\n{}", maps).parse().unwrap();
+        assert!(odd.seed_ranges().is_err());
+        assert_eq!(
+            part2(&format!("seeds: {} 1\n{}", i64::MAX, maps))
+                .unwrap_err()
+                .to_string(),
+            format!("the seed range {} 1 overflows", i64::MAX)
+        );
+        // nothing at all is no seeds, and zero-length ranges hold none
+        assert!("\n\n".parse::<Almanac>().unwrap().seeds.is_empty());
+        assert_eq!(part1("").unwrap(), 0);
+        assert_eq!(part2(&format!("seeds: 4 0\n{}", maps)).unwrap(), 0);
+        // CRLF line endings read the same
+        assert_eq!(
+            part1(&format!("seeds: 2 9\r\n\r\n{}", maps.replace('\n', "\r\n"))).unwrap(),
+            1
+        );
+    }
+}
@@ -0,0 +1,6 @@
+aoc_common::aoc_main!(
+    day = 5,
+    parse = fertilizer::parse_data,
+    part1 = fertilizer::lowest_location,
+    part2 = fertilizer::lowest_range_location
+);
@@ -0,0 +1,30 @@
+use crate::{parse_data, Play};
+use aoc_common::shuffle::Shuffler;
+use color_eyre::eyre::Result;
+
+/// The plays in a new order. A hand's rank depends only on the other hands,
+/// with equal hands ranked by bid, so both answers stay the same.
+pub fn anonymize(lines: &[String], seed: u64) -> Result<Vec<String>> {
+    let mut plays = parse_data(lines.to_vec())?;
+    Shuffler::new(seed).shuffle(&mut plays);
+    Ok(plays.iter().map(Play::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, read_input};
+
+    #[test]
+    fn test_anonymize() {
+        let lines = read_input("../test-1.txt").unwrap();
+        let anonymized = anonymize(&lines, 42).unwrap();
+        assert_eq!(anonymized.len(), lines.len());
+        assert_ne!(anonymized, lines);
+        let (input, shared) = (lines.join("\n"), anonymized.join("\n"));
+        assert_eq!(part1(&shared).unwrap(), part1(&input).unwrap());
+        assert_eq!(part2(&shared).unwrap(), part2(&input).unwrap());
+        assert_eq!(anonymize(&lines, 42).unwrap(), anonymized);
+        assert!(anonymize(&[String::from("32T3K one")], 42).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::hand::Rules;
+use crate::{ranked, total_winnings, Play};
+use aoc_common::explain::Narrative;
+
+/// Every hand's kind, rank and winnings, first with J a jack and then with J
+/// a joker, then the totals.
+pub fn explain(plays: &[Play]) -> Narrative {
+    let mut narrative = Narrative::new();
+    for (title, rules) in [
+        ("Ranks with J a jack", Rules::Jacks),
+        ("Ranks with J a joker", Rules::Jokers),
+    ] {
+        narrative.section(title);
+        for ((hand, bid), rank) in ranked(plays, rules).iter().zip(1u64..) {
+            let winnings = bid
+                .checked_mul(rank)
+                .map_or(String::from("too much"), |winnings| winnings.to_string());
+            narrative.line(format!(
+                "rank {}: {} ({}) bids {}, wins {}",
+                rank,
+                hand,
+                hand.kind(),
+                bid,
+                winnings
+            ));
+        }
+    }
+
+    let total = |total: color_eyre::Result<u64>| {
+        total.map_or_else(|err| err.to_string(), |total| total.to_string())
+    };
+    narrative
+        .section("Answers")
+        .line(format!(
+            "part 1: the winnings add up to {}",
+            total(total_winnings(plays, Rules::Jacks))
+        ))
+        .line(format!(
+            "part 2: with jokers they add up to {}",
+            total(total_winnings(plays, Rules::Jokers))
+        ));
+    narrative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_data, read_input};
+
+    #[test]
+    fn test_explain() {
+        let plays = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let narrative = explain(&plays);
+        let [jacks, jokers, answers] = narrative.sections() else {
+            panic!("expected three sections");
+        };
+        assert_eq!(
+            jacks.lines[0],
+            "rank 1: 32T3K (one pair) bids 765, wins 765"
+        );
+        assert_eq!(
+            jacks.lines[4],
+            "rank 5: QQQJA (three of a kind) bids 483, wins 2415"
+        );
+        assert_eq!(
+            jokers.lines[4],
+            "rank 5: KTJJT (four of a kind) bids 220, wins 1100"
+        );
+        assert_eq!(
+            answers.lines,
+            [
+                "part 1: the winnings add up to 6440",
+                "part 2: with jokers they add up to 5905"
+            ]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use color_eyre::eyre::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+// every card, weakest first
+const CARDS: &str = "23456789TJQKA";
+
+/// How a hand's cards group, weakest first, so the derived order ranks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Kind::HighCard => "high card",
+            Kind::OnePair => "one pair",
+            Kind::TwoPair => "two pair",
+            Kind::ThreeOfAKind => "three of a kind",
+            Kind::FullHouse => "full house",
+            Kind::FourOfAKind => "four of a kind",
+            Kind::FiveOfAKind => "five of a kind",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What a `J` is: a jack between `T` and `Q` (part 1), or a joker that
+/// stands in for whichever card makes the strongest kind but is the weakest
+/// card when breaking ties (part 2).
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Rules {
+    #[default]
+    Jacks,
+    Jokers,
+}
+
+impl Rules {
+    // 0 for a joker, then 1 for a 2 up to 13 for an ace
+    fn strength(self, card: char) -> u8 {
+        match (self, card) {
+            (Rules::Jokers, 'J') => 0,
+            _ => CARDS.find(card).map_or(0, |i| i as u8 + 1),
+        }
+    }
+}
+
+/// Five cards, ordered by [`Kind`] and then card by card from the first.
+/// Serialized with its cards as one string, and checked by [`Hand::new`]
+/// when read back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Cards", into = "Cards")]
+pub struct Hand {
+    cards: [char; 5],
+    rules: Rules,
+}
+
+// A hand as serde sees it
+#[derive(Serialize, Deserialize)]
+struct Cards {
+    cards: String,
+    rules: Rules,
+}
+
+impl TryFrom<Cards> for Hand {
+    type Error = color_eyre::Report;
+
+    fn try_from(hand: Cards) -> Result<Self> {
+        Hand::new(&hand.cards, hand.rules)
+    }
+}
+
+impl From<Hand> for Cards {
+    fn from(hand: Hand) -> Self {
+        Cards {
+            cards: hand.cards.iter().collect(),
+            rules: hand.rules,
+        }
+    }
+}
+
+impl Hand {
+    pub fn new(cards: &str, rules: Rules) -> Result<Self> {
+        let chars: Vec<char> = cards.chars().collect();
+        let Ok(cards) = <[char; 5]>::try_from(chars) else {
+            bail!("a hand has 5 cards, not {:?}", cards);
+        };
+        if let Some(card) = cards.iter().find(|&&card| !CARDS.contains(card)) {
+            bail!("{:?} is not a card", card);
+        }
+        Ok(Hand { cards, rules })
+    }
+
+    pub fn cards(&self) -> [char; 5] {
+        self.cards
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// The same cards read under other rules.
+    pub fn with_rules(&self, rules: Rules) -> Hand {
+        Hand {
+            cards: self.cards,
+            rules,
+        }
+    }
+
+    // Jokers join whichever card there is most of, since that always makes
+    // the strongest kind
+    pub fn kind(&self) -> Kind {
+        let mut counts: Vec<usize> = CARDS
+            .chars()
+            .filter(|&card| !(card == 'J' && self.rules == Rules::Jokers))
+            .map(|card| self.cards.iter().filter(|&&c| c == card).count())
+            .filter(|&count| count > 0)
+            .collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let jokers = match self.rules {
+            Rules::Jacks => 0,
+            Rules::Jokers => self.cards.iter().filter(|&&c| c == 'J').count(),
+        };
+        match counts.first_mut() {
+            Some(most) => *most += jokers,
+            None => counts.push(jokers),
+        }
+
+        match counts.as_slice() {
+            [5] => Kind::FiveOfAKind,
+            [4, ..] => Kind::FourOfAKind,
+            [3, 2] => Kind::FullHouse,
+            [3, ..] => Kind::ThreeOfAKind,
+            [2, 2, ..] => Kind::TwoPair,
+            [2, ..] => Kind::OnePair,
+            _ => Kind::HighCard,
+        }
+    }
+
+    fn strengths(&self) -> [u8; 5] {
+        self.cards.map(|card| self.rules.strength(card))
+    }
+}
+
+// The rules only break ties between the same cards read both ways, which
+// keeps the order consistent with ==
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind()
+            .cmp(&other.kind())
+            .then_with(|| self.strengths().cmp(&other.strengths()))
+            .then_with(|| self.rules.cmp(&other.rules))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.cards.iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn hand(cards: &str, rules: Rules) -> Hand {
+        Hand::new(cards, rules).unwrap()
+    }
+
+    #[test_case("AAAAA", Kind::FiveOfAKind, Kind::FiveOfAKind)]
+    #[test_case("AA8AA", Kind::FourOfAKind, Kind::FourOfAKind)]
+    #[test_case("23332", Kind::FullHouse, Kind::FullHouse)]
+    #[test_case("TTT98", Kind::ThreeOfAKind, Kind::ThreeOfAKind)]
+    #[test_case("23432", Kind::TwoPair, Kind::TwoPair)]
+    #[test_case("A23A4", Kind::OnePair, Kind::OnePair)]
+    #[test_case("23456", Kind::HighCard, Kind::HighCard)]
+    #[test_case("QJJQ2", Kind::TwoPair, Kind::FourOfAKind)]
+    #[test_case("T55J5", Kind::ThreeOfAKind, Kind::FourOfAKind)]
+    #[test_case("KTJJT", Kind::TwoPair, Kind::FourOfAKind)]
+    #[test_case("2345J", Kind::HighCard, Kind::OnePair)]
+    #[test_case("22JJ3", Kind::TwoPair, Kind::FourOfAKind)]
+    #[test_case("2233J", Kind::TwoPair, Kind::FullHouse)]
+    #[test_case("JJJJJ", Kind::FiveOfAKind, Kind::FiveOfAKind)]
+    fn test_kind(cards: &str, jacks: Kind, jokers: Kind) {
+        assert_eq!(hand(cards, Rules::Jacks).kind(), jacks);
+        assert_eq!(hand(cards, Rules::Jokers).kind(), jokers);
+    }
+
+    #[test]
+    fn test_order() {
+        let jacks = |cards| hand(cards, Rules::Jacks);
+        // kind first, then card by card from the first
+        assert!(jacks("22223") > jacks("AAKKK"));
+        assert!(jacks("33332") > jacks("2AAAA"));
+        assert!(jacks("77888") > jacks("77788"));
+        assert!(jacks("KK677") > jacks("KTJJT"));
+        assert!(jacks("QQQJA") > jacks("T55J5"));
+        assert_eq!(jacks("AKQJT").cmp(&jacks("AKQJT")), Ordering::Equal);
+
+        // a joker is the weakest card when kinds tie
+        let jokers = |cards| hand(cards, Rules::Jokers);
+        assert!(jokers("JKKK2") < jokers("QQQQ2"));
+        assert!(jokers("J2222") < jokers("22222"));
+        assert!(jokers("KTJJT") > jokers("QQQJA"));
+        // the same cards read both ways differ, jacks outranking jokers
+        assert!(jacks("JJJJJ") > jacks("JJJJJ").with_rules(Rules::Jokers));
+        assert_ne!(jacks("T55J5"), jokers("T55J5"));
+    }
+
+    #[test]
+    fn test_new() {
+        let hand = hand("32T3K", Rules::Jokers);
+        assert_eq!(hand.cards(), ['3', '2', 'T', '3', 'K']);
+        assert_eq!(hand.rules(), Rules::Jokers);
+        assert_eq!(hand.to_string(), "32T3K");
+
+        assert!(Hand::new("32T3", Rules::Jacks).is_err());
+        assert!(Hand::new("32T3KK", Rules::Jacks).is_err());
+        assert_eq!(
+            Hand::new("32t3K", Rules::Jacks).unwrap_err().to_string(),
+            "'t' is not a card"
+        );
+        assert!(Hand::new("1AAAA", Rules::Jacks).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use aoc_common::input::read_lines;
+use aoc_common::parse::{parse_lines, uint, ParseMode};
+use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+pub mod anonymize;
+pub mod explain;
+pub mod hand;
+
+use hand::{Hand, Rules};
+
+// One line of the input: a hand and the bid on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Play {
+    pub hand: Hand,
+    pub bid: u64,
+}
+
+impl fmt::Display for Play {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.hand, self.bid)
+    }
+}
+
+// Read with J as a jack; total_winnings reads the cards again under its rules
+impl FromStr for Play {
+    type Err = color_eyre::Report;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let Some((cards, bid)) = line.split_once(' ') else {
+            return Err(eyre!("expected \"<cards> <bid>\", got {:?}", line));
+        };
+        let hand = Hand::new(cards, Rules::Jacks)?;
+        let bid = uint(bid).ok_or_else(|| eyre!("invalid bid {:?}", bid))?;
+        Ok(Play { hand, bid })
+    }
+}
+
+#[tracing::instrument]
+pub fn read_input(filename: &str) -> Result<Vec<String>> {
+    Ok(read_lines(filename)?)
+}
+
+// Sum of every bid times its hand's rank, J being a jack (part 1)
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 7, part = 1)]
+pub fn part1_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_winnings(&parse_data_with(input, mode)?, Rules::Jacks)
+}
+
+// The same with J a joker (part 2)
+pub fn part2(input: &str) -> Result<u64> {
+    part2_with(input, ParseMode::Strict)
+}
+
+#[aoc(day = 7, part = 2)]
+pub fn part2_with(input: &str, mode: ParseMode) -> Result<u64> {
+    let input = input.lines().map(String::from).collect();
+    total_winnings(&parse_data_with(input, mode)?, Rules::Jokers)
+}
+
+/// Day 7's parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CamelCards;
+
+impl Solver for CamelCards {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+// Every hand read under `rules` with its bid, weakest first, so the hand at
+// index i has rank i + 1. Equal hands are ranked by bid so the order doesn't
+// depend on the input's.
+pub fn ranked(plays: &[Play], rules: Rules) -> Vec<(Hand, u64)> {
+    let mut ranked: Vec<(Hand, u64)> = plays
+        .iter()
+        .map(|play| (play.hand.with_rules(rules), play.bid))
+        .collect();
+    ranked.sort();
+    ranked
+}
+
+// Each play's rank under `rules`, in the order of `plays`
+pub fn ranks(plays: &[Play], rules: Rules) -> Vec<u64> {
+    let mut order: Vec<usize> = (0..plays.len()).collect();
+    order.sort_by_key(|&i| (plays[i].hand.with_rules(rules), plays[i].bid));
+    let mut ranks = vec![0; plays.len()];
+    for (rank, i) in (1..).zip(order) {
+        ranks[i] = rank;
+    }
+    ranks
+}
+
+// The weakest hand is rank 1 and the strongest rank `plays.len()`; the total
+// is checked rather than wrapping.
+pub fn total_winnings(plays: &[Play], rules: Rules) -> Result<u64> {
+    ranked(plays, rules)
+        .iter()
+        .zip(1u64..)
+        .try_fold(0u64, |acc, ((hand, bid), rank)| {
+            bid.checked_mul(rank)
+                .and_then(|winnings| acc.checked_add(winnings))
+                .ok_or_else(|| eyre!("the total winnings overflow at {}", hand))
+        })
+}
+
+// parse a vector of plays
+#[tracing::instrument]
+pub fn parse_data(input: Vec<String>) -> Result<Vec<Play>> {
+    parse_data_with(input, ParseMode::Strict)
+}
+
+#[tracing::instrument]
+pub fn parse_data_with(input: Vec<String>, mode: ParseMode) -> Result<Vec<Play>> {
+    let parsed = parse_lines(&input, mode, |line| line.parse::<Play>())?;
+    if parsed.blank > 0 {
+        warn!("Skipped {} blank lines", parsed.blank);
+    }
+    if let Some(summary) = parsed.summary() {
+        warn!("{}", summary);
+    }
+    Ok(parsed.items)
+}
+
+// Structural problems worth reporting before solving, as (line number, what's
+// wrong): a line that isn't a hand and a bid, or a hand dealt twice, whose
+// rank then comes down to the bids
+pub fn validate(input: &[String]) -> Vec<(usize, String)> {
+    let mut findings = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (i, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse::<Play>() {
+            Ok(play) => {
+                let cards = play.hand.to_string();
+                if let Some(first) = seen.get(&cards) {
+                    findings.push((i + 1, format!("{} was dealt on line {} too", cards, first)));
+                } else {
+                    seen.insert(cards, i + 1);
+                }
+            }
+            Err(err) => findings.push((i + 1, err.to_string())),
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_input() {
+        let input = read_input("../test-1.txt").unwrap();
+        assert_eq!(input.len(), 5);
+        assert_eq!(input[2], "KK677 28");
+    }
+
+    #[test]
+    fn test_parse_play() {
+        let play: Play = "32T3K 765".parse().unwrap();
+        assert_eq!(play.hand.to_string(), "32T3K");
+        assert_eq!(play.bid, 765);
+
+        assert!("32T3K".parse::<Play>().is_err());
+        assert!("32T3K -5".parse::<Play>().is_err());
+        assert!("32T3K  765".parse::<Play>().is_err());
+        assert!("32T3 765".parse::<Play>().is_err());
+    }
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 6440);
+        assert_eq!(part2(&input).unwrap(), 5905);
+        assert_eq!(CamelCards.part1(&input).unwrap(), "6440");
+        assert_eq!(CamelCards.part2(&input).unwrap(), "5905");
+    }
+
+    #[test]
+    fn test_total_winnings() {
+        let plays = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        // input order doesn't matter, even between equal hands
+        let mut reversed = plays.clone();
+        reversed.reverse();
+        assert_eq!(total_winnings(&reversed, Rules::Jacks).unwrap(), 6440);
+        let same = parse_data(vec![String::from("AAAAA 1"), String::from("AAAAA 2")]).unwrap();
+        assert_eq!(total_winnings(&same, Rules::Jacks).unwrap(), 5);
+        assert_eq!(total_winnings(&[], Rules::Jokers).unwrap(), 0);
+
+        let huge = parse_data(vec![
+            format!("22222 {}", u64::MAX),
+            format!("33333 {}", u64::MAX),
+        ])
+        .unwrap();
+        assert_eq!(
+            total_winnings(&huge, Rules::Jacks).unwrap_err().to_string(),
+            "the total winnings overflow at 33333"
+        );
+    }
+
+    #[test]
+    fn test_ranks() {
+        let plays = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        assert_eq!(ranks(&plays, Rules::Jacks), vec![1, 4, 3, 2, 5]);
+        assert_eq!(ranks(&plays, Rules::Jokers), vec![1, 3, 2, 5, 4]);
+    }
+
+    #[test]
+    fn test_serde() {
+        let plays = parse_data(read_input("../test-1.txt").unwrap()).unwrap();
+        let json = serde_json::to_string(&plays).unwrap();
+        let result: Vec<Play> = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, plays);
+
+        let json = r#"{"hand":{"cards":"32T3K","rules":"jacks"},"bid":765}"#;
+        assert_eq!(serde_json::to_string(&plays[0]).unwrap(), json);
+        assert!(serde_json::from_str::<Play>(&json.replace("32T3K", "32T3X")).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        for line in read_input("../test-1.txt").unwrap() {
+            assert_eq!(line.parse::<Play>().unwrap().to_string(), line);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&read_input("../test-1.txt").unwrap()).is_empty());
+
+        let input = ["32T3K 765", "", "32T3K 1", "32T3 765", "32T3K -5"].map(String::from);
+        assert_eq!(
+            validate(&input),
+            vec![
+                (3, String::from("32T3K was dealt on line 1 too")),
+                (4, String::from("a hand has 5 cards, not \"32T3\"")),
+                (5, String::from("invalid bid \"-5\"")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "32T3K 765\nXYZ 1\nKK677 28";
+        let err = part1(input).unwrap_err();
+        assert!(err.to_string().starts_with("line 2:"));
+        assert_eq!(part1_with(input, ParseMode::Lenient).unwrap(), 765 + 2 * 28);
+    }
+}
@@ -0,0 +1,9 @@
+use camel_cards::hand::Rules;
+use camel_cards::Play;
+
+aoc_common::aoc_main!(
+    day = 7,
+    parse = camel_cards::parse_data,
+    part1 = |plays: &[Play]| camel_cards::total_winnings(plays, Rules::Jacks),
+    part2 = |plays: &[Play]| camel_cards::total_winnings(plays, Rules::Jokers)
+);
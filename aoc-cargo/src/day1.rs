@@ -0,0 +1,27 @@
+use aoc_runner_derive::aoc;
+use color_eyre::Report;
+
+// the parts read the digits differently, so each parses the raw input itself
+
+#[aoc(day1, part1)]
+pub fn part1(input: &str) -> Result<usize, Report> {
+    trebuchet::part1(input)
+}
+
+#[aoc(day1, part2)]
+pub fn part2(input: &str) -> Result<usize, Report> {
+    trebuchet::part2(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../Day-1/test-1.txt").unwrap();
+        assert_eq!(part1(&input).unwrap(), 142);
+        let input = std::fs::read_to_string("../Day-1/test-2.txt").unwrap();
+        assert_eq!(part2(&input).unwrap(), 281);
+    }
+}
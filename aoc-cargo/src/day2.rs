@@ -0,0 +1,31 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+use bag_game::Game;
+use color_eyre::Report;
+
+#[aoc_generator(day2)]
+pub fn games(input: &str) -> Result<Vec<Game>, Report> {
+    bag_game::parse_data(input.lines().map(String::from).collect())
+}
+
+#[aoc(day2, part1)]
+pub fn part1(games: &[Game]) -> Result<u64, Report> {
+    bag_game::total_feasible(games)
+}
+
+#[aoc(day2, part2)]
+pub fn part2(games: &[Game]) -> Result<u64, Report> {
+    bag_game::total_power(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let games = games(&input).unwrap();
+        assert_eq!(part1(&games).unwrap(), 8);
+        assert_eq!(part2(&games).unwrap(), 2286);
+    }
+}
@@ -0,0 +1,31 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+use color_eyre::Report;
+use map_reader::Schematic;
+
+#[aoc_generator(day3)]
+pub fn schematic(input: &str) -> Result<Schematic, Report> {
+    input.parse()
+}
+
+#[aoc(day3, part1)]
+pub fn part1(schematic: &Schematic) -> u128 {
+    schematic.part_sum()
+}
+
+#[aoc(day3, part2)]
+pub fn part2(schematic: &Schematic) -> u128 {
+    schematic.gear_ratio_sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let schematic = schematic(&input).unwrap();
+        assert_eq!(part1(&schematic), 4361);
+        assert_eq!(part2(&schematic), 467835);
+    }
+}
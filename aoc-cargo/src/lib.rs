@@ -0,0 +1,11 @@
+// The solvers as cargo-aoc expects them: run `cargo aoc` (or `cargo aoc bench`)
+// from this directory once `cargo xtask cargo-aoc-inputs` has copied the inputs
+// to input/2023. Every function here hands straight over to its day crate, so
+// the answers are the ones `aoc run` gives.
+use aoc_runner_derive::aoc_lib;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+aoc_lib! { year = 2023 }
@@ -0,0 +1,103 @@
+use std::fmt;
+
+/// A puzzle answer in canonical form, so a stored answer and a computed one
+/// compare equal however each was written down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A whole number, e.g. 142
+    Integer(i128),
+    /// One line of anything else, e.g. a password or letters read off a display
+    Text(String),
+    /// Several lines, e.g. letters drawn in # and .
+    Grid(Vec<String>),
+}
+
+/// Normalization on top of the canonical form, for answers compared loosely.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Normalize {
+    /// Compare text and grids case-insensitively
+    pub uppercase: bool,
+}
+
+impl Value {
+    /// Type `text` by its shape: several lines make a grid, and a single line
+    /// is an integer when it's written the way the integer displays ("42",
+    /// not "042"). Blank lines around the answer and trailing spaces go;
+    /// leading spaces on a grid's lines stay, they position its letters.
+    pub fn parse(text: &str, normalize: Normalize) -> Value {
+        let text = if normalize.uppercase {
+            text.to_uppercase()
+        } else {
+            text.to_string()
+        };
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let first = lines.iter().position(|line| !line.is_empty());
+        let last = lines.iter().rposition(|line| !line.is_empty());
+        let lines = match (first, last) {
+            (Some(first), Some(last)) => &lines[first..=last],
+            _ => &[],
+        };
+        match lines {
+            [] => Value::Text(String::new()),
+            [line] => {
+                let line = line.trim();
+                match line.parse::<i128>() {
+                    Ok(number) if number.to_string() == line => Value::Integer(number),
+                    _ => Value::Text(line.to_string()),
+                }
+            }
+            lines => Value::Grid(lines.iter().map(|line| line.to_string()).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(number) => write!(f, "{}", number),
+            Value::Text(text) => write!(f, "{}", text),
+            Value::Grid(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// Whether `actual` is the same answer as `expected` once both are canonical.
+pub fn matches(expected: &str, actual: &str, normalize: Normalize) -> bool {
+    Value::parse(expected, normalize) == Value::parse(actual, normalize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let exact = Normalize::default();
+        assert_eq!(Value::parse("142", exact), Value::Integer(142));
+        assert_eq!(Value::parse(" -7\n", exact), Value::Integer(-7));
+        assert_eq!(Value::parse("042", exact), Value::Text(String::from("042")));
+        assert_eq!(Value::parse("abc", exact), Value::Text(String::from("abc")));
+        assert_eq!(Value::parse("\n \n", exact), Value::Text(String::new()));
+        assert_eq!(
+            Value::parse("\n#..# \n ##.\n\n", exact),
+            Value::Grid(vec![String::from("#..#"), String::from(" ##.")])
+        );
+
+        let upper = Normalize { uppercase: true };
+        assert_eq!(Value::parse("abc", upper), Value::Text(String::from("ABC")));
+        assert_eq!(Value::parse("abc", upper).to_string(), "ABC");
+        assert_eq!(Value::parse("a\nb", upper).to_string(), "A\nB");
+    }
+
+    #[test]
+    fn test_matches() {
+        let exact = Normalize::default();
+        assert!(matches("2286", "2286\n", exact));
+        assert!(!matches("2286", "2287", exact));
+        assert!(!matches("42", "042", exact));
+        assert!(matches("#.\n.#", "#.  \n.#\n", exact));
+        assert!(!matches("#.\n.#", "#.\n #", exact));
+        assert!(!matches("RGZ", "rgz", exact));
+        assert!(matches("RGZ", "rgz", Normalize { uppercase: true }));
+    }
+}
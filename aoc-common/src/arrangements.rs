@@ -0,0 +1,174 @@
+/// One position of a run-length pattern, as in the Day 12 spring rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// `.`: never part of a group
+    Operational,
+    /// `#`: always part of a group
+    Damaged,
+    /// `?`: either
+    Unknown,
+}
+
+impl Cell {
+    pub fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell::Operational),
+            '#' => Some(Cell::Damaged),
+            '?' => Some(Cell::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Parse a whole pattern such as `???.###`; `None` on any other character.
+    pub fn parse(pattern: &str) -> Option<Vec<Cell>> {
+        pattern.chars().map(Cell::from_char).collect()
+    }
+}
+
+/// Count the ways to resolve every `Unknown` cell so that the runs of
+/// `Damaged` cells are exactly `groups`, in order.
+///
+/// `ways[i][j]` holds the count for `pattern[i..]` against `groups[j..]`,
+/// filled from the end, so the cost is O(len(pattern) * len(groups)) no
+/// matter how many unknowns there are.
+pub fn count_arrangements(pattern: &[Cell], groups: &[usize]) -> u64 {
+    let (n, m) = (pattern.len(), groups.len());
+
+    // run[i]: how many cells from i on could all be damaged
+    let mut run = vec![0; n + 1];
+    for i in (0..n).rev() {
+        run[i] = if pattern[i] == Cell::Operational {
+            0
+        } else {
+            run[i + 1] + 1
+        };
+    }
+
+    let mut ways = vec![vec![0u64; m + 1]; n + 2];
+    ways[n][m] = 1;
+    ways[n + 1][m] = 1;
+
+    for i in (0..n).rev() {
+        for j in 0..=m {
+            let mut count = 0;
+
+            // leave cell i operational
+            if pattern[i] != Cell::Damaged {
+                count += ways[i + 1][j];
+            }
+
+            // start group j at cell i; it must be followed by an operational
+            // cell (skipped over) or the end of the pattern
+            if pattern[i] != Cell::Operational && j < m {
+                let len = groups[j];
+                let end = i + len;
+                if len > 0 && run[i] >= len && (end == n || pattern[end] != Cell::Damaged) {
+                    count += ways[end + 1][j + 1];
+                }
+            }
+
+            ways[i][j] = count;
+        }
+    }
+
+    ways[0][0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(pattern: &str, groups: &[usize]) -> u64 {
+        count_arrangements(&Cell::parse(pattern).unwrap(), groups)
+    }
+
+    // join `copies` copies of the pattern with '?' and repeat the groups
+    fn unfold(pattern: &str, groups: &[usize], copies: usize) -> (String, Vec<usize>) {
+        (vec![pattern; copies].join("?"), groups.repeat(copies))
+    }
+
+    // try every assignment of the unknowns
+    fn brute_force(pattern: &[Cell], groups: &[usize]) -> u64 {
+        let unknowns: Vec<usize> = (0..pattern.len())
+            .filter(|&i| pattern[i] == Cell::Unknown)
+            .collect();
+
+        (0..1u32 << unknowns.len())
+            .filter(|mask| {
+                let mut cells = pattern.to_vec();
+                for (bit, &i) in unknowns.iter().enumerate() {
+                    cells[i] = if mask & (1 << bit) != 0 {
+                        Cell::Damaged
+                    } else {
+                        Cell::Operational
+                    };
+                }
+                let runs: Vec<usize> = cells
+                    .split(|&cell| cell == Cell::Operational)
+                    .map(<[Cell]>::len)
+                    .filter(|&len| len > 0)
+                    .collect();
+                runs == groups
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn test_day12_examples() {
+        assert_eq!(count("???.###", &[1, 1, 3]), 1);
+        assert_eq!(count(".??..??...?##.", &[1, 1, 3]), 4);
+        assert_eq!(count("?#?#?#?#?#?#?#?", &[1, 3, 1, 6]), 1);
+        assert_eq!(count("????.#...#...", &[4, 1, 1]), 1);
+        assert_eq!(count("????.######..#####.", &[1, 6, 5]), 4);
+        assert_eq!(count("?###????????", &[3, 2, 1]), 10);
+    }
+
+    #[test]
+    fn test_day12_unfolded() {
+        let cases: [(&str, &[usize], u64); 6] = [
+            ("???.###", &[1, 1, 3], 1),
+            (".??..??...?##.", &[1, 1, 3], 16384),
+            ("?#?#?#?#?#?#?#?", &[1, 3, 1, 6], 1),
+            ("????.#...#...", &[4, 1, 1], 16),
+            ("????.######..#####.", &[1, 6, 5], 2500),
+            ("?###????????", &[3, 2, 1], 506250),
+        ];
+        for (pattern, groups, expected) in cases {
+            let (pattern, groups) = unfold(pattern, groups, 5);
+            assert_eq!(count(&pattern, &groups), expected, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(count("", &[]), 1);
+        assert_eq!(count("", &[1]), 0);
+        assert_eq!(count("...", &[]), 1);
+        assert_eq!(count("??", &[]), 1);
+        assert_eq!(count("#", &[]), 0);
+        assert_eq!(count("###", &[2]), 0);
+        assert_eq!(count("##", &[1, 1]), 0);
+        assert_eq!(count("?????", &[1, 1, 1]), 1);
+        assert_eq!(Cell::parse("?x"), None);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let cells = [Cell::Operational, Cell::Damaged, Cell::Unknown];
+        let groupings: [&[usize]; 6] = [&[], &[1], &[2], &[1, 1], &[2, 1], &[1, 1, 1]];
+
+        // every pattern of length 6
+        for code in 0..3usize.pow(6) {
+            let pattern: Vec<Cell> = (0..6).map(|i| cells[code / 3usize.pow(i) % 3]).collect();
+            for groups in groupings {
+                assert_eq!(
+                    count_arrangements(&pattern, groups),
+                    brute_force(&pattern, groups),
+                    "{:?} {:?}",
+                    pattern,
+                    groups
+                );
+            }
+        }
+    }
+}
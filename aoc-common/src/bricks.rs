@@ -0,0 +1,217 @@
+use crate::parse::ints;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// An axis-aligned block of cubes from `min` to `max` inclusive, as `[x, y, z]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+impl Brick {
+    /// Parse `x,y,z~x,y,z`; `None` if it isn't two triples of integers.
+    pub fn parse(line: &str) -> Option<Brick> {
+        let (a, b) = line.trim().split_once('~')?;
+        let (a, b): ([i64; 3], [i64; 3]) = (ints(a, ',')?, ints(b, ',')?);
+        Some(Brick {
+            min: [0, 1, 2].map(|i| a[i].min(b[i])),
+            max: [0, 1, 2].map(|i| a[i].max(b[i])),
+        })
+    }
+
+    /// Every `(x, y)` column the brick covers.
+    pub fn footprint(&self) -> impl Iterator<Item = (i64, i64)> {
+        let [x0, y0, _] = self.min;
+        let [x1, y1, _] = self.max;
+        (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+    }
+
+    fn dropped_to(&self, z: i64) -> Brick {
+        let height = self.max[2] - self.min[2];
+        Brick {
+            min: [self.min[0], self.min[1], z],
+            max: [self.max[0], self.max[1], z + height],
+        }
+    }
+}
+
+/// Bricks after falling as far as they can, with who rests on whom. Bricks
+/// keep the index they had in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    pub bricks: Vec<Brick>,
+    /// `supports[i]`: the bricks resting directly on brick `i`.
+    pub supports: Vec<Vec<usize>>,
+    /// `supported_by[i]`: the bricks brick `i` rests on directly; empty for
+    /// bricks on the ground.
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Drop every brick onto the ground (z = 1) or the bricks below it,
+    /// lowest first.
+    pub fn settle(bricks: &[Brick]) -> Stack {
+        let mut order: Vec<usize> = (0..bricks.len()).collect();
+        order.sort_by_key(|&i| bricks[i].min[2]);
+
+        let mut settled = bricks.to_vec();
+        let mut supports = vec![Vec::new(); bricks.len()];
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+        // the highest settled brick over each column, and its top z
+        let mut tops: HashMap<(i64, i64), (i64, usize)> = HashMap::new();
+
+        for i in order {
+            let below: Vec<(i64, usize)> = bricks[i]
+                .footprint()
+                .filter_map(|column| tops.get(&column).copied())
+                .collect();
+            let floor = below.iter().map(|&(z, _)| z).max().unwrap_or(0);
+
+            let mut resting_on: Vec<usize> = below
+                .into_iter()
+                .filter(|&(z, _)| z == floor)
+                .map(|(_, j)| j)
+                .collect();
+            resting_on.sort_unstable();
+            resting_on.dedup();
+            for &j in &resting_on {
+                supports[j].push(i);
+            }
+            supported_by[i] = resting_on;
+
+            settled[i] = bricks[i].dropped_to(floor + 1);
+            for column in settled[i].footprint() {
+                tops.insert(column, (settled[i].max[2], i));
+            }
+        }
+
+        for list in &mut supports {
+            list.sort_unstable();
+        }
+
+        Stack {
+            bricks: settled,
+            supports,
+            supported_by,
+        }
+    }
+
+    /// Bricks that could be taken out without anything else falling: every
+    /// brick they hold up rests on something else too.
+    pub fn safe_to_remove(&self) -> Vec<usize> {
+        (0..self.bricks.len())
+            .filter(|&i| {
+                self.supports[i]
+                    .iter()
+                    .all(|&j| self.supported_by[j].len() > 1)
+            })
+            .collect()
+    }
+
+    /// How many other bricks fall if `brick` is taken out.
+    pub fn chain_fall_count(&self, brick: usize) -> usize {
+        let mut remaining: Vec<usize> = self.supported_by.iter().map(Vec::len).collect();
+        let mut queue = VecDeque::from([brick]);
+        let mut fallen = 0;
+
+        while let Some(i) = queue.pop_front() {
+            for &j in &self.supports[i] {
+                remaining[j] -= 1;
+                if remaining[j] == 0 {
+                    fallen += 1;
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        fallen
+    }
+
+    /// The support graph in Graphviz DOT, an edge from each brick to the
+    /// bricks resting on it. Bricks are labelled with their input index.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bricks {\n    rankdir=BT;\n");
+        for (i, brick) in self.bricks.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    {} [label=\"{}\\nz={}..{}\"];",
+                i, i, brick.min[2], brick.max[2]
+            );
+        }
+        for (i, above) in self.supports.iter().enumerate() {
+            for j in above {
+                let _ = writeln!(dot, "    {} -> {};", i, j);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    fn example() -> Stack {
+        let bricks: Vec<Brick> = EXAMPLE.lines().map(|l| Brick::parse(l).unwrap()).collect();
+        Stack::settle(&bricks)
+    }
+
+    #[test]
+    fn test_day22_example() {
+        let stack = example();
+        // B, C, D, E and G in the puzzle's lettering
+        assert_eq!(stack.safe_to_remove(), vec![1, 2, 3, 4, 6]);
+
+        let chains: Vec<usize> = (0..7).map(|i| stack.chain_fall_count(i)).collect();
+        assert_eq!(chains, vec![6, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(chains.iter().sum::<usize>(), 7);
+    }
+
+    #[test]
+    fn test_settle() {
+        let stack = example();
+        assert_eq!(stack.bricks[1].min[2], 2);
+        assert_eq!(stack.bricks[2].min[2], 2);
+        assert_eq!(stack.bricks[6].min[2], 5);
+        assert_eq!(stack.bricks[6].max[2], 6);
+
+        assert_eq!(stack.supported_by[0], Vec::<usize>::new());
+        assert_eq!(stack.supports[0], vec![1, 2]);
+        assert_eq!(stack.supported_by[3], vec![1, 2]);
+        assert_eq!(stack.supported_by[6], vec![5]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Brick::parse("2,2,2~2,0,2"),
+            Some(Brick {
+                min: [2, 0, 2],
+                max: [2, 2, 2]
+            })
+        );
+        assert_eq!(Brick::parse("1,2~1,2,3"), None);
+        assert_eq!(Brick::parse("1,2,3,4~1,2,3"), None);
+        assert_eq!(Brick::parse("1,2,3"), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = example().to_dot();
+        assert!(dot.starts_with("digraph bricks {\n"));
+        assert!(dot.contains("    0 -> 1;\n    0 -> 2;\n"));
+        assert!(dot.contains("    6 [label=\"6\\nz=5..6\"];"));
+        assert_eq!(dot.matches("->").count(), 9);
+    }
+}
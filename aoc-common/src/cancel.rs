@@ -0,0 +1,106 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Why a [`CancelToken`] stopped the work polling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cancelled {
+    /// [`CancelToken::cancel`] was called, e.g. on Ctrl-C
+    Interrupted,
+    /// The token's timeout passed
+    TimedOut,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cancelled::Interrupted => write!(f, "interrupted"),
+            Cancelled::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Asks long-running work to stop early. Work polls it, typically with
+/// `cancel.check()?` once per iteration of its outer loop; every clone of a
+/// token is cancelled together.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    interrupted: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    /// A token that is only ever cancelled by [`CancelToken::cancel`].
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    /// A token that also cancels itself `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        CancelToken {
+            deadline: Instant::now().checked_add(timeout),
+            ..CancelToken::default()
+        }
+    }
+
+    /// Cancel this token and every clone of it.
+    pub fn cancel(&self) {
+        self.interrupted.store(true, Ordering::Relaxed);
+    }
+
+    /// Why the token is cancelled, or None while work may go on.
+    pub fn reason(&self) -> Option<Cancelled> {
+        if self.interrupted.load(Ordering::Relaxed) {
+            Some(Cancelled::Interrupted)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(Cancelled::TimedOut)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Ok while work may go on, so a loop can poll with `?`.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        self.reason().map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert_eq!(clone.check(), Ok(()));
+        token.cancel();
+        assert_eq!(clone.check(), Err(Cancelled::Interrupted));
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn test_timeout() {
+        assert_eq!(
+            CancelToken::with_timeout(Duration::from_secs(60)).reason(),
+            None
+        );
+        let token = CancelToken::with_timeout(Duration::ZERO);
+        assert_eq!(token.reason(), Some(Cancelled::TimedOut));
+        assert_eq!(token.check().unwrap_err().to_string(), "timed out");
+
+        // an interruption wins over the deadline
+        token.cancel();
+        assert_eq!(token.reason(), Some(Cancelled::Interrupted));
+    }
+}
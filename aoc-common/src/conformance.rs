@@ -0,0 +1,93 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+/// Check that `text` survives a trip through `T` and return its canonical
+/// form: parsing the displayed value gives the same value back, and
+/// displaying that again gives the same text.
+///
+/// Meant for tests, so any mismatch panics with both sides.
+pub fn round_trip<T>(text: &str) -> String
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Debug,
+{
+    let parsed: T = text
+        .parse()
+        .unwrap_or_else(|err| panic!("failed to parse {:?}: {:?}", text, err));
+    let canonical = parsed.to_string();
+    let reparsed: T = canonical
+        .parse()
+        .unwrap_or_else(|err| panic!("failed to parse {:?} back: {:?}", canonical, err));
+    assert_eq!(reparsed, parsed, "parse(display(x)) != x for {:?}", text);
+    assert_eq!(
+        reparsed.to_string(),
+        canonical,
+        "display is not canonical for {:?}",
+        text
+    );
+    canonical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    // a comma-separated list that displays without the spaces
+    #[derive(Debug, PartialEq)]
+    struct List(Vec<u32>);
+
+    impl FromStr for List {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.split(',')
+                .map(|n| n.trim().parse())
+                .collect::<Result<_, _>>()
+                .map(List)
+        }
+    }
+
+    impl fmt::Display for List {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let items: Vec<String> = self.0.iter().map(u32::to_string).collect();
+            write!(f, "{}", items.join(","))
+        }
+    }
+
+    // displays only the first item, so nothing survives a round trip
+    #[derive(Debug, PartialEq)]
+    struct Lossy(Vec<u32>);
+
+    impl FromStr for Lossy {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse::<List>().map(|list| Lossy(list.0))
+        }
+    }
+
+    impl fmt::Display for Lossy {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.0[0])
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(round_trip::<List>("1, 2,3"), "1,2,3");
+        assert_eq!(round_trip::<List>("1,2,3"), "1,2,3");
+    }
+
+    #[test]
+    #[should_panic(expected = "parse(display(x)) != x")]
+    fn test_round_trip_lossy() {
+        round_trip::<Lossy>("1,2");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse")]
+    fn test_round_trip_unparsable() {
+        round_trip::<List>("1,x");
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use crate::graph::Graph;
+use crate::grid::{Direction, Grid, Point};
+
+/// How to treat the Day 23 slope tiles `^ > v <`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Slopes {
+    /// Only walk down a slope in the direction it points.
+    #[default]
+    OneWay,
+    /// Treat slopes like any other open tile.
+    Ignore,
+}
+
+/// A maze reduced to its junctions: `nodes[i]` is where node `i` of `graph`
+/// sits, and each edge weighs the number of steps along its corridor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Maze {
+    pub graph: Graph,
+    pub nodes: Vec<Point>,
+}
+
+impl Maze {
+    pub fn node(&self, point: Point) -> Option<usize> {
+        self.nodes.iter().position(|&node| node == point)
+    }
+}
+
+/// Collapse every corridor of `grid` (walls are `#`) into a single weighted
+/// edge between the cells it connects: `start`, `goal` and any cell with
+/// three or more open neighbors. Dead ends are dropped.
+pub fn contract(grid: &Grid<char>, start: Point, goal: Point, slopes: Slopes) -> Maze {
+    let open = |point: Point| grid.get(point).is_some_and(|&c| c != '#');
+    let exits = |point: Point| {
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            let next = point.step(direction)?;
+            (open(next) && can_step(grid, point, direction, next, slopes)).then_some(next)
+        })
+    };
+    let degree = |point: Point| point.neighbors4().filter(|&next| open(next)).count();
+
+    let nodes: Vec<Point> = grid
+        .points()
+        .filter(|&point| open(point) && (point == start || point == goal || degree(point) >= 3))
+        .collect();
+    let ids: HashMap<Point, usize> = nodes.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+
+    let mut graph = Graph::new(nodes.len());
+    for (from, &junction) in nodes.iter().enumerate() {
+        'corridor: for first in exits(junction) {
+            let (mut previous, mut current, mut steps) = (junction, first, 1);
+            while !ids.contains_key(&current) {
+                let Some(next) = exits(current).find(|&next| next != previous) else {
+                    continue 'corridor;
+                };
+                (previous, current) = (current, next);
+                steps += 1;
+            }
+            graph.add_edge(from, ids[&current], steps);
+        }
+    }
+
+    Maze { graph, nodes }
+}
+
+// whether the step is allowed by any slope it leaves from or lands on
+fn can_step(
+    grid: &Grid<char>,
+    from: Point,
+    direction: Direction,
+    to: Point,
+    slopes: Slopes,
+) -> bool {
+    if slopes == Slopes::Ignore {
+        return true;
+    }
+
+    let downhill = |point: Point| match grid.get(point) {
+        Some('^') => Some(Direction::Up),
+        Some('>') => Some(Direction::Right),
+        Some('v') => Some(Direction::Down),
+        Some('<') => Some(Direction::Left),
+        _ => None,
+    };
+    [from, to]
+        .into_iter()
+        .all(|point| downhill(point).is_none_or(|slope| slope == direction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    fn hike(slopes: Slopes) -> (Maze, Option<u64>) {
+        let grid = Grid::parse(EXAMPLE);
+        let (start, goal) = (Point::new(0, 1), Point::new(22, 21));
+        let maze = contract(&grid, start, goal, slopes);
+        let longest = maze
+            .graph
+            .longest_path(maze.node(start).unwrap(), maze.node(goal).unwrap());
+        (maze, longest)
+    }
+
+    #[test]
+    fn test_day23_example() {
+        let (maze, longest) = hike(Slopes::OneWay);
+        assert_eq!(longest, Some(94));
+        // start, goal and seven junctions
+        assert_eq!(maze.nodes.len(), 9);
+
+        let (maze, longest) = hike(Slopes::Ignore);
+        assert_eq!(longest, Some(154));
+        // every corridor can now be walked both ways
+        let edges: Vec<_> = maze.graph.edges().collect();
+        assert!(edges
+            .iter()
+            .all(|&(from, to, steps)| edges.contains(&(to, from, steps))));
+    }
+
+    #[test]
+    fn test_corridor_weights() {
+        let grid = Grid::parse("#.###\n#...#\n###.#\n###.#");
+        let maze = contract(&grid, Point::new(0, 1), Point::new(3, 3), Slopes::Ignore);
+        assert_eq!(maze.nodes, vec![Point::new(0, 1), Point::new(3, 3)]);
+        let edges: Vec<_> = maze.graph.edges().collect();
+        assert_eq!(edges, vec![(0, 1, 5), (1, 0, 5)]);
+    }
+
+    #[test]
+    fn test_dead_ends_are_dropped() {
+        // the branch off the middle junction leads nowhere
+        let grid = Grid::parse("#.###\n#..##\n#.###\n#.###");
+        let maze = contract(&grid, Point::new(0, 1), Point::new(3, 1), Slopes::Ignore);
+        assert_eq!(maze.nodes.len(), 3);
+        assert_eq!(maze.graph.edges().count(), 4);
+        assert_eq!(maze.graph.neighbors(1).len(), 2);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::trace::{Event, Tracer};
+
+/// Where a sequence of states starts repeating: the state after
+/// `start + length` steps is the state after `start` steps again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Period {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Period {
+    /// The first step count that lands on the same state as `n` steps.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Run `step` until a state repeats or `limit` steps have been taken.
+///
+/// Only `key(state)` is remembered, so `key` can compress large states
+/// (pack a grid into bits, hash it, ...) as long as different states keep
+/// different keys.
+pub fn find_period<S, K: Hash + Eq>(
+    initial: &S,
+    limit: usize,
+    step: impl FnMut(&S) -> S,
+    key: impl FnMut(&S) -> K,
+) -> Option<Period> {
+    find_period_traced(initial, limit, step, key, &mut ())
+}
+
+/// [`find_period`], telling `tracer` about the cycle once it's found.
+pub fn find_period_traced<S, K: Hash + Eq>(
+    initial: &S,
+    limit: usize,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+    tracer: &mut impl Tracer,
+) -> Option<Period> {
+    let mut seen = HashMap::new();
+    seen.insert(key(initial), 0);
+
+    let mut state = step(initial);
+    for i in 1..=limit {
+        if let Some(&start) = seen.get(&key(&state)) {
+            let length = i - start;
+            tracer.event(Event::Cycle { start, length });
+            return Some(Period { start, length });
+        }
+        seen.insert(key(&state), i);
+        state = step(&state);
+    }
+
+    None
+}
+
+/// The state after `n` steps, jumping ahead once the states start repeating.
+///
+/// Every state is kept, so this suits states that are cheap to clone; see
+/// [`nth_by_key`] for large ones.
+pub fn nth<S: Clone + Hash + Eq>(initial: S, n: usize, mut step: impl FnMut(&S) -> S) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let period = Period {
+                start,
+                length: i - start,
+            };
+            return states.swap_remove(period.equivalent(n));
+        }
+        seen.insert(state.clone(), i);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/// Like [`nth`] but only stores `key(state)` for each step. Once a repeat is
+/// found the remaining `(n - start) % length` steps are replayed from the
+/// current state instead of being looked up.
+pub fn nth_by_key<S, K: Hash + Eq>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> S {
+    let mut seen = HashMap::new();
+
+    let mut state = initial;
+    let mut i = 0;
+    while i < n {
+        let k = key(&state);
+        if let Some(&start) = seen.get(&k) {
+            let period = Period {
+                start,
+                length: i - start,
+            };
+            // the current state is the state at `start`
+            let remaining = period.equivalent(n) - start;
+            return (0..remaining).fold(state, |state, _| step(&state));
+        }
+        seen.insert(k, i);
+        state = step(&state);
+        i += 1;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &u64) -> u64 {
+        (x * x + 7) % 1009
+    }
+
+    fn brute_force(initial: u64, n: usize) -> u64 {
+        (0..n).fold(initial, |x, _| next(&x))
+    }
+
+    #[test]
+    fn test_find_period() {
+        let period = find_period(&3u64, 10_000, next, |x| *x).unwrap();
+        assert!(period.length > 0);
+        let at_start = brute_force(3, period.start);
+        assert_eq!(brute_force(3, period.start + period.length), at_start);
+        // nothing earlier repeats
+        assert!((0..period.start).all(|i| brute_force(3, i) != brute_force(3, i + period.length)));
+
+        assert_eq!(find_period(&0u64, 3, |x| x + 1, |x| *x), None);
+    }
+
+    #[test]
+    fn test_find_period_traced() {
+        let mut events = Vec::new();
+        let period = find_period_traced(&0u64, 10, |x| (x + 1) % 3, |x| *x, &mut events);
+        assert_eq!(
+            period,
+            Some(Period {
+                start: 0,
+                length: 3
+            })
+        );
+        assert_eq!(
+            events,
+            [Event::Cycle {
+                start: 0,
+                length: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let period = Period {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(period.equivalent(2), 2);
+        assert_eq!(period.equivalent(3), 3);
+        assert_eq!(period.equivalent(7), 3);
+        assert_eq!(
+            period.equivalent(1_000_000_000),
+            3 + (1_000_000_000 - 3) % 4
+        );
+    }
+
+    #[test]
+    fn test_nth_matches_brute_force() {
+        for n in [0, 1, 2, 10, 100, 1000, 5000] {
+            assert_eq!(nth(3u64, n, next), brute_force(3, n), "n = {}", n);
+            assert_eq!(
+                nth_by_key(3u64, n, next, |x| *x),
+                brute_force(3, n),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_jumps_to_a_billion() {
+        let mut steps = 0;
+        let result = nth(3u64, 1_000_000_000, |x| {
+            steps += 1;
+            next(x)
+        });
+
+        let period = find_period(&3u64, 10_000, next, |x| *x).unwrap();
+        assert_eq!(result, brute_force(3, period.equivalent(1_000_000_000)));
+        assert!(steps <= period.start + period.length);
+    }
+
+    #[test]
+    fn test_compressed_grid_states() {
+        // a row of rocks rolling one cell right per step and wrapping around,
+        // remembered as a bitmask instead of the whole grid
+        let grid: Vec<Vec<char>> = vec!["O..O....".chars().collect(), "........".chars().collect()];
+        let roll = |grid: &Vec<Vec<char>>| -> Vec<Vec<char>> {
+            grid.iter()
+                .map(|row| {
+                    let mut row = row.clone();
+                    row.rotate_right(1);
+                    row
+                })
+                .collect()
+        };
+        let pack = |grid: &Vec<Vec<char>>| -> u64 {
+            grid.iter()
+                .flatten()
+                .fold(0, |bits, &c| bits << 1 | (c == 'O') as u64)
+        };
+
+        let period = find_period(&grid, 100, roll, pack).unwrap();
+        assert_eq!(
+            period,
+            Period {
+                start: 0,
+                length: 8
+            }
+        );
+
+        let result = nth_by_key(grid.clone(), 1_000_000_003, roll, pack);
+        let expected: Vec<char> = "...O..O.".chars().collect();
+        assert_eq!(result[0], expected);
+        assert_eq!(result, nth(grid, 1_000_000_003, roll));
+    }
+}
@@ -0,0 +1,154 @@
+/// Something that happens at `offset` and then every `period` steps after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub offset: u64,
+    pub period: u64,
+}
+
+impl Cycle {
+    /// Panics if `period` is zero.
+    pub fn new(offset: u64, period: u64) -> Self {
+        assert!(period > 0, "a cycle needs a period of at least 1");
+        Cycle { offset, period }
+    }
+
+    /// Whether the event happens at step `t`.
+    pub fn hits(&self, t: u64) -> bool {
+        t >= self.offset && (t - self.offset).is_multiple_of(self.period)
+    }
+}
+
+/// The cycle of steps where both `a` and `b` happen, or `None` if they never
+/// line up or only do past `u64::MAX`.
+///
+/// This is the Chinese remainder theorem for moduli that need not be coprime,
+/// so it also covers Day-8-style inputs where an offset differs from its
+/// period and the plain LCM is the wrong answer.
+pub fn combine(a: Cycle, b: Cycle) -> Option<Cycle> {
+    let (p1, p2) = (a.period as i128, b.period as i128);
+    let (o1, o2) = (a.offset as i128, b.offset as i128);
+
+    // t = o1 + p1 * k with p1 * k ≡ o2 - o1 (mod p2)
+    let (g, inverse, _) = extended_gcd(p1, p2);
+    let diff = o2 - o1;
+    if diff % g != 0 {
+        return None;
+    }
+    // periods near u64::MAX take these products past i128, and the answer
+    // past u64 anyway
+    let step = p2 / g;
+    let k = ((diff / g % step).checked_mul(inverse)? % step).rem_euclid(step);
+
+    let period = (p1 / g).checked_mul(p2)?;
+    let residue = p1.checked_mul(k)?.checked_add(o1)?.rem_euclid(period);
+
+    // first alignment at or after both offsets
+    let earliest = o1.max(o2);
+    let offset = if residue >= earliest {
+        residue
+    } else {
+        let periods = (earliest - residue).checked_add(period - 1)? / period;
+        residue.checked_add(periods.checked_mul(period)?)?
+    };
+
+    Some(Cycle {
+        offset: u64::try_from(offset).ok()?,
+        period: u64::try_from(period).ok()?,
+    })
+}
+
+/// The combined cycle of every input cycle, or `None` if they never line up.
+pub fn combine_all(cycles: &[Cycle]) -> Option<Cycle> {
+    let (first, rest) = cycles.split_first()?;
+    rest.iter()
+        .try_fold(*first, |acc, &cycle| combine(acc, cycle))
+}
+
+/// The first step at which every cycle happens at once.
+pub fn first_alignment(cycles: &[Cycle]) -> Option<u64> {
+    combine_all(cycles).map(|cycle| cycle.offset)
+}
+
+// (gcd, x, y) with a * x + b * y = gcd
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(cycles: &[Cycle], limit: u64) -> Option<u64> {
+        (0..limit).find(|&t| cycles.iter().all(|cycle| cycle.hits(t)))
+    }
+
+    #[test]
+    fn test_offset_equals_period() {
+        // the LCM shortcut case: every ghost reaches Z after exactly one period
+        let cycles = [Cycle::new(2, 2), Cycle::new(3, 3), Cycle::new(4, 4)];
+        assert_eq!(first_alignment(&cycles), Some(12));
+        assert_eq!(combine_all(&cycles), Some(Cycle::new(12, 12)));
+    }
+
+    #[test]
+    fn test_offsets_differ_from_periods() {
+        // t ≡ 2 (mod 3), t ≡ 3 (mod 5), t ≡ 2 (mod 7): 23, not lcm 105
+        let cycles = [Cycle::new(2, 3), Cycle::new(3, 5), Cycle::new(2, 7)];
+        assert_eq!(first_alignment(&cycles), Some(23));
+
+        // 1 satisfies both congruences but the second cycle hasn't started yet
+        let cycles = [Cycle::new(1, 4), Cycle::new(9, 2)];
+        assert_eq!(first_alignment(&cycles), Some(9));
+        let cycles = [Cycle::new(1, 4), Cycle::new(13, 6)];
+        assert_eq!(first_alignment(&cycles), Some(13));
+    }
+
+    #[test]
+    fn test_never_aligns() {
+        let cycles = [Cycle::new(0, 4), Cycle::new(1, 6)];
+        assert_eq!(first_alignment(&cycles), None);
+        assert_eq!(first_alignment(&[]), None);
+    }
+
+    #[test]
+    fn test_large_periods() {
+        // periods in the style of a real Day 8 input
+        let periods = [20_777, 19_199, 18_673, 16_043, 12_361, 15_517];
+        let cycles: Vec<Cycle> = periods.iter().map(|&p| Cycle::new(p, p)).collect();
+        assert_eq!(first_alignment(&cycles), Some(18_215_611_419_223));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // lined up only past u64::MAX, with products past i128::MAX on the way
+        let cycles = [Cycle::new(1, u64::MAX), Cycle::new(2, u64::MAX - 1)];
+        assert_eq!(first_alignment(&cycles), None);
+        let cycles = [Cycle::new(u64::MAX - 1, u64::MAX), Cycle::new(0, 1 << 63)];
+        assert_eq!(first_alignment(&cycles), None);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for p1 in 1..=6 {
+            for p2 in 1..=6 {
+                for o1 in 0..8 {
+                    for o2 in 0..8 {
+                        let cycles = [Cycle::new(o1, p1), Cycle::new(o2, p2)];
+                        let expected = brute_force(&cycles, 100);
+                        assert_eq!(first_alignment(&cycles), expected, "{:?}", cycles);
+
+                        if let Some(combined) = combine_all(&cycles) {
+                            let t = combined.offset + combined.period;
+                            assert!(cycles.iter().all(|cycle| cycle.hits(t)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
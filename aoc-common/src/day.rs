@@ -0,0 +1,171 @@
+use std::borrow::Borrow;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// One part's answer and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u32,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Part {}: {} ({:.2?})",
+            self.part, self.answer, self.elapsed
+        )
+    }
+}
+
+/// Parse `lines` and answer both parts from the result, timing each part;
+/// what [`aoc_main!`](crate::aoc_main) runs. The parts may take a borrowed
+/// form of what `parse` returns, such as `&[Card]` for a `Vec<Card>`.
+pub fn solve<P, T, E, A, B>(
+    lines: Vec<String>,
+    parse: impl FnOnce(Vec<String>) -> Result<P, E>,
+    part1: impl FnOnce(&T) -> Result<A, E>,
+    part2: impl FnOnce(&T) -> Result<B, E>,
+) -> Result<[Answer; 2], E>
+where
+    P: Borrow<T>,
+    T: ?Sized,
+    A: Display,
+    B: Display,
+{
+    let parsed = parse(lines)?;
+    let data = parsed.borrow();
+
+    let start = Instant::now();
+    let answer = part1(data)?.to_string();
+    let first = Answer {
+        part: 1,
+        answer,
+        elapsed: start.elapsed(),
+    };
+
+    let start = Instant::now();
+    let answer = part2(data)?.to_string();
+    let second = Answer {
+        part: 2,
+        answer,
+        elapsed: start.elapsed(),
+    };
+
+    Ok([first, second])
+}
+
+/// The standard `main` for a day crate, so the crate holds only the puzzle:
+///
+/// ```ignore
+/// aoc_common::aoc_main!(day = 4, parse = parse, part1 = part1, part2 = part2);
+/// ```
+///
+/// `parse` takes the lines of the day's input, found with
+/// [`input::resolve`](crate::input::resolve), and both parts take what it
+/// returns by reference, or a borrowed form of it; all three are functions or
+/// closures returning a `color_eyre` `Result`. The main
+/// installs `color_eyre` and [`timing::install`](crate::timing::install),
+/// so the day crate needs `color-eyre`, `tracing` and aoc-common's `timing`
+/// feature. A day of an earlier year starts with `year = 2022, ...` and reads
+/// its input with [`input::resolve_year`](crate::input::resolve_year).
+#[macro_export]
+macro_rules! aoc_main {
+    (day = $day:literal, parse = $parse:expr, part1 = $part1:expr, part2 = $part2:expr $(,)?) => {
+        $crate::aoc_main!(
+            year = $crate::input::YEAR,
+            day = $day,
+            parse = $parse,
+            part1 = $part1,
+            part2 = $part2
+        );
+    };
+    (
+        year = $year:expr,
+        day = $day:literal,
+        parse = $parse:expr,
+        part1 = $part1:expr,
+        part2 = $part2:expr $(,)?
+    ) => {
+        fn main() -> ::color_eyre::eyre::Result<()> {
+            use ::color_eyre::eyre::WrapErr;
+
+            ::color_eyre::install()?;
+            let timings = $crate::timing::install("info")?;
+            ::tracing::info!("Starting up...");
+
+            let input = $crate::input::resolve_year($year, $day)?;
+            ::tracing::info!("Reading {}", input);
+            let input = ::std::fs::read_to_string(&input.path)
+                .wrap_err_with(|| format!("failed to read {}", input))?;
+            let lines = input.lines().map(String::from).collect();
+            for answer in $crate::day::solve(lines, $parse, $part1, $part2)? {
+                println!("Day {} {}", $day, answer);
+            }
+
+            eprint!("{}", timings);
+            ::tracing::info!("Winding Down...");
+            Ok(())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers(Vec<u64>);
+
+    fn parse(lines: Vec<String>) -> Result<Numbers, String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.parse()
+                    .map_err(|_| format!("not a number: {:?}", line))
+            })
+            .collect::<Result<_, _>>()
+            .map(Numbers)
+    }
+
+    fn sum(numbers: &Numbers) -> Result<u64, String> {
+        Ok(numbers.0.iter().sum())
+    }
+
+    fn largest(numbers: &Numbers) -> Result<u64, String> {
+        numbers
+            .0
+            .iter()
+            .max()
+            .copied()
+            .ok_or(String::from("no numbers"))
+    }
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let [first, second] = solve(lines("3\n9\n4"), parse, sum, largest).unwrap();
+        assert_eq!((first.part, first.answer.as_str()), (1, "16"));
+        assert_eq!((second.part, second.answer.as_str()), (2, "9"));
+        assert!(first.to_string().starts_with("Part 1: 16 ("));
+
+        let err = solve(lines("3\nx"), parse, sum, largest).unwrap_err();
+        assert_eq!(err, "not a number: \"x\"");
+        let err = solve(Vec::new(), parse, sum, largest).unwrap_err();
+        assert_eq!(err, "no numbers");
+    }
+
+    #[test]
+    fn test_solve_borrowed() {
+        // parts over a slice of what parse collects, or closures
+        let parse = |lines: Vec<String>| parse(lines).map(|numbers| numbers.0);
+        let count = |numbers: &[u64]| Ok::<_, String>(numbers.len());
+        let even = |numbers: &[u64]| Ok(numbers.iter().filter(|n| *n % 2 == 0).count());
+        let [first, second] = solve(lines("3\n9\n4"), parse, count, even).unwrap();
+        assert_eq!((first.answer.as_str(), second.answer.as_str()), ("3", "1"));
+    }
+}
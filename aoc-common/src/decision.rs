@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use crate::interval::Interval;
+
+/// A box of `N` independent attribute ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hyperrect<const N: usize>(pub [Interval; N]);
+
+impl<const N: usize> Hyperrect<N> {
+    /// The number of integer points inside.
+    pub fn volume(&self) -> u64 {
+        self.0
+            .iter()
+            .map(|range| range.len().max(0) as u64)
+            .product()
+    }
+
+    pub fn contains(&self, point: &[i64; N]) -> bool {
+        self.0
+            .iter()
+            .zip(point)
+            .all(|(range, &v)| range.contains(v))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Less,
+    Greater,
+}
+
+/// `attribute[axis] < value` or `attribute[axis] > value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Condition {
+    pub axis: usize,
+    pub comparison: Comparison,
+    pub value: i64,
+}
+
+impl Condition {
+    pub fn holds(&self, point: &[i64]) -> bool {
+        match self.comparison {
+            Comparison::Less => point[self.axis] < self.value,
+            Comparison::Greater => point[self.axis] > self.value,
+        }
+    }
+
+    /// Cut `region` along this condition's axis into the part that matches
+    /// and the part that doesn't; either is `None` when empty.
+    pub fn split<const N: usize>(
+        &self,
+        region: &Hyperrect<N>,
+    ) -> (Option<Hyperrect<N>>, Option<Hyperrect<N>>) {
+        let range = region.0[self.axis];
+        let (matching, rest) = match self.comparison {
+            Comparison::Less => range.split_at(self.value),
+            Comparison::Greater => {
+                let (below, above) = range.split_at(self.value.saturating_add(1));
+                (above, below)
+            }
+        };
+
+        let with = |range: Interval| {
+            let mut ranges = region.0;
+            ranges[self.axis] = range;
+            Hyperrect(ranges)
+        };
+        (matching.map(with), rest.map(with))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    Accept,
+    Reject,
+    Node(String),
+}
+
+/// Rules are tried in order; the first whose condition holds picks the
+/// target, and `fallback` is used when none do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub rules: Vec<(Condition, Target)>,
+    pub fallback: Target,
+}
+
+/// Named nodes of comparisons, as in the Day 19 workflows. Following targets
+/// from any node must never lead back to it.
+#[derive(Debug, Clone, Default)]
+pub struct DecisionTree {
+    nodes: HashMap<String, Node>,
+}
+
+impl DecisionTree {
+    pub fn new() -> Self {
+        DecisionTree::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, node: Node) {
+        self.nodes.insert(name.into(), node);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Node> {
+        self.nodes.get(name)
+    }
+
+    /// Whether a single point starting at `start` ends up accepted. Panics on
+    /// a target naming a missing node.
+    pub fn accepts(&self, start: &str, point: &[i64]) -> bool {
+        let mut name = start;
+        loop {
+            let node = self.node(name);
+            let target = node
+                .rules
+                .iter()
+                .find(|(condition, _)| condition.holds(point))
+                .map_or(&node.fallback, |(_, target)| target);
+            match target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Node(next) => name = next,
+            }
+        }
+    }
+
+    /// Push `region` through the tree from `start`, returning the disjoint
+    /// boxes that end up accepted.
+    pub fn accepted<const N: usize>(&self, start: &str, region: Hyperrect<N>) -> Vec<Hyperrect<N>> {
+        let mut accepted = Vec::new();
+        let mut pending = vec![(start, region)];
+
+        while let Some((name, region)) = pending.pop() {
+            let node = self.node(name);
+            let mut rest = Some(region);
+            for (condition, target) in &node.rules {
+                let Some(region) = rest else {
+                    break;
+                };
+                let (matching, remaining) = condition.split(&region);
+                if let Some(matching) = matching {
+                    match target {
+                        Target::Accept => accepted.push(matching),
+                        Target::Reject => {}
+                        Target::Node(next) => pending.push((next, matching)),
+                    }
+                }
+                rest = remaining;
+            }
+
+            if let Some(region) = rest {
+                match &node.fallback {
+                    Target::Accept => accepted.push(region),
+                    Target::Reject => {}
+                    Target::Node(next) => pending.push((next, region)),
+                }
+            }
+        }
+
+        accepted
+    }
+
+    /// How many integer points of `region` end up accepted.
+    pub fn count_accepted<const N: usize>(&self, start: &str, region: Hyperrect<N>) -> u64 {
+        self.accepted(start, region)
+            .iter()
+            .map(Hyperrect::volume)
+            .sum()
+    }
+
+    fn node(&self, name: &str) -> &Node {
+        self.nodes
+            .get(name)
+            .unwrap_or_else(|| panic!("no node named {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    fn target(s: &str) -> Target {
+        match s {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Node(name.to_string()),
+        }
+    }
+
+    fn axis(s: &str) -> usize {
+        "xmas".find(s).unwrap()
+    }
+
+    fn parse(input: &str) -> (DecisionTree, Vec<[i64; 4]>) {
+        let (workflows, parts) = input.split_once("\n\n").unwrap();
+
+        let mut tree = DecisionTree::new();
+        for line in workflows.lines() {
+            let (name, body) = line.trim_end_matches('}').split_once('{').unwrap();
+            let mut rules: Vec<&str> = body.split(',').collect();
+            let fallback = target(rules.pop().unwrap());
+            let rules = rules
+                .into_iter()
+                .map(|rule| {
+                    let (test, to) = rule.split_once(':').unwrap();
+                    let comparison = if test.contains('<') {
+                        Comparison::Less
+                    } else {
+                        Comparison::Greater
+                    };
+                    let condition = Condition {
+                        axis: axis(&test[..1]),
+                        comparison,
+                        value: test[2..].parse().unwrap(),
+                    };
+                    (condition, target(to))
+                })
+                .collect();
+            tree.insert(name, Node { rules, fallback });
+        }
+
+        let parts = parts
+            .lines()
+            .map(|line| {
+                let mut part = [0; 4];
+                for field in line.trim_matches(|c| c == '{' || c == '}').split(',') {
+                    let (name, value) = field.split_once('=').unwrap();
+                    part[axis(name)] = value.parse().unwrap();
+                }
+                part
+            })
+            .collect();
+
+        (tree, parts)
+    }
+
+    #[test]
+    fn test_day19_example() {
+        let (tree, parts) = parse(EXAMPLE);
+
+        let rating: i64 = parts
+            .iter()
+            .filter(|part| tree.accepts("in", &part[..]))
+            .map(|part| part.iter().sum::<i64>())
+            .sum();
+        assert_eq!(rating, 19114);
+
+        let everything = Hyperrect([Interval::new(1, 4001); 4]);
+        assert_eq!(tree.count_accepted("in", everything), 167409079868000);
+    }
+
+    #[test]
+    fn test_matches_pointwise() {
+        let (tree, _) = parse(EXAMPLE);
+        // a small box straddling many of the example's cut points
+        let region = Hyperrect([
+            Interval::new(1410, 1420),
+            Interval::new(1545, 1552),
+            Interval::new(2000, 2010),
+            Interval::new(1348, 1354),
+        ]);
+
+        let accepted = tree.accepted("in", region);
+        let mut expected = 0;
+        for x in 1410..1420 {
+            for m in 1545..1552 {
+                for a in 2000..2010 {
+                    for s in 1348..1354 {
+                        let point = [x, m, a, s];
+                        let hits = accepted.iter().filter(|r| r.contains(&point)).count();
+                        let accepts = tree.accepts("in", &point);
+                        assert_eq!(hits, accepts as usize, "{:?}", point);
+                        expected += accepts as u64;
+                    }
+                }
+            }
+        }
+        assert_eq!(tree.count_accepted("in", region), expected);
+    }
+
+    #[test]
+    fn test_split() {
+        let region = Hyperrect([Interval::new(1, 11), Interval::new(0, 5)]);
+        let less = Condition {
+            axis: 0,
+            comparison: Comparison::Less,
+            value: 4,
+        };
+        let (matching, rest) = less.split(&region);
+        assert_eq!(matching.unwrap().0[0], Interval::new(1, 4));
+        assert_eq!(rest.unwrap().0[0], Interval::new(4, 11));
+
+        let greater = Condition {
+            axis: 1,
+            comparison: Comparison::Greater,
+            value: 10,
+        };
+        assert_eq!(greater.split(&region), (None, Some(region)));
+        assert_eq!(region.volume(), 50);
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// How a solver reached its answers: titled sections of intermediate values,
+/// one line each, for `aoc run --explain`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Narrative {
+    sections: Vec<Section>,
+}
+
+/// One titled part of a [`Narrative`], e.g. every game's minimal bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Narrative {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a section; lines go to the section started last.
+    pub fn section(&mut self, title: impl Into<String>) -> &mut Self {
+        self.sections.push(Section {
+            title: title.into(),
+            lines: Vec::new(),
+        });
+        self
+    }
+
+    /// Add a line to the last section, starting an untitled one if there's none.
+    pub fn line(&mut self, text: impl Into<String>) -> &mut Self {
+        if self.sections.is_empty() {
+            self.section("");
+        }
+        if let Some(section) = self.sections.last_mut() {
+            section.lines.push(text.into());
+        }
+        self
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+}
+
+// Titles flush left and their lines indented under them, a blank line
+// between sections
+impl fmt::Display for Narrative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if !section.title.is_empty() {
+                writeln!(f, "{}", section.title)?;
+            }
+            for line in &section.lines {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_narrative() {
+        let mut narrative = Narrative::new();
+        narrative.line("untitled").section("Answers").line("part 1: 8");
+        narrative.line("part 2: 2286");
+        assert_eq!(narrative.sections().len(), 2);
+        assert_eq!(
+            narrative.to_string(),
+            "  untitled\n\nAnswers\n  part 1: 8\n  part 2: 2286\n"
+        );
+        assert_eq!(Narrative::new().to_string(), "");
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Write as _;
+
+/// A weighted directed graph with nodes numbered from 0. An undirected edge
+/// is stored as two directed ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    adjacency: Vec<Vec<(usize, u64)>>,
+}
+
+impl Graph {
+    pub fn new(nodes: usize) -> Self {
+        Graph {
+            adjacency: vec![Vec::new(); nodes],
+        }
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.adjacency[from].push((to, weight));
+    }
+
+    pub fn add_undirected(&mut self, a: usize, b: usize, weight: u64) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// `(to, weight)` for every edge leaving `node`.
+    pub fn neighbors(&self, node: usize) -> &[(usize, u64)] {
+        &self.adjacency[node]
+    }
+
+    /// Every directed edge as `(from, to, weight)`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, u64)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |&(to, weight)| (from, to, weight)))
+    }
+
+    /// The heaviest path from `start` to `goal` that visits no node twice, or
+    /// `None` if `goal` can't be reached. This tries every simple path, so it
+    /// is only practical on small graphs such as a contracted maze.
+    pub fn longest_path(&self, start: usize, goal: usize) -> Option<u64> {
+        let mut visited = vec![false; self.len()];
+        self.longest_from(start, goal, &mut visited)
+    }
+
+    /// The lightest set of edges whose removal splits the graph in two
+    /// (Stoer–Wagner), or `None` with fewer than two nodes. The graph must be
+    /// undirected: every edge added both ways, as `add_undirected` does.
+    pub fn min_cut(&self) -> Option<Cut> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        // merged nodes keep their summed edges and the original nodes inside
+        let mut adjacency: Vec<HashMap<usize, u64>> = vec![HashMap::new(); n];
+        for (from, to, weight) in self.edges() {
+            if from != to {
+                *adjacency[from].entry(to).or_default() += weight;
+            }
+        }
+        debug_assert!(
+            self.edges()
+                .all(|(from, to, _)| adjacency[from].get(&to) == adjacency[to].get(&from)),
+            "min_cut needs an undirected graph"
+        );
+        let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+
+        let mut best: Option<Cut> = None;
+        while active.len() > 1 {
+            let (s, t, weight) = maximum_adjacency(&adjacency, &active);
+            if best.as_ref().is_none_or(|cut| weight < cut.weight) {
+                best = Some(Cut {
+                    weight,
+                    side: members[t].clone(),
+                });
+            }
+
+            // merge t into s
+            let edges = std::mem::take(&mut adjacency[t]);
+            for (neighbor, w) in edges {
+                adjacency[neighbor].remove(&t);
+                if neighbor != s {
+                    *adjacency[s].entry(neighbor).or_default() += w;
+                    *adjacency[neighbor].entry(s).or_default() += w;
+                }
+            }
+            let moved = std::mem::take(&mut members[t]);
+            members[s].extend(moved);
+            active.retain(|&node| node != t);
+        }
+
+        best.map(|mut cut| {
+            cut.side.sort_unstable();
+            cut
+        })
+    }
+
+    /// Whether every edge is matched by one going back with the same weight,
+    /// as `add_undirected` leaves them.
+    pub fn is_undirected(&self) -> bool {
+        let mut balance: HashMap<(usize, usize, u64), i64> = HashMap::new();
+        for (from, to, weight) in self.edges() {
+            *balance.entry((from, to, weight)).or_default() += 1;
+            *balance.entry((to, from, weight)).or_default() -= 1;
+        }
+        balance.values().all(|&count| count == 0)
+    }
+
+    /// Graphviz DOT for the graph, naming node `i` with `label(i)` and
+    /// labelling edges with their weights. An undirected graph is written as
+    /// a `graph`, each edge once.
+    pub fn to_dot(&self, name: &str, label: impl Fn(usize) -> String) -> String {
+        let undirected = self.is_undirected();
+        let (kind, arrow) = if undirected {
+            ("graph", "--")
+        } else {
+            ("digraph", "->")
+        };
+
+        let mut dot = format!("{} {} {{\n", kind, quote(name));
+        for node in 0..self.len() {
+            let _ = writeln!(dot, "    {} [label={}];", node, quote(&label(node)));
+        }
+        for (from, to, weight) in self.edges() {
+            if undirected && from > to {
+                continue;
+            }
+            let _ = writeln!(dot, "    {} {} {} [label=\"{}\"];", from, arrow, to, weight);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn longest_from(&self, node: usize, goal: usize, visited: &mut [bool]) -> Option<u64> {
+        if node == goal {
+            return Some(0);
+        }
+
+        visited[node] = true;
+        let mut best = None;
+        for &(next, weight) in &self.adjacency[node] {
+            if visited[next] {
+                continue;
+            }
+            if let Some(rest) = self.longest_from(next, goal, visited) {
+                best = best.max(Some(rest + weight));
+            }
+        }
+        visited[node] = false;
+
+        best
+    }
+}
+
+/// A cut through a graph: `side` holds the nodes on one side of it, and
+/// `weight` the total weight of the edges crossing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub weight: u64,
+    pub side: Vec<usize>,
+}
+
+// a DOT string literal
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// one Stoer–Wagner phase: add the most tightly connected node until all are
+// in, returning the last two and the weight of the cut around the last
+fn maximum_adjacency(adjacency: &[HashMap<usize, u64>], active: &[usize]) -> (usize, usize, u64) {
+    let mut connection: HashMap<usize, u64> = active.iter().map(|&node| (node, 0)).collect();
+    let mut added = HashSet::new();
+    let mut queue = BinaryHeap::from([(0, active[0])]);
+    let (mut previous, mut last, mut weight) = (active[0], active[0], 0);
+
+    while let Some((key, node)) = queue.pop() {
+        if added.contains(&node) || connection[&node] != key {
+            continue;
+        }
+        added.insert(node);
+        (previous, last, weight) = (last, node, key);
+
+        for (&neighbor, &w) in &adjacency[node] {
+            if !added.contains(&neighbor) {
+                let key = connection.get_mut(&neighbor).unwrap();
+                *key += w;
+                queue.push((*key, neighbor));
+            }
+        }
+
+        // a disconnected graph: carry on from any node not yet reached
+        if queue.is_empty() && added.len() < active.len() {
+            let next = *active.iter().find(|node| !added.contains(node)).unwrap();
+            queue.push((0, next));
+        }
+    }
+
+    (previous, last, weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_path() {
+        // 0 -> 1 -> 3 is short, 0 -> 2 -> 1 -> 3 is longer
+        let mut graph = Graph::new(4);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 5);
+        graph.add_edge(2, 1, 5);
+        graph.add_edge(1, 3, 1);
+        assert_eq!(graph.longest_path(0, 3), Some(11));
+        assert_eq!(graph.longest_path(3, 0), None);
+        assert_eq!(graph.longest_path(2, 2), Some(0));
+    }
+
+    #[test]
+    fn test_undirected_cycle() {
+        // a square: the long way round beats the direct edge
+        let mut graph = Graph::default();
+        let nodes: Vec<usize> = (0..4).map(|_| graph.add_node()).collect();
+        for i in 0..4 {
+            graph.add_undirected(nodes[i], nodes[(i + 1) % 4], 2);
+        }
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edges().count(), 8);
+        assert_eq!(graph.longest_path(0, 1), Some(6));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::new(3);
+        graph.add_undirected(0, 1, 4);
+        graph.add_undirected(1, 2, 7);
+        let names = ["a", "b", "say \"c\""];
+        assert_eq!(
+            graph.to_dot("day 3", |i| names[i].to_string()),
+            "graph \"day 3\" {\n    0 [label=\"a\"];\n    1 [label=\"b\"];\n    \
+             2 [label=\"say \\\"c\\\"\"];\n    0 -- 1 [label=\"4\"];\n    1 -- 2 [label=\"7\"];\n}\n"
+        );
+
+        // a single one-way edge makes the whole graph directed
+        graph.add_edge(2, 0, 1);
+        assert!(!graph.is_undirected());
+        let dot = graph.to_dot("g", |i| i.to_string());
+        assert!(dot.starts_with("digraph \"g\" {"));
+        assert_eq!(dot.matches(" -> ").count(), 5);
+    }
+
+    #[test]
+    fn test_min_cut_stoer_wagner_example() {
+        // the example graph from Stoer and Wagner's paper, renumbered from 0
+        let mut graph = Graph::new(8);
+        for (a, b, w) in [
+            (0, 1, 2),
+            (0, 4, 3),
+            (1, 2, 3),
+            (1, 4, 2),
+            (1, 5, 2),
+            (2, 3, 4),
+            (2, 6, 2),
+            (3, 6, 2),
+            (3, 7, 2),
+            (4, 5, 3),
+            (5, 6, 1),
+            (6, 7, 3),
+        ] {
+            graph.add_undirected(a, b, w);
+        }
+
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.weight, 4);
+        assert!(cut.side == vec![2, 3, 6, 7] || cut.side == vec![0, 1, 4, 5]);
+    }
+
+    #[test]
+    fn test_min_cut_day25_example() {
+        let input = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+        let mut graph = Graph::default();
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        let mut id = |name, graph: &mut Graph| *ids.entry(name).or_insert_with(|| graph.add_node());
+        for line in input.lines() {
+            let (from, to) = line.split_once(": ").unwrap();
+            let from = id(from, &mut graph);
+            for name in to.split_whitespace() {
+                let to = id(name, &mut graph);
+                graph.add_undirected(from, to, 1);
+            }
+        }
+
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.side.len() * (graph.len() - cut.side.len()), 54);
+    }
+
+    #[test]
+    fn test_min_cut_edge_cases() {
+        assert_eq!(Graph::new(1).min_cut(), None);
+
+        // two separate pairs: nothing needs cutting
+        let mut graph = Graph::new(4);
+        graph.add_undirected(0, 1, 5);
+        graph.add_undirected(2, 3, 5);
+        assert_eq!(graph.min_cut().unwrap().weight, 0);
+    }
+}
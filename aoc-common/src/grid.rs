@@ -0,0 +1,481 @@
+use std::ops::{Index, IndexMut};
+
+/// A cell of a [`Grid`]; row 0 is the top line of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Point { row, col }
+    }
+
+    /// Up, down, left and right, skipping anything left of or above 0.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Point> {
+        let Point { row, col } = *self;
+        [
+            row.checked_sub(1).map(|row| Point { row, col }),
+            Some(Point { row: row + 1, col }),
+            col.checked_sub(1).map(|col| Point { row, col }),
+            Some(Point { row, col: col + 1 }),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// The neighbor in `direction`, or `None` past row or column 0.
+    pub fn step(&self, direction: Direction) -> Option<Point> {
+        let Point { row, col } = *self;
+        match direction {
+            Direction::Up => row.checked_sub(1).map(|row| Point { row, col }),
+            Direction::Down => Some(Point { row: row + 1, col }),
+            Direction::Left => col.checked_sub(1).map(|col| Point { row, col }),
+            Direction::Right => Some(Point { row, col: col + 1 }),
+        }
+    }
+
+    pub fn is_adjacent4(&self, other: &Point) -> bool {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col) == 1
+    }
+}
+
+/// A step across the grid; `Up` moves towards row 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// The two directions at right angles to this one.
+    pub fn turns(self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+}
+
+/// The size of a grid, for positions that have to stay on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Extent {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Extent {
+    pub fn new(width: usize, height: usize) -> Self {
+        Extent { width, height }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+
+    /// `point` as a [`GridPos`], or `None` if it is off the grid.
+    pub fn pos(&self, point: Point) -> Option<GridPos> {
+        self.contains(point).then_some(GridPos {
+            point,
+            extent: *self,
+        })
+    }
+}
+
+/// A [`Point`] known to be on a grid of some [`Extent`]. Moving it gives
+/// `None` instead of wrapping below 0 or running off the far edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridPos {
+    point: Point,
+    extent: Extent,
+}
+
+impl GridPos {
+    pub fn point(&self) -> Point {
+        self.point
+    }
+
+    pub fn extent(&self) -> Extent {
+        self.extent
+    }
+
+    /// `rows` down and `cols` right (negative for up and left), if that is
+    /// still on the grid.
+    pub fn offset(&self, rows: isize, cols: isize) -> Option<GridPos> {
+        let row = self.point.row.checked_add_signed(rows)?;
+        let col = self.point.col.checked_add_signed(cols)?;
+        self.extent.pos(Point { row, col })
+    }
+
+    pub fn step(&self, direction: Direction) -> Option<GridPos> {
+        match direction {
+            Direction::Up => self.offset(-1, 0),
+            Direction::Down => self.offset(1, 0),
+            Direction::Left => self.offset(0, -1),
+            Direction::Right => self.offset(0, 1),
+        }
+    }
+
+    /// Up, down, left and right, as far as the grid goes.
+    pub fn neighbors4(&self) -> impl Iterator<Item = GridPos> {
+        let pos = *self;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| pos.step(direction))
+    }
+
+    /// All eight neighbors on the grid, diagonals included, in reading order.
+    pub fn neighbors8(&self) -> impl Iterator<Item = GridPos> {
+        let pos = *self;
+        (-1..=1)
+            .flat_map(|rows| (-1..=1).map(move |cols| (rows, cols)))
+            .filter(|&offset| offset != (0, 0))
+            .filter_map(move |(rows, cols)| pos.offset(rows, cols))
+    }
+}
+
+impl From<GridPos> for Point {
+    fn from(pos: GridPos) -> Point {
+        pos.point
+    }
+}
+
+/// A dense rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// One row per line with `cell` applied to every character. Unlike
+    /// [`Grid::parse`] this takes ragged lines, padding the short ones out to
+    /// the longest with `fill`.
+    pub fn from_lines<S: AsRef<str>>(
+        lines: &[S],
+        fill: T,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Self {
+        let width = lines
+            .iter()
+            .map(|line| line.as_ref().chars().count())
+            .max()
+            .unwrap_or(0);
+        let rows = lines
+            .iter()
+            .map(|line| {
+                let mut row: Vec<T> = line.as_ref().chars().map(&mut cell).collect();
+                row.resize(width, fill.clone());
+                row
+            })
+            .collect();
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics if the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+        for (i, row) in rows.into_iter().enumerate() {
+            assert_eq!(
+                row.len(),
+                width,
+                "row {} has {} cells, expected {}",
+                i,
+                row.len(),
+                width
+            );
+            cells.extend(row);
+        }
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn extent(&self) -> Extent {
+        Extent::new(self.width, self.height)
+    }
+
+    pub fn contains(&self, point: impl Into<Point>) -> bool {
+        self.extent().contains(point.into())
+    }
+
+    pub fn get(&self, point: impl Into<Point>) -> Option<&T> {
+        let point = point.into();
+        self.contains(point)
+            .then(|| &self.cells[point.row * self.width + point.col])
+    }
+
+    pub fn get_mut(&mut self, point: impl Into<Point>) -> Option<&mut T> {
+        let point = point.into();
+        if self.contains(point) {
+            Some(&mut self.cells[point.row * self.width + point.col])
+        } else {
+            None
+        }
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Point { row, col }))
+    }
+
+    /// Up, down, left and right of `point`, as far as the grid goes.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.extent()
+            .pos(point)
+            .into_iter()
+            .flat_map(|pos| pos.neighbors4())
+            .map(Point::from)
+    }
+
+    /// All eight neighbors of `point` on the grid, in reading order.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.extent()
+            .pos(point)
+            .into_iter()
+            .flat_map(|pos| pos.neighbors8())
+            .map(Point::from)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Column `col` from top to bottom; empty past the last column.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let cells = if col < self.width {
+            &self.cells[col..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Every point whose cell satisfies `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(move |(i, _)| Point::new(i / width, i % width))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// One row per line. Panics if the lines have different lengths.
+    pub fn parse(input: &str) -> Self {
+        Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect())
+    }
+}
+
+// Indexed by anything that converts to a point, so a day's own position type
+// works once it has a `From` into [`Point`]
+impl<T, P: Into<Point>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: P) -> &T {
+        let point = point.into();
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, point: P) -> &mut T {
+        let point = point.into();
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("467..\n...*.\n");
+        assert_eq!((grid.width(), grid.height()), (5, 2));
+        assert_eq!(grid[Point::new(1, 3)], '*');
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.rows().next().unwrap(), &['4', '6', '7', '.', '.']);
+        assert_eq!(grid.points().count(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has 2 cells, expected 3")]
+    fn test_ragged_rows() {
+        Grid::parse("abc\nde");
+    }
+
+    #[test]
+    fn test_neighbors4() {
+        let corner: Vec<Point> = Point::new(0, 0).neighbors4().collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(Point::new(3, 3).neighbors4().count(), 4);
+        assert!(Point::new(3, 3).is_adjacent4(&Point::new(3, 4)));
+        assert!(!Point::new(3, 3).is_adjacent4(&Point::new(4, 4)));
+    }
+
+    #[test]
+    fn test_grid_pos() {
+        let extent = Grid::parse("467..\n...*.\n").extent();
+        assert_eq!(extent, Extent::new(5, 2));
+        assert_eq!(extent.pos(Point::new(2, 0)), None);
+
+        let corner = extent.pos(Point::new(0, 0)).unwrap();
+        assert_eq!(corner.offset(-1, 0), None);
+        assert_eq!(corner.step(Direction::Left), None);
+        let neighbors: Vec<Point> = corner.neighbors8().map(Point::from).collect();
+        assert_eq!(
+            neighbors,
+            vec![Point::new(0, 1), Point::new(1, 0), Point::new(1, 1)]
+        );
+
+        let far = extent.pos(Point::new(1, 4)).unwrap();
+        assert_eq!(far.step(Direction::Right), None);
+        assert_eq!(far.step(Direction::Down), None);
+        assert_eq!(far.neighbors4().count(), 2);
+        assert_eq!(far.neighbors8().count(), 3);
+
+        let middle = extent.pos(Point::new(1, 2)).unwrap();
+        assert_eq!(middle.neighbors8().count(), 5);
+        assert_eq!(
+            middle.offset(-1, 1).map(Point::from),
+            Some(Point::new(0, 3))
+        );
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Point::new(0, 2).step(Direction::Up), None);
+        assert_eq!(
+            Point::new(0, 2).step(Direction::Left),
+            Some(Point::new(0, 1))
+        );
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::Up.turns(), [Direction::Left, Direction::Right]);
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let lines = ["12", "", "3.4"];
+        let grid = Grid::from_lines(&lines, None, |ch| ch.to_digit(10));
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Point::new(0, 1)], Some(2));
+        assert_eq!(grid[Point::new(0, 2)], None);
+        assert_eq!(grid.rows().nth(1).unwrap(), &[None, None, None]);
+        assert_eq!(
+            Grid::from_lines::<&str>(&[], '.', |ch| ch).points().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = Grid::parse("467..\n...*.\n");
+        let corner: Vec<Point> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        // unlike Point::neighbors4, nothing past the far edges
+        assert_eq!(grid.neighbors4(Point::new(1, 4)).count(), 2);
+        let far: Vec<Point> = grid.neighbors8(Point::new(1, 4)).collect();
+        assert_eq!(
+            far,
+            vec![Point::new(0, 3), Point::new(0, 4), Point::new(1, 3)]
+        );
+        assert_eq!(grid.neighbors8(Point::new(1, 2)).count(), 5);
+        assert_eq!(grid.neighbors8(Point::new(2, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_columns_and_find_all() {
+        let grid = Grid::parse("467..\n...*.\n");
+        let column: String = grid.column(3).collect();
+        assert_eq!(column, ".*");
+        assert_eq!(grid.column(5).count(), 0);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["4.", "6.", "7.", ".*", ".."]);
+
+        let digits: Vec<Point> = grid.find_all(|ch| ch.is_ascii_digit()).collect();
+        assert_eq!(
+            digits,
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)]
+        );
+        assert_eq!(
+            grid.find_all(|&ch| ch == '*').next(),
+            Some(Point::new(1, 3))
+        );
+        assert_eq!(Grid::parse("").find_all(|_| true).count(), 0);
+    }
+
+    #[test]
+    fn test_map_and_index_mut() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[Point::new(1, 2)] = 7;
+        let doubled = grid.map(|v| v * 2);
+        assert_eq!(doubled[Point::new(1, 2)], 14);
+        assert_eq!(Grid::<u8>::from_rows(Vec::new()).rows().count(), 0);
+    }
+}
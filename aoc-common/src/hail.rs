@@ -0,0 +1,436 @@
+use crate::parse::ints;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// An exact fraction, always stored in lowest terms with a positive
+/// denominator. Arithmetic is checked: `None` means an i128 overflowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "rational with a zero denominator");
+        let g = gcd(num, den).max(1);
+        let sign = den.signum();
+        Rational {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    pub fn integer(value: i128) -> Self {
+        Rational { num: value, den: 1 }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// The value if it is a whole number.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let g = gcd(self.den, other.den);
+        let den = (self.den / g).checked_mul(other.den)?;
+        let num = self
+            .num
+            .checked_mul(other.den / g)?
+            .checked_add(other.num.checked_mul(self.den / g)?)?;
+        Some(Rational::new(num, den))
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(Rational {
+            num: other.num.checked_neg()?,
+            den: other.den,
+        })
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // cross-reduce first to keep the products small
+        let g1 = gcd(self.num, other.den).max(1);
+        let g2 = gcd(other.num, self.den).max(1);
+        let num = (self.num / g1).checked_mul(other.num / g2)?;
+        let den = (self.den / g2).checked_mul(other.den / g1)?;
+        Some(Rational::new(num, den))
+    }
+
+    /// `None` on overflow or division by zero.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        if other.is_zero() {
+            return None;
+        }
+        self.checked_mul(Rational {
+            num: other.den * other.num.signum(),
+            den: other.num.checked_abs()?,
+        })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational::integer(value as i128)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_fractions(self.num, self.den, other.num, other.den)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+// compare a/b with c/d (b, d > 0) by their continued fractions, so nothing
+// is ever multiplied and nothing can overflow
+fn compare_fractions(a: i128, b: i128, c: i128, d: i128) -> Ordering {
+    let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+    let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+    match (q1.cmp(&q2), r1 == 0, r2 == 0) {
+        (Ordering::Equal, true, true) => Ordering::Equal,
+        (Ordering::Equal, true, false) => Ordering::Less,
+        (Ordering::Equal, false, true) => Ordering::Greater,
+        // r1/b < r2/d exactly when d/r2 < b/r1
+        (Ordering::Equal, false, false) => compare_fractions(d, r2, b, r1),
+        (ordering, _, _) => ordering,
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+/// Solve the square system `matrix * x = rhs` exactly. `None` if the matrix
+/// is singular or an intermediate value overflows.
+pub fn solve_linear(matrix: &[Vec<Rational>], rhs: &[Rational]) -> Option<Vec<Rational>> {
+    let n = rhs.len();
+    assert!(
+        matrix.len() == n && matrix.iter().all(|row| row.len() == n),
+        "expected a {}x{} matrix",
+        n,
+        n
+    );
+
+    // augmented rows, eliminated to reduced row echelon form
+    let mut rows: Vec<Vec<Rational>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().copied().chain([b]).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| !rows[r][col].is_zero())?;
+        rows.swap(col, pivot);
+
+        let lead = rows[col][col];
+        for value in rows[col].iter_mut() {
+            *value = value.checked_div(lead)?;
+        }
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == col || factor.is_zero() {
+                continue;
+            }
+            for (value, &p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = value.checked_sub(factor.checked_mul(p)?)?;
+            }
+        }
+    }
+
+    Some(rows.into_iter().map(|row| row[n]).collect())
+}
+
+/// A hailstone at `position` when t = 0, moving `velocity` every step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hailstone {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
+
+impl Hailstone {
+    /// Parse `19, 13, 30 @ -2, 1, -2`.
+    pub fn parse(line: &str) -> Option<Hailstone> {
+        let (position, velocity) = line.split_once('@')?;
+        Some(Hailstone {
+            position: ints(position, ',')?,
+            velocity: ints(velocity, ',')?,
+        })
+    }
+
+    /// Whether `self` and `other` are ever in the same place at the same
+    /// time t >= 0.
+    pub fn collides(&self, other: &Hailstone) -> bool {
+        let mut time = None;
+        for axis in 0..3 {
+            let dp = other.position[axis] as i128 - self.position[axis] as i128;
+            let dv = self.velocity[axis] as i128 - other.velocity[axis] as i128;
+            if dv == 0 {
+                if dp != 0 {
+                    return false;
+                }
+                continue;
+            }
+            if dp % dv != 0 || dp / dv < 0 || time.is_some_and(|t| t != dp / dv) {
+                return false;
+            }
+            time = Some(dp / dv);
+        }
+        true
+    }
+}
+
+/// Where two paths cross in the x-y plane, and when each stone is there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossing {
+    pub x: Rational,
+    pub y: Rational,
+    pub time_a: Rational,
+    pub time_b: Rational,
+}
+
+impl Crossing {
+    /// Both stones reach the crossing at t >= 0 and it lies in the square
+    /// `min..=max` (Day 24 part 1).
+    pub fn is_future_within(&self, min: i64, max: i64) -> bool {
+        let (min, max) = (Rational::from(min), Rational::from(max));
+        self.time_a >= Rational::ZERO
+            && self.time_b >= Rational::ZERO
+            && (min..=max).contains(&self.x)
+            && (min..=max).contains(&self.y)
+    }
+}
+
+/// Where the x-y paths of `a` and `b` cross, ignoring z and allowing either
+/// stone to be there at a different time. `None` for parallel paths.
+pub fn intersect_2d(a: &Hailstone, b: &Hailstone) -> Option<Crossing> {
+    let [pax, pay, _] = a.position.map(i128::from);
+    let [vax, vay, _] = a.velocity.map(i128::from);
+    let [pbx, pby, _] = b.position.map(i128::from);
+    let [vbx, vby, _] = b.velocity.map(i128::from);
+
+    // pa + t * va = pb + s * vb, by Cramer's rule
+    let det = vbx * vay - vax * vby;
+    if det == 0 {
+        return None;
+    }
+    let (dx, dy) = (pbx - pax, pby - pay);
+    let time_a = Rational::new(vbx * dy - vby * dx, det);
+    let time_b = Rational::new(vax * dy - vay * dx, det);
+
+    let x = Rational::integer(pax).checked_add(time_a.checked_mul(Rational::integer(vax))?)?;
+    let y = Rational::integer(pay).checked_add(time_a.checked_mul(Rational::integer(vay))?)?;
+    Some(Crossing {
+        x,
+        y,
+        time_a,
+        time_b,
+    })
+}
+
+/// The rock thrown with whole-number position and velocity that hits every
+/// hailstone (Day 24 part 2), or `None` if there is none.
+///
+/// Each stone gives (P - p) x (V - v) = 0. Subtracting two stones' equations
+/// cancels the P x V term and leaves equations linear in the six unknowns;
+/// they are solved as an x-y system and an x-z system of four unknowns each,
+/// which keeps the elimination well inside i128 for real puzzle inputs.
+pub fn throw(hail: &[Hailstone]) -> Option<Hailstone> {
+    // try successive runs of five stones in case one gives a singular system
+    (0..hail.len().saturating_sub(4)).find_map(|start| {
+        let stones = &hail[start..start + 5];
+        let [px, py, vx, vy] = solve_plane(stones, 0, 1)?;
+        let [px2, pz, vx2, vz] = solve_plane(stones, 0, 2)?;
+        if (px, vx) != (px2, vx2) {
+            return None;
+        }
+
+        let rock = Hailstone {
+            position: [px, py, pz],
+            velocity: [vx, vy, vz],
+        };
+        hail.iter()
+            .all(|stone| rock.collides(stone))
+            .then_some(rock)
+    })
+}
+
+// solve for [P_a, P_b, V_a, V_b] from the first stone paired with each of the
+// next four, on axes `a` and `b`
+fn solve_plane(stones: &[Hailstone], a: usize, b: usize) -> Option<[i64; 4]> {
+    let r = |v: i64| Rational::from(v);
+    let (first, rest) = stones.split_first()?;
+    let (pai, pbi, vai, vbi) = (
+        first.position[a],
+        first.position[b],
+        first.velocity[a],
+        first.velocity[b],
+    );
+
+    let mut matrix = Vec::new();
+    let mut rhs = Vec::new();
+    for stone in &rest[..4] {
+        let (paj, pbj, vaj, vbj) = (
+            stone.position[a],
+            stone.position[b],
+            stone.velocity[a],
+            stone.velocity[b],
+        );
+        matrix.push(vec![r(vbj - vbi), r(vai - vaj), r(pbi - pbj), r(paj - pai)]);
+        let cross = |p_a: i64, p_b: i64, v_a: i64, v_b: i64| {
+            p_a as i128 * v_b as i128 - p_b as i128 * v_a as i128
+        };
+        rhs.push(Rational::integer(
+            cross(paj, pbj, vaj, vbj) - cross(pai, pbi, vai, vbi),
+        ));
+    }
+
+    let solution = solve_linear(&matrix, &rhs)?;
+    let mut values = [0; 4];
+    for (value, x) in values.iter_mut().zip(solution) {
+        *value = i64::try_from(x.to_integer()?).ok()?;
+    }
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    fn example() -> Vec<Hailstone> {
+        EXAMPLE
+            .lines()
+            .map(|line| Hailstone::parse(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_day24_part1() {
+        let hail = example();
+        let mut crossings = 0;
+        for (i, a) in hail.iter().enumerate() {
+            for b in &hail[i + 1..] {
+                if intersect_2d(a, b).is_some_and(|c| c.is_future_within(7, 27)) {
+                    crossings += 1;
+                }
+            }
+        }
+        assert_eq!(crossings, 2);
+
+        let crossing = intersect_2d(&hail[0], &hail[1]).unwrap();
+        assert_eq!(crossing.x, Rational::new(43, 3));
+        assert_eq!(crossing.y, Rational::new(46, 3));
+        // parallel paths never cross
+        assert_eq!(intersect_2d(&hail[1], &hail[2]), None);
+    }
+
+    #[test]
+    fn test_day24_part2() {
+        let rock = throw(&example()).unwrap();
+        assert_eq!(rock.position, [24, 13, 10]);
+        assert_eq!(rock.velocity, [-3, 1, 2]);
+        assert_eq!(throw(&example()[..4]), None);
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // puzzle-sized numbers where f64 loses the last digits
+        let rock = Hailstone {
+            position: [
+                287_430_900_705_823,
+                451_620_998_712_421,
+                260_730_677_041_648,
+            ],
+            velocity: [-61, -275, 38],
+        };
+        let hail: Vec<Hailstone> = [
+            ([-3, 17, 5], 515_423_201_312),
+            ([12, -9, -14], 331_107_519_088),
+            ([-35, 22, 40], 88_003_421_551),
+            ([7, -101, 3], 712_119_004_337),
+            ([64, 44, -27], 240_605_883_102),
+            ([-18, -60, 91], 402_338_776_019),
+        ]
+        .iter()
+        .map(|&(velocity, t): &([i64; 3], i64)| {
+            let position =
+                [0, 1, 2].map(|i| rock.position[i] + t * (rock.velocity[i] - velocity[i]));
+            Hailstone { position, velocity }
+        })
+        .collect();
+
+        assert!(hail.iter().all(|stone| rock.collides(stone)));
+        assert_eq!(throw(&hail), Some(rock));
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numer(), half.denom()), (-1, 2));
+        assert_eq!(half.to_string(), "-1/2");
+        let sum = half.checked_add(Rational::new(3, 2)).unwrap();
+        assert_eq!(sum.to_integer(), Some(1));
+        assert_eq!(half.checked_div(Rational::ZERO), None);
+        assert!(half < Rational::ZERO);
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert!(Rational::new(i128::MAX, 3) > Rational::new(i128::MAX - 1, 3));
+        assert!(Rational::new(-7, 2) < Rational::new(-10, 3));
+        assert_eq!(
+            Rational::integer(i128::MAX).checked_add(Rational::integer(1)),
+            None
+        );
+
+        let matrix = vec![
+            vec![Rational::integer(0), Rational::integer(2)],
+            vec![Rational::integer(3), Rational::integer(1)],
+        ];
+        let rhs = [Rational::integer(4), Rational::integer(5)];
+        assert_eq!(
+            solve_linear(&matrix, &rhs),
+            Some(vec![Rational::integer(1), Rational::integer(2)])
+        );
+        let singular = vec![vec![Rational::integer(1); 2]; 2];
+        assert_eq!(solve_linear(&singular, &rhs), None);
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// A directory of `dayNN.txt` inputs, and a `YYYY` directory of them per
+/// earlier year, that takes precedence over the workspace's own.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// The season the workspace started with: its days sit in `Day-N` at the
+/// root, and everything that takes only a day means one of them.
+pub const YEAR: u32 = 2023;
+
+// puzzles unlock at midnight US Eastern (UTC-5), starting 2023-12-01T05:00:00Z
+const FIRST_UNLOCK: i64 = 1_701_406_800;
+
+/// Where a day's input was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// `$AOC_INPUT_DIR/dayNN.txt`, or `$AOC_INPUT_DIR/YYYY/dayNN.txt`
+    Env,
+    /// `inputs/YYYY/dayNN.txt` at the workspace root
+    Inputs,
+    /// `input.txt` in the day's directory, next to its crate
+    Legacy,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Env => write!(f, "{}", INPUT_DIR_ENV),
+            Source::Inputs => write!(f, "the inputs directory"),
+            Source::Legacy => write!(f, "the day's directory"),
+        }
+    }
+}
+
+/// A day's input file and which of the places it was looked for held it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub path: PathBuf,
+    pub source: Source,
+}
+
+impl fmt::Display for Resolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (from {})", self.path.display(), self.source)
+    }
+}
+
+/// No input for a day in any of the places it was looked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotFound {
+    pub year: u32,
+    pub day: u32,
+    pub tried: Vec<PathBuf>,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tried: Vec<String> = self
+            .tried
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        write!(f, "no input for ")?;
+        if self.year != YEAR {
+            write!(f, "{} ", self.year)?;
+        }
+        write!(f, "day {}; tried {}", self.day, tried.join(", "))
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// The workspace root, found from this crate's manifest so it doesn't depend
+/// on the directory a binary is run from.
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc-common lives inside the workspace")
+        .to_path_buf()
+}
+
+/// Find `day`'s input: in `$AOC_INPUT_DIR`, then `inputs/2023/dayNN.txt` at
+/// the workspace root, then the day's own `Day-N/input.txt`.
+pub fn resolve(day: u32) -> Result<Resolved, NotFound> {
+    resolve_year(YEAR, day)
+}
+
+/// [`resolve`] for a day of any year, whose own input is in
+/// [`day_dir`]`(year, day)`.
+pub fn resolve_year(year: u32, day: u32) -> Result<Resolved, NotFound> {
+    resolve_in(&workspace_root(), input_dir().as_deref(), year, day)
+}
+
+/// `inputs/YYYY/dayNN.txt`, relative to the workspace root: inputs kept out
+/// of version control go there.
+pub fn inputs_path(year: u32, day: u32) -> PathBuf {
+    Path::new("inputs")
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// The directory of a day's crate, example inputs and answers, relative to
+/// the workspace root: `Day-N` for [`YEAR`] and `YYYY/Day-N` for the others.
+pub fn day_dir(year: u32, day: u32) -> PathBuf {
+    let dir = PathBuf::from(format!("Day-{}", day));
+    if year == YEAR {
+        dir
+    } else {
+        Path::new(&year.to_string()).join(dir)
+    }
+}
+
+/// `$AOC_INPUT_DIR`, if it's set.
+pub fn input_dir() -> Option<PathBuf> {
+    std::env::var_os(INPUT_DIR_ENV).map(PathBuf::from)
+}
+
+/// [`resolve_year`], under `root` and with `dir` standing in for `$AOC_INPUT_DIR`.
+pub fn resolve_in(
+    root: &Path,
+    dir: Option<&Path>,
+    year: u32,
+    day: u32,
+) -> Result<Resolved, NotFound> {
+    let name = format!("day{:02}.txt", day);
+    // the other years' inputs sit apart, so their days don't shadow this one's
+    let dir = dir.map(|dir| match year {
+        YEAR => dir.to_path_buf(),
+        _ => dir.join(year.to_string()),
+    });
+    let candidates = [
+        dir.map(|dir| (dir.join(name), Source::Env)),
+        Some((root.join(inputs_path(year, day)), Source::Inputs)),
+        Some((
+            root.join(day_dir(year, day)).join("input.txt"),
+            Source::Legacy,
+        )),
+    ];
+
+    let mut tried = Vec::new();
+    for (path, source) in candidates.into_iter().flatten() {
+        if path.is_file() {
+            return Ok(Resolved { path, source });
+        }
+        tried.push(path);
+    }
+    Err(NotFound { year, day, tried })
+}
+
+/// When `day` of [`YEAR`] unlocks, in seconds since the Unix epoch.
+pub fn unlock(day: u32) -> i64 {
+    unlock_in(YEAR, day)
+}
+
+/// [`unlock`] for a day of any year; every season starts on 1 December.
+pub fn unlock_in(year: u32, day: u32) -> i64 {
+    // from one 1 December to the next spans the next year's February
+    let season = |year: u32| {
+        let next = year + 1;
+        let leap = next.is_multiple_of(4) && !next.is_multiple_of(100) || next.is_multiple_of(400);
+        365 + i64::from(leap)
+    };
+    let days = if year >= YEAR {
+        (YEAR..year).map(season).sum::<i64>()
+    } else {
+        -(year..YEAR).map(season).sum::<i64>()
+    };
+    FIRST_UNLOCK + (days + i64::from(day) - 1) * 24 * 60 * 60
+}
+
+/// The lines of the file at `path`, without their line endings; what every
+/// day's `read_input` reads its input with.
+pub fn read_lines(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(path)?).lines().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock() {
+        assert_eq!(unlock(1), 1_701_406_800);
+        // 2023-12-25T05:00:00Z
+        assert_eq!(unlock(25), 1_703_480_400);
+        assert_eq!(unlock_in(YEAR, 7), unlock(7));
+        // 2022-12-01T05:00:00Z, and 2024's after a leap February
+        assert_eq!(unlock_in(2022, 1), 1_669_870_800);
+        assert_eq!(unlock_in(2024, 1), 1_733_029_200);
+        assert_eq!(unlock_in(2015, 1), 1_448_946_000);
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let lines = read_lines("../Day-2/test-1.txt").unwrap();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("Game 1:"));
+        assert!(read_lines("../Day-2/missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_resolve_in() {
+        let root = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let dir = root.join("elsewhere");
+        let inputs = root.join("inputs").join("2023");
+        std::fs::create_dir_all(root.join("Day-3")).unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::create_dir_all(&inputs).unwrap();
+
+        let err = resolve_in(&root, Some(&dir), YEAR, 3).unwrap_err();
+        assert_eq!(err.tried.len(), 3);
+        assert!(err.to_string().starts_with("no input for day 3; tried "));
+        assert_eq!(resolve_in(&root, None, YEAR, 3).unwrap_err().tried.len(), 2);
+
+        std::fs::write(root.join("Day-3").join("input.txt"), "").unwrap();
+        let resolved = resolve_in(&root, Some(&dir), YEAR, 3).unwrap();
+        assert_eq!(resolved.source, Source::Legacy);
+
+        std::fs::write(inputs.join("day03.txt"), "").unwrap();
+        let resolved = resolve_in(&root, Some(&dir), YEAR, 3).unwrap();
+        assert_eq!(resolved.path, inputs.join("day03.txt"));
+        assert_eq!(resolved.source, Source::Inputs);
+
+        std::fs::write(dir.join("day03.txt"), "").unwrap();
+        let resolved = resolve_in(&root, Some(&dir), YEAR, 3).unwrap();
+        assert_eq!(resolved.source, Source::Env);
+        assert!(resolved
+            .to_string()
+            .ends_with("day03.txt (from AOC_INPUT_DIR)"));
+        assert_eq!(
+            resolve_in(&root, None, YEAR, 3).unwrap().source,
+            Source::Inputs
+        );
+
+        // another year's day 3 is looked for in places of its own
+        let err = resolve_in(&root, Some(&dir), 2022, 3).unwrap_err();
+        assert_eq!(
+            err.tried,
+            [
+                dir.join("2022").join("day03.txt"),
+                root.join("inputs").join("2022").join("day03.txt"),
+                root.join("2022").join("Day-3").join("input.txt"),
+            ]
+        );
+        assert!(err.to_string().starts_with("no input for 2022 day 3; "));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_root() {
+        assert!(workspace_root().join("Cargo.toml").is_file());
+        assert!(workspace_root().join("aoc-common").is_dir());
+        assert_eq!(inputs_path(YEAR, 7), Path::new("inputs/2023/day07.txt"));
+        assert_eq!(day_dir(YEAR, 7), Path::new("Day-7"));
+        assert_eq!(day_dir(2022, 7), Path::new("2022/Day-7"));
+    }
+}
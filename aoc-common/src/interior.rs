@@ -0,0 +1,217 @@
+use crate::grid::{Grid, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+/// Classify every cell of `grid` against one or more closed loops.
+///
+/// Each loop lists its cells in walking order, every cell 4-adjacent to the
+/// next and the last one back to the first. Cells are scanned row by row with
+/// a ray cast to the left: only loop cells connected to the cell above them
+/// count as a crossing, so runs along a row and loops running side by side
+/// (as in Day 10's `||` squeezes) are handled without any special cases.
+/// Several loops combine with the even-odd rule, so a loop nested inside
+/// another cuts a hole in it. Loops must not share cells.
+pub fn classify<T>(grid: &Grid<T>, loops: &[&[Point]]) -> Grid<Region> {
+    // None: not on a loop, Some(true): on a loop and connected upwards
+    let mut cells: Grid<Option<bool>> = Grid::new(grid.width(), grid.height(), None);
+
+    for path in loops {
+        assert!(path.len() >= 4, "a closed loop needs at least 4 cells");
+        for (i, &point) in path.iter().enumerate() {
+            let previous = path[(i + path.len() - 1) % path.len()];
+            let next = path[(i + 1) % path.len()];
+            assert!(
+                point.is_adjacent4(&next),
+                "{:?} and {:?} are not adjacent",
+                point,
+                next
+            );
+
+            let up = |other: Point| other.col == point.col && other.row + 1 == point.row;
+            cells[point] = Some(up(previous) || up(next));
+        }
+    }
+
+    let mut regions = Grid::new(grid.width(), grid.height(), Region::Outside);
+    for row in 0..grid.height() {
+        let mut inside = false;
+        for col in 0..grid.width() {
+            let point = Point { row, col };
+            regions[point] = match cells[point] {
+                Some(north) => {
+                    inside ^= north;
+                    Region::Loop
+                }
+                None if inside => Region::Inside,
+                None => Region::Outside,
+            };
+        }
+    }
+
+    regions
+}
+
+/// Walk a loop drawn with Day 10 pipe characters (`|-LJ7F`) from `start`,
+/// returning its cells in order. `start` must itself be a pipe, not `S`.
+pub fn trace_pipes(grid: &Grid<char>, start: Point) -> Option<Vec<Point>> {
+    let connections = |point: Point| -> Vec<Point> {
+        let Point { row, col } = point;
+        let up = row.checked_sub(1).map(|row| Point { row, col });
+        let down = Some(Point { row: row + 1, col });
+        let left = col.checked_sub(1).map(|col| Point { row, col });
+        let right = Some(Point { row, col: col + 1 });
+        let ends = match grid.get(point) {
+            Some('|') => [up, down],
+            Some('-') => [left, right],
+            Some('L') => [up, right],
+            Some('J') => [up, left],
+            Some('7') => [down, left],
+            Some('F') => [down, right],
+            _ => [None, None],
+        };
+        ends.into_iter().flatten().collect()
+    };
+
+    let mut path = vec![start];
+    let mut previous = start;
+    let mut current = *connections(start).first()?;
+    while current != start {
+        let ends = connections(current);
+        if !ends.contains(&previous) {
+            return None;
+        }
+        path.push(current);
+        let next = ends.into_iter().find(|&end| end != previous)?;
+        previous = current;
+        current = next;
+    }
+
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // render regions back to text: loop '#', inside 'I', outside '.'
+    fn render(regions: &Grid<Region>) -> String {
+        regions
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|region| match region {
+                        Region::Loop => '#',
+                        Region::Inside => 'I',
+                        Region::Outside => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // the border of a rectangle, clockwise from its top-left corner
+    fn rectangle(top: usize, left: usize, bottom: usize, right: usize) -> Vec<Point> {
+        let mut path = Vec::new();
+        (left..right).for_each(|col| path.push(Point::new(top, col)));
+        (top..bottom).for_each(|row| path.push(Point::new(row, right)));
+        (left + 1..=right)
+            .rev()
+            .for_each(|col| path.push(Point::new(bottom, col)));
+        (top + 1..=bottom)
+            .rev()
+            .for_each(|row| path.push(Point::new(row, left)));
+        path
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let grid = Grid::new(6, 5, ());
+        let path = rectangle(0, 1, 3, 4);
+        let regions = classify(&grid, &[&path]);
+        assert_eq!(render(&regions), ".####.\n.#II#.\n.#II#.\n.####.\n......");
+    }
+
+    #[test]
+    fn test_nested_loops() {
+        let grid = Grid::new(9, 9, ());
+        let outer = rectangle(0, 0, 8, 8);
+        let inner = rectangle(2, 2, 6, 6);
+        let regions = classify(&grid, &[&outer, &inner]);
+        assert_eq!(
+            render(&regions),
+            [
+                "#########",
+                "#IIIIIII#",
+                "#I#####I#",
+                "#I#...#I#",
+                "#I#...#I#",
+                "#I#...#I#",
+                "#I#####I#",
+                "#IIIIIII#",
+                "#########",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_touching_edges() {
+        // two boxes side by side with no gap: their shared edges are
+        // adjacent but not connected, so both interiors stay separate
+        let grid = Grid::new(8, 4, ());
+        let left = rectangle(0, 0, 3, 3);
+        let right = rectangle(0, 4, 3, 7);
+        let regions = classify(&grid, &[&left, &right]);
+        assert_eq!(
+            render(&regions),
+            ["########", "#II##II#", "#II##II#", "########"].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_day10_squeeze() {
+        // the Day 10 example where the outside squeezes between pipes
+        let input = [
+            "..........",
+            ".F------7.",
+            ".|F----7|.",
+            ".||....||.",
+            ".||....||.",
+            ".|L-7F-J|.",
+            ".|..||..|.",
+            ".L--JL--J.",
+            "..........",
+        ]
+        .join("\n");
+        let grid = Grid::parse(&input);
+        let path = trace_pipes(&grid, Point::new(1, 1)).unwrap();
+        assert_eq!(path.len(), 44);
+
+        let regions = classify(&grid, &[&path]);
+        let inside: Vec<Point> = regions
+            .points()
+            .filter(|&p| regions[p] == Region::Inside)
+            .collect();
+        assert_eq!(
+            inside,
+            vec![
+                Point::new(6, 2),
+                Point::new(6, 3),
+                Point::new(6, 6),
+                Point::new(6, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_pipes_broken() {
+        let grid = Grid::parse("F-7\n|.|\nL-.");
+        assert_eq!(trace_pipes(&grid, Point::new(0, 0)), None);
+    }
+}
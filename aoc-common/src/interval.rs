@@ -0,0 +1,399 @@
+use crate::trace::{Event, Tracer};
+
+/// A half-open range of integers, `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// Panics if `start > end`.
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(
+            start <= end,
+            "interval start {} is past its end {}",
+            start,
+            end
+        );
+        Interval { start, end }
+    }
+
+    pub fn len(&self) -> i64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Interval { start, end })
+    }
+
+    /// Split into the values below `at` and the values from `at` on; either
+    /// side is `None` when it would be empty.
+    pub fn split_at(&self, at: i64) -> (Option<Interval>, Option<Interval>) {
+        let at = at.clamp(self.start, self.end);
+        let below = Interval::new(self.start, at);
+        let above = Interval::new(at, self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+}
+
+/// A set of integers stored as sorted, disjoint, non-touching intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an interval, merging it with anything it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // everything ending before the new interval starts stays put
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let mut merged = interval;
+        let mut last = first;
+        while last < self.intervals.len() && self.intervals[last].start <= merged.end {
+            merged.start = merged.start.min(self.intervals[last].start);
+            merged.end = merged.end.max(self.intervals[last].end);
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    /// Number of integers in the set.
+    pub fn measure(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+/// One rule of an [`IntervalMap`]: values in `source` move by `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    pub source: Interval,
+    pub offset: i64,
+}
+
+/// A piecewise translation of the integers, like one section of the Day 5
+/// almanac: values inside a rule's source range are shifted, everything else
+/// maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalMap {
+    // sorted by source start, sources never overlap
+    rules: Vec<Mapping>,
+}
+
+impl IntervalMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an almanac line: `len` values starting at `source` map to the
+    /// values starting at `destination`.
+    pub fn add(&mut self, destination: i64, source: i64, len: i64) {
+        self.insert(Mapping {
+            source: Interval::new(source, source + len),
+            offset: destination - source,
+        });
+    }
+
+    /// Panics if the rule's source overlaps an existing rule.
+    pub fn insert(&mut self, mapping: Mapping) {
+        if mapping.source.is_empty() {
+            return;
+        }
+
+        let i = self
+            .rules
+            .partition_point(|rule| rule.source.start < mapping.source.start);
+        let overlaps = |rule: &Mapping| rule.source.intersect(&mapping.source).is_some();
+        assert!(
+            !(i > 0 && overlaps(&self.rules[i - 1]) || self.rules.get(i).is_some_and(overlaps)),
+            "source range {:?} overlaps an existing rule",
+            mapping.source
+        );
+        self.rules.insert(i, mapping);
+    }
+
+    pub fn rules(&self) -> &[Mapping] {
+        &self.rules
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        let i = self.rules.partition_point(|rule| rule.source.end <= value);
+        match self.rules.get(i) {
+            Some(rule) if rule.source.contains(value) => value + rule.offset,
+            _ => value,
+        }
+    }
+
+    /// Map every value of `set` at once by splitting its intervals on rule
+    /// boundaries, so the cost depends on the number of intervals rather than
+    /// how many values they hold.
+    pub fn transform(&self, set: &IntervalSet) -> IntervalSet {
+        self.transform_traced(set, &mut ())
+    }
+
+    /// [`transform`](Self::transform), telling `tracer` about every cut it
+    /// makes in one of `set`'s intervals.
+    pub fn transform_traced(&self, set: &IntervalSet, tracer: &mut impl Tracer) -> IntervalSet {
+        let mut result = IntervalSet::new();
+        let mut split = |interval: &Interval, at| {
+            tracer.event(Event::Split {
+                start: interval.start,
+                end: interval.end,
+                at,
+            })
+        };
+
+        for interval in set.intervals() {
+            let mut cursor = interval.start;
+            let first = self
+                .rules
+                .partition_point(|rule| rule.source.end <= interval.start);
+
+            for rule in &self.rules[first..] {
+                if rule.source.start >= interval.end {
+                    break;
+                }
+                // the gap before this rule maps to itself
+                if rule.source.start > cursor {
+                    split(interval, rule.source.start);
+                    result.insert(Interval::new(cursor, rule.source.start));
+                    cursor = rule.source.start;
+                }
+                let end = interval.end.min(rule.source.end);
+                result.insert(Interval::new(cursor, end).shift(rule.offset));
+                cursor = end;
+                if cursor < interval.end {
+                    split(interval, cursor);
+                }
+            }
+
+            if cursor < interval.end {
+                result.insert(Interval::new(cursor, interval.end));
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3));
+        assert!(!interval.contains(7));
+        assert_eq!(
+            interval.intersect(&Interval::new(5, 10)),
+            Some(Interval::new(5, 7))
+        );
+        assert_eq!(interval.intersect(&Interval::new(7, 10)), None);
+        assert_eq!(
+            interval.split_at(5),
+            (Some(Interval::new(3, 5)), Some(Interval::new(5, 7)))
+        );
+        assert_eq!(interval.split_at(3), (None, Some(interval)));
+        assert_eq!(interval.split_at(9), (Some(interval), None));
+        assert_eq!(interval.shift(-3), Interval::new(0, 4));
+    }
+
+    #[test]
+    fn test_set_insert_merges() {
+        let mut s = set(&[(0, 2), (5, 7), (10, 12)]);
+        assert_eq!(s.measure(), 6);
+
+        // touching intervals merge, disjoint ones don't
+        s.insert(Interval::new(2, 3));
+        assert_eq!(s, set(&[(0, 3), (5, 7), (10, 12)]));
+
+        // one insert can swallow several intervals
+        s.insert(Interval::new(4, 11));
+        assert_eq!(s, set(&[(0, 3), (4, 12)]));
+
+        s.insert(Interval::new(20, 20));
+        assert_eq!(s.intervals().len(), 2);
+        assert!(s.contains(4) && !s.contains(3) && !s.contains(12));
+        assert_eq!(s.min(), Some(0));
+    }
+
+    #[test]
+    fn test_map_add_rejects_overlap() {
+        let mut map = IntervalMap::new();
+        map.add(50, 98, 2);
+        map.add(52, 50, 48);
+        assert_eq!(map.rules()[0].source, Interval::new(50, 98));
+
+        let result = std::panic::catch_unwind(move || map.add(0, 97, 2));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_transform_almanac_example() {
+        // seed-to-soil map from the Day 5 example
+        let mut map = IntervalMap::new();
+        map.add(50, 98, 2);
+        map.add(52, 50, 48);
+
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(13), 13);
+
+        let seeds = set(&[(79, 79 + 14), (55, 55 + 13)]);
+        assert_eq!(map.transform(&seeds), set(&[(57, 70), (81, 95)]));
+    }
+
+    #[test]
+    fn test_transform_split_edges() {
+        // a single rule 10..20 shifted by +100
+        let mut map = IntervalMap::new();
+        map.add(110, 10, 10);
+
+        type Case = ((i64, i64), &'static [(i64, i64)]);
+        let cases: &[Case] = &[
+            // entirely before / after / touching either edge
+            ((0, 5), &[(0, 5)]),
+            ((25, 30), &[(25, 30)]),
+            ((5, 10), &[(5, 10)]),
+            ((20, 25), &[(20, 25)]),
+            // overlapping the left edge, the right edge, both
+            ((5, 15), &[(5, 10), (110, 115)]),
+            ((15, 25), &[(20, 25), (115, 120)]),
+            ((5, 25), &[(5, 10), (20, 25), (110, 120)]),
+            // inside, exactly equal, one value at each boundary
+            ((12, 18), &[(112, 118)]),
+            ((10, 20), &[(110, 120)]),
+            ((10, 11), &[(110, 111)]),
+            ((19, 20), &[(119, 120)]),
+            ((9, 11), &[(9, 10), (110, 111)]),
+            ((19, 21), &[(20, 21), (119, 120)]),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                map.transform(&set(&[*input])),
+                set(expected),
+                "transforming {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform_traced() {
+        let mut map = IntervalMap::new();
+        map.add(110, 10, 10);
+        let cuts = |input: (i64, i64)| {
+            let mut events = Vec::new();
+            map.transform_traced(&set(&[input]), &mut events);
+            events
+                .into_iter()
+                .map(|event| match event {
+                    Event::Split { at, .. } => at,
+                    event => panic!("unexpected {:?}", event),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cuts((5, 25)), [10, 20]);
+        assert_eq!(cuts((5, 15)), [10]);
+        assert_eq!(cuts((15, 25)), [20]);
+        assert!(cuts((10, 20)).is_empty());
+        assert!(cuts((0, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_transform_matches_pointwise() {
+        // adjacent rules, a gap, and rules landing on unmapped values
+        let mut map = IntervalMap::new();
+        map.add(20, 2, 3);
+        map.add(0, 5, 2);
+        map.add(3, 9, 4);
+
+        // every interval within 0..16 against mapping each value on its own
+        for start in 0..16 {
+            for end in start..16 {
+                let input = set(&[(start, end)]);
+                let expected: IntervalSet = (start..end)
+                    .map(|v| Interval::new(map.map(v), map.map(v) + 1))
+                    .collect();
+                let result = map.transform(&input);
+                assert_eq!(result, expected, "transforming {}..{}", start, end);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_chain() {
+        let mut first = IntervalMap::new();
+        first.add(100, 0, 10);
+        let mut second = IntervalMap::new();
+        second.add(0, 105, 10);
+
+        let seeds = set(&[(0, 10), (50, 60)]);
+        let result = second.transform(&first.transform(&seeds));
+        assert_eq!(result, set(&[(0, 5), (50, 60), (100, 105)]));
+        assert_eq!(result.min(), Some(0));
+    }
+}
@@ -0,0 +1,36 @@
+pub mod answer;
+pub mod arrangements;
+pub mod bricks;
+pub mod cancel;
+pub mod conformance;
+pub mod corridors;
+pub mod cycle_cache;
+pub mod cycles;
+pub mod day;
+pub mod decision;
+pub mod explain;
+pub mod graph;
+pub mod grid;
+pub mod hail;
+pub mod input;
+pub mod interior;
+pub mod interval;
+pub mod parallel;
+pub mod parse;
+pub mod pathfinding;
+pub mod polygon;
+#[cfg(feature = "pretty")]
+pub mod pretty;
+pub mod progress;
+pub mod pulse;
+#[cfg(feature = "render")]
+pub mod raster;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod sequence;
+pub mod shuffle;
+pub mod table;
+#[cfg(feature = "timing")]
+pub mod timing;
+pub mod trace;
+pub mod visualize;
@@ -0,0 +1,82 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// `f` applied to every item on as many threads as there are cores, the
+/// results in the items' order. Threads claim one item at a time, so a few
+/// slow items don't leave the other threads idle.
+pub fn par_map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            break done;
+                        };
+                        done.push((i, f(item)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    results.sort_unstable_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// `range` cut into `count` back-to-back shards whose lengths differ by at
+/// most one; fewer shards when the range is shorter than `count`.
+pub fn shards(range: Range<u64>, count: u64) -> Vec<Range<u64>> {
+    let len = range.end.saturating_sub(range.start);
+    let count = count.clamp(1, len.max(1));
+    let (size, longer) = (len / count, len % count);
+    let mut start = range.start;
+    let mut shards = Vec::new();
+    for i in 0..count {
+        let end = start + size + u64::from(i < longer);
+        if end > start {
+            shards.push(start..end);
+        }
+        start = end;
+    }
+    shards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_map() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = par_map(&items, |n| n * n);
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert!(par_map(&[] as &[u64], |n| *n).is_empty());
+    }
+
+    #[test]
+    fn test_shards() {
+        assert_eq!(shards(0..10, 3), [0..4, 4..7, 7..10]);
+        assert_eq!(shards(5..8, 10), [5..6, 6..7, 7..8]);
+        assert_eq!(shards(0..10, 0), vec![0..10]);
+        assert!(shards(3..3, 4).is_empty());
+    }
+}
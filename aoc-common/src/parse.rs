@@ -0,0 +1,194 @@
+use std::fmt::{self, Display};
+
+// Number parsing that only ever accepts ASCII digits. Nothing here depends on
+// the locale, so `1.234`, `1,234`, `١٢` or `１２` are rejected everywhere rather
+// than read differently on different machines.
+
+/// An unsigned decimal integer made only of `0`-`9`; `None` if it's empty,
+/// has anything else in it, or doesn't fit in a u64.
+pub fn uint(s: &str) -> Option<u64> {
+    if s.is_empty() {
+        return None;
+    }
+    s.bytes().try_fold(0u64, |value, byte| {
+        let digit = byte.is_ascii_digit().then(|| (byte - b'0') as u64)?;
+        value.checked_mul(10)?.checked_add(digit)
+    })
+}
+
+/// [`uint`] with an optional leading `-` or `+`.
+pub fn int(s: &str) -> Option<i64> {
+    match s.as_bytes().first()? {
+        b'-' => {
+            let magnitude = uint(&s[1..])?;
+            // i64::MIN has no positive counterpart
+            0i64.checked_sub_unsigned(magnitude)
+        }
+        b'+' => i64::try_from(uint(&s[1..])?).ok(),
+        _ => i64::try_from(uint(s)?).ok(),
+    }
+}
+
+/// Exactly `N` integers separated by `separator`, each padded with any
+/// whitespace, e.g. `19, 13, -2`.
+pub fn ints<const N: usize>(s: &str, separator: char) -> Option<[i64; N]> {
+    let mut parts = s.split(separator).map(|part| int(part.trim()));
+    let mut values = [0; N];
+    for value in &mut values {
+        *value = parts.next()??;
+    }
+    parts.next().is_none().then_some(values)
+}
+
+/// What a day's parser does with a malformed line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    /// Stop at the first malformed line, and reject input a day could only
+    /// guess at, such as a color named twice in one Day 2 round
+    #[default]
+    Strict,
+    /// Skip malformed lines and report them once parsing is done, and warn
+    /// about input a day has to guess at rather than rejecting it
+    Lenient,
+}
+
+/// A line a parser couldn't read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Malformed {
+    /// 1-based, as in an editor.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for Malformed {}
+
+/// What [`parse_lines`] read, and what it passed over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parsed<T> {
+    pub items: Vec<T>,
+    /// Malformed lines, only ever non-empty in lenient mode.
+    pub skipped: Vec<Malformed>,
+    /// Blank lines, which are never an error.
+    pub blank: usize,
+}
+
+impl<T> Parsed<T> {
+    /// One line about the skipped lines, if there were any.
+    pub fn summary(&self) -> Option<String> {
+        let (first, rest) = self.skipped.split_first()?;
+        let mut summary = format!("Skipped {} malformed lines: {}", self.skipped.len(), first);
+        for malformed in rest {
+            summary.push_str("; ");
+            summary.push_str(&malformed.to_string());
+        }
+        Some(summary)
+    }
+}
+
+/// Parse each non-blank line with `parse`. A failure stops everything in
+/// strict mode and is set aside in lenient mode.
+pub fn parse_lines<S, T, E>(
+    lines: &[S],
+    mode: ParseMode,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Parsed<T>, Malformed>
+where
+    S: AsRef<str>,
+    E: Display,
+{
+    let mut parsed = Parsed {
+        items: Vec::new(),
+        skipped: Vec::new(),
+        blank: 0,
+    };
+    for (i, line) in lines.iter().map(AsRef::as_ref).enumerate() {
+        if line.trim().is_empty() {
+            parsed.blank += 1;
+            continue;
+        }
+        match parse(line) {
+            Ok(item) => parsed.items.push(item),
+            Err(err) => {
+                let malformed = Malformed {
+                    line: i + 1,
+                    message: err.to_string(),
+                };
+                match mode {
+                    ParseMode::Strict => return Err(malformed),
+                    ParseMode::Lenient => parsed.skipped.push(malformed),
+                }
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uint() {
+        assert_eq!(uint("0"), Some(0));
+        assert_eq!(uint("467"), Some(467));
+        assert_eq!(uint("18446744073709551615"), Some(u64::MAX));
+        assert_eq!(uint("18446744073709551616"), None);
+        assert_eq!(uint(""), None);
+        assert_eq!(uint("+1"), None);
+        assert_eq!(uint(" 1"), None);
+    }
+
+    #[test]
+    fn test_not_localized() {
+        // grouping and decimal separators, and digits from other scripts
+        for s in ["1,234", "1.234", "1 234", "1'234", "١٢", "１２", "۱۲", "१२"] {
+            assert_eq!(uint(s), None, "{:?}", s);
+            assert_eq!(int(s), None, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int("-2"), Some(-2));
+        assert_eq!(int("+2"), Some(2));
+        assert_eq!(int("-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(int("9223372036854775808"), None);
+        assert_eq!(int("-"), None);
+        assert_eq!(int("--1"), None);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints::<3>("19, 13, -30", ','), Some([19, 13, -30]));
+        assert_eq!(ints::<3>("1,0,1", ','), Some([1, 0, 1]));
+        assert_eq!(ints::<3>("1,0", ','), None);
+        assert_eq!(ints::<3>("1,0,1,2", ','), None);
+        assert_eq!(ints::<2>("1,5", ','), Some([1, 5]));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = ["1", "", "x", "3", "4.5"];
+        let number = |line: &str| uint(line).ok_or(format!("not a number: {:?}", line));
+
+        let err = parse_lines(&lines, ParseMode::Strict, number).unwrap_err();
+        assert_eq!(err.to_string(), "line 3: not a number: \"x\"");
+
+        let parsed = parse_lines(&lines, ParseMode::Lenient, number).unwrap();
+        assert_eq!(parsed.items, vec![1, 3]);
+        assert_eq!(parsed.blank, 1);
+        assert_eq!(
+            parsed.summary().unwrap(),
+            "Skipped 2 malformed lines: line 3: not a number: \"x\"; line 5: not a number: \"4.5\""
+        );
+
+        let parsed = parse_lines(&["7"], ParseMode::Lenient, number).unwrap();
+        assert_eq!(parsed.summary(), None);
+    }
+}
@@ -0,0 +1,416 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+
+use crate::grid::{Direction, Grid, Point};
+use crate::trace::{Event, Tracer};
+use crate::visualize::{Glyph, Scene, Style, Visualize};
+
+// heatmap shades, least to most visited
+const SHADES: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// Decides which moves a searcher may make given how far it has already
+/// gone in a straight line.
+pub trait Constraints {
+    /// Whether it may take another step in the same direction after `run`.
+    fn can_continue(&self, run: usize) -> bool;
+    /// Whether it may turn left or right after `run` straight steps.
+    fn can_turn(&self, run: usize) -> bool;
+    /// Whether it may finish on the goal after `run` straight steps.
+    fn can_stop(&self, run: usize) -> bool {
+        self.can_turn(run)
+    }
+}
+
+/// The Day 17 rule: at least `min` and at most `max` steps before turning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StraightRun {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl StraightRun {
+    pub fn new(min: usize, max: usize) -> Self {
+        assert!(min <= max, "a run of {} to {} steps is empty", min, max);
+        StraightRun { min, max }
+    }
+}
+
+impl Constraints for StraightRun {
+    fn can_continue(&self, run: usize) -> bool {
+        run < self.max
+    }
+
+    fn can_turn(&self, run: usize) -> bool {
+        run >= self.min
+    }
+}
+
+/// Where the searcher is, which way it is facing and how many steps it has
+/// taken in that direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State {
+    pub point: Point,
+    pub direction: Direction,
+    pub run: usize,
+}
+
+// e.g. "3,4 Right 2": row, column, direction and run
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{} {:?} {}",
+            self.point.row, self.point.col, self.direction, self.run
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub cost: u64,
+    /// Every point visited, `start` first.
+    pub points: Vec<Point>,
+}
+
+/// Watches a search as it runs.
+pub trait SearchObserver {
+    /// `state` has just been taken off the queue to be expanded at `cost`.
+    fn expanded(&mut self, state: &State, cost: u64);
+
+    /// `state` has just been queued, to be expanded at `cost`.
+    fn queued(&mut self, _state: &State, _cost: u64) {}
+}
+
+impl SearchObserver for () {
+    fn expanded(&mut self, _: &State, _: u64) {}
+}
+
+/// A search's queue, reported to a [`Tracer`] as push and pop events.
+pub struct TraceQueue<'a, T: Tracer + ?Sized>(pub &'a mut T);
+
+impl<T: Tracer + ?Sized> SearchObserver for TraceQueue<'_, T> {
+    fn expanded(&mut self, state: &State, cost: u64) {
+        self.0.event(Event::Pop {
+            queue: "search",
+            item: state.to_string(),
+            priority: cost,
+        });
+    }
+
+    fn queued(&mut self, state: &State, cost: u64) {
+        self.0.event(Event::Push {
+            queue: "search",
+            item: state.to_string(),
+            priority: cost,
+        });
+    }
+}
+
+/// How many states the search expanded in each cell, for seeing where it
+/// spent its effort.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    pub visits: Grid<u32>,
+    /// Expansions in total.
+    pub expansions: u64,
+}
+
+impl Heatmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        Heatmap {
+            visits: Grid::new(width, height, 0),
+            expansions: 0,
+        }
+    }
+
+    pub fn busiest(&self) -> u32 {
+        self.visits.rows().flatten().copied().max().unwrap_or(0)
+    }
+}
+
+impl SearchObserver for Heatmap {
+    fn expanded(&mut self, state: &State, _: u64) {
+        if let Some(count) = self.visits.get_mut(state.point) {
+            *count += 1;
+        }
+        self.expansions += 1;
+    }
+}
+
+// Shaded from ' ' for untouched cells to '@' for the busiest, with the
+// busiest tenth or so flagged
+impl Visualize for Heatmap {
+    fn visualize(&self) -> Scene {
+        let busiest = self.busiest().max(1) as u64;
+        Scene::Grid(self.visits.map(|&count| {
+            if count == 0 {
+                return Glyph::new(SHADES[0], Style::Dim);
+            }
+            let shade = (count as u64 * (SHADES.len() as u64 - 1)).div_ceil(busiest);
+            let style = match shade {
+                0..=4 => Style::Plain,
+                5..=8 => Style::Accent,
+                _ => Style::Alert,
+            };
+            Glyph::new(SHADES[shade as usize], style)
+        }))
+    }
+}
+
+/// The cheapest path from `start` to `goal` where entering a cell costs its
+/// value in `costs`. The searcher may set off in any direction, never
+/// reverses, and otherwise moves as `constraints` allows.
+pub fn shortest_path<T, C>(
+    costs: &Grid<T>,
+    start: Point,
+    goal: Point,
+    constraints: &C,
+) -> Option<Path>
+where
+    T: Copy + Into<u64>,
+    C: Constraints,
+{
+    shortest_path_observed(costs, start, goal, constraints, &mut ())
+}
+
+/// [`shortest_path`], telling `observer` about every state it expands.
+pub fn shortest_path_observed<T, C>(
+    costs: &Grid<T>,
+    start: Point,
+    goal: Point,
+    constraints: &C,
+    observer: &mut impl SearchObserver,
+) -> Option<Path>
+where
+    T: Copy + Into<u64>,
+    C: Constraints,
+{
+    let mut best: HashMap<State, u64> = HashMap::new();
+    let mut previous: HashMap<State, State> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for direction in Direction::ALL {
+        let state = State {
+            point: start,
+            direction,
+            run: 0,
+        };
+        best.insert(state, 0);
+        observer.queued(&state, 0);
+        queue.push(Reverse((0, state)));
+    }
+
+    while let Some(Reverse((cost, state))) = queue.pop() {
+        if best.get(&state).is_some_and(|&known| known < cost) {
+            continue;
+        }
+        observer.expanded(&state, cost);
+        if state.point == goal && constraints.can_stop(state.run) {
+            return Some(Path {
+                cost,
+                points: walk_back(&previous, state),
+            });
+        }
+
+        let mut moves = Vec::with_capacity(3);
+        if constraints.can_continue(state.run) {
+            moves.push((state.direction, state.run + 1));
+        }
+        // the starting states have not moved yet, so they may not turn
+        if state.run > 0 && constraints.can_turn(state.run) {
+            moves.extend(state.direction.turns().map(|direction| (direction, 1)));
+        }
+
+        for (direction, run) in moves {
+            let Some(point) = state.point.step(direction) else {
+                continue;
+            };
+            let Some(&step_cost) = costs.get(point) else {
+                continue;
+            };
+
+            let next = State {
+                point,
+                direction,
+                run,
+            };
+            let cost = cost + step_cost.into();
+            if best.get(&next).is_none_or(|&known| cost < known) {
+                best.insert(next, cost);
+                previous.insert(next, state);
+                observer.queued(&next, cost);
+                queue.push(Reverse((cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+fn walk_back(previous: &HashMap<State, State>, mut state: State) -> Vec<Point> {
+    let mut points = vec![state.point];
+    while let Some(&before) = previous.get(&state) {
+        points.push(before.point);
+        state = before;
+    }
+    points.reverse();
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Grid<u8> {
+        Grid::parse(input).map(|c| c.to_digit(10).unwrap() as u8)
+    }
+
+    fn corner(grid: &Grid<u8>) -> Point {
+        Point::new(grid.height() - 1, grid.width() - 1)
+    }
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_day17_example() {
+        let grid = parse(EXAMPLE);
+        let goal = corner(&grid);
+
+        let crucible =
+            shortest_path(&grid, Point::new(0, 0), goal, &StraightRun::new(1, 3)).unwrap();
+        assert_eq!(crucible.cost, 102);
+        assert_eq!(crucible.points.first(), Some(&Point::new(0, 0)));
+        assert_eq!(crucible.points.last(), Some(&goal));
+        let total: u64 = crucible.points[1..].iter().map(|&p| grid[p] as u64).sum();
+        assert_eq!(total, 102);
+
+        let ultra = shortest_path(&grid, Point::new(0, 0), goal, &StraightRun::new(4, 10)).unwrap();
+        assert_eq!(ultra.cost, 94);
+    }
+
+    #[test]
+    fn test_minimum_run_at_goal() {
+        // cutting down early is cheaper but ends on a run of only 1
+        let grid = parse("111111111111\n999999999991\n999999999991\n999999999991\n999999999991");
+        let goal = corner(&grid);
+        let ultra = shortest_path(&grid, Point::new(0, 0), goal, &StraightRun::new(4, 10)).unwrap();
+        assert_eq!(ultra.cost, 71);
+    }
+
+    #[test]
+    fn test_custom_constraints() {
+        // never more than one step in a row: a zig-zag to the far corner
+        struct Alternate;
+        impl Constraints for Alternate {
+            fn can_continue(&self, run: usize) -> bool {
+                run == 0
+            }
+            fn can_turn(&self, _: usize) -> bool {
+                true
+            }
+        }
+
+        let grid = Grid::new(4, 4, 1u8);
+        let path = shortest_path(&grid, Point::new(0, 0), Point::new(3, 3), &Alternate).unwrap();
+        assert_eq!(path.cost, 6);
+        assert!(path
+            .points
+            .windows(3)
+            .all(|w| w[0].row != w[2].row && w[0].col != w[2].col));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let grid = parse(EXAMPLE);
+        let goal = corner(&grid);
+        let mut heatmap = Heatmap::new(grid.width(), grid.height());
+        let path = shortest_path_observed(
+            &grid,
+            Point::new(0, 0),
+            goal,
+            &StraightRun::new(1, 3),
+            &mut heatmap,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 102);
+
+        let total: u64 = heatmap.visits.rows().flatten().map(|&n| n as u64).sum();
+        assert_eq!(total, heatmap.expansions);
+        // all four starting directions get expanded
+        assert!(heatmap.visits[Point::new(0, 0)] >= 4);
+
+        let Scene::Grid(shades) = heatmap.visualize() else {
+            panic!("expected a grid scene");
+        };
+        assert!(shades.rows().flatten().any(|glyph| glyph.ch == '@'));
+        assert_eq!(shades[goal].style == Style::Dim, heatmap.visits[goal] == 0);
+    }
+
+    #[test]
+    fn test_trace_queue() {
+        let grid = parse("19\n11");
+        let mut events = Vec::new();
+        let path = shortest_path_observed(
+            &grid,
+            Point::new(0, 0),
+            Point::new(1, 1),
+            &StraightRun::new(1, 3),
+            &mut TraceQueue(&mut events),
+        )
+        .unwrap();
+        assert_eq!(path.cost, 2);
+        // the four starting states go in first, and the goal comes out last
+        assert_eq!(
+            events[0],
+            Event::Push {
+                queue: "search",
+                item: String::from("0,0 Up 0"),
+                priority: 0,
+            }
+        );
+        assert!(events[..4].iter().all(|event| event.kind() == "push"));
+        assert!(matches!(
+            events.last(),
+            Some(Event::Pop { item, priority: 2, .. }) if item.starts_with("1,1 ")
+        ));
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let grid = Grid::new(5, 1, 1u8);
+        // can't travel 4 cells in a row without turning, and there's nowhere to turn
+        assert_eq!(
+            shortest_path(
+                &grid,
+                Point::new(0, 0),
+                Point::new(0, 4),
+                &StraightRun::new(1, 3)
+            ),
+            None
+        );
+        assert_eq!(
+            shortest_path(
+                &grid,
+                Point::new(0, 0),
+                Point::new(0, 0),
+                &StraightRun::new(0, 3)
+            )
+            .map(|p| p.cost),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::grid::Direction;
+
+/// A lattice point that may go negative, unlike [`crate::grid::Point`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vertex {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Vertex {
+    pub fn new(row: i64, col: i64) -> Self {
+        Vertex { row, col }
+    }
+
+    pub fn moved(&self, direction: Direction, distance: i64) -> Vertex {
+        let Vertex { row, col } = *self;
+        match direction {
+            Direction::Up => Vertex::new(row - distance, col),
+            Direction::Down => Vertex::new(row + distance, col),
+            Direction::Left => Vertex::new(row, col - distance),
+            Direction::Right => Vertex::new(row, col + distance),
+        }
+    }
+}
+
+/// A closed loop of axis-aligned edges traced through cell centres, as dug
+/// in Day 18.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    /// Corners in walking order, starting at the origin. The last edge runs
+    /// from the final vertex back to the first.
+    pub vertices: Vec<Vertex>,
+    /// Total length of every edge.
+    pub perimeter: u64,
+}
+
+impl Polygon {
+    /// Follow `(direction, distance)` instructions from the origin. Panics if
+    /// they don't end back where they started.
+    pub fn from_instructions(instructions: &[(Direction, u64)]) -> Self {
+        let mut vertices = vec![Vertex::default()];
+        let mut perimeter = 0;
+        let mut current = Vertex::default();
+
+        for &(direction, distance) in instructions {
+            let distance_i64 = i64::try_from(distance).expect("distance fits in an i64");
+            current = current.moved(direction, distance_i64);
+            vertices.push(current);
+            perimeter += distance;
+        }
+
+        assert_eq!(
+            vertices.pop(),
+            Some(Vertex::default()),
+            "the instructions don't close the loop"
+        );
+        if vertices.is_empty() {
+            vertices.push(Vertex::default());
+        }
+
+        Polygon {
+            vertices,
+            perimeter,
+        }
+    }
+
+    /// Area enclosed by the vertices (shoelace formula). This treats the
+    /// edges as lines through cell centres, so it misses the outer half of
+    /// every boundary cell.
+    pub fn area(&self) -> u64 {
+        let n = self.vertices.len();
+        let twice: i128 = (0..n)
+            .map(|i| {
+                let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+                a.col as i128 * b.row as i128 - b.col as i128 * a.row as i128
+            })
+            .sum();
+        (twice.unsigned_abs() / 2) as u64
+    }
+
+    /// Cells strictly inside the loop (Pick's theorem: A = i + b/2 - 1).
+    pub fn interior_cells(&self) -> u64 {
+        (self.area() + 1).saturating_sub(self.perimeter / 2)
+    }
+
+    /// Cells on the loop or inside it: the Day 18 lagoon size.
+    pub fn enclosed_cells(&self) -> u64 {
+        self.interior_cells() + self.perimeter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    fn direction(c: char) -> Direction {
+        match c {
+            'U' | '3' => Direction::Up,
+            'D' | '1' => Direction::Down,
+            'L' | '2' => Direction::Left,
+            'R' | '0' => Direction::Right,
+            _ => panic!("unknown direction {}", c),
+        }
+    }
+
+    fn parse(input: &str, hex: bool) -> Vec<(Direction, u64)> {
+        input
+            .lines()
+            .map(|line| {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if hex {
+                    let code = &parts[2][2..8];
+                    let distance = u64::from_str_radix(&code[..5], 16).unwrap();
+                    (direction(code.chars().last().unwrap()), distance)
+                } else {
+                    (
+                        direction(parts[0].chars().next().unwrap()),
+                        parts[1].parse().unwrap(),
+                    )
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_day18_example() {
+        let polygon = Polygon::from_instructions(&parse(EXAMPLE, false));
+        assert_eq!(polygon.perimeter, 38);
+        assert_eq!(polygon.vertices.len(), 14);
+        assert_eq!(polygon.vertices[1], Vertex::new(0, 6));
+        assert_eq!(polygon.enclosed_cells(), 62);
+
+        let polygon = Polygon::from_instructions(&parse(EXAMPLE, true));
+        assert_eq!(polygon.enclosed_cells(), 952408144115);
+    }
+
+    #[test]
+    fn test_square() {
+        // a 3x3 block of cells: 8 on the boundary and 1 inside
+        let square = [
+            (Direction::Right, 2),
+            (Direction::Down, 2),
+            (Direction::Left, 2),
+            (Direction::Up, 2),
+        ];
+        let polygon = Polygon::from_instructions(&square);
+        assert_eq!(polygon.area(), 4);
+        assert_eq!(polygon.interior_cells(), 1);
+        assert_eq!(polygon.enclosed_cells(), 9);
+
+        // the same square walked the other way round
+        let reversed: Vec<_> = square
+            .iter()
+            .rev()
+            .map(|&(direction, distance)| (direction.reverse(), distance))
+            .collect();
+        assert_eq!(Polygon::from_instructions(&reversed).enclosed_cells(), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "the instructions don't close the loop")]
+    fn test_open_loop() {
+        Polygon::from_instructions(&[(Direction::Right, 3), (Direction::Down, 1)]);
+    }
+}
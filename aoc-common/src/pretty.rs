@@ -0,0 +1,98 @@
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{CellAlignment, ContentArrangement, Table};
+use std::fmt;
+use std::time::Duration;
+
+/// How the cells of one column line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A summary table for the terminal, fitted to its width when stdout is one.
+/// Cells may hold colour escape codes; they don't count towards the width.
+pub struct Pretty {
+    table: Table,
+}
+
+impl Pretty {
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL_CONDENSED)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(columns.iter().map(|(name, _)| *name));
+        for (index, (_, align)) in columns.iter().enumerate() {
+            if let Some(column) = table.column_mut(index) {
+                column.set_cell_alignment(match align {
+                    Align::Left => CellAlignment::Left,
+                    Align::Right => CellAlignment::Right,
+                });
+            }
+        }
+        Pretty { table }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) -> &mut Self {
+        self.table.add_row(cells);
+        self
+    }
+
+    /// Fit to `width` columns instead of the terminal's, e.g. when piped.
+    pub fn width(&mut self, width: u16) -> &mut Self {
+        self.table.set_width(width);
+        self
+    }
+}
+
+impl fmt::Display for Pretty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.table)
+    }
+}
+
+/// `elapsed` in the largest unit that keeps it above one: 850µs, 12.30ms, 1.25s.
+pub fn duration(elapsed: Duration) -> String {
+    let us = elapsed.as_micros();
+    if us < 1_000 {
+        format!("{}µs", us)
+    } else if us < 1_000_000 {
+        format!("{:.2}ms", us as f64 / 1e3)
+    } else {
+        format!("{:.2}s", us as f64 / 1e6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(duration(Duration::from_micros(850)), "850µs");
+        assert_eq!(duration(Duration::from_micros(12_300)), "12.30ms");
+        assert_eq!(duration(Duration::from_millis(1250)), "1.25s");
+        assert_eq!(duration(Duration::ZERO), "0µs");
+    }
+
+    #[test]
+    fn test_pretty() {
+        let mut table = Pretty::new(&[("Day", Align::Right), ("Answer", Align::Left)]);
+        table
+            .row(vec![String::from("1"), String::from("142")])
+            .row(vec![String::from("25"), String::from("\x1b[1;32m8\x1b[0m")]);
+        let text = table.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[1], "│ Day ┆ Answer │");
+        assert_eq!(lines[3], "│   1 ┆ 142    │");
+        // the escape codes take no room
+        assert_eq!(lines[4], "│  25 ┆ \x1b[1;32m8\x1b[0m      │");
+
+        // too narrow for the answers, so they wrap
+        let mut table = Pretty::new(&[("Answer", Align::Left)]);
+        table.row(vec![String::from("a b c d e f g h")]).width(10);
+        assert!(table.to_string().lines().count() > 5);
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How much of a known amount of work is done, counted from any number of
+/// threads, with the throughput and time left that follow.
+#[derive(Debug)]
+pub struct Progress {
+    done: AtomicU64,
+    total: u64,
+    started: Instant,
+}
+
+impl Progress {
+    /// Nothing done yet out of `total`, with the clock starting now.
+    pub fn new(total: u64) -> Self {
+        Progress {
+            done: AtomicU64::new(0),
+            total,
+            started: Instant::now(),
+        }
+    }
+
+    /// Count `n` more done.
+    pub fn advance(&self, n: u64) {
+        self.done.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            describe(self.done(), self.total, self.started.elapsed())
+        )
+    }
+}
+
+// e.g. "1.2M of 10.0M (12%), 3.4M/s, about 3s left"
+fn describe(done: u64, total: u64, elapsed: Duration) -> String {
+    let percent = (done * 100).checked_div(total).unwrap_or(100);
+    let mut text = format!("{} of {} ({}%)", count(done), count(total), percent);
+    let seconds = elapsed.as_secs_f64();
+    if done > 0 && seconds > 0.0 {
+        let rate = done as f64 / seconds;
+        text.push_str(&format!(", {}/s", count(rate as u64)));
+        if done < total {
+            let left = (total - done) as f64 / rate;
+            text.push_str(&format!(
+                ", about {:.0?} left",
+                Duration::from_secs_f64(left.ceil())
+            ));
+        }
+    }
+    text
+}
+
+// A count short enough to read at a glance: 950, 12.3k, 1.2M, 3.4G
+fn count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.1}k", n as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", n as f64 / 1e6),
+        _ => format!("{:.1}G", n as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress::new(10);
+        progress.advance(3);
+        progress.advance(4);
+        assert_eq!(progress.done(), 7);
+        assert!(progress.to_string().starts_with("7 of 10 (70%)"));
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(1_200_000, 10_000_000, Duration::from_millis(500)),
+            "1.2M of 10.0M (12%), 2.4M/s, about 4s left"
+        );
+        assert_eq!(describe(0, 950, Duration::ZERO), "0 of 950 (0%)");
+        assert_eq!(
+            describe(12_345, 12_345, Duration::from_secs(1)),
+            "12.3k of 12.3k (100%), 12.3k/s"
+        );
+        assert_eq!(count(3_400_000_000), "3.4G");
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleState {
+    /// Sends a low pulse to `broadcaster` on every press.
+    Button,
+    Broadcaster,
+    /// `%`: toggles on a low pulse and sends its new state.
+    FlipFlop {
+        on: bool,
+    },
+    /// `&`: the last pulse from each input, in `inputs` order. Sends low once
+    /// every input was last high.
+    Conjunction {
+        memory: Vec<Pulse>,
+    },
+    /// Only ever named as a destination, such as `rx`.
+    Sink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub inputs: Vec<usize>,
+    pub outputs: Vec<usize>,
+    pub state: ModuleState,
+}
+
+/// A pulse in flight between two modules, named by their ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signal {
+    pub from: usize,
+    pub to: usize,
+    pub pulse: Pulse,
+}
+
+/// Hooks called while a press is being simulated; both do nothing unless
+/// overridden.
+pub trait Observer {
+    /// Called as each signal is delivered, before the receiver reacts.
+    fn signal(&mut self, _press: u64, _signal: &Signal, _network: &Network) {}
+
+    /// Called after `module`'s state changed from `before`.
+    fn transition(
+        &mut self,
+        _press: u64,
+        _module: usize,
+        _before: &ModuleState,
+        _network: &Network,
+    ) {
+    }
+}
+
+impl Observer for () {}
+
+/// The Day 20 module network, run one button press at a time.
+#[derive(Debug, Clone)]
+pub struct Network {
+    modules: Vec<Module>,
+    ids: HashMap<String, usize>,
+    button: usize,
+    broadcaster: usize,
+    presses: u64,
+}
+
+impl Network {
+    /// Parse lines like `%a -> b, c`. `None` if a line has no `->` or there is
+    /// no `broadcaster`.
+    pub fn parse(input: &str) -> Option<Network> {
+        let mut modules = Vec::new();
+        let mut ids = HashMap::new();
+        let mut id = |name: &str, modules: &mut Vec<Module>| -> usize {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                modules.push(Module {
+                    name: name.to_string(),
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                    state: ModuleState::Sink,
+                });
+                modules.len() - 1
+            })
+        };
+
+        let button = id("button", &mut modules);
+        modules[button].state = ModuleState::Button;
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (source, destinations) = line.split_once("->")?;
+            let source = source.trim();
+            let (name, state) = if let Some(name) = source.strip_prefix('%') {
+                (name, ModuleState::FlipFlop { on: false })
+            } else if let Some(name) = source.strip_prefix('&') {
+                (name, ModuleState::Conjunction { memory: Vec::new() })
+            } else {
+                (source, ModuleState::Broadcaster)
+            };
+
+            let from = id(name, &mut modules);
+            modules[from].state = state;
+            for destination in destinations.split(',').map(str::trim) {
+                let to = id(destination, &mut modules);
+                modules[from].outputs.push(to);
+                modules[to].inputs.push(from);
+            }
+        }
+
+        let broadcaster = *ids.get("broadcaster")?;
+        modules[button].outputs.push(broadcaster);
+        modules[broadcaster].inputs.push(button);
+
+        let mut network = Network {
+            modules,
+            ids,
+            button,
+            broadcaster,
+            presses: 0,
+        };
+        network.reset();
+        Some(network)
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn module(&self, id: usize) -> &Module {
+        &self.modules[id]
+    }
+
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// Button presses so far; the first press is press 1.
+    pub fn presses(&self) -> u64 {
+        self.presses
+    }
+
+    /// Turn every flip-flop off, set every conjunction's memory to low and
+    /// zero the press count.
+    pub fn reset(&mut self) {
+        for module in &mut self.modules {
+            match &mut module.state {
+                ModuleState::FlipFlop { on } => *on = false,
+                ModuleState::Conjunction { memory } => {
+                    *memory = vec![Pulse::Low; module.inputs.len()]
+                }
+                _ => {}
+            }
+        }
+        self.presses = 0;
+    }
+
+    /// Press the button once and deliver pulses until the network is quiet.
+    pub fn press(&mut self, observer: &mut impl Observer) {
+        self.presses += 1;
+        let press = self.presses;
+
+        let mut queue = VecDeque::from([Signal {
+            from: self.button,
+            to: self.broadcaster,
+            pulse: Pulse::Low,
+        }]);
+        while let Some(signal) = queue.pop_front() {
+            observer.signal(press, &signal, self);
+
+            let Module {
+                inputs,
+                outputs,
+                state,
+                ..
+            } = &mut self.modules[signal.to];
+            let before = state.clone();
+            let sent = match state {
+                ModuleState::Button | ModuleState::Broadcaster => Some(signal.pulse),
+                ModuleState::FlipFlop { .. } if signal.pulse == Pulse::High => None,
+                ModuleState::FlipFlop { on } => {
+                    *on = !*on;
+                    Some(if *on { Pulse::High } else { Pulse::Low })
+                }
+                ModuleState::Conjunction { memory } => {
+                    let slot = inputs
+                        .iter()
+                        .position(|&input| input == signal.from)
+                        .expect("parse lists every sender as an input");
+                    memory[slot] = signal.pulse;
+                    if memory.iter().all(|&pulse| pulse == Pulse::High) {
+                        Some(Pulse::Low)
+                    } else {
+                        Some(Pulse::High)
+                    }
+                }
+                ModuleState::Sink => None,
+            };
+
+            if let Some(pulse) = sent {
+                queue.extend(outputs.iter().map(|&to| Signal {
+                    from: signal.to,
+                    to,
+                    pulse,
+                }));
+            }
+            if self.modules[signal.to].state != before {
+                observer.transition(press, signal.to, &before, self);
+            }
+        }
+    }
+}
+
+/// Counts every pulse delivered, including the button's (Day 20 part 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PulseCounter {
+    pub low: u64,
+    pub high: u64,
+}
+
+impl Observer for PulseCounter {
+    fn signal(&mut self, _press: u64, signal: &Signal, _network: &Network) {
+        match signal.pulse {
+            Pulse::Low => self.low += 1,
+            Pulse::High => self.high += 1,
+        }
+    }
+}
+
+/// Records the presses on which chosen modules send chosen pulses, for
+/// finding the cycles behind Day 20 part 2.
+#[derive(Debug, Clone, Default)]
+pub struct EmitRecorder {
+    hits: HashMap<(usize, Pulse), Vec<u64>>,
+}
+
+impl EmitRecorder {
+    pub fn new(watches: impl IntoIterator<Item = (usize, Pulse)>) -> Self {
+        EmitRecorder {
+            hits: watches
+                .into_iter()
+                .map(|watch| (watch, Vec::new()))
+                .collect(),
+        }
+    }
+
+    /// Presses on which `module` sent `pulse`, each listed once.
+    pub fn hits(&self, module: usize, pulse: Pulse) -> &[u64] {
+        self.hits.get(&(module, pulse)).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Observer for EmitRecorder {
+    fn signal(&mut self, press: u64, signal: &Signal, _network: &Network) {
+        if let Some(presses) = self.hits.get_mut(&(signal.from, signal.pulse)) {
+            if presses.last() != Some(&press) {
+                presses.push(press);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::{first_alignment, Cycle};
+
+    const EXAMPLE_1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const EXAMPLE_2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    fn pulse_product(input: &str) -> u64 {
+        let mut network = Network::parse(input).unwrap();
+        let mut counter = PulseCounter::default();
+        for _ in 0..1000 {
+            network.press(&mut counter);
+        }
+        counter.low * counter.high
+    }
+
+    #[test]
+    fn test_day20_examples() {
+        assert_eq!(pulse_product(EXAMPLE_1), 32000000);
+        assert_eq!(pulse_product(EXAMPLE_2), 11687500);
+    }
+
+    #[test]
+    fn test_parse() {
+        let network = Network::parse(EXAMPLE_2).unwrap();
+        let con = network.id("con").unwrap();
+        let output = network.module(network.id("output").unwrap());
+        assert_eq!(output.state, ModuleState::Sink);
+        assert_eq!(output.inputs, vec![con]);
+        assert_eq!(
+            network.module(con).state,
+            ModuleState::Conjunction {
+                memory: vec![Pulse::Low; 2]
+            }
+        );
+
+        assert!(Network::parse("%a -> b").is_none());
+        assert!(Network::parse("broadcaster a").is_none());
+    }
+
+    #[test]
+    fn test_emit_recorder_cycles() {
+        // two bit counters of different widths feeding conjunctions: each
+        // conjunction sends low once every bit of its counter is on
+        let input = "\
+broadcaster -> a, x
+%a -> b, ab
+%b -> ab
+&ab -> rx
+%x -> y, xyz
+%y -> z, xyz
+%z -> xyz
+&xyz -> rx";
+        let mut network = Network::parse(input).unwrap();
+        let ab = network.id("ab").unwrap();
+        let xyz = network.id("xyz").unwrap();
+        let mut recorder = EmitRecorder::new([(ab, Pulse::Low), (xyz, Pulse::Low)]);
+        for _ in 0..24 {
+            network.press(&mut recorder);
+        }
+
+        assert_eq!(recorder.hits(ab, Pulse::Low), &[3, 7, 11, 15, 19, 23]);
+        assert_eq!(recorder.hits(xyz, Pulse::Low), &[7, 15, 23]);
+        assert_eq!(recorder.hits(ab, Pulse::High).len(), 0);
+
+        let cycles = [Cycle::new(3, 4), Cycle::new(7, 8)];
+        assert_eq!(first_alignment(&cycles), Some(7));
+    }
+
+    #[test]
+    fn test_transitions() {
+        #[derive(Default)]
+        struct FlipFlops(Vec<(u64, String)>);
+        impl Observer for FlipFlops {
+            fn transition(
+                &mut self,
+                press: u64,
+                module: usize,
+                before: &ModuleState,
+                network: &Network,
+            ) {
+                if *before == (ModuleState::FlipFlop { on: false }) {
+                    self.0.push((press, network.module(module).name.clone()));
+                }
+            }
+        }
+
+        let mut network = Network::parse(EXAMPLE_1).unwrap();
+        let mut turned_on = FlipFlops::default();
+        // every press turns all three on and then back off again
+        network.press(&mut turned_on);
+        network.press(&mut turned_on);
+        network.press(&mut ());
+        assert_eq!(network.presses(), 3);
+
+        let names: Vec<&str> = turned_on.0.iter().map(|(_, name)| name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c", "a", "b", "c"]);
+        let presses: Vec<u64> = turned_on.0.iter().map(|&(press, _)| press).collect();
+        assert_eq!(presses, vec![1, 1, 1, 2, 2, 2]);
+
+        network.reset();
+        assert_eq!(network.presses(), 0);
+    }
+}
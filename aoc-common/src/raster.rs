@@ -0,0 +1,136 @@
+use crate::grid::{Grid, Point};
+use crate::pathfinding::Heatmap;
+use crate::visualize::{Glyph, Style};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageResult, Rgba, RgbaImage};
+use std::io::Write;
+use std::path::Path;
+
+const BACKGROUND: Rgba<u8> = Rgba([16, 16, 16, 255]);
+
+/// The fill colour for a cell drawn in `style`.
+pub fn color(style: Style) -> Rgba<u8> {
+    match style {
+        Style::Plain => Rgba([200, 200, 200, 255]),
+        Style::Dim => Rgba([56, 56, 56, 255]),
+        Style::Highlight => Rgba([64, 200, 96, 255]),
+        Style::Accent => Rgba([240, 200, 64, 255]),
+        Style::Alert => Rgba([224, 64, 64, 255]),
+    }
+}
+
+/// Draw each cell as a `cell`-pixel square in its style's colour, with a
+/// one pixel gutter so neighbours stay apart. Blank cells are left as
+/// background. There is no font: a glyph's character only decides whether
+/// it is blank.
+pub fn rasterize(grid: &Grid<Glyph>, cell: u32) -> RgbaImage {
+    let cell = cell.max(2);
+    let (width, height) = (grid.width() as u32, grid.height() as u32);
+    let mut image = RgbaImage::from_pixel(width * cell, height * cell, BACKGROUND);
+    for point in grid.points() {
+        let glyph = grid[point];
+        if !glyph.ch.is_whitespace() {
+            fill(&mut image, point, cell, color(glyph.style));
+        }
+    }
+
+    image
+}
+
+/// Draw a search heatmap like [`rasterize`], shading visited cells from
+/// dark blue for the quietest to yellow for the busiest.
+pub fn heatmap(heatmap: &Heatmap, cell: u32) -> RgbaImage {
+    let cell = cell.max(2);
+    let visits = &heatmap.visits;
+    let busiest = heatmap.busiest().max(1) as f64;
+    let (width, height) = (visits.width() as u32, visits.height() as u32);
+    let mut image = RgbaImage::from_pixel(width * cell, height * cell, BACKGROUND);
+    for point in visits.points() {
+        if visits[point] > 0 {
+            let heat = visits[point] as f64 / busiest;
+            let mix = |cold: u8, hot: u8| (cold as f64 + (hot as f64 - cold as f64) * heat) as u8;
+            let shade = Rgba([mix(32, 250), mix(48, 220), mix(160, 40), 255]);
+            fill(&mut image, point, cell, shade);
+        }
+    }
+
+    image
+}
+
+// a cell's square, less the gutter
+fn fill(image: &mut RgbaImage, point: Point, cell: u32, color: Rgba<u8>) {
+    let (x, y) = (point.col as u32 * cell, point.row as u32 * cell);
+    for dy in 0..cell - 1 {
+        for dx in 0..cell - 1 {
+            image.put_pixel(x + dx, y + dy, color);
+        }
+    }
+}
+
+/// Save one grid as a PNG.
+pub fn save_png(grid: &Grid<Glyph>, cell: u32, path: impl AsRef<Path>) -> ImageResult<()> {
+    rasterize(grid, cell).save_with_format(path, image::ImageFormat::Png)
+}
+
+/// Encode grids as a looping animated GIF, showing each for `delay_ms`.
+pub fn write_gif<'a>(
+    out: impl Write,
+    grids: impl IntoIterator<Item = &'a Grid<Glyph>>,
+    cell: u32,
+    delay_ms: u32,
+) -> ImageResult<()> {
+    let mut encoder = GifEncoder::new(out);
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(grids.into_iter().map(|grid| {
+        Frame::from_parts(
+            rasterize(grid, cell),
+            0,
+            0,
+            Delay::from_numer_denom_ms(delay_ms, 1),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<Glyph> {
+        Grid::from_rows(vec![
+            vec![
+                Glyph::new('4', Style::Highlight),
+                Glyph::new(' ', Style::Plain),
+            ],
+            vec![Glyph::new('.', Style::Dim), Glyph::new('*', Style::Accent)],
+        ])
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let image = rasterize(&example(), 4);
+        assert_eq!(image.dimensions(), (8, 8));
+        assert_eq!(*image.get_pixel(0, 0), color(Style::Highlight));
+        // the gutter and blank cells stay background
+        assert_eq!(*image.get_pixel(3, 0), BACKGROUND);
+        assert_eq!(*image.get_pixel(5, 1), BACKGROUND);
+        assert_eq!(*image.get_pixel(5, 5), color(Style::Accent));
+        assert_eq!(*image.get_pixel(1, 6), color(Style::Dim));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let mut heat = Heatmap::new(2, 1);
+        heat.visits[Point::new(0, 1)] = 4;
+        let image = heatmap(&heat, 4);
+        assert_eq!(*image.get_pixel(0, 0), BACKGROUND);
+        assert_eq!(*image.get_pixel(4, 0), Rgba([250, 220, 40, 255]));
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let grid = example();
+        let mut out = Vec::new();
+        write_gif(&mut out, [&grid, &grid], 4, 100).unwrap();
+        assert!(out.starts_with(b"GIF89a"));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::cancel::CancelToken;
+use crate::parse::ParseMode;
+use color_eyre::eyre::{bail, Result};
+use std::ops::Range;
+
+pub use aoc_macros::aoc;
+// for what #[aoc] expands to, so day crates needn't depend on it themselves
+#[doc(hidden)]
+pub use inventory;
+
+/// One part of one day, registered with [`aoc`].
+pub struct Solution {
+    /// [`YEAR`](crate::input::YEAR) unless the day is from an earlier one.
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The answer as it would be submitted.
+    pub solve: fn(&str, ParseMode, &CancelToken) -> Result<String>,
+}
+
+inventory::collect!(Solution);
+
+/// How the answers found in the shards of a [`BruteForce`] search combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduce {
+    Min,
+    Max,
+    Sum,
+}
+
+/// A naive search standing in for a part without a clever solution yet, for
+/// `aoc run --brute-force`. Registered with `inventory::submit!`.
+pub struct BruteForce {
+    pub day: u32,
+    pub part: u32,
+    /// How many candidates the input has, numbered from 0.
+    pub space: fn(&str) -> Result<u64>,
+    /// The answer among the candidates in `range`, if any of them is one.
+    pub search: fn(&str, Range<u64>) -> Result<Option<u64>>,
+    pub reduce: Reduce,
+}
+
+inventory::collect!(BruteForce);
+
+/// Every registered solution, ordered by year, day then part.
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions: Vec<&Solution> = inventory::iter::<Solution>.into_iter().collect();
+    solutions.sort_by_key(|solution| (solution.year, solution.day, solution.part));
+    solutions
+}
+
+/// A day's two parts, answering the way they'd be submitted.
+pub trait Solver {
+    fn part1(&self, input: &str) -> Result<String>;
+    fn part2(&self, input: &str) -> Result<String>;
+}
+
+/// A day with registered parts, solved through what its crate registered with
+/// [`aoc`]; see [`days`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl Day {
+    fn solve(&self, part: u32, input: &str) -> Result<String> {
+        let found = inventory::iter::<Solution>.into_iter().find(|solution| {
+            (solution.year, solution.day, solution.part) == (self.year, self.day, part)
+        });
+        match found {
+            Some(solution) => (solution.solve)(input, ParseMode::Strict, &CancelToken::new()),
+            None => bail!(
+                "{} day {} part {} is not implemented",
+                self.year,
+                self.day,
+                part
+            ),
+        }
+    }
+}
+
+impl Solver for Day {
+    fn part1(&self, input: &str) -> Result<String> {
+        self.solve(1, input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        self.solve(2, input)
+    }
+}
+
+/// Every day with a registered part, ordered by year then day.
+pub fn days() -> Vec<Day> {
+    let mut days: Vec<Day> = solutions()
+        .into_iter()
+        .map(|solution| Day {
+            year: solution.year,
+            day: solution.day,
+        })
+        .collect();
+    days.dedup();
+    days
+}
+
+/// Every registered brute-force search, ordered by day then part.
+pub fn brute_forces() -> Vec<&'static BruteForce> {
+    let mut searches: Vec<&BruteForce> = inventory::iter::<BruteForce>.into_iter().collect();
+    searches.sort_by_key(|search| (search.day, search.part));
+    searches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    inventory::submit! {
+        Solution { year: 2023, day: 25, part: 2, solve: |_, _, _| Ok(String::from("last")) }
+    }
+
+    inventory::submit! {
+        Solution { year: 2023, day: 25, part: 1, solve: |input, _, _| Ok(input.len().to_string()) }
+    }
+
+    inventory::submit! {
+        Solution { year: 2015, day: 25, part: 1, solve: |_, _, _| Ok(String::from("first")) }
+    }
+
+    inventory::submit! {
+        BruteForce {
+            day: 25,
+            part: 2,
+            space: |input| Ok(input.len() as u64),
+            search: |_, range| Ok(range.last()),
+            reduce: Reduce::Max,
+        }
+    }
+
+    #[test]
+    fn test_solutions() {
+        let solutions = solutions();
+        let keys: Vec<(u32, u32, u32)> =
+            solutions.iter().map(|s| (s.year, s.day, s.part)).collect();
+        assert_eq!(keys, [(2015, 25, 1), (2023, 25, 1), (2023, 25, 2)]);
+        let cancel = CancelToken::new();
+        assert_eq!(
+            (solutions[1].solve)("abc", ParseMode::Strict, &cancel).unwrap(),
+            "3"
+        );
+    }
+
+    #[test]
+    fn test_days() {
+        let days = days();
+        assert_eq!(
+            days.first(),
+            Some(&Day {
+                year: 2015,
+                day: 25
+            })
+        );
+        assert_eq!(
+            days.last(),
+            Some(&Day {
+                year: 2023,
+                day: 25
+            })
+        );
+        assert_eq!(days.len(), 2);
+
+        let day = Day {
+            year: 2023,
+            day: 25,
+        };
+        assert_eq!(day.part1("abc").unwrap(), "3");
+        assert_eq!(day.part2("abc").unwrap(), "last");
+        let first = Day {
+            year: 2015,
+            day: 25,
+        };
+        assert_eq!(first.part1("").unwrap(), "first");
+        assert!(first.part2("").is_err());
+    }
+
+    #[test]
+    fn test_brute_forces() {
+        let searches = brute_forces();
+        assert_eq!(searches.len(), 1);
+        assert_eq!((searches[0].day, searches[0].part), (25, 2));
+        assert_eq!((searches[0].space)("abc").unwrap(), 3);
+        assert_eq!((searches[0].search)("abc", 0..3).unwrap(), Some(2));
+    }
+}
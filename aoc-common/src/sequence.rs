@@ -0,0 +1,126 @@
+/// Rows of repeated differences, starting with `values` itself and ending
+/// with a single entry.
+pub fn difference_table(values: &[i64]) -> Vec<Vec<i128>> {
+    let mut rows = vec![values.iter().map(|&v| v as i128).collect::<Vec<_>>()];
+    while rows.last().is_some_and(|row| row.len() > 1) {
+        let next = rows
+            .last()
+            .unwrap()
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .collect();
+        rows.push(next);
+    }
+    rows
+}
+
+/// The degree of the polynomial through `values`, or `None` when there are
+/// too few samples to tell: its next differences must be all zero.
+pub fn degree(values: &[i64]) -> Option<usize> {
+    difference_table(values)
+        .iter()
+        .position(|row| row.iter().all(|&v| v == 0))
+        .map(|zero_row| zero_row.saturating_sub(1))
+}
+
+/// The value at position `n` of the lowest-degree polynomial through the
+/// samples, where `values[i]` is position `i`. `n` may be negative or far past
+/// the end; the answer is exact, or `None` if it doesn't fit in an i128.
+///
+/// This is Newton's forward-difference formula: f(n) = sum C(n, j) * Δʲf(0).
+pub fn extrapolate(values: &[i64], n: i128) -> Option<i128> {
+    let mut total: i128 = 0;
+    // C(n, j), which stays an integer even for negative n
+    let mut binomial: i128 = 1;
+    for (j, row) in difference_table(values)
+        .iter()
+        .take_while(|row| !row.is_empty())
+        .enumerate()
+    {
+        if j > 0 {
+            let j = j as i128;
+            binomial = binomial.checked_mul(n - (j - 1))? / j;
+        }
+        total = total.checked_add(binomial.checked_mul(row[0])?)?;
+    }
+    Some(total)
+}
+
+/// Values sampled at `start`, `start + step`, `start + 2 * step`, ..., as in
+/// Day 21 part 2 where the reachable count is quadratic in the number of
+/// whole map widths walked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Samples {
+    pub start: i64,
+    pub step: i64,
+    pub values: Vec<i64>,
+}
+
+impl Samples {
+    /// The extrapolated value at `x`, or `None` if `x` isn't a whole number of
+    /// steps from `start` or the answer overflows.
+    pub fn at(&self, x: i64) -> Option<i128> {
+        let offset = x as i128 - self.start as i128;
+        let step = self.step as i128;
+        if step == 0 || offset % step != 0 {
+            return None;
+        }
+        extrapolate(&self.values, offset / step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day9_example() {
+        let histories: [&[i64]; 3] = [
+            &[0, 3, 6, 9, 12, 15],
+            &[1, 3, 6, 10, 15, 21],
+            &[10, 13, 16, 21, 30, 45],
+        ];
+        let next: Vec<i128> = histories
+            .iter()
+            .map(|h| extrapolate(h, h.len() as i128).unwrap())
+            .collect();
+        assert_eq!(next, vec![18, 28, 68]);
+
+        let previous: Vec<i128> = histories
+            .iter()
+            .map(|h| extrapolate(h, -1).unwrap())
+            .collect();
+        assert_eq!(previous, vec![-3, 0, 5]);
+
+        assert_eq!(histories.map(degree), [Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn test_quadratic_at_large_n() {
+        // triangular numbers have half-integer coefficients but stay exact
+        let f = |x: i64| (x as i128 * x as i128 + x as i128) / 2;
+        let samples = Samples {
+            start: 65,
+            step: 131,
+            values: [65, 196, 327].map(|x| f(x) as i64).to_vec(),
+        };
+        assert_eq!(samples.at(26501365), Some(f(26501365)));
+        assert_eq!(samples.at(65), Some(f(65)));
+        assert_eq!(samples.at(66), None);
+        assert_eq!(degree(&samples.values), None);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(extrapolate(&[], 10), Some(0));
+        assert_eq!(extrapolate(&[7], 10), Some(7));
+        assert_eq!(degree(&[7, 7]), Some(0));
+        assert_eq!(degree(&[0, 0]), Some(0));
+        assert_eq!(extrapolate(&[0, 1, 4], 1 << 62), Some(1 << 124));
+        assert_eq!(extrapolate(&[0, 1, 4], 1 << 64), None);
+        assert_eq!(
+            difference_table(&[1, 4, 9]),
+            vec![vec![1, 4, 9], vec![3, 5], vec![2]]
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/// A small seeded generator (SplitMix64): the same seed gives the same
+/// numbers on every machine, which is all shuffling an input needs.
+#[derive(Debug, Clone)]
+pub struct Shuffler {
+    state: u64,
+}
+
+impl Shuffler {
+    pub fn new(seed: u64) -> Self {
+        Shuffler { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`; panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing to choose from");
+        // the modulo bias is at most n / 2^64, far below anything that matters here
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Put `items` in a random order (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffler() {
+        let (mut a, mut b) = (Shuffler::new(7), Shuffler::new(7));
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(Shuffler::new(8).next_u64(), Shuffler::new(7).next_u64());
+        assert!((0..100).all(|_| a.below(3) < 3));
+
+        let mut items: Vec<u32> = (0..20).collect();
+        a.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,144 @@
+use std::io::{self, Write};
+
+/// One column of per-entity values, one entry per row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    UInt(Vec<u64>),
+    Bool(Vec<bool>),
+    Str(Vec<String>),
+}
+
+impl Column {
+    pub fn len(&self) -> usize {
+        match self {
+            Column::UInt(values) => values.len(),
+            Column::Bool(values) => values.len(),
+            Column::Str(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Per-entity records of a day (one row per game, number, ...), stored by column
+/// so exporters can hand whole columns to columnar formats.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    name: String,
+    columns: Vec<(String, Column)>,
+}
+
+impl Table {
+    pub fn new(name: &str) -> Self {
+        Table {
+            name: name.to_string(),
+            columns: Vec::new(),
+        }
+    }
+
+    /// Append a column. Panics if its length differs from the existing columns.
+    pub fn with_column(mut self, name: &str, column: Column) -> Self {
+        if let Some((first, existing)) = self.columns.first() {
+            assert_eq!(
+                column.len(),
+                existing.len(),
+                "column {} has a different number of rows than {}",
+                name,
+                first
+            );
+        }
+        self.columns.push((name.to_string(), column));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn columns(&self) -> &[(String, Column)] {
+        &self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.columns.first().map_or(0, |(_, column)| column.len())
+    }
+
+    /// Write the table as CSV: a header of column names, then a line per row.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        let header: Vec<String> = self.columns.iter().map(|(name, _)| field(name)).collect();
+        writeln!(out, "{}", header.join(","))?;
+        for row in 0..self.rows() {
+            let fields: Vec<String> = self
+                .columns
+                .iter()
+                .map(|(_, column)| match column {
+                    Column::UInt(values) => values[row].to_string(),
+                    Column::Bool(values) => values[row].to_string(),
+                    Column::Str(values) => field(&values[row]),
+                })
+                .collect();
+            writeln!(out, "{}", fields.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+// A CSV field, quoted when it holds a separator, quote or line break
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let table = Table::new("games")
+            .with_column("id", Column::UInt(vec![1, 2]))
+            .with_column("feasible", Column::Bool(vec![true, false]));
+        assert_eq!(table.name(), "games");
+        assert_eq!(table.rows(), 2);
+        assert_eq!(table.columns()[1].0, "feasible");
+        assert_eq!(Table::new("empty").rows(), 0);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let table = Table::new("lines")
+            .with_column("id", Column::UInt(vec![1, 2, 3]))
+            .with_column("valid", Column::Bool(vec![true, false, true]))
+            .with_column(
+                "text",
+                Column::Str(vec![
+                    String::from("1abc2"),
+                    String::from("a, b"),
+                    String::from("say \"hi\""),
+                ]),
+            );
+        let mut out = Vec::new();
+        table.write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,valid,text\n1,true,1abc2\n2,false,\"a, b\"\n3,true,\"say \"\"hi\"\"\"\n"
+        );
+
+        let mut out = Vec::new();
+        Table::new("empty").write_csv(&mut out).unwrap();
+        assert_eq!(out, b"\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "column text has a different number of rows than id")]
+    fn test_table_mismatched_rows() {
+        let _ = Table::new("lines")
+            .with_column("id", Column::UInt(vec![1, 2]))
+            .with_column("text", Column::Str(vec![String::from("a")]));
+    }
+}
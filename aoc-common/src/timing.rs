@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::filter::{targets::Targets, ParseError};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// Accumulated time for every span sharing a name.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SpanStats {
+    pub calls: usize,
+    pub total: Duration,
+}
+
+impl SpanStats {
+    pub fn mean(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.calls as u32
+        }
+    }
+}
+
+// per-span bookkeeping, kept in the span's extensions until it closes
+struct Busy {
+    elapsed: Duration,
+    entered: Option<Instant>,
+}
+
+type Stats = Arc<Mutex<HashMap<&'static str, SpanStats>>>;
+
+/// A `tracing` layer that adds up the time spent inside each span name.
+///
+/// Time is measured between enter and exit, so a span that is entered several
+/// times (e.g. across `.await` points) only counts the time it was active.
+/// Nested spans are inclusive: a parent's total contains its children.
+#[derive(Debug, Default, Clone)]
+pub struct TimingLayer {
+    stats: Stats,
+}
+
+impl TimingLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle for reading the accumulated timings once the layer is installed.
+    pub fn summary(&self) -> TimingSummary {
+        TimingSummary {
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Busy {
+                elapsed: Duration::ZERO,
+                entered: None,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(busy) = span.extensions_mut().get_mut::<Busy>() {
+                busy.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(busy) = span.extensions_mut().get_mut::<Busy>() {
+                if let Some(start) = busy.entered.take() {
+                    busy.elapsed += start.elapsed();
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            let elapsed = span
+                .extensions()
+                .get::<Busy>()
+                .map(|busy| busy.elapsed)
+                .unwrap_or_default();
+
+            let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = stats.entry(span.name()).or_default();
+            entry.calls += 1;
+            entry.total += elapsed;
+        }
+    }
+}
+
+/// Read side of a [`TimingLayer`]; `Display` renders the end-of-run table.
+#[derive(Debug, Clone)]
+pub struct TimingSummary {
+    stats: Stats,
+}
+
+impl TimingSummary {
+    /// Snapshot of all span names, slowest total first.
+    pub fn spans(&self) -> Vec<(&'static str, SpanStats)> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut spans: Vec<_> = stats.iter().map(|(name, s)| (*name, *s)).collect();
+        spans.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        spans
+    }
+}
+
+impl fmt::Display for TimingSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spans = self.spans();
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let width = spans
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("span".len());
+
+        writeln!(
+            f,
+            "{:<width$}  {:>12}  {:>8}  {:>12}",
+            "span", "total", "calls", "mean"
+        )?;
+        for (name, stats) in spans {
+            writeln!(
+                f,
+                "{:<width$}  {:>12}  {:>8}  {:>12}",
+                name,
+                format!("{:.3?}", stats.total),
+                stats.calls,
+                format!("{:.3?}", stats.mean()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Install the subscriber every day binary uses: formatted logs filtered by
+/// `RUST_LOG` (`level` when it isn't set) and a [`TimingLayer`], whose
+/// summary is returned.
+pub fn install(level: &str) -> Result<TimingSummary, ParseError> {
+    let filter_layer = Targets::from_str(std::env::var("RUST_LOG").as_deref().unwrap_or(level))?;
+    let format_layer = tracing_subscriber::fmt::layer();
+    let timing_layer = TimingLayer::new();
+    let timings = timing_layer.summary();
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(format_layer)
+        .with(timing_layer)
+        .init();
+
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_counts_calls_per_span_name() {
+        let layer = TimingLayer::new();
+        let summary = layer.summary();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..3 {
+                let _span = tracing::info_span!("parse_game").entered();
+            }
+            let _span = tracing::info_span!("border").entered();
+        });
+
+        let spans = summary.spans();
+        assert_eq!(spans.len(), 2);
+        let parse_game = spans
+            .iter()
+            .find(|(name, _)| *name == "parse_game")
+            .unwrap();
+        assert_eq!(parse_game.1.calls, 3);
+        let border = spans.iter().find(|(name, _)| *name == "border").unwrap();
+        assert_eq!(border.1.calls, 1);
+    }
+
+    #[test]
+    fn test_accumulates_busy_time() {
+        let layer = TimingLayer::new();
+        let summary = layer.summary();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("sleepy").entered();
+            std::thread::sleep(Duration::from_millis(5));
+        });
+
+        let spans = summary.spans();
+        assert!(spans[0].1.total >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_display_table() {
+        let layer = TimingLayer::new();
+        let summary = layer.summary();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        assert_eq!(summary.to_string(), "");
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("border").entered();
+        });
+
+        let table = summary.to_string();
+        let mut lines = table.lines();
+        assert!(lines.next().unwrap().starts_with("span"));
+        assert!(lines.next().unwrap().starts_with("border"));
+        assert!(lines.next().is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::visualize::{Checkpoint, Stepper};
+
+/// One state transition of an algorithm, for `aoc run --trace-algo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// `item` joined `queue`, to come off it at `priority`
+    Push {
+        queue: &'static str,
+        item: String,
+        priority: u64,
+    },
+    /// `item` came off `queue` at `priority`, to be expanded
+    Pop {
+        queue: &'static str,
+        item: String,
+        priority: u64,
+    },
+    /// The interval `start..end` was cut in two at `at`
+    Split { start: i64, end: i64, at: i64 },
+    /// The states repeat: the one after `start + length` steps is the one
+    /// after `start`
+    Cycle { start: usize, length: usize },
+    /// Anything else a solver reports, such as a checkpoint's variables
+    Update {
+        label: String,
+        variables: Vec<(String, String)>,
+    },
+}
+
+impl Event {
+    /// What kind of transition this is, as a trace names it.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Push { .. } => "push",
+            Event::Pop { .. } => "pop",
+            Event::Split { .. } => "split",
+            Event::Cycle { .. } => "cycle",
+            Event::Update { .. } => "update",
+        }
+    }
+}
+
+/// Hears about each state transition of a traced algorithm, in order.
+pub trait Tracer {
+    fn event(&mut self, event: Event);
+}
+
+impl Tracer for () {
+    fn event(&mut self, _: Event) {}
+}
+
+impl Tracer for Vec<Event> {
+    fn event(&mut self, event: Event) {
+        self.push(event);
+    }
+}
+
+/// A cooperating solver's checkpoints, passed on to a [`Tracer`] as
+/// [`Event::Update`]s; their scenes are for people and are left out.
+pub struct Checkpoints<'a, T: Tracer + ?Sized>(pub &'a mut T);
+
+impl<T: Tracer + ?Sized> Stepper for Checkpoints<'_, T> {
+    fn checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.0.event(Event::Update {
+            label: checkpoint.label,
+            variables: checkpoint.variables,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::Scene;
+
+    #[test]
+    fn test_checkpoints() {
+        let mut events = Vec::new();
+        let mut stepper = Checkpoints(&mut events);
+        stepper.checkpoint(
+            Checkpoint::new("Game 1")
+                .scene(Scene::List(Vec::new()))
+                .var("total", 1),
+        );
+        assert_eq!(
+            events,
+            [Event::Update {
+                label: String::from("Game 1"),
+                variables: vec![(String::from("total"), String::from("1"))],
+            }]
+        );
+        assert_eq!(events[0].kind(), "update");
+        assert_eq!(
+            Event::Cycle {
+                start: 0,
+                length: 1
+            }
+            .kind(),
+            "cycle"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::grid::{Grid, Point};
+use std::fmt::Display;
+
+/// How a renderer should set a piece of a scene apart; what each one looks
+/// like is up to the renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Style {
+    #[default]
+    Plain,
+    /// Background detail, such as empty cells.
+    Dim,
+    /// What the puzzle is counting: part numbers, feasible games, ...
+    Highlight,
+    /// Landmarks worth spotting: symbols, junctions, ...
+    Accent,
+    /// Whatever breaks a rule.
+    Alert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Glyph {
+    pub ch: char,
+    pub style: Style,
+}
+
+impl Glyph {
+    pub fn new(ch: char, style: Style) -> Self {
+        Glyph { ch, style }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Item {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Item {
+    pub fn new(text: impl Into<String>, style: Style) -> Self {
+        Item {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub label: Option<String>,
+}
+
+/// A day's state, ready for any renderer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scene {
+    Grid(Grid<Glyph>),
+    /// `nodes[i]` labels node `i`; edges refer to nodes by index.
+    Graph {
+        nodes: Vec<Item>,
+        edges: Vec<Edge>,
+    },
+    List(Vec<Item>),
+}
+
+/// Implemented by anything that can show its state as a [`Scene`].
+pub trait Visualize {
+    fn visualize(&self) -> Scene;
+}
+
+/// Implemented by solvers that can replay their progress as a sequence of
+/// scenes, the last one showing the finished state.
+pub trait Animate {
+    fn frames(&self) -> Vec<Scene>;
+}
+
+/// Implemented by grid days that can describe a cell of their scene, for the
+/// interactive explorer.
+pub trait Inspect {
+    /// `(name, value)` pairs about the cell at `point`; empty when there is
+    /// nothing to say.
+    fn inspect(&self, point: Point) -> Vec<(String, String)>;
+}
+
+/// A labelled point a cooperating solver stops at, with the state and
+/// variables it wants shown there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub label: String,
+    pub scene: Option<Scene>,
+    pub variables: Vec<(String, String)>,
+}
+
+impl Checkpoint {
+    pub fn new(label: impl Into<String>) -> Self {
+        Checkpoint {
+            label: label.into(),
+            scene: None,
+            variables: Vec::new(),
+        }
+    }
+
+    pub fn scene(mut self, scene: Scene) -> Self {
+        self.scene = Some(scene);
+        self
+    }
+
+    pub fn var(mut self, name: &str, value: impl Display) -> Self {
+        self.variables.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Hears about each checkpoint a cooperating solver reaches, e.g. to wait
+/// for a keypress before letting it carry on.
+pub trait Stepper {
+    fn checkpoint(&mut self, checkpoint: Checkpoint);
+}
+
+impl Stepper for () {
+    fn checkpoint(&mut self, _: Checkpoint) {}
+}
+
+impl Stepper for Vec<Checkpoint> {
+    fn checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.push(checkpoint);
+    }
+}
+
+impl Visualize for Grid<char> {
+    fn visualize(&self) -> Scene {
+        Scene::Grid(self.map(|&ch| Glyph::new(ch, Style::Plain)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_scene() {
+        let Scene::Grid(glyphs) = Grid::parse("ab\ncd").visualize() else {
+            panic!("expected a grid scene");
+        };
+        assert_eq!(glyphs[Point::new(1, 0)], Glyph::new('c', Style::Plain));
+        assert_eq!((glyphs.width(), glyphs.height()), (2, 2));
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let mut steps = Vec::new();
+        steps.checkpoint(Checkpoint::new("start").var("total", 0).var("done", false));
+        assert_eq!(steps[0].label, "start");
+        assert_eq!(steps[0].scene, None);
+        assert_eq!(
+            steps[0].variables,
+            vec![
+                (String::from("total"), String::from("0")),
+                (String::from("done"), String::from("false"))
+            ]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * C interface to the AoC 2023 solvers (aoc-ffi).
+ *
+ * Link against the cdylib produced by `cargo build -p aoc-ffi --release`
+ * (libaoc_ffi.so / libaoc_ffi.dylib / aoc_ffi.dll).
+ */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_ERR_NULL_POINTER -1
+#define AOC_ERR_INVALID_UTF8 -2
+#define AOC_ERR_SOLVE -3
+#define AOC_ERR_BUFFER_TOO_SMALL -4
+#define AOC_ERR_PANIC -5
+
+/*
+ * Solve `part` of `day` for `len` bytes of UTF-8 input.
+ *
+ * `*out_len` is the capacity of `out_buf` on entry and the number of bytes
+ * written on exit. Output is not NUL terminated. On AOC_ERR_BUFFER_TOO_SMALL
+ * nothing is written and `*out_len` holds the required capacity. On
+ * AOC_ERR_SOLVE the error message is written instead, truncated if need be
+ * at a character boundary so it is still valid UTF-8.
+ * All buffers remain owned by the caller.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t len,
+                  uint8_t *out_buf, size_t *out_len);
+
+#endif /* AOC_H */
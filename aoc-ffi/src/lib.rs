@@ -0,0 +1,155 @@
+// C ABI for the solvers, see `include/aoc.h` for the matching declarations.
+//
+// Conventions:
+// - the caller owns every buffer; nothing allocated here crosses the boundary
+// - input is UTF-8 bytes, not NUL terminated (`len` bytes are read)
+// - `out_len` is in/out: capacity of `out_buf` on entry, length of the
+//   written text on exit (again not NUL terminated)
+// - the return value is one of the `AOC_*` codes below
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::str;
+
+// the answer was written to `out_buf`
+pub const AOC_OK: i32 = 0;
+// `out_buf` or `out_len` was null, or `input_ptr` was null with a non-zero `len`
+pub const AOC_ERR_NULL_POINTER: i32 = -1;
+// the input was not valid UTF-8
+pub const AOC_ERR_INVALID_UTF8: i32 = -2;
+// the solver failed; its error message was written (truncated to fit, at a
+// character boundary so it stays UTF-8) to `out_buf`
+pub const AOC_ERR_SOLVE: i32 = -3;
+// the answer did not fit; `out_len` holds the required capacity and nothing was written
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = -4;
+// the solver panicked; the unwind was caught at the boundary
+pub const AOC_ERR_PANIC: i32 = -5;
+
+/// Solve `part` of `day` for the given input.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes (it may be null only when
+/// `len` is 0), `out_len` must point to a writable `usize`, and `out_buf`
+/// must point to at least `*out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if (input_ptr.is_null() && len != 0) || out_buf.is_null() || out_len.is_null() {
+        return AOC_ERR_NULL_POINTER;
+    }
+
+    let input = if len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(input_ptr, len)
+    };
+    let Ok(input) = str::from_utf8(input) else {
+        return AOC_ERR_INVALID_UTF8;
+    };
+
+    let capacity = *out_len;
+    let (code, text) = match panic::catch_unwind(|| aoc_solver::solve(day, part, input)) {
+        Ok(Ok(answer)) => (AOC_OK, answer),
+        Ok(Err(err)) => (AOC_ERR_SOLVE, err.to_string()),
+        Err(_) => (AOC_ERR_PANIC, String::from("solver panicked")),
+    };
+
+    let bytes = text.as_bytes();
+    let written = if code == AOC_OK {
+        // an answer is never truncated
+        if bytes.len() > capacity {
+            *out_len = bytes.len();
+            return AOC_ERR_BUFFER_TOO_SMALL;
+        }
+        bytes.len()
+    } else {
+        // back off to the start of a character rather than split one
+        let mut end = bytes.len().min(capacity);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        end
+    };
+
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out_buf, written);
+    *out_len = written;
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &[u8], capacity: usize) -> (i32, String, usize) {
+        let mut out = vec![0u8; capacity];
+        let mut out_len = capacity;
+        let code = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                &mut out_len,
+            )
+        };
+        let text = String::from_utf8_lossy(&out[..out_len.min(capacity)]).to_string();
+        (code, text, out_len)
+    }
+
+    #[test]
+    fn test_solve_ok() {
+        let input = std::fs::read("../Day-2/test-1.txt").unwrap();
+        assert_eq!(call(2, 1, &input, 64), (AOC_OK, String::from("8"), 1));
+        assert_eq!(call(2, 2, &input, 64), (AOC_OK, String::from("2286"), 4));
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let input = std::fs::read("../Day-2/test-1.txt").unwrap();
+        let (code, _, out_len) = call(2, 2, &input, 2);
+        assert_eq!(code, AOC_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(out_len, 4);
+    }
+
+    #[test]
+    fn test_solve_error_is_truncated() {
+        let (code, text, out_len) = call(25, 1, b"", 3);
+        assert_eq!(code, AOC_ERR_SOLVE);
+        assert_eq!(out_len, 3);
+        assert_eq!(text, "day");
+    }
+
+    #[test]
+    fn test_solve_error_truncated_at_char() {
+        let (code, text, _) = call(7, 1, "32T3é 765".as_bytes(), 64);
+        assert_eq!(code, AOC_ERR_SOLVE);
+        let at = text.find('é').unwrap();
+        // a cut inside the é drops all of it
+        let (code, cut, out_len) = call(7, 1, "32T3é 765".as_bytes(), at + 1);
+        assert_eq!(code, AOC_ERR_SOLVE);
+        assert_eq!(out_len, at);
+        assert_eq!(cut, text[..at]);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let (code, _, _) = call(2, 1, &[0xff, 0xfe], 64);
+        assert_eq!(code, AOC_ERR_INVALID_UTF8);
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let mut out_len = 0;
+        let code = unsafe { aoc_solve(2, 1, ptr::null(), 5, ptr::null_mut(), &mut out_len) };
+        assert_eq!(code, AOC_ERR_NULL_POINTER);
+    }
+}
@@ -0,0 +1,71 @@
+use aoc_common::shuffle::Shuffler;
+
+/// A walk through numbered states that runs `tail` steps before entering a
+/// loop of `length` states, the shape every cycle-finding day boils down to.
+#[derive(Debug, Clone)]
+pub struct Walk {
+    seed: u64,
+    tail: usize,
+    length: usize,
+}
+
+impl Walk {
+    pub fn new(seed: u64) -> Self {
+        Walk {
+            seed,
+            tail: 100,
+            length: 50,
+        }
+    }
+
+    /// Steps taken before the first repeated state.
+    pub fn tail(mut self, tail: usize) -> Self {
+        self.tail = tail;
+        self
+    }
+
+    /// States in the loop.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length.max(1);
+        self
+    }
+
+    /// Each state's successor, the walk starting from state 0; the other
+    /// states are numbered at random so the order gives nothing away.
+    pub fn build(&self) -> Vec<usize> {
+        let mut rng = Shuffler::new(self.seed);
+        let states = self.tail + self.length;
+        let mut order: Vec<usize> = (1..states).collect();
+        rng.shuffle(&mut order);
+        order.insert(0, 0);
+
+        let mut next = vec![0; states];
+        for (i, &state) in order.iter().enumerate() {
+            next[state] = order.get(i + 1).copied().unwrap_or(order[self.tail]);
+        }
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::cycle_cache::{find_period, Period};
+
+    #[test]
+    fn test_walk() {
+        for (tail, length) in [(0, 1), (3, 1), (100, 50), (7, 2000)] {
+            let next = Walk::new(9).tail(tail).length(length).build();
+            assert_eq!(next.len(), tail + length);
+            let period = find_period(&0, next.len(), |&state| next[state], |&state| state);
+            assert_eq!(
+                period,
+                Some(Period {
+                    start: tail,
+                    length
+                })
+            );
+        }
+        assert_eq!(Walk::new(9).build(), Walk::new(9).build());
+    }
+}
@@ -0,0 +1,121 @@
+use aoc_common::shuffle::Shuffler;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+// two words sharing a letter, which a left-to-right replace gets wrong
+const OVERLAPS: [&str; 8] = [
+    "oneight",
+    "twone",
+    "threeight",
+    "fiveight",
+    "sevenine",
+    "eightwo",
+    "eighthree",
+    "nineight",
+];
+// letters in none of the words, so filler never spells a digit by accident
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+/// A calibration document: lines of letters with digits among them, some
+/// spelled out.
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    seed: u64,
+    lines: usize,
+    length: usize,
+    words: usize,
+    overlaps: usize,
+}
+
+impl Calibration {
+    pub fn new(seed: u64) -> Self {
+        Calibration {
+            seed,
+            lines: 1000,
+            length: 8,
+            words: 50,
+            overlaps: 10,
+        }
+    }
+
+    /// How many lines to write.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// The most pieces (filler, digits, words) on one line.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length.max(1);
+        self
+    }
+
+    /// The percentage of digits spelled out; at 0 both parts agree.
+    pub fn words(mut self, percent: usize) -> Self {
+        self.words = percent.min(100);
+        self
+    }
+
+    /// The percentage of spelled-out digits written as two overlapping words.
+    pub fn overlaps(mut self, percent: usize) -> Self {
+        self.overlaps = percent.min(100);
+        self
+    }
+
+    pub fn build(&self) -> String {
+        let mut rng = Shuffler::new(self.seed);
+        let mut out = String::new();
+        for _ in 0..self.lines {
+            let pieces = 1 + rng.below(self.length);
+            // part 1 needs a literal digit on every line
+            let digit_at = rng.below(pieces);
+            for i in 0..pieces {
+                match rng.below(3) {
+                    _ if i == digit_at => out.push(digit(&mut rng)),
+                    0 => {
+                        for _ in 0..1 + rng.below(4) {
+                            out.push(char::from(FILLER[rng.below(FILLER.len())]));
+                        }
+                    }
+                    _ if rng.below(100) >= self.words => out.push(digit(&mut rng)),
+                    _ if rng.below(100) < self.overlaps => {
+                        out.push_str(OVERLAPS[rng.below(OVERLAPS.len())]);
+                    }
+                    _ => out.push_str(WORDS[rng.below(WORDS.len())]),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn digit(rng: &mut Shuffler) -> char {
+    char::from(b'1' + rng.below(9) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration() {
+        let input = Calibration::new(1).lines(200).build();
+        assert_eq!(input.lines().count(), 200);
+        assert_eq!(input, Calibration::new(1).lines(200).build());
+        assert_ne!(input, Calibration::new(2).lines(200).build());
+        assert!(trebuchet::part1(&input).is_ok());
+        assert!(trebuchet::part2(&input).is_ok());
+
+        let digits = Calibration::new(1).lines(200).words(0).build();
+        assert!(!WORDS.iter().any(|word| digits.contains(word)));
+        assert_eq!(
+            trebuchet::part1(&digits).unwrap(),
+            trebuchet::part2(&digits).unwrap()
+        );
+
+        let words = Calibration::new(1).words(100).overlaps(100).build();
+        assert!(OVERLAPS.iter().any(|pair| words.contains(pair)));
+    }
+}
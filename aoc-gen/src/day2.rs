@@ -0,0 +1,84 @@
+use aoc_common::shuffle::Shuffler;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// A record of cube games: rounds of cubes drawn from a bag, a game per line.
+#[derive(Debug, Clone)]
+pub struct Games {
+    seed: u64,
+    games: usize,
+    rounds: usize,
+    cubes: usize,
+}
+
+impl Games {
+    pub fn new(seed: u64) -> Self {
+        Games {
+            seed,
+            games: 100,
+            rounds: 6,
+            cubes: 20,
+        }
+    }
+
+    /// How many games to write, numbered from 1.
+    pub fn games(mut self, games: usize) -> Self {
+        self.games = games;
+        self
+    }
+
+    /// The most rounds in one game.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds.max(1);
+        self
+    }
+
+    /// The most cubes of one colour in a round; the bag holds 12 to 14 of
+    /// each, so raising this leaves fewer games possible.
+    pub fn cubes(mut self, cubes: usize) -> Self {
+        self.cubes = cubes.max(1);
+        self
+    }
+
+    pub fn build(&self) -> String {
+        let mut rng = Shuffler::new(self.seed);
+        let mut out = String::new();
+        for id in 1..=self.games {
+            let rounds: Vec<String> = (0..1 + rng.below(self.rounds))
+                .map(|_| {
+                    let mut colors = COLORS;
+                    rng.shuffle(&mut colors);
+                    colors[..1 + rng.below(COLORS.len())]
+                        .iter()
+                        .map(|color| format!("{} {}", 1 + rng.below(self.cubes), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            out.push_str(&format!("Game {}: {}\n", id, rounds.join("; ")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_games() {
+        let input = Games::new(1).games(50).build();
+        assert_eq!(input.lines().count(), 50);
+        assert_eq!(input, Games::new(1).games(50).build());
+        let games = bag_game::parse_data(input.lines().map(String::from).collect()).unwrap();
+        assert_eq!(games.len(), 50);
+        assert!(games.iter().all(|game| game.rounds.len() <= 6));
+
+        // every game is possible when no colour outnumbers the bag
+        let small = Games::new(1).games(50).cubes(12).build();
+        let all: u64 = (1..=50).sum();
+        assert_eq!(bag_game::part1(&small).unwrap(), all);
+        let large = Games::new(1).games(50).cubes(100).build();
+        assert!(bag_game::part1(&large).unwrap() < all);
+    }
+}
@@ -0,0 +1,124 @@
+use aoc_common::shuffle::Shuffler;
+
+// the symbols Day 3 knows; '*' is the one that can be a gear
+const SYMBOLS: [char; 3] = ['$', '+', '#'];
+
+/// An engine schematic: a grid of numbers and symbols on a field of dots.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    seed: u64,
+    width: usize,
+    height: usize,
+    numbers: usize,
+    symbols: usize,
+    digits: usize,
+    gears: usize,
+}
+
+impl Schematic {
+    pub fn new(seed: u64) -> Self {
+        Schematic {
+            seed,
+            width: 140,
+            height: 140,
+            numbers: 8,
+            symbols: 3,
+            digits: 3,
+            gears: 40,
+        }
+    }
+
+    /// The grid's size in cells.
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// The percentage of cells a number starts in.
+    pub fn numbers(mut self, percent: usize) -> Self {
+        self.numbers = percent.min(100);
+        self
+    }
+
+    /// The percentage of cells holding a symbol; the two densities share
+    /// the cells, so together they top out at 100.
+    pub fn symbols(mut self, percent: usize) -> Self {
+        self.symbols = percent.min(100);
+        self
+    }
+
+    /// The most digits in one number.
+    pub fn digits(mut self, digits: usize) -> Self {
+        self.digits = digits.max(1);
+        self
+    }
+
+    /// The percentage of symbols that are `*`, the only ones that can be gears.
+    pub fn gears(mut self, percent: usize) -> Self {
+        self.gears = percent.min(100);
+        self
+    }
+
+    pub fn build(&self) -> String {
+        let mut rng = Shuffler::new(self.seed);
+        let mut out = String::new();
+        for _ in 0..self.height {
+            let mut col = 0;
+            while col < self.width {
+                let roll = rng.below(100);
+                let len = 1 + rng.below(self.digits);
+                if roll < self.numbers && col + len <= self.width {
+                    out.push(char::from(b'1' + rng.below(9) as u8));
+                    for _ in 1..len {
+                        out.push(char::from(b'0' + rng.below(10) as u8));
+                    }
+                    col += len;
+                    // a dot keeps the next number from running into this one
+                    if col < self.width {
+                        out.push('.');
+                        col += 1;
+                    }
+                    continue;
+                }
+                out.push(if roll < self.numbers + self.symbols {
+                    if rng.below(100) < self.gears {
+                        '*'
+                    } else {
+                        SYMBOLS[rng.below(SYMBOLS.len())]
+                    }
+                } else {
+                    '.'
+                });
+                col += 1;
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schematic() {
+        let input = Schematic::new(1).size(40, 30).build();
+        assert!(input.lines().all(|line| line.len() == 40));
+        assert_eq!(input.lines().count(), 30);
+        assert_eq!(input, Schematic::new(1).size(40, 30).build());
+
+        let schematic: map_reader::Schematic = input.parse().unwrap();
+        assert!(!schematic.numbers.is_empty());
+        assert!(!schematic.symbols.is_empty());
+
+        let small = Schematic::new(1).size(40, 30).digits(1).gears(100).build();
+        let schematic: map_reader::Schematic = small.parse().unwrap();
+        assert!(schematic.numbers.iter().all(|number| number.value() < 10));
+        assert!(schematic.symbols.iter().all(|symbol| symbol.symbol == '*'));
+
+        let empty = Schematic::new(1).size(40, 30).numbers(0).symbols(0).build();
+        assert!(empty.lines().all(|line| line.chars().all(|ch| ch == '.')));
+    }
+}
@@ -0,0 +1,8 @@
+// Seeded generators of puzzle-like inputs, for benchmarks, fuzzing and
+// exercises. Each builder starts from the real puzzle's shape; its knobs make
+// the input bigger, denser or harder, and the same knobs with the same seed
+// write the same input on every machine.
+pub mod cycle;
+pub mod day1;
+pub mod day2;
+pub mod day3;
@@ -0,0 +1,317 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Error, FnArg, ItemFn, LitInt, PathArguments, ReturnType, Type};
+
+/// Register a solution with the runner's registry:
+///
+/// ```ignore
+/// #[aoc(day = 2, part = 1)]
+/// pub fn part1_with(input: &str, mode: ParseMode) -> Result<u64> { ... }
+/// ```
+///
+/// A day of an earlier year says which, as in `#[aoc(year = 2022, day = 1,
+/// part = 1)]`; without one it's a day of `aoc_common::input::YEAR`.
+///
+/// What the function is handed follows from its parameters: `&str` is the
+/// whole input, `Vec<String>` or `&[String]` its lines, `ParseMode` the
+/// runner's `--lenient` choice and `&CancelToken` the token its long loops
+/// should poll. It returns its answer, or a `Result` of it, as anything
+/// `Display`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("year") {
+            year = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("day") {
+            day = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("part") {
+            part = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `year`, `day` or `part`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+    expand(year, day, part, function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// what one parameter of a solution is handed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Text,
+    Lines,
+    LineSlice,
+    Mode,
+    Cancel,
+}
+
+fn expand(
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+    part: Option<LitInt>,
+    function: ItemFn,
+) -> syn::Result<TokenStream2> {
+    let year = match year {
+        Some(lit) => match lit.base10_parse::<u32>()? {
+            year @ FIRST_YEAR.. => quote!(#year),
+            _ => {
+                return Err(Error::new_spanned(
+                    lit,
+                    format!("Advent of Code started in {}", FIRST_YEAR),
+                ))
+            }
+        },
+        None => quote!(::aoc_common::input::YEAR),
+    };
+    let day = number(day, "day", 25)?;
+    let part = number(part, "part", 2)?;
+    if !function.sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &function.sig.generics,
+            "a solution can't be generic",
+        ));
+    }
+
+    let args = function
+        .sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Typed(arg) => match classify(&arg.ty) {
+                Some(Input::Text) => Ok(quote!(input)),
+                Some(Input::Lines) => Ok(quote!(input.lines().map(String::from).collect())),
+                Some(Input::LineSlice) => Ok(quote!(&input
+                    .lines()
+                    .map(String::from)
+                    .collect::<Vec<String>>())),
+                Some(Input::Mode) => Ok(quote!(mode)),
+                Some(Input::Cancel) => Ok(quote!(cancel)),
+                None => Err(Error::new_spanned(
+                    &arg.ty,
+                    "a solution takes `&str`, `Vec<String>`, `&[String]`, `ParseMode` or \
+                     `&CancelToken`",
+                )),
+            },
+            FnArg::Receiver(arg) => Err(Error::new_spanned(arg, "a solution can't be a method")),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &function.sig.ident;
+    let answer = match &function.sig.output {
+        ReturnType::Default => {
+            return Err(Error::new_spanned(
+                &function.sig,
+                "a solution must return its answer",
+            ))
+        }
+        ReturnType::Type(_, ty) if is_result(ty) => quote!(#name(#(#args),*)?),
+        ReturnType::Type(..) => quote!(#name(#(#args),*)),
+    };
+
+    Ok(quote! {
+        #function
+
+        const _: () = {
+            #[allow(unused_variables)]
+            fn solve(
+                input: &str,
+                mode: ::aoc_common::parse::ParseMode,
+                cancel: &::aoc_common::cancel::CancelToken,
+            ) -> ::color_eyre::eyre::Result<String> {
+                Ok(#answer.to_string())
+            }
+
+            ::aoc_common::registry::inventory::submit! {
+                ::aoc_common::registry::Solution { year: #year, day: #day, part: #part, solve }
+            }
+        };
+    })
+}
+
+// the earliest `year = N` there are puzzles for
+const FIRST_YEAR: u32 = 2015;
+
+// `day = N` or `part = N`, between 1 and `max`
+fn number(lit: Option<LitInt>, name: &str, max: u32) -> syn::Result<u32> {
+    let Some(lit) = lit else {
+        return Err(Error::new(
+            Span::call_site(),
+            format!("missing `{} = N`", name),
+        ));
+    };
+    match lit.base10_parse()? {
+        n @ 1.. if n <= max => Ok(n),
+        _ => Err(Error::new_spanned(
+            lit,
+            format!("{} must be between 1 and {}", name, max),
+        )),
+    }
+}
+
+fn classify(ty: &Type) -> Option<Input> {
+    match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => Some(Input::Text),
+            Type::Slice(slice) if is_string(&slice.elem) => Some(Input::LineSlice),
+            Type::Path(path) if path.path.segments.last()?.ident == "CancelToken" => {
+                Some(Input::Cancel)
+            }
+            _ => None,
+        },
+        Type::Path(path) => {
+            let last = path.path.segments.last()?;
+            match &last.arguments {
+                PathArguments::None if last.ident == "ParseMode" => Some(Input::Mode),
+                PathArguments::AngleBracketed(args) if last.ident == "Vec" => {
+                    match args.args.first()? {
+                        syn::GenericArgument::Type(ty) if is_string(ty) => Some(Input::Lines),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_string(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "String"))
+}
+
+fn is_result(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "Result"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&parse_quote!(&str)), Some(Input::Text));
+        assert_eq!(classify(&parse_quote!(&'static str)), Some(Input::Text));
+        assert_eq!(classify(&parse_quote!(Vec<String>)), Some(Input::Lines));
+        assert_eq!(classify(&parse_quote!(&[String])), Some(Input::LineSlice));
+        assert_eq!(
+            classify(&parse_quote!(aoc_common::parse::ParseMode)),
+            Some(Input::Mode)
+        );
+        assert_eq!(
+            classify(&parse_quote!(&aoc_common::cancel::CancelToken)),
+            Some(Input::Cancel)
+        );
+        assert_eq!(classify(&parse_quote!(CancelToken)), None);
+        assert_eq!(classify(&parse_quote!(&[u32])), None);
+        assert_eq!(classify(&parse_quote!(Vec<u32>)), None);
+        assert_eq!(classify(&parse_quote!(String)), None);
+    }
+
+    #[test]
+    fn test_expand() {
+        let day = Some(parse_quote!(2));
+        let part = Some(parse_quote!(1));
+        let function: ItemFn = parse_quote! {
+            pub fn part1_with(input: &str, mode: ParseMode) -> Result<u64> { todo!() }
+        };
+        let expanded = expand(None, day.clone(), part.clone(), function)
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("part1_with (input , mode) ?"));
+        assert!(expanded.contains(
+            "Solution { year : :: aoc_common :: input :: YEAR , day : 2u32 , part : 1u32 , solve }"
+        ));
+
+        let function: ItemFn = parse_quote!(
+            fn count(lines: Vec<String>) -> usize {
+                0
+            }
+        );
+        let expanded = expand(None, day.clone(), part.clone(), function)
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("count (input . lines () . map (String :: from) . collect ())"));
+        assert!(!expanded.contains(") ?"));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let function: ItemFn = parse_quote!(
+            fn part1(input: &str) -> u64 {
+                0
+            }
+        );
+        let err = |day: Option<LitInt>, part: Option<LitInt>, function: &ItemFn| {
+            expand(None, day, part, function.clone())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err(None, Some(parse_quote!(1)), &function),
+            "missing `day = N`"
+        );
+        assert_eq!(
+            err(Some(parse_quote!(26)), Some(parse_quote!(1)), &function),
+            "day must be between 1 and 25"
+        );
+        assert_eq!(
+            err(Some(parse_quote!(1)), Some(parse_quote!(0)), &function),
+            "part must be between 1 and 2"
+        );
+
+        let (day, part) = (Some(parse_quote!(1)), Some(parse_quote!(1)));
+        let function: ItemFn = parse_quote!(
+            fn part1(input: &[u32]) -> u64 {
+                0
+            }
+        );
+        assert!(err(day.clone(), part.clone(), &function).starts_with("a solution takes"));
+        let function: ItemFn = parse_quote!(
+            fn part1(input: &str) {}
+        );
+        assert_eq!(
+            err(day.clone(), part.clone(), &function),
+            "a solution must return its answer"
+        );
+        let function: ItemFn = parse_quote!(
+            fn part1<T>(input: &str) -> u64 {
+                0
+            }
+        );
+        assert_eq!(
+            err(day.clone(), part.clone(), &function),
+            "a solution can't be generic"
+        );
+
+        let function: ItemFn = parse_quote!(
+            fn part1(input: &str) -> u64 {
+                0
+            }
+        );
+        let expanded = expand(
+            Some(parse_quote!(2022)),
+            day.clone(),
+            part.clone(),
+            function.clone(),
+        )
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("Solution { year : 2022u32 , day : 1u32"));
+        assert_eq!(
+            expand(Some(parse_quote!(2014)), day, part, function)
+                .unwrap_err()
+                .to_string(),
+            "Advent of Code started in 2015"
+        );
+    }
+}
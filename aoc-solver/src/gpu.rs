@@ -0,0 +1,278 @@
+use aoc_common::parse::ParseMode;
+use bag_game::{Color, Game};
+use color_eyre::eyre::{bail, eyre, Result};
+use std::fmt;
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+// invocations per workgroup, as declared in the shaders
+const WORKGROUP: u32 = 64;
+
+type Kernel = fn(&Gpu, &str, ParseMode) -> Result<String>;
+
+// each part with a compute-shader implementation
+static KERNELS: &[(u32, u32, Kernel)] = &[
+    (2, 1, |gpu, input, mode| {
+        let games = games(input, mode)?;
+        let bags = gpu.minimum_bags(&games)?;
+        Ok(feasible(&games, &bags).to_string())
+    }),
+    (2, 2, |gpu, input, mode| {
+        let games = games(input, mode)?;
+        let bags = gpu.minimum_bags(&games)?;
+        Ok(power(&bags)?.to_string())
+    }),
+];
+
+// One invocation per round, raising its game's bag (red, green, blue) to the
+// round's counts; the invocations stride over the rounds, so any number of
+// them fits the dispatch limit
+const MINIMUM_BAGS: &str = r"
+struct Round {
+    game: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+@group(0) @binding(0) var<storage, read> rounds: array<Round>;
+@group(0) @binding(1) var<storage, read_write> bags: array<atomic<u32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let stride = groups.x * 64u;
+    for (var i = id.x; i < arrayLength(&rounds); i += stride) {
+        let round = rounds[i];
+        atomicMax(&bags[round.game * 3u], round.red);
+        atomicMax(&bags[round.game * 3u + 1u], round.green);
+        atomicMax(&bags[round.game * 3u + 2u], round.blue);
+    }
+}
+";
+
+/// A GPU to run compute shaders on, for `aoc run --impl gpu`.
+pub struct Gpu {
+    name: String,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl Gpu {
+    /// The fastest GPU there is, or an error when there's none to use.
+    pub fn new() -> Result<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or_else(|| eyre!("no GPU adapter is available"))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("aoc"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+            },
+            None,
+        ))?;
+        Ok(Gpu {
+            name: adapter.get_info().name,
+            device,
+            queue,
+        })
+    }
+
+    // Each game's minimum bag as red, green and blue counts, in the games'
+    // order; counts past u32::MAX stay at u32::MAX
+    fn minimum_bags(&self, games: &[Game]) -> Result<Vec<[u32; 3]>> {
+        let rounds = rounds(games);
+        if rounds.is_empty() {
+            return Ok(vec![[0; 3]; games.len()]);
+        }
+        let rounds = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("rounds"),
+                contents: bytemuck::cast_slice(&rounds),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+        let size = (games.len() * 3 * std::mem::size_of::<u32>()) as u64;
+        let bags = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("bags"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("read"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let module = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("minimum bags"),
+                source: wgpu::ShaderSource::Wgsl(MINIMUM_BAGS.into()),
+            });
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("minimum bags"),
+                layout: None,
+                module: &module,
+                entry_point: "main",
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: rounds.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: bags.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            let count = u32::try_from(rounds.size() / 16).unwrap_or(u32::MAX);
+            let limit = self.device.limits().max_compute_workgroups_per_dimension;
+            pass.dispatch_workgroups(count.div_ceil(WORKGROUP).min(limit), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&bags, 0, &read, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = read.slice(..);
+        let (sender, mapped) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        mapped.recv()??;
+        let counts: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()[..]).to_vec();
+        read.unmap();
+        Ok(counts
+            .chunks_exact(3)
+            .map(|bag| [bag[0], bag[1], bag[2]])
+            .collect())
+    }
+}
+
+impl fmt::Display for Gpu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The parts of `day` with a compute-shader implementation.
+pub fn parts(day: u32) -> Vec<u32> {
+    KERNELS
+        .iter()
+        .filter(|(d, _, _)| *d == day)
+        .map(|(_, part, _)| *part)
+        .collect()
+}
+
+/// Solve one part of one day with its compute shader.
+pub fn solve(gpu: &Gpu, day: u32, part: u32, input: &str, mode: ParseMode) -> Result<String> {
+    match KERNELS.iter().find(|(d, p, _)| *d == day && *p == part) {
+        Some((_, _, kernel)) => kernel(gpu, input, mode),
+        None => bail!("day {} part {} has no GPU implementation", day, part),
+    }
+}
+
+fn games(input: &str, mode: ParseMode) -> Result<Vec<Game>> {
+    bag_game::parse_data_with(input.lines().map(String::from).collect(), mode)
+}
+
+// Every round as [game index, red, green, blue], a color named twice in a
+// round counting twice as on the CPU
+fn rounds(games: &[Game]) -> Vec<[u32; 4]> {
+    let clamp = |count: usize| u32::try_from(count).unwrap_or(u32::MAX);
+    games
+        .iter()
+        .enumerate()
+        .flat_map(|(index, game)| {
+            game.rounds.iter().map(move |round| {
+                let mut counts = [clamp(index), 0, 0, 0];
+                for color_count in &round.0 {
+                    let slot = match color_count.color {
+                        Color::Red => 1,
+                        Color::Green => 2,
+                        Color::Blue => 3,
+                    };
+                    counts[slot] = counts[slot].saturating_add(clamp(color_count.count));
+                }
+                counts
+            })
+        })
+        .collect()
+}
+
+// The sum of the ids of the games whose bag fits in 12 red, 13 green and 14 blue
+fn feasible(games: &[Game], bags: &[[u32; 3]]) -> u64 {
+    games
+        .iter()
+        .zip(bags)
+        .filter(|(_, [red, green, blue])| *red <= 12 && *green <= 13 && *blue <= 14)
+        .map(|(game, _)| game.id as u64)
+        .sum()
+}
+
+fn power(bags: &[[u32; 3]]) -> Result<u64> {
+    bags.iter().try_fold(0u64, |total, bag| {
+        bag.iter()
+            .try_fold(1u64, |power, &count| power.checked_mul(u64::from(count)))
+            .and_then(|power| total.checked_add(power))
+            .ok_or_else(|| eyre!("the total power overflows"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds() {
+        // only a lenient run lets a color be named twice
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 1 red\nGame 7: 5 green";
+        assert!(games(input, ParseMode::Strict).is_err());
+        let games = games(input, ParseMode::Lenient).unwrap();
+        assert_eq!(rounds(&games), [[0, 4, 0, 3], [0, 2, 2, 0], [1, 0, 5, 0]]);
+        let bags = [[4, 2, 3], [0, 5, 0]];
+        assert_eq!(feasible(&games, &bags), 8);
+        assert_eq!(power(&bags).unwrap(), 24);
+        assert!(power(&[[u32::MAX; 3]]).is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(parts(2), vec![1, 2]);
+        assert!(parts(1).is_empty());
+        // machines without a GPU (most CI runners) only check the CPU side
+        let Ok(gpu) = Gpu::new() else {
+            return;
+        };
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        assert_eq!(solve(&gpu, 2, 1, &input, ParseMode::Strict).unwrap(), "8");
+        assert_eq!(
+            solve(&gpu, 2, 2, &input, ParseMode::Strict).unwrap(),
+            "2286"
+        );
+        assert!(solve(&gpu, 1, 1, &input, ParseMode::Strict).is_err());
+    }
+}
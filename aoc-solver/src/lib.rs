@@ -0,0 +1,851 @@
+use aoc_common::cancel::CancelToken;
+use aoc_common::explain::Narrative;
+use aoc_common::input::YEAR;
+use aoc_common::parallel::{par_map, shards};
+use aoc_common::parse::ParseMode;
+use aoc_common::progress::Progress;
+use aoc_common::registry::{brute_forces, solutions, BruteForce, Reduce, Solution};
+use color_eyre::eyre::{bail, eyre, Result};
+use std::any::Any;
+use std::fmt;
+// linked for the parts it registers, since nothing here names it
+use calorie_counting as _;
+
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod records;
+pub mod visuals;
+
+type Parse = fn(&str) -> Result<()>;
+type ParseOnce = fn(&str, ParseMode) -> Result<Box<dyn Any + Send>>;
+type SolveParsed = fn(&dyn Any, u32) -> Result<String>;
+type Validate = fn(&[String]) -> Vec<(usize, String)>;
+
+// each day's parsing step alone, so the runner can time it apart from solving
+static PARSERS: &[(u32, Parse)] = &[
+    (1, |input| {
+        trebuchet::parse_data(input.lines().map(String::from).collect()).map(drop)
+    }),
+    (2, |input| {
+        bag_game::parse_data(input.lines().map(String::from).collect()).map(drop)
+    }),
+    (3, |input| input.parse::<map_reader::Schematic>().map(drop)),
+    (4, |input| {
+        scratchcards::parse_data(input.lines().map(String::from).collect()).map(drop)
+    }),
+    (5, |input| input.parse::<fertilizer::Almanac>().map(drop)),
+    (7, |input| {
+        camel_cards::parse_data(input.lines().map(String::from).collect()).map(drop)
+    }),
+];
+
+// the days whose parts all start from the same parse, with how to answer a
+// part from it; Day 1's parts read its lines differently, so it isn't here
+static SHARED_PARSES: &[(u32, ParseOnce, SolveParsed)] = &[
+    (
+        2,
+        |input, mode| Ok(Box::new(bag_game::parse_data_with(lines(input), mode)?)),
+        |data, part| {
+            let games = downcast::<Vec<bag_game::Game>>(data)?;
+            match part {
+                1 => answer(bag_game::total_feasible(games)),
+                _ => answer(bag_game::total_power(games)),
+            }
+        },
+    ),
+    (
+        3,
+        |input, mode| Ok(Box::new(map_reader::parse_schematic_with(input, mode)?)),
+        |data, part| {
+            let schematic = downcast::<map_reader::Schematic>(data)?;
+            match part {
+                1 => Ok(schematic.part_sum().to_string()),
+                _ => Ok(schematic.gear_ratio_sum().to_string()),
+            }
+        },
+    ),
+    (
+        4,
+        |input, mode| Ok(Box::new(scratchcards::parse_data_with(lines(input), mode)?)),
+        |data, part| {
+            let cards = downcast::<Vec<scratchcards::Card>>(data)?;
+            match part {
+                1 => answer(scratchcards::total_points(cards)),
+                _ => answer(scratchcards::total_cards(cards)),
+            }
+        },
+    ),
+    (
+        5,
+        |input, mode| Ok(Box::new(fertilizer::parse_almanac_with(input, mode)?)),
+        |data, part| {
+            let almanac = downcast::<fertilizer::Almanac>(data)?;
+            match part {
+                1 => answer(fertilizer::lowest_location(almanac)),
+                _ => answer(fertilizer::lowest_range_location(almanac)),
+            }
+        },
+    ),
+    (
+        7,
+        |input, mode| Ok(Box::new(camel_cards::parse_data_with(lines(input), mode)?)),
+        |data, part| {
+            let plays = downcast::<Vec<camel_cards::Play>>(data)?;
+            let rules = match part {
+                1 => camel_cards::hand::Rules::Jacks,
+                _ => camel_cards::hand::Rules::Jokers,
+            };
+            answer(camel_cards::total_winnings(plays, rules))
+        },
+    ),
+];
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+fn downcast<T: 'static>(data: &dyn Any) -> Result<&T> {
+    data.downcast_ref()
+        .ok_or_else(|| eyre!("the parsed input is of another day"))
+}
+
+fn answer(answer: Result<impl fmt::Display>) -> Result<String> {
+    Ok(answer?.to_string())
+}
+
+// how many shards a brute-force search is cut into: enough for progress to
+// move smoothly and for no thread to sit idle at the end
+const SHARDS: u64 = 1024;
+
+// each day's structural checks, run before solving
+static VALIDATORS: &[(u32, Validate)] = &[
+    (1, trebuchet::validate),
+    (2, bag_game::validate),
+    (3, map_reader::validate),
+    (4, scratchcards::validate),
+    (5, fertilizer::validate),
+    (7, camel_cards::validate),
+];
+
+/// Something structurally off about one line of a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// 1-based, as in an editor.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// What [`solve_with`] does with an input holding nothing but whitespace.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Empty {
+    /// Fail with an "input is empty" diagnostic
+    #[default]
+    Reject,
+    /// Let the day answer it, which every day does with 0
+    Lenient,
+}
+
+/// How [`solve_with`] treats an input that isn't quite right.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub empty: Empty,
+    /// Whether a malformed line stops the day or is skipped
+    pub mode: ParseMode,
+}
+
+/// Every year with at least one implemented day, in order.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = solutions().iter().map(|solution| solution.year).collect();
+    years.dedup();
+    years
+}
+
+/// Every day of [`YEAR`] with at least one implemented part, in order.
+pub fn days() -> Vec<u32> {
+    days_in(YEAR)
+}
+
+/// [`days`] of any year.
+pub fn days_in(year: u32) -> Vec<u32> {
+    let mut days: Vec<u32> = solutions()
+        .iter()
+        .filter(|solution| solution.year == year)
+        .map(|solution| solution.day)
+        .collect();
+    days.dedup();
+    days
+}
+
+/// The implemented parts of `day`, in order.
+pub fn parts(day: u32) -> Vec<u32> {
+    parts_in(YEAR, day)
+}
+
+/// [`parts`] of a day of any year.
+pub fn parts_in(year: u32, day: u32) -> Vec<u32> {
+    solutions()
+        .iter()
+        .filter(|solution| solution.year == year && solution.day == day)
+        .map(|solution| solution.part)
+        .collect()
+}
+
+/// Solve one part of one day, returning the answer as it would be submitted.
+/// An empty input, or a malformed line, is an error.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String> {
+    solve_with(day, part, input, Options::default())
+}
+
+/// [`solve`], choosing what an empty input and malformed lines mean.
+pub fn solve_with(day: u32, part: u32, input: &str, options: Options) -> Result<String> {
+    solve_cancellable(day, part, input, options, &CancelToken::new())
+}
+
+/// [`solve_with`], stopping early with [`Cancelled`](aoc_common::cancel::Cancelled)
+/// once `cancel` is: before starting, or wherever the day's solver polls it.
+pub fn solve_cancellable(
+    day: u32,
+    part: u32,
+    input: &str,
+    options: Options,
+    cancel: &CancelToken,
+) -> Result<String> {
+    solve_in(YEAR, day, part, input, options, cancel)
+}
+
+/// [`solve_cancellable`] for a day of any year.
+pub fn solve_in(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: &str,
+    options: Options,
+    cancel: &CancelToken,
+) -> Result<String> {
+    // the other years name theirs, so their errors don't read as this year's
+    let name = match year {
+        YEAR => format!("day {}", day),
+        _ => format!("{} day {}", year, day),
+    };
+    let Some(Solution { solve, .. }) = solutions()
+        .into_iter()
+        .find(|solution| solution.year == year && solution.day == day && solution.part == part)
+    else {
+        bail!("{} part {} is not implemented", name, part);
+    };
+    if options.empty == Empty::Reject && input.trim().is_empty() {
+        bail!("{} input is empty", name);
+    }
+    cancel.check()?;
+    solve(input, options.mode, cancel)
+}
+
+/// The parts of `day` that only a brute-force search solves, for
+/// `--brute-force`.
+pub fn brute_force_parts(day: u32) -> Vec<u32> {
+    let clever = parts(day);
+    brute_forces()
+        .iter()
+        .filter(|search| search.day == day && !clever.contains(&search.part))
+        .map(|search| search.part)
+        .collect()
+}
+
+/// How many candidates the brute-force search of one part tries on `input`.
+pub fn search_space(day: u32, part: u32, input: &str) -> Result<u64> {
+    (find_brute_force(day, part)?.space)(input)
+}
+
+/// Solve one part with its brute-force search, sharded over every core.
+/// `progress`, made for [`search_space`] candidates, counts those tried, and
+/// `cancel` is checked before each shard.
+pub fn brute_force(
+    day: u32,
+    part: u32,
+    input: &str,
+    progress: &Progress,
+    cancel: &CancelToken,
+) -> Result<String> {
+    let search = find_brute_force(day, part)?;
+    let shards = shards(0..progress.total(), SHARDS);
+    let found = par_map(&shards, |shard| {
+        if cancel.is_cancelled() {
+            return Ok(None);
+        }
+        let found = (search.search)(input, shard.clone());
+        progress.advance(shard.end - shard.start);
+        found
+    });
+    cancel.check()?;
+    let found = found
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten();
+    let answer = match search.reduce {
+        Reduce::Min => found.min(),
+        Reduce::Max => found.max(),
+        Reduce::Sum => Some(found.sum()),
+    };
+    match answer {
+        Some(answer) => Ok(answer.to_string()),
+        None => bail!("no candidate for day {} part {} is an answer", day, part),
+    }
+}
+
+fn find_brute_force(day: u32, part: u32) -> Result<&'static BruteForce> {
+    match brute_forces()
+        .into_iter()
+        .find(|search| search.day == day && search.part == part)
+    {
+        Some(search) => Ok(search),
+        None => bail!("day {} part {} has no brute-force search", day, part),
+    }
+}
+
+/// A day's input parsed once, for [`solve_parsed`] to answer each part from.
+pub struct Parsed {
+    day: u32,
+    data: Box<dyn Any + Send>,
+}
+
+impl fmt::Debug for Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Parsed").field("day", &self.day).finish()
+    }
+}
+
+/// Parse `day`'s input for all its parts at once, with the checks
+/// [`solve_with`] makes first; `None` for a day whose parts each read the
+/// input their own way.
+pub fn parse_once(day: u32, input: &str, options: Options) -> Result<Option<Parsed>> {
+    let Some((_, parse, _)) = SHARED_PARSES.iter().find(|(d, _, _)| *d == day) else {
+        return Ok(None);
+    };
+    if options.empty == Empty::Reject && input.trim().is_empty() {
+        bail!("day {} input is empty", day);
+    }
+    Ok(Some(Parsed {
+        day,
+        data: parse(input, options.mode)?,
+    }))
+}
+
+/// One part of the day `parsed` holds, as [`solve_cancellable`] would answer
+/// it from the text.
+pub fn solve_parsed(parsed: &Parsed, part: u32, cancel: &CancelToken) -> Result<String> {
+    if !parts(parsed.day).contains(&part) {
+        bail!("day {} part {} is not implemented", parsed.day, part);
+    }
+    let Some((_, _, solve)) = SHARED_PARSES.iter().find(|(d, _, _)| *d == parsed.day) else {
+        bail!("day {} has no shared parse", parsed.day);
+    };
+    cancel.check()?;
+    solve(parsed.data.as_ref(), part)
+}
+
+/// Parse `day`'s input without solving anything.
+pub fn parse(day: u32, input: &str) -> Result<()> {
+    match PARSERS.iter().find(|(d, _)| *d == day) {
+        Some((_, parse)) => parse(input),
+        None => bail!("day {} has no separate parsing step", day),
+    }
+}
+
+/// Check `day`'s input for structural problems, such as a truncated line,
+/// before solving; empty for a day without checks.
+pub fn validate(day: u32, input: &str) -> Vec<Finding> {
+    let Some((_, validate)) = VALIDATORS.iter().find(|(d, _)| *d == day) else {
+        return Vec::new();
+    };
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    validate(&lines)
+        .into_iter()
+        .map(|(line, message)| Finding { line, message })
+        .collect()
+}
+
+/// How `day`'s solver reaches its answers, step by step, for `aoc run --explain`.
+pub fn explain(day: u32, input: &str) -> Result<Narrative> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    match day {
+        1 => Ok(trebuchet::explain::explain(&lines)),
+        2 => Ok(bag_game::explain::explain(&bag_game::parse_data(lines)?)),
+        3 => Ok(map_reader::explain::explain(&input.parse()?)),
+        4 => Ok(scratchcards::explain::explain(&scratchcards::parse_data(
+            lines,
+        )?)),
+        5 => Ok(fertilizer::explain::explain(&input.parse()?)),
+        7 => Ok(camel_cards::explain::explain(&camel_cards::parse_data(
+            lines,
+        )?)),
+        _ => bail!("day {} has nothing to explain", day),
+    }
+}
+
+/// `day`'s input rewritten from `seed` so it can be shared: different text,
+/// the same answers to every part.
+pub fn anonymize(day: u32, input: &str, seed: u64) -> Result<String> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let lines = match day {
+        1 => trebuchet::anonymize::anonymize(&lines, seed),
+        2 => bag_game::anonymize::anonymize(&lines, seed)?,
+        3 => map_reader::anonymize::anonymize(&lines, seed),
+        4 => scratchcards::anonymize::anonymize(&lines, seed)?,
+        5 => fertilizer::anonymize::anonymize(&lines, seed)?,
+        7 => camel_cards::anonymize::anonymize(&lines, seed)?,
+        _ => bail!("day {} has no anonymizer", day),
+    };
+    Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        assert_eq!(solve(2, 1, &input).unwrap(), "8");
+        assert_eq!(solve(2, 2, &input).unwrap(), "2286");
+
+        let input = std::fs::read_to_string("../Day-1/test-1.txt").unwrap();
+        assert_eq!(solve(1, 1, &input).unwrap(), "142");
+
+        let input = std::fs::read_to_string("../Day-1/test-2.txt").unwrap();
+        assert_eq!(solve(1, 2, &input).unwrap(), "281");
+
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        assert_eq!(solve(3, 1, &input).unwrap(), "4361");
+        assert_eq!(solve(3, 2, &input).unwrap(), "467835");
+
+        let input = std::fs::read_to_string("../Day-4/test-1.txt").unwrap();
+        assert_eq!(solve(4, 1, &input).unwrap(), "13");
+        assert_eq!(solve(4, 2, &input).unwrap(), "30");
+
+        let input = std::fs::read_to_string("../Day-5/test-1.txt").unwrap();
+        assert_eq!(solve(5, 1, &input).unwrap(), "35");
+        assert_eq!(solve(5, 2, &input).unwrap(), "46");
+
+        let input = std::fs::read_to_string("../Day-7/test-1.txt").unwrap();
+        assert_eq!(solve(7, 1, &input).unwrap(), "6440");
+        assert_eq!(solve(7, 2, &input).unwrap(), "5905");
+    }
+
+    #[test]
+    fn test_solve_cancellable() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let cancel = CancelToken::new();
+        let solve = |cancel| solve_cancellable(2, 1, &input, Options::default(), cancel);
+        assert_eq!(solve(&cancel).unwrap(), "8");
+        cancel.cancel();
+        let err = solve(&cancel).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<aoc_common::cancel::Cancelled>(),
+            Some(&aoc_common::cancel::Cancelled::Interrupted)
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let narrative = explain(2, &input).unwrap();
+        assert!(narrative
+            .to_string()
+            .contains("part 2: the powers add up to 2286"));
+
+        let input = std::fs::read_to_string("../Day-4/test-1.txt").unwrap();
+        let narrative = explain(4, &input).unwrap();
+        assert!(narrative
+            .to_string()
+            .contains("part 2: the copies add up to 30 cards"));
+
+        let input = std::fs::read_to_string("../Day-5/test-1.txt").unwrap();
+        let narrative = explain(5, &input).unwrap();
+        assert!(narrative
+            .to_string()
+            .contains("part 2: the lowest location of a seed range is 46"));
+
+        let input = std::fs::read_to_string("../Day-7/test-1.txt").unwrap();
+        let narrative = explain(7, &input).unwrap();
+        assert!(narrative
+            .to_string()
+            .contains("part 2: with jokers they add up to 5905"));
+        assert!(explain(25, &input).is_err());
+    }
+
+    #[test]
+    fn test_solvers() {
+        use aoc_common::registry::{Day, Solver};
+        use bag_game::BagGame;
+        use map_reader::MapReader;
+        use trebuchet::Trebuchet;
+
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let registered = Day { year: YEAR, day: 2 };
+        assert_eq!(
+            BagGame.part1(&input).unwrap(),
+            registered.part1(&input).unwrap()
+        );
+        assert_eq!(
+            BagGame.part2(&input).unwrap(),
+            registered.part2(&input).unwrap()
+        );
+
+        let input = std::fs::read_to_string("../Day-1/test-2.txt").unwrap();
+        let registered = Day { year: YEAR, day: 1 };
+        assert_eq!(
+            Trebuchet.part2(&input).unwrap(),
+            registered.part2(&input).unwrap()
+        );
+        assert!(aoc_common::registry::days().contains(&registered));
+
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let registered = Day { year: YEAR, day: 3 };
+        assert_eq!(
+            MapReader.part2(&input).unwrap(),
+            registered.part2(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let shared = anonymize(2, &input, 7).unwrap();
+        assert_ne!(shared, input);
+        assert_eq!(solve(2, 1, &shared).unwrap(), "8");
+        assert_eq!(solve(2, 2, &shared).unwrap(), "2286");
+
+        let input = std::fs::read_to_string("../Day-4/test-1.txt").unwrap();
+        let shared = anonymize(4, &input, 7).unwrap();
+        assert_ne!(shared, input);
+        assert_eq!(solve(4, 1, &shared).unwrap(), "13");
+        assert_eq!(solve(4, 2, &shared).unwrap(), "30");
+
+        let input = std::fs::read_to_string("../Day-5/test-1.txt").unwrap();
+        let shared = anonymize(5, &input, 7).unwrap();
+        assert_ne!(shared, input);
+        assert_eq!(solve(5, 1, &shared).unwrap(), "35");
+        assert_eq!(solve(5, 2, &shared).unwrap(), "46");
+
+        let input = std::fs::read_to_string("../Day-7/test-1.txt").unwrap();
+        let shared = anonymize(7, &input, 7).unwrap();
+        assert_ne!(shared, input);
+        assert_eq!(solve(7, 1, &shared).unwrap(), "6440");
+        assert_eq!(solve(7, 2, &shared).unwrap(), "5905");
+        assert!(anonymize(25, &input, 7).is_err());
+    }
+
+    #[test]
+    fn test_solve_in() {
+        assert_eq!(years(), [2022, 2023]);
+        assert_eq!(days_in(2022), [1]);
+        assert_eq!(parts_in(2022, 1), [1, 2]);
+
+        let input = std::fs::read_to_string("../2022/Day-1/test-1.txt").unwrap();
+        let cancel = CancelToken::new();
+        let solve = |year, part| solve_in(year, 1, part, &input, Options::default(), &cancel);
+        assert_eq!(solve(2022, 1).unwrap(), "24000");
+        assert_eq!(solve(2022, 2).unwrap(), "45000");
+        // 2023's day 1 reads the same input as calibration lines, not Calories
+        assert_ne!(solve(2023, 1).unwrap(), "24000");
+        let err = solve_in(2022, 2, 1, &input, Options::default(), &cancel).unwrap_err();
+        assert_eq!(err.to_string(), "2022 day 2 part 1 is not implemented");
+    }
+
+    #[test]
+    fn test_solve_unknown() {
+        assert!(solve(25, 1, "").is_err());
+        assert!(solve(2, 3, "").is_err());
+    }
+
+    #[test]
+    fn test_registry() {
+        assert_eq!(days(), [1, 2, 3, 4, 5, 7]);
+        assert_eq!(parts(1), [1, 2]);
+        // a part registered twice would make which one runs arbitrary
+        let mut keys: Vec<(u32, u32, u32)> = solutions()
+            .iter()
+            .map(|s| (s.year, s.day, s.part))
+            .collect();
+        keys.dedup();
+        assert_eq!(keys.len(), solutions().len());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        for day in days() {
+            for part in parts(day) {
+                for input in ["", "\n\n"] {
+                    let err = solve(day, part, input).unwrap_err();
+                    assert_eq!(err.to_string(), format!("day {} input is empty", day));
+                    let options = Options {
+                        empty: Empty::Lenient,
+                        ..Options::default()
+                    };
+                    assert_eq!(solve_with(day, part, input, options).unwrap(), "0");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lenient() {
+        let input = "Game 1: 3 blue\nGame 2: three blue\nGame 3: 4 red";
+        let err = solve(2, 1, input).unwrap_err();
+        assert!(err.to_string().starts_with("line 2: invalid game"));
+
+        let options = Options {
+            mode: ParseMode::Lenient,
+            ..Options::default()
+        };
+        assert_eq!(solve_with(2, 1, input, options).unwrap(), "4");
+        assert_eq!(solve_with(1, 1, "a1b\nnone\n2", options).unwrap(), "33");
+        let almanac = "seeds: 79\nseed-to-location map:\n1 2\n0 60 30";
+        assert!(solve(5, 1, almanac).is_err());
+        assert_eq!(solve_with(5, 1, almanac, options).unwrap(), "19");
+    }
+
+    // inputs a user could paste or post, with the days that must reject them
+    const MALFORMED: &[(&str, &[u32])] = &[
+        ("\0", &[1, 2, 4, 5, 7]),
+        ("Game", &[1, 2, 4, 5, 7]),
+        ("Game 1:", &[2]),
+        ("Game 1: ", &[2]),
+        ("Game 1: 3", &[2]),
+        ("Game 1: 3 purple", &[2]),
+        ("Game 1: 3 red;", &[2]),
+        ("Game -1: 3 red", &[2]),
+        ("Game 99999999999999999999999: 1 red", &[2]),
+        ("Game 1: 99999999999999999999999 red", &[2]),
+        ("Game 1: 18446744073709551615 red, 1 red", &[2]),
+        ("\u{feff}Game 1: 3 red", &[2]),
+        ("Game 1: 3 red\r\nGame 2: 4 blue\r\n", &[]),
+        ("éàü", &[1, 2, 4, 5, 7]),
+        ("eighthree\n\u{1f468}\u{200d}\u{1f469}", &[1, 2]),
+        ("twone\n٣", &[1, 2]),
+        ("467..114..\n...*......", &[1, 2]),
+        ("\n\n\u{b}", &[1, 2]),
+        ("Card 1: 41 48 | 83 86 |", &[4]),
+        ("Card 1: 41 |", &[4]),
+        ("Card 99999999999999999999999: 1 | 1", &[4]),
+        ("Card 1: 99999999999 | 1", &[4]),
+        ("seeds: 1 2\n\nseed-to-location map:\n5 0 10\n5 9 1", &[5]),
+        ("seeds: 1 2 3\n\nseed-to-location map:\n5 0 10", &[]),
+        ("seeds: 1\n\nseed-to-soil map:\n5 0 10", &[5]),
+        ("32T3K", &[7]),
+        ("32T3K 765 1", &[7]),
+        ("32T3 765", &[7]),
+        ("32t3K 765", &[7]),
+        ("32T3K 99999999999999999999999", &[7]),
+    ];
+
+    #[test]
+    fn test_malformed_input() {
+        let long = "9".repeat(100_000);
+        let inputs = MALFORMED
+            .iter()
+            .copied()
+            .chain([(long.as_str(), &[2, 7][..])]);
+        for (input, rejected_by) in inputs {
+            for day in days() {
+                for part in parts(day) {
+                    let result = std::panic::catch_unwind(|| solve(day, part, input))
+                        .unwrap_or_else(|_| {
+                            panic!("day {} part {} panicked on {:?}", day, part, input)
+                        });
+                    if rejected_by.contains(&day) {
+                        assert!(
+                            result.is_err(),
+                            "day {} part {} answered {:?}",
+                            day,
+                            part,
+                            input
+                        );
+                    }
+                }
+                assert!(std::panic::catch_unwind(|| parse(day, input)).is_ok());
+                assert!(std::panic::catch_unwind(|| validate(day, input)).is_ok());
+            }
+        }
+    }
+
+    // the examples' answers; test_locale_independent runs this in a child
+    // process under each locale, as changing the environment here would race
+    // the harness's other threads
+    #[test]
+    #[ignore = "run by test_locale_independent under a forced locale"]
+    fn locale_answers() {
+        let examples = [
+            (1, 1, "../Day-1/test-1.txt", "142"),
+            (1, 2, "../Day-1/test-2.txt", "281"),
+            (2, 1, "../Day-2/test-1.txt", "8"),
+            (2, 2, "../Day-2/test-1.txt", "2286"),
+            (3, 1, "../Day-3/test-1.txt", "4361"),
+            (3, 2, "../Day-3/test-1.txt", "467835"),
+            (4, 1, "../Day-4/test-1.txt", "13"),
+            (4, 2, "../Day-4/test-1.txt", "30"),
+            (5, 1, "../Day-5/test-1.txt", "35"),
+            (5, 2, "../Day-5/test-1.txt", "46"),
+            (7, 1, "../Day-7/test-1.txt", "6440"),
+            (7, 2, "../Day-7/test-1.txt", "5905"),
+        ];
+        let mut covered: Vec<u32> = examples.iter().map(|(day, ..)| *day).collect();
+        covered.dedup();
+        assert_eq!(covered, days(), "every day needs an example here");
+        for (day, part, path, answer) in examples {
+            let input = std::fs::read_to_string(path).unwrap();
+            assert_eq!(solve(day, part, &input).unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn test_locale_independent() {
+        let exe = std::env::current_exe().unwrap();
+        // comma decimals, dotted grouping and Turkish case rules
+        for locale in ["de_DE.UTF-8", "tr_TR.UTF-8", "ar_EG.UTF-8"] {
+            let output = std::process::Command::new(&exe)
+                .args(["tests::locale_answers", "--exact", "--ignored"])
+                .env("LC_ALL", locale)
+                .env("LC_NUMERIC", locale)
+                .env("LANG", locale)
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(output.status.success(), "{}: {}", locale, stdout);
+            assert!(stdout.contains("1 passed"), "{}: {}", locale, stdout);
+        }
+    }
+
+    #[test]
+    fn test_parse_once() {
+        let examples = [
+            (2, "../Day-2/test-1.txt"),
+            (3, "../Day-3/test-1.txt"),
+            (4, "../Day-4/test-1.txt"),
+            (5, "../Day-5/test-1.txt"),
+            (7, "../Day-7/test-1.txt"),
+        ];
+        let cancel = CancelToken::new();
+        for (day, path) in examples {
+            let input = std::fs::read_to_string(path).unwrap();
+            let parsed = parse_once(day, &input, Options::default())
+                .unwrap()
+                .unwrap();
+            for part in parts(day) {
+                assert_eq!(
+                    solve_parsed(&parsed, part, &cancel).unwrap(),
+                    solve(day, part, &input).unwrap()
+                );
+            }
+            assert!(solve_parsed(&parsed, 3, &cancel).is_err());
+        }
+
+        assert!(parse_once(1, "1abc2", Options::default())
+            .unwrap()
+            .is_none());
+        assert!(parse_once(2, "", Options::default()).is_err());
+        assert!(parse_once(2, "Game one", Options::default()).is_err());
+        let options = Options {
+            mode: ParseMode::Lenient,
+            ..Options::default()
+        };
+        let parsed = parse_once(2, "Game 1: 3 red\nGame two", options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(solve_parsed(&parsed, 1, &cancel).unwrap(), "1");
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        assert!(parse(2, &input).is_ok());
+        assert!(parse(25, "").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        assert!(validate(3, &input).is_empty());
+
+        let findings = validate(2, "Game 2: 1 red\nGame 1: 2 blue");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].to_string(), "line 2: game 1 comes after game 2");
+
+        let input = std::fs::read_to_string("../Day-4/test-1.txt").unwrap();
+        assert!(validate(4, &input).is_empty());
+        let findings = validate(4, "Card 1: 41 48 | 83 86\nCard 2: 13 | 61 30");
+        assert_eq!(
+            findings[0].to_string(),
+            "line 2: 1 winning and 2 held numbers, expected 2 and 2"
+        );
+
+        let input = std::fs::read_to_string("../Day-5/test-1.txt").unwrap();
+        assert!(validate(5, &input).is_empty());
+        let findings = validate(5, "seeds: 1 2 3\nseed-to-location map:\n0 0 1");
+        assert_eq!(
+            findings[0].to_string(),
+            "line 1: the seeds line has 3 numbers, which don't pair up into ranges"
+        );
+
+        let input = std::fs::read_to_string("../Day-7/test-1.txt").unwrap();
+        assert!(validate(7, &input).is_empty());
+        let findings = validate(7, "32T3K 765\n32T3K 1");
+        assert_eq!(
+            findings[0].to_string(),
+            "line 2: 32T3K was dealt on line 1 too"
+        );
+        assert!(validate(25, "anything").is_empty());
+    }
+
+    // the multiples of 7 below the input's number, largest first
+    aoc_common::registry::inventory::submit! {
+        BruteForce {
+            day: 24,
+            part: 1,
+            space: |input| Ok(input.trim().parse()?),
+            search: |_, range| Ok(range.rev().find(|n| n % 7 == 0)),
+            reduce: Reduce::Max,
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        assert_eq!(brute_force_parts(24), vec![1]);
+        assert!(brute_force_parts(2).is_empty());
+
+        let total = search_space(24, 1, "100\n").unwrap();
+        assert_eq!(total, 100);
+        let progress = Progress::new(total);
+        let answer = brute_force(24, 1, "100\n", &progress, &CancelToken::new()).unwrap();
+        assert_eq!(answer, "98");
+        assert_eq!(progress.done(), 100);
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(brute_force(24, 1, "100", &Progress::new(100), &cancel).is_err());
+        assert!(search_space(24, 2, "100").is_err());
+    }
+
+    #[test]
+    fn test_days() {
+        assert_eq!(days(), vec![1, 2, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(parts(1), vec![1, 2]);
+        assert_eq!(parts(2), vec![1, 2]);
+        assert_eq!(parts(3), vec![1, 2]);
+        assert_eq!(parts(4), vec![1, 2]);
+        assert_eq!(parts(5), vec![1, 2]);
+        assert_eq!(parts(7), vec![1, 2]);
+        assert!(parts(25).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+use aoc_common::table::{Column, Table};
+use camel_cards::hand::Rules;
+use color_eyre::eyre::{bail, eyre, Result};
+use map_reader::{Number, Schematic};
+
+/// Per-entity records for a day: one row per calibration line, game, number,
+/// card or hand.
+pub fn records(day: u32, input: &str) -> Result<Table> {
+    match day {
+        1 => calibrations(input),
+        2 => games(input),
+        3 => numbers(input),
+        4 => cards(input),
+        7 => hands(input),
+        _ => bail!("day {} has no per-entity records", day),
+    }
+}
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+fn calibrations(input: &str) -> Result<Table> {
+    // blank lines produce no value, so keep the original line numbers alongside
+    let (numbers, lines): (Vec<u64>, Vec<String>) = lines(input)
+        .into_iter()
+        .zip(1..)
+        .filter(|(line, _)| !line.trim().is_empty())
+        .map(|(line, number)| (number, line))
+        .unzip();
+    let values = trebuchet::parse_data(lines.clone())?;
+
+    Ok(Table::new("calibrations")
+        .with_column("line", Column::UInt(numbers))
+        .with_column("text", Column::Str(lines))
+        .with_column("value", uint(values.into_iter())))
+}
+
+fn games(input: &str) -> Result<Table> {
+    let games = bag_game::parse_data(lines(input))?;
+    let bags: Vec<_> = games.iter().map(bag_game::minimum_bag).collect();
+    let powers = games
+        .iter()
+        .map(|game| {
+            bag_game::get_power(game)
+                .ok_or_else(|| eyre!("the power of game {} overflows", game.id))
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    Ok(Table::new("games")
+        .with_column("id", uint(games.iter().map(|game| game.id)))
+        .with_column("rounds", uint(games.iter().map(|game| game.rounds.len())))
+        .with_column("min_red", uint(bags.iter().map(|bag| bag.red)))
+        .with_column("min_green", uint(bags.iter().map(|bag| bag.green)))
+        .with_column("min_blue", uint(bags.iter().map(|bag| bag.blue)))
+        .with_column(
+            "feasible",
+            Column::Bool(games.iter().map(bag_game::is_feasible).collect()),
+        )
+        .with_column("power", Column::UInt(powers)))
+}
+
+fn numbers(input: &str) -> Result<Table> {
+    let schematic: Schematic = input.parse()?;
+    let numbers = &schematic.numbers;
+
+    Ok(Table::new("numbers")
+        .with_column("row", uint(numbers.iter().map(|n| n.0[0].position.row)))
+        .with_column("col", uint(numbers.iter().map(|n| n.0[0].position.col)))
+        .with_column(
+            "value",
+            Column::UInt(numbers.iter().map(Number::value).collect()),
+        )
+        .with_column("digits", uint(numbers.iter().map(|n| n.0.len())))
+        .with_column("is_part", Column::Bool(schematic.part_flags())))
+}
+
+fn cards(input: &str) -> Result<Table> {
+    let cards = scratchcards::parse_data(lines(input))?;
+    let points = cards
+        .iter()
+        .map(|card| {
+            card.points()
+                .ok_or_else(|| eyre!("the points of card {} overflow", card.id))
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    Ok(Table::new("cards")
+        .with_column("id", uint(cards.iter().map(|card| card.id)))
+        .with_column(
+            "matches",
+            uint(cards.iter().map(scratchcards::Card::matches)),
+        )
+        .with_column("points", Column::UInt(points))
+        .with_column("copies", Column::UInt(scratchcards::copies(&cards)?)))
+}
+
+fn hands(input: &str) -> Result<Table> {
+    let plays = camel_cards::parse_data(lines(input))?;
+    let kinds = |rules| {
+        let kinds = plays.iter().map(|play| play.hand.with_rules(rules).kind());
+        Column::Str(kinds.map(|kind| kind.to_string()).collect())
+    };
+
+    Ok(Table::new("hands")
+        .with_column(
+            "hand",
+            Column::Str(plays.iter().map(|play| play.hand.to_string()).collect()),
+        )
+        .with_column(
+            "bid",
+            Column::UInt(plays.iter().map(|play| play.bid).collect()),
+        )
+        .with_column("kind", kinds(Rules::Jacks))
+        .with_column(
+            "rank",
+            Column::UInt(camel_cards::ranks(&plays, Rules::Jacks)),
+        )
+        .with_column("joker_kind", kinds(Rules::Jokers))
+        .with_column(
+            "joker_rank",
+            Column::UInt(camel_cards::ranks(&plays, Rules::Jokers)),
+        ))
+}
+
+fn uint(values: impl Iterator<Item = usize>) -> Column {
+    Column::UInt(values.map(|value| value as u64).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_records() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let table = records(2, &input).unwrap();
+        assert_eq!(table.name(), "games");
+        assert_eq!(table.rows(), 5);
+
+        let (name, column) = &table.columns()[5];
+        assert_eq!(name, "feasible");
+        assert_eq!(column, &Column::Bool(vec![true, true, false, false, true]));
+        let (name, column) = &table.columns()[6];
+        assert_eq!(name, "power");
+        assert_eq!(column, &Column::UInt(vec![48, 12, 1560, 630, 36]));
+    }
+
+    #[test]
+    fn test_number_records() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let table = records(3, &input).unwrap();
+        assert_eq!(table.rows(), 10);
+
+        let (name, column) = &table.columns()[4];
+        assert_eq!(name, "is_part");
+        let Column::Bool(is_part) = column else {
+            panic!("is_part should be boolean");
+        };
+        assert_eq!(is_part.iter().filter(|&&part| !part).count(), 2);
+    }
+
+    #[test]
+    fn test_card_records() {
+        let input = std::fs::read_to_string("../Day-4/test-1.txt").unwrap();
+        let table = records(4, &input).unwrap();
+        assert_eq!(table.name(), "cards");
+        assert_eq!(table.rows(), 6);
+        assert_eq!(table.columns()[2].1, Column::UInt(vec![8, 2, 2, 1, 0, 0]));
+        assert_eq!(table.columns()[3].1, Column::UInt(vec![1, 2, 4, 8, 14, 1]));
+    }
+
+    #[test]
+    fn test_hand_records() {
+        let input = std::fs::read_to_string("../Day-7/test-1.txt").unwrap();
+        let table = records(7, &input).unwrap();
+        assert_eq!(table.name(), "hands");
+        assert_eq!(table.rows(), 5);
+        assert_eq!(table.columns()[3].1, Column::UInt(vec![1, 4, 3, 2, 5]));
+        let (name, column) = &table.columns()[4];
+        assert_eq!(name, "joker_kind");
+        let Column::Str(kinds) = column else {
+            panic!("joker_kind should be text");
+        };
+        assert_eq!(kinds[1], "four of a kind");
+    }
+
+    #[test]
+    fn test_calibration_records() {
+        let input = std::fs::read_to_string("../Day-1/test-2.txt").unwrap();
+        let table = records(1, &input).unwrap();
+        assert_eq!(table.rows(), 7);
+        assert_eq!(
+            table.columns()[2].1,
+            Column::UInt(vec![29, 83, 13, 24, 42, 14, 76])
+        );
+
+        let table = records(1, "1abc2\n\ntreb7uchet\n").unwrap();
+        assert_eq!(table.rows(), 2);
+        assert_eq!(table.columns()[0].1, Column::UInt(vec![1, 3]));
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(records(25, "").is_err());
+    }
+}
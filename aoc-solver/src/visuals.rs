@@ -0,0 +1,170 @@
+use aoc_common::graph::Graph;
+use aoc_common::grid::Grid;
+use aoc_common::trace::{Checkpoints, Tracer};
+use aoc_common::visualize::{Animate, Glyph, Inspect, Scene, Stepper, Visualize};
+use color_eyre::eyre::{bail, Result};
+use map_reader::Schematic;
+
+/// A day's parsed state as titled scenes, for `aoc run --visualize`.
+pub fn scenes(day: u32, input: &str) -> Result<Vec<(String, Scene)>> {
+    match day {
+        2 => {
+            let games = bag_game::parse_data(input.lines().map(String::from).collect())?;
+            Ok(games
+                .iter()
+                .map(|game| (format!("Game {}", game.id), game.visualize()))
+                .collect())
+        }
+        3 => {
+            let schematic: Schematic = input.parse()?;
+            Ok(vec![(String::from("Schematic"), schematic.visualize())])
+        }
+        _ => bail!("day {} has nothing to visualize", day),
+    }
+}
+
+/// A day's own SVG rendering, for days that draw more than their scenes show.
+pub fn svg(day: u32, input: &str) -> Result<String> {
+    match day {
+        3 => {
+            let lines: Vec<String> = input.lines().map(String::from).collect();
+            let schematic: Schematic = input.parse()?;
+            Ok(map_reader::svg::render(&lines, &schematic))
+        }
+        _ => bail!("day {} has no SVG rendering of its own", day),
+    }
+}
+
+/// A day's solving progress as frames, for `aoc run --animate`.
+pub fn frames(day: u32, input: &str) -> Result<Vec<Scene>> {
+    match day {
+        3 => {
+            let schematic: Schematic = input.parse()?;
+            Ok(schematic.frames())
+        }
+        _ => bail!("day {} has no animation", day),
+    }
+}
+
+/// A grid day's scene and what can be said about each of its cells, for
+/// `aoc explore`.
+pub fn explorable(day: u32, input: &str) -> Result<(Grid<Glyph>, Box<dyn Inspect>)> {
+    match day {
+        3 => {
+            let schematic: Schematic = input.parse()?;
+            let Scene::Grid(grid) = schematic.visualize() else {
+                bail!("day 3 should visualize as a grid");
+            };
+            Ok((grid, Box::new(schematic)))
+        }
+        _ => bail!("day {} has no grid to explore", day),
+    }
+}
+
+/// A graph-shaped day's structure with a label per node, for `aoc run --dot`.
+pub fn graph(day: u32, input: &str) -> Result<(Graph, Vec<String>)> {
+    match day {
+        3 => {
+            let schematic: Schematic = input.parse()?;
+            Ok(schematic.adjacency_graph())
+        }
+        _ => bail!("day {} has no graph to export", day),
+    }
+}
+
+/// Solve one part with `stepper` hearing about every checkpoint, for
+/// `aoc run --step`.
+pub fn stepped(day: u32, part: u32, input: &str, stepper: &mut impl Stepper) -> Result<String> {
+    match (day, part) {
+        (2, 1) => Ok(bag_game::visualize::part1_stepped(input, stepper)?.to_string()),
+        (2, 2) => Ok(bag_game::visualize::part2_stepped(input, stepper)?.to_string()),
+        _ => bail!(
+            "day {} part {} has no checkpoints to step through",
+            day,
+            part
+        ),
+    }
+}
+
+/// Solve one part with `tracer` hearing about each step of its algorithm,
+/// for `aoc run --trace-algo`; a day's steps are the checkpoints it stops at
+/// for `--step`.
+pub fn traced(day: u32, part: u32, input: &str, tracer: &mut impl Tracer) -> Result<String> {
+    match day {
+        2 => stepped(day, part, input, &mut Checkpoints(tracer)),
+        _ => bail!("day {} part {} has no algorithm to trace", day, part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenes() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let scenes = scenes(2, &input).unwrap();
+        assert_eq!(scenes.len(), 5);
+        assert_eq!(scenes[4].0, "Game 5");
+
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let scenes = super::scenes(3, &input).unwrap();
+        assert!(matches!(scenes[0].1, Scene::Grid(_)));
+
+        assert!(super::scenes(1, "").is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        assert!(svg(3, &input).unwrap().starts_with("<svg"));
+        assert!(svg(2, "").is_err());
+    }
+
+    #[test]
+    fn test_frames() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        assert_eq!(frames(3, &input).unwrap().len(), 10);
+        assert!(frames(2, "").is_err());
+    }
+
+    #[test]
+    fn test_explorable() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let (grid, cells) = explorable(3, &input).unwrap();
+        assert_eq!(grid.height(), 10);
+        assert!(!cells.inspect(aoc_common::grid::Point::new(0, 0)).is_empty());
+        assert!(explorable(2, "").is_err());
+    }
+
+    #[test]
+    fn test_stepped() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let mut steps = Vec::new();
+        assert_eq!(stepped(2, 2, &input, &mut steps).unwrap(), "2286");
+        assert_eq!(steps.len(), 5);
+        assert!(stepped(1, 1, "", &mut ()).is_err());
+    }
+
+    #[test]
+    fn test_traced() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let mut events = Vec::new();
+        assert_eq!(traced(2, 1, &input, &mut events).unwrap(), "8");
+        assert_eq!(events.len(), 5);
+        let aoc_common::trace::Event::Update { label, variables } = &events[4] else {
+            panic!("expected an update");
+        };
+        assert_eq!(label, "Game 5");
+        assert!(variables.contains(&(String::from("total"), String::from("8"))));
+        assert!(traced(3, 1, "", &mut ()).is_err());
+    }
+
+    #[test]
+    fn test_graph() {
+        let input = std::fs::read_to_string("../Day-3/test-1.txt").unwrap();
+        let (graph, labels) = graph(3, &input).unwrap();
+        assert_eq!(graph.len(), labels.len());
+        assert!(super::graph(1, "").is_err());
+    }
+}
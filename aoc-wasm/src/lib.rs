@@ -0,0 +1,23 @@
+// Browser bindings for the solvers.
+//
+// Build the playground with:
+//   wasm-pack build aoc-wasm --target web --out-dir www/pkg
+// and serve `aoc-wasm/www` with any static file server.
+use wasm_bindgen::prelude::*;
+
+// Errors are thrown as JS exceptions, so callers see `solve(...) -> string`
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, JsError> {
+    aoc_solver::solve(day, part, input).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        assert_eq!(solve(2, 1, &input).ok(), Some(String::from("8")));
+    }
+}
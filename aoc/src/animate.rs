@@ -0,0 +1,203 @@
+use crate::theme::Theme;
+use crate::visualize::render;
+use aoc_common::visualize::Scene;
+use color_eyre::eyre::{ensure, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 120.0;
+
+/// How fast to play an animation and how many of its frames to show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Playback {
+    pub fps: f64,
+    pub budget: Option<usize>,
+}
+
+/// Indices of the frames to show: every one, or an evenly spread selection
+/// keeping the first and last when there are more than `budget`.
+pub fn within_budget(len: usize, budget: Option<usize>) -> Vec<usize> {
+    match budget {
+        Some(budget) if budget < len => {
+            if budget <= 1 {
+                return vec![len - 1];
+            }
+            (0..budget).map(|i| i * (len - 1) / (budget - 1)).collect()
+        }
+        _ => (0..len).collect(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Pause,
+    Faster,
+    Slower,
+    Quit,
+}
+
+fn control(key: KeyEvent) -> Option<Control> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char(' ') => Some(Control::Pause),
+        KeyCode::Char('+') | KeyCode::Char('=') => Some(Control::Faster),
+        KeyCode::Char('-') => Some(Control::Slower),
+        // raw mode swallows the usual Ctrl-C
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Control::Quit),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Control::Quit),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Player {
+    fps: f64,
+    paused: bool,
+}
+
+impl Player {
+    fn new(fps: f64) -> Self {
+        Player {
+            fps: fps.clamp(MIN_FPS, MAX_FPS),
+            paused: false,
+        }
+    }
+
+    // false once asked to quit
+    fn apply(&mut self, control: Control) -> bool {
+        match control {
+            Control::Pause => self.paused = !self.paused,
+            Control::Faster => self.fps = (self.fps * 2.0).min(MAX_FPS),
+            Control::Slower => self.fps = (self.fps / 2.0).max(MIN_FPS),
+            Control::Quit => return false,
+        }
+        true
+    }
+
+    // Sit out one frame (and however long it stays paused), handling keys
+    // meanwhile; false once asked to quit
+    fn wait(&mut self) -> io::Result<bool> {
+        let deadline = Instant::now() + Duration::from_secs_f64(1.0 / self.fps);
+        loop {
+            let now = Instant::now();
+            if !self.paused && now >= deadline {
+                return Ok(true);
+            }
+            let timeout = if self.paused {
+                Duration::from_millis(100)
+            } else {
+                deadline - now
+            };
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()? {
+                    if let Some(control) = control(key) {
+                        if !self.apply(control) {
+                            return Ok(false);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Raw mode for single key presses, undone however playback ends
+struct RawMode;
+
+impl RawMode {
+    fn enable(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        write!(out, "{}", HIDE_CURSOR)?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = write!(io::stderr(), "{}", SHOW_CURSOR);
+    }
+}
+
+/// Play `frames` on stderr, clearing and redrawing for each one. Space
+/// pauses, `+`/`-` double or halve the speed, and `q` stops. Without a
+/// terminal only the last frame is drawn.
+pub fn play(frames: &[Scene], playback: Playback, theme: &Theme) -> Result<()> {
+    ensure!(playback.fps > 0.0, "the frame rate must be positive");
+    let shown = within_budget(frames.len(), playback.budget);
+    let stderr = io::stderr();
+    let mut out = stderr.lock();
+
+    if !stderr.is_terminal() {
+        if let Some(&last) = shown.last() {
+            render(&mut out, "Final frame", &frames[last], theme)?;
+        }
+        return Ok(());
+    }
+
+    let _raw = RawMode::enable(&mut out)?;
+    let mut player = Player::new(playback.fps);
+    for (n, &i) in shown.iter().enumerate() {
+        let mut frame = Vec::new();
+        let title = format!("Frame {}/{}", n + 1, shown.len());
+        render(&mut frame, &title, &frames[i], theme)?;
+        writeln!(frame, "space: pause  +/-: speed  q: quit")?;
+
+        // raw mode doesn't return the carriage on a bare newline
+        let frame = String::from_utf8_lossy(&frame).replace('\n', "\r\n");
+        write!(out, "{}{}", CLEAR, frame)?;
+        out.flush()?;
+        if !player.wait()? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within_budget() {
+        assert_eq!(within_budget(4, None), vec![0, 1, 2, 3]);
+        assert_eq!(within_budget(4, Some(10)), vec![0, 1, 2, 3]);
+        assert_eq!(within_budget(10, Some(4)), vec![0, 3, 6, 9]);
+        assert_eq!(within_budget(10, Some(1)), vec![9]);
+        assert_eq!(within_budget(10, Some(0)), vec![9]);
+        assert!(within_budget(0, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn test_controls() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(control(key(KeyCode::Char(' '))), Some(Control::Pause));
+        assert_eq!(control(key(KeyCode::Char('+'))), Some(Control::Faster));
+        assert_eq!(control(key(KeyCode::Esc)), Some(Control::Quit));
+        assert_eq!(control(key(KeyCode::Char('x'))), None);
+        assert_eq!(
+            control(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Control::Quit)
+        );
+
+        let mut player = Player::new(8.0);
+        assert!(player.apply(Control::Pause));
+        assert!(player.paused);
+        player.apply(Control::Faster);
+        assert_eq!(player.fps, 16.0);
+        for _ in 0..10 {
+            player.apply(Control::Slower);
+        }
+        assert_eq!(player.fps, MIN_FPS);
+        assert!(!player.apply(Control::Quit));
+    }
+}
@@ -0,0 +1,64 @@
+use clap::Args;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+#[derive(Debug, Args)]
+pub struct AnonymizeArgs {
+    /// Day whose input to anonymize; the puzzle input is read from stdin
+    #[arg(long)]
+    pub day: u32,
+
+    /// Seed for the rewrite, to reproduce one; by default taken from the clock and logged
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+pub fn run(args: AnonymizeArgs) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .wrap_err("failed to read the puzzle input from stdin")?;
+
+    let seed = match args.seed {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+    };
+    info!("anonymizing day {} with seed {}", args.day, seed);
+    let shared = aoc_solver::anonymize(args.day, &input, seed)?;
+    check(args.day, &input, &shared)?;
+    std::io::stdout().write_all(shared.as_bytes())?;
+    Ok(())
+}
+
+// refuse to hand out an input that no longer reproduces the original answers
+fn check(day: u32, input: &str, shared: &str) -> Result<()> {
+    for part in aoc_solver::parts(day) {
+        let expected = aoc_solver::solve(day, part, input)?;
+        let actual = aoc_solver::solve(day, part, shared)?;
+        if actual != expected {
+            bail!(
+                "the anonymized input answers day {} part {} with {} instead of {}",
+                day,
+                part,
+                actual,
+                expected
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let shared = aoc_solver::anonymize(2, &input, 3).unwrap();
+        assert!(check(2, &input, &shared).is_ok());
+        assert!(check(2, &input, "Game 1: 1 red\n").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::output::Solution;
+use aoc_common::input::{day_dir, workspace_root};
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+// what the day's answers.toml can say about it
+#[derive(Debug, Default, Deserialize)]
+struct DayFile {
+    // the longest all of the day's parts should take together, e.g. "1s" or "250ms"
+    budget: Option<String>,
+}
+
+/// A day whose parts together took longer than its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overrun {
+    pub day: u32,
+    pub elapsed: Duration,
+    pub budget: Duration,
+}
+
+impl fmt::Display for Overrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} took {:.2?}, over its {:?} budget",
+            self.day, self.elapsed, self.budget
+        )
+    }
+}
+
+/// The budget the answers.toml in `day`'s directory (`Day-N`, or `YYYY/Day-N`
+/// for an earlier year) declares, if it declares one.
+pub fn budget(year: u32, day: u32) -> Result<Option<Duration>> {
+    let path = workspace_root()
+        .join(day_dir(year, day))
+        .join("answers.toml");
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    let file: DayFile =
+        toml::from_str(&text).wrap_err_with(|| format!("invalid {}", path.display()))?;
+    file.budget
+        .map(|budget| parse_duration(&budget))
+        .transpose()
+        .wrap_err_with(|| format!("invalid budget in {}", path.display()))
+}
+
+/// How `solutions` to one day measure up against `budget`.
+pub fn check(day: u32, solutions: &[Solution], budget: Duration) -> Option<Overrun> {
+    let elapsed: u64 = solutions.iter().map(|solution| solution.elapsed_us).sum();
+    let elapsed = Duration::from_micros(elapsed);
+    (elapsed > budget).then_some(Overrun {
+        day,
+        elapsed,
+        budget,
+    })
+}
+
+/// A number and a unit: "1s", "1.5s", "250ms", "800us" or "800µs".
+pub fn parse_duration(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let Ok(number) = number.parse::<f64>() else {
+        bail!("{:?} doesn't start with a number", text);
+    };
+    let seconds = match unit.trim() {
+        "s" => number,
+        "ms" => number / 1e3,
+        "us" | "µs" => number / 1e6,
+        unit => bail!("unknown unit {:?} in {:?}; use s, ms or us", unit, text),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1s").unwrap(), Duration::from_secs(1));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(
+            parse_duration(" 250 ms").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert_eq!(parse_duration("800us").unwrap(), Duration::from_micros(800));
+        assert_eq!(parse_duration("800µs").unwrap(), Duration::from_micros(800));
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("1h").is_err());
+        assert!(parse_duration("1").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let solutions = [
+            Solution::new(3, 1, String::from("4361"), Duration::from_millis(600)),
+            Solution::new(3, 2, String::from("467835"), Duration::from_millis(500)),
+        ];
+        assert_eq!(check(3, &solutions, Duration::from_secs(2)), None);
+        let overrun = check(3, &solutions, Duration::from_secs(1)).unwrap();
+        assert_eq!(overrun.elapsed, Duration::from_millis(1100));
+        assert_eq!(overrun.to_string(), "day 3 took 1.10s, over its 1s budget");
+    }
+
+    #[test]
+    fn test_serde_day_file() {
+        let file: DayFile = toml::from_str("budget = \"250ms\"\n").unwrap();
+        assert_eq!(file.budget.as_deref(), Some("250ms"));
+        let file: DayFile = toml::from_str("").unwrap();
+        assert_eq!(file.budget, None);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::history::{current_commit, Entry, History};
+use crate::output::Solution;
+use crate::report::latest;
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+// every file in the archive sits under this directory
+const ROOT: &str = "aoc-bundle";
+
+#[derive(Debug, Args)]
+pub struct BundleArgs {
+    /// Archive to write
+    #[arg(long, default_value = "aoc-bundle.tar.gz")]
+    pub out: PathBuf,
+}
+
+// one stored result, with its times in microseconds like the results
+#[derive(Debug, Serialize)]
+struct HistoryRow {
+    run_id: i64,
+    recorded_at: String,
+    commit: Option<String>,
+    day: u32,
+    part: u32,
+    answer: String,
+    elapsed_us: u128,
+    parse_us: Option<u128>,
+    input_sha256: String,
+}
+
+// the input a day's latest results were solved on, by hash only
+#[derive(Debug, Serialize)]
+struct InputHash {
+    day: u32,
+    sha256: String,
+}
+
+// what the numbers were measured on
+#[derive(Debug, Serialize)]
+struct Environment {
+    aoc_version: &'static str,
+    commit: Option<String>,
+    profile: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    cpu: Option<String>,
+    cpus: Option<usize>,
+    rustc: Option<String>,
+}
+
+/// Everything in a bundle; the puzzle inputs themselves are never part of it.
+#[derive(Debug)]
+struct Bundle {
+    results: Vec<Solution>,
+    history: Vec<HistoryRow>,
+    inputs: Vec<InputHash>,
+    environment: Environment,
+}
+
+// Pack the latest results, the whole history, the hashes of the inputs behind
+// the results and a description of this machine into one archive, so timings
+// can be shared and checked without sharing inputs
+pub fn run(args: BundleArgs) -> Result<()> {
+    let entries = History::open_default()?.all_entries()?;
+    let bundle = collect(&entries, environment());
+    let file = std::fs::File::create(&args.out)
+        .wrap_err_with(|| format!("failed to create {}", args.out.display()))?;
+    write(GzEncoder::new(file, Compression::default()), &bundle)?.finish()?;
+    info!(
+        "Wrote {} results and {} history rows to {}",
+        bundle.results.len(),
+        bundle.history.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+fn collect(entries: &[Entry], environment: Environment) -> Bundle {
+    let latest = latest(entries);
+    let results = latest
+        .iter()
+        .map(|entry| Solution::new(entry.day, entry.part, entry.answer.clone(), entry.elapsed))
+        .collect();
+    // both parts of a day read the same input, part 2's from the later run if they differ
+    let inputs: BTreeMap<u32, &str> = latest
+        .iter()
+        .map(|entry| (entry.day, entry.input_sha256.as_str()))
+        .collect();
+    let history = entries
+        .iter()
+        .map(|entry| HistoryRow {
+            run_id: entry.run_id,
+            recorded_at: entry.recorded_at.clone(),
+            commit: entry.commit.clone(),
+            day: entry.day,
+            part: entry.part,
+            answer: entry.answer.clone(),
+            elapsed_us: entry.elapsed.as_micros(),
+            parse_us: entry.parse.map(|parse| parse.as_micros()),
+            input_sha256: entry.input_sha256.clone(),
+        })
+        .collect();
+    Bundle {
+        results,
+        history,
+        inputs: inputs
+            .into_iter()
+            .map(|(day, sha256)| InputHash {
+                day,
+                sha256: sha256.to_string(),
+            })
+            .collect(),
+        environment,
+    }
+}
+
+// One JSON file per part of the bundle in a tar archive; results.json reads
+// like `aoc run --format json`, so `aoc diff` can compare two bundles
+fn write<W: Write>(out: W, bundle: &Bundle) -> Result<W> {
+    let mut archive = tar::Builder::new(out);
+    append(&mut archive, "results.json", &bundle.results)?;
+    append(&mut archive, "history.json", &bundle.history)?;
+    append(&mut archive, "inputs.json", &bundle.inputs)?;
+    append(&mut archive, "environment.json", &bundle.environment)?;
+    Ok(archive.into_inner()?)
+}
+
+fn append<W: Write>(
+    archive: &mut tar::Builder<W>,
+    name: &str,
+    value: &impl Serialize,
+) -> Result<()> {
+    let data = serde_json::to_vec_pretty(value)?;
+    let mtime = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    archive.append_data(&mut header, format!("{}/{}", ROOT, name), data.as_slice())?;
+    Ok(())
+}
+
+fn environment() -> Environment {
+    let rustc = Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    // only Linux says what the processor is without extra dependencies
+    let cpu = std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find_map(|line| line.strip_prefix("model name"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, name)| name.trim().to_string())
+        });
+    Environment {
+        aoc_version: env!("CARGO_PKG_VERSION"),
+        commit: current_commit(),
+        profile: if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        },
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        cpu,
+        cpus: std::thread::available_parallelism().ok().map(usize::from),
+        rustc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{input_sha256, Record};
+    use std::io::Read;
+    use std::time::Duration;
+
+    fn record(day: u32, part: u32, answer: &str, input: &str) -> Record {
+        Record {
+            solution: Solution::new(day, part, answer.to_string(), Duration::from_micros(150)),
+            input_sha256: input_sha256(input),
+            parse: None,
+        }
+    }
+
+    #[test]
+    fn test_bundle() {
+        let mut history = History::open_in_memory().unwrap();
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        history
+            .record(Some("abc1234"), &[record(2, 1, "7", &input)])
+            .unwrap();
+        history
+            .record(
+                Some("def5678"),
+                &[record(2, 1, "8", &input), record(2, 2, "2286", &input)],
+            )
+            .unwrap();
+
+        let entries = history.all_entries().unwrap();
+        let bundle = collect(&entries, environment());
+        assert_eq!(bundle.history.len(), 3);
+        let answers: Vec<&str> = bundle.results.iter().map(|r| r.answer.as_str()).collect();
+        assert_eq!(answers, ["8", "2286"]);
+        assert_eq!(bundle.inputs.len(), 1);
+        assert_eq!(bundle.inputs[0].sha256, input_sha256(&input));
+
+        let archive = write(Vec::new(), &bundle).unwrap();
+        let mut archive = tar::Archive::new(archive.as_slice());
+        let mut files = BTreeMap::new();
+        for file in archive.entries().unwrap() {
+            let mut file = file.unwrap();
+            let name = file.path().unwrap().to_string_lossy().into_owned();
+            let mut text = String::new();
+            file.read_to_string(&mut text).unwrap();
+            files.insert(name, text);
+        }
+        let names: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(
+            names,
+            [
+                "aoc-bundle/environment.json",
+                "aoc-bundle/history.json",
+                "aoc-bundle/inputs.json",
+                "aoc-bundle/results.json"
+            ]
+        );
+        // the hashes go in, never the input
+        assert!(files.values().all(|text| !text.contains("Game 1")));
+        let results: Vec<Solution> =
+            serde_json::from_str(&files["aoc-bundle/results.json"]).unwrap();
+        assert_eq!(results, bundle.results);
+    }
+}
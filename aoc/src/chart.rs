@@ -0,0 +1,264 @@
+use crate::history::{Entry, History};
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// layout, in SVG user units
+const BAR: usize = 14;
+const GAP: usize = 4;
+const GROUP_GAP: usize = 28;
+const PLOT_HEIGHT: usize = 240;
+const MARGIN: usize = 48;
+
+#[derive(Debug, Args)]
+pub struct ChartArgs {
+    /// Write the SVG here instead of stdout
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+// One run of one day: its parts' times added up, split into parsing and the
+// rest
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Bar {
+    run_id: i64,
+    label: String,
+    parse: Duration,
+    solve: Duration,
+}
+
+impl Bar {
+    fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+pub fn run(args: ChartArgs) -> Result<()> {
+    let history = History::open_default()?;
+    let svg = svg(&history.all_entries()?);
+    match args.out {
+        Some(path) => std::fs::write(&path, svg)
+            .wrap_err_with(|| format!("failed to write {}", path.display()))?,
+        None => print!("{}", svg),
+    }
+    Ok(())
+}
+
+// Bars for each day, oldest run first. Every part parses its own input, so
+// each contributes its parse time; runs without one count as all solve.
+fn bars(entries: &[Entry]) -> BTreeMap<u32, Vec<Bar>> {
+    let mut runs: BTreeMap<u32, BTreeMap<i64, Bar>> = BTreeMap::new();
+    for entry in entries {
+        let bar = runs
+            .entry(entry.day)
+            .or_default()
+            .entry(entry.run_id)
+            .or_insert_with(|| Bar {
+                run_id: entry.run_id,
+                label: format!(
+                    "{} {}",
+                    entry.recorded_at,
+                    entry.commit.as_deref().unwrap_or("-")
+                ),
+                ..Bar::default()
+            });
+        let parse = entry.parse.unwrap_or_default().min(entry.elapsed);
+        bar.parse += parse;
+        bar.solve += entry.elapsed - parse;
+    }
+
+    runs.into_iter()
+        .map(|(day, runs)| (day, runs.into_values().collect()))
+        .collect()
+}
+
+// bar height for `duration`, scaled to the slowest run
+fn scale(duration: Duration, slowest: Duration) -> usize {
+    if slowest.is_zero() {
+        0
+    } else {
+        (duration.as_secs_f64() / slowest.as_secs_f64() * PLOT_HEIGHT as f64).round() as usize
+    }
+}
+
+/// A stacked bar chart of every recorded run: one group of bars per day, one
+/// bar per run in recording order, parse time below solve time.
+pub fn svg(entries: &[Entry]) -> String {
+    let days = bars(entries);
+    let slowest = days
+        .values()
+        .flatten()
+        .map(Bar::total)
+        .max()
+        .unwrap_or_default();
+    let plot_width: usize = days
+        .values()
+        .map(|bars| bars.len() * (BAR + GAP) - GAP)
+        .sum::<usize>()
+        + GROUP_GAP * days.len().saturating_sub(1);
+    let width = (plot_width + 2 * MARGIN).max(320);
+    let height = PLOT_HEIGHT + 2 * MARGIN;
+    let baseline = MARGIN + PLOT_HEIGHT;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="11">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        out,
+        "<style>.parse {{ fill: #8da0cb; }} .solve {{ fill: #66c2a5; }} .axis {{ stroke: #444; }}</style>"
+    );
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="20" font-size="14">Solve times by day</text>"#,
+        MARGIN
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect class="parse" x="{x}" y="10" width="10" height="10"/><text x="{tx}" y="19">parse</text>"#,
+        x = width - MARGIN - 110,
+        tx = width - MARGIN - 96
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect class="solve" x="{x}" y="10" width="10" height="10"/><text x="{tx}" y="19">solve</text>"#,
+        x = width - MARGIN - 50,
+        tx = width - MARGIN - 36
+    );
+
+    if days.is_empty() {
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}">No recorded runs</text>"#,
+            MARGIN,
+            MARGIN + PLOT_HEIGHT / 2
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            r#"<text x="4" y="{}">{:.2?}</text>"#,
+            MARGIN + 4,
+            slowest
+        );
+    }
+
+    let mut x = MARGIN;
+    for (day, bars) in &days {
+        let group_width = bars.len() * (BAR + GAP) - GAP;
+        for bar in bars {
+            let total = scale(bar.total(), slowest);
+            let parse = scale(bar.parse, slowest).min(total);
+            let _ = writeln!(
+                out,
+                "<g><title>Day {} run #{} ({}): parse {:.2?}, solve {:.2?}</title>",
+                day, bar.run_id, bar.label, bar.parse, bar.solve
+            );
+            let _ = writeln!(
+                out,
+                r#"<rect class="solve" x="{}" y="{}" width="{}" height="{}"/>"#,
+                x,
+                baseline - total,
+                BAR,
+                total - parse
+            );
+            let _ = writeln!(
+                out,
+                r#"<rect class="parse" x="{}" y="{}" width="{}" height="{}"/></g>"#,
+                x,
+                baseline - parse,
+                BAR,
+                parse
+            );
+            x += BAR + GAP;
+        }
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}" text-anchor="middle">Day {}</text>"#,
+            x - GAP - group_width / 2,
+            baseline + 16,
+            day
+        );
+        x += GROUP_GAP - GAP;
+    }
+
+    let _ = writeln!(
+        out,
+        r#"<line class="axis" x1="{}" y1="{b}" x2="{}" y2="{b}"/>"#,
+        MARGIN,
+        width - MARGIN,
+        b = baseline
+    );
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(run_id: i64, day: u32, part: u32, elapsed_us: u64, parse_us: Option<u64>) -> Entry {
+        Entry {
+            run_id,
+            recorded_at: String::from("2023-12-03 10:00:00"),
+            commit: Some(String::from("abc1234")),
+            day,
+            part,
+            answer: String::new(),
+            elapsed: Duration::from_micros(elapsed_us),
+            input_sha256: String::new(),
+            parse: parse_us.map(Duration::from_micros),
+        }
+    }
+
+    #[test]
+    fn test_bars() {
+        let entries = [
+            entry(1, 2, 1, 300, Some(100)),
+            entry(1, 2, 2, 500, Some(100)),
+            entry(2, 2, 1, 200, None),
+            // parsing can't take longer than the whole part
+            entry(1, 3, 1, 50, Some(80)),
+        ];
+        let days = bars(&entries);
+
+        assert_eq!(days[&2].len(), 2);
+        assert_eq!(days[&2][0].parse, Duration::from_micros(200));
+        assert_eq!(days[&2][0].solve, Duration::from_micros(600));
+        assert_eq!(days[&2][1].parse, Duration::ZERO);
+        assert_eq!(days[&3][0].parse, Duration::from_micros(50));
+        assert_eq!(days[&3][0].solve, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_svg() {
+        let entries = [
+            entry(1, 1, 1, 400, Some(100)),
+            entry(2, 1, 1, 200, Some(100)),
+            entry(1, 2, 1, 100, None),
+        ];
+        let svg = svg(&entries);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        // a legend swatch plus one per bar
+        assert_eq!(svg.matches(r#"<rect class="parse""#).count(), 4);
+        assert!(svg.contains(">Day 1</text>"));
+        assert!(svg.contains(">Day 2</text>"));
+        // the slowest bar fills the plot
+        assert!(svg.contains(&format!(
+            r#"<rect class="solve" x="{}" y="{}" width="{}" height="{}"/>"#,
+            MARGIN,
+            MARGIN,
+            BAR,
+            PLOT_HEIGHT * 3 / 4
+        )));
+
+        assert!(super::svg(&[]).contains("No recorded runs"));
+    }
+}
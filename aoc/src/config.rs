@@ -0,0 +1,117 @@
+use crate::fetch::SESSION_ENV;
+use aoc_common::input::INPUT_DIR_ENV;
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+// a config file somewhere other than the usual place
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+// the year `aoc run --day` solves without --year
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// The settings in `~/.config/aoc2023/config.toml` (under `$XDG_CONFIG_HOME`
+/// when that's set, or wherever AOC_CONFIG points). Each has an environment
+/// variable that overrides it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The adventofcode.com session cookie; AOC_SESSION
+    pub session: Option<String>,
+    /// Where inputs are read from and downloaded to; AOC_INPUT_DIR
+    pub input_dir: Option<PathBuf>,
+    /// The log level without RUST_LOG, e.g. "warn"
+    pub log_level: Option<String>,
+    /// The year to solve without --year; AOC_YEAR
+    pub year: Option<u32>,
+}
+
+impl Config {
+    /// The config file's settings; all unset when there is no file.
+    pub fn load() -> Result<Self> {
+        let var = |name| std::env::var_os(name).map(PathBuf::from);
+        match path(var(CONFIG_ENV), var("XDG_CONFIG_HOME"), var("HOME")) {
+            Some(path) if path.exists() => Self::read(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("cannot read {}", path.display()))?;
+        toml::from_str(&text).wrap_err_with(|| format!("invalid {}", path.display()))
+    }
+
+    /// Set the environment variable of every setting the environment doesn't
+    /// set already, so everything reading them (the input resolver in
+    /// aoc-common among them) sees the file without being told about it.
+    /// Called before any other thread starts.
+    pub fn apply(&self) {
+        let settings = [
+            (SESSION_ENV, self.session.clone()),
+            (
+                INPUT_DIR_ENV,
+                self.input_dir
+                    .as_ref()
+                    .map(|dir| dir.to_string_lossy().into_owned()),
+            ),
+            (YEAR_ENV, self.year.map(|year| year.to_string())),
+        ];
+        for (name, value) in settings {
+            if let (None, Some(value)) = (std::env::var_os(name), value) {
+                std::env::set_var(name, value);
+            }
+        }
+    }
+}
+
+/// The year in AOC_YEAR, if it's set.
+pub fn year() -> Result<Option<u32>> {
+    std::env::var(YEAR_ENV)
+        .ok()
+        .map(|year| year.parse())
+        .transpose()
+        .wrap_err_with(|| format!("{} isn't a year", YEAR_ENV))
+}
+
+// where the config file is: AOC_CONFIG, else aoc2023/config.toml under the
+// XDG config directory or ~/.config
+fn path(config: Option<PathBuf>, xdg: Option<PathBuf>, home: Option<PathBuf>) -> Option<PathBuf> {
+    config.or_else(|| {
+        xdg.or_else(|| home.map(|home| home.join(".config")))
+            .map(|dir| dir.join("aoc2023").join("config.toml"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let home = Some(PathBuf::from("/home/elf"));
+        assert_eq!(
+            path(None, None, home.clone()),
+            Some(PathBuf::from("/home/elf/.config/aoc2023/config.toml"))
+        );
+        assert_eq!(
+            path(None, Some(PathBuf::from("/xdg")), home.clone()),
+            Some(PathBuf::from("/xdg/aoc2023/config.toml"))
+        );
+        assert_eq!(
+            path(Some(PathBuf::from("aoc.toml")), None, home),
+            Some(PathBuf::from("aoc.toml"))
+        );
+        assert_eq!(path(None, None, None), None);
+    }
+
+    #[test]
+    fn test_config() {
+        let config: Config =
+            toml::from_str("session = \"53cr3t\"\ninput_dir = \"/inputs\"\nyear = 2022\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("53cr3t"));
+        assert_eq!(config.input_dir, Some(PathBuf::from("/inputs")));
+        assert_eq!(config.log_level, None);
+        assert_eq!(config.year, Some(2022));
+        assert!(toml::from_str::<Config>("sesion = \"typo\"\n").is_err());
+    }
+}
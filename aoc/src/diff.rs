@@ -0,0 +1,210 @@
+use crate::output::Solution;
+use crate::theme::{Theme, ThemeName};
+use aoc_common::answer::{matches, Normalize};
+use aoc_common::pretty::{duration, Align, Pretty};
+use aoc_common::visualize::Style;
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// timing changes smaller than this, in percent, are noise and left unstyled
+const NOISE: f64 = 5.0;
+
+// one part's result in each run, if that run solved it
+type Pair<'a> = (Option<&'a Solution>, Option<&'a Solution>);
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Results of the first run, as written by `aoc run --format json`
+    pub before: PathBuf,
+
+    /// Results of the second run, compared against the first
+    pub after: PathBuf,
+}
+
+pub fn run(args: DiffArgs, theme: Option<ThemeName>) -> Result<()> {
+    let before = read(&args.before)?;
+    let after = read(&args.after)?;
+    let theme = Theme::select(theme, std::io::stdout().is_terminal());
+    print!("{}", report(&before, &after, &theme));
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<Vec<Solution>> {
+    let text = std::fs::read(path).wrap_err_with(|| format!("cannot read {}", path.display()))?;
+    serde_json::from_slice(&text)
+        .wrap_err_with(|| format!("{} doesn't hold JSON results", path.display()))
+}
+
+// A row per part in either run, then the totals: how many answers changed,
+// which parts only one run solved, and the summed solve time
+fn report(before: &[Solution], after: &[Solution], theme: &Theme) -> String {
+    let mut parts: BTreeMap<(u32, u32), Pair> = BTreeMap::new();
+    for solution in before {
+        parts.entry((solution.day, solution.part)).or_default().0 = Some(solution);
+    }
+    for solution in after {
+        parts.entry((solution.day, solution.part)).or_default().1 = Some(solution);
+    }
+
+    let mut table = Pretty::new(&[
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        ("Answer", Align::Left),
+        ("Before", Align::Right),
+        ("After", Align::Right),
+        ("Change", Align::Right),
+    ]);
+    let (mut changed, mut only_before, mut only_after) = (0, 0, 0);
+    for (&(day, part), &(a, b)) in &parts {
+        let answer = match (a, b) {
+            (Some(a), Some(b)) if !matches(&a.answer, &b.answer, Normalize::default()) => {
+                changed += 1;
+                format!("{} -> {}", a.answer, theme.paint(&b.answer, Style::Alert))
+            }
+            (Some(a), Some(_)) => a.answer.clone(),
+            (Some(a), None) => {
+                only_before += 1;
+                format!("{} -> {}", a.answer, theme.paint("-", Style::Alert))
+            }
+            (None, Some(b)) => {
+                only_after += 1;
+                format!("- -> {}", theme.paint(&b.answer, Style::Highlight))
+            }
+            (None, None) => unreachable!("every part comes from one of the runs"),
+        };
+        let (a_us, b_us) = (a.map(|a| a.elapsed_us), b.map(|b| b.elapsed_us));
+        table.row(vec![
+            day.to_string(),
+            part.to_string(),
+            answer,
+            elapsed(a_us),
+            elapsed(b_us),
+            change(a_us, b_us, theme),
+        ]);
+    }
+
+    let total = |run: &[Solution]| run.iter().map(|solution| solution.elapsed_us).sum::<u64>();
+    let (a_total, b_total) = (total(before), total(after));
+    table.row(vec![
+        String::new(),
+        String::new(),
+        String::from("Total"),
+        elapsed(Some(a_total)),
+        elapsed(Some(b_total)),
+        change(Some(a_total), Some(b_total), theme),
+    ]);
+
+    let mut out = table.to_string();
+    let _ = write!(out, "{} of {} answers changed", changed, parts.len());
+    if only_before > 0 {
+        let _ = write!(out, ", {} solved only by the first run", only_before);
+    }
+    if only_after > 0 {
+        let _ = write!(out, ", {} solved only by the second", only_after);
+    }
+    out.push('\n');
+    out
+}
+
+fn elapsed(us: Option<u64>) -> String {
+    us.map_or(String::from("-"), |us| duration(Duration::from_micros(us)))
+}
+
+// The relative timing change, faster in the highlight colour and slower as an
+// alert once it's beyond the noise
+fn change(before: Option<u64>, after: Option<u64>, theme: &Theme) -> String {
+    match (before, after) {
+        (Some(before), Some(after)) if before > 0 => {
+            let percent = (after as f64 - before as f64) / before as f64 * 100.0;
+            let text = format!("{:+.1}%", percent);
+            if percent <= -NOISE {
+                theme.paint(&text, Style::Highlight)
+            } else if percent >= NOISE {
+                theme.paint(&text, Style::Alert)
+            } else {
+                text
+            }
+        }
+        _ => String::from("-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(day: u32, part: u32, answer: &str, elapsed_us: u64) -> Solution {
+        Solution {
+            day,
+            part,
+            answer: String::from(answer),
+            elapsed_us,
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let before = [
+            solution(1, 1, "142", 200),
+            solution(1, 2, "281", 400),
+            solution(2, 1, "8", 100),
+        ];
+        let after = [
+            solution(1, 1, "142", 100),
+            solution(1, 2, "282", 404),
+            solution(3, 1, "4361", 1_000),
+        ];
+        let report = report(&before, &after, &Theme::plain());
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(
+            lines[1],
+            "│ Day ┆ Part ┆ Answer     ┆ Before ┆  After ┆  Change │"
+        );
+        assert_eq!(
+            lines[3],
+            "│   1 ┆    1 ┆ 142        ┆  200µs ┆  100µs ┆  -50.0% │"
+        );
+        assert_eq!(
+            lines[4],
+            "│   1 ┆    2 ┆ 281 -> 282 ┆  400µs ┆  404µs ┆   +1.0% │"
+        );
+        assert_eq!(
+            lines[5],
+            "│   2 ┆    1 ┆ 8 -> -     ┆  100µs ┆      - ┆       - │"
+        );
+        assert_eq!(
+            lines[6],
+            "│   3 ┆    1 ┆ - -> 4361  ┆      - ┆ 1.00ms ┆       - │"
+        );
+        assert_eq!(
+            lines[7],
+            "│     ┆      ┆ Total      ┆  700µs ┆ 1.50ms ┆ +114.9% │"
+        );
+        assert_eq!(
+            lines[9],
+            "1 of 4 answers changed, 1 solved only by the first run, 1 solved only by the second"
+        );
+    }
+
+    #[test]
+    fn test_change() {
+        let theme = Theme::new(ThemeName::Default);
+        assert_eq!(change(Some(100), Some(102), &theme), "+2.0%");
+        assert_eq!(
+            change(Some(100), Some(50), &theme),
+            "\x1b[1;32m-50.0%\x1b[0m"
+        );
+        assert_eq!(
+            change(Some(100), Some(200), &theme),
+            "\x1b[1;31m+100.0%\x1b[0m"
+        );
+        assert_eq!(change(Some(0), Some(200), &theme), "-");
+        assert_eq!(change(None, Some(200), &theme), "-");
+    }
+}
@@ -0,0 +1,379 @@
+use crate::theme::{Theme, ThemeName};
+use aoc_common::grid::{Grid, Point};
+use aoc_common::visualize::{Glyph, Inspect, Style};
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseEventKind,
+};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction as Axis, Layout, Rect};
+use ratatui::style::{Modifier, Style as TuiStyle};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Terminal;
+use std::io::{self, IsTerminal, Read};
+
+// zoom levels as (grid cells per character, characters per grid cell)
+const LEVELS: [(usize, usize); 5] = [(4, 1), (2, 1), (1, 1), (1, 2), (1, 3)];
+const DEFAULT_LEVEL: usize = 2;
+const PANEL_WIDTH: u16 = 36;
+
+#[derive(Debug, Args)]
+pub struct ExploreArgs {
+    /// Grid day to explore; the puzzle input is read from stdin
+    #[arg(long)]
+    pub day: u32,
+}
+
+pub fn run(args: ExploreArgs, theme: Option<ThemeName>) -> Result<()> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .wrap_err("failed to read the puzzle input from stdin")?;
+    let (grid, cells) = aoc_solver::visuals::explorable(args.day, &input)?;
+
+    let theme = Theme::select(theme, io::stderr().is_terminal());
+
+    // keys come from the terminal itself, since stdin held the input
+    let _tui = Tui::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stderr()))?;
+    let mut explorer = Explorer::new(&grid);
+    let mut view = Rect::default();
+    loop {
+        terminal.draw(|frame| {
+            let [map, panel] = split(frame.size());
+            view = inner(map);
+            explorer.follow(view);
+            frame.render_widget(explorer.map(&grid, args.day, &theme), map);
+            frame.render_widget(explorer.panel(&grid, cells.as_ref(), &theme), panel);
+        })?;
+
+        match event::read()? {
+            Event::Key(key) if !explorer.key(key, view) => break,
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Moved => {
+                explorer.hover(mouse.column, mouse.row, view);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Raw mode, the alternate screen and mouse reporting, undone however the
+// explorer ends
+struct Tui;
+
+impl Tui {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(Tui)
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture);
+    }
+}
+
+fn split(area: Rect) -> [Rect; 2] {
+    let chunks = Layout::default()
+        .direction(Axis::Horizontal)
+        .constraints([Constraint::Min(8), Constraint::Length(PANEL_WIDTH)])
+        .split(area);
+    [chunks[0], chunks[1]]
+}
+
+// the area inside a bordered block
+fn inner(area: Rect) -> Rect {
+    Block::default().borders(Borders::ALL).inner(area)
+}
+
+// how much a style matters when several cells share one character
+fn priority(style: Style) -> u8 {
+    match style {
+        Style::Dim => 0,
+        Style::Plain => 1,
+        Style::Accent => 2,
+        Style::Highlight => 3,
+        Style::Alert => 4,
+    }
+}
+
+// The most important glyph in the `block` by `block` square at `top`
+fn summarize(grid: &Grid<Glyph>, top: Point, block: usize) -> Glyph {
+    (top.row..top.row + block)
+        .flat_map(|row| (top.col..top.col + block).map(move |col| Point::new(row, col)))
+        .filter_map(|point| grid.get(point).copied())
+        .max_by_key(|glyph| priority(glyph.style))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Explorer {
+    width: usize,
+    height: usize,
+    cursor: Point,
+    // the grid cell drawn in the top left corner
+    origin: Point,
+    level: usize,
+}
+
+impl Explorer {
+    fn new(grid: &Grid<Glyph>) -> Self {
+        Explorer {
+            width: grid.width(),
+            height: grid.height(),
+            cursor: Point::new(0, 0),
+            origin: Point::new(0, 0),
+            level: DEFAULT_LEVEL,
+        }
+    }
+
+    fn block(&self) -> usize {
+        LEVELS[self.level].0
+    }
+
+    fn cell_width(&self) -> usize {
+        LEVELS[self.level].1
+    }
+
+    // grid cells that fit in `view`, as (rows, columns)
+    fn visible(&self, view: Rect) -> (usize, usize) {
+        let block = self.block();
+        let cols = (view.width as usize / self.cell_width()).max(1);
+        ((view.height as usize).max(1) * block, cols * block)
+    }
+
+    fn clamp(&self, row: isize, col: isize) -> Point {
+        Point::new(
+            row.clamp(0, self.height.saturating_sub(1) as isize) as usize,
+            col.clamp(0, self.width.saturating_sub(1) as isize) as usize,
+        )
+    }
+
+    // move the cursor by whole characters at the current zoom
+    fn step(&mut self, rows: isize, cols: isize) {
+        let block = self.block() as isize;
+        self.cursor = self.clamp(
+            self.cursor.row as isize + rows * block,
+            self.cursor.col as isize + cols * block,
+        );
+    }
+
+    // move the view by half a screen, taking the cursor along
+    fn pan(&mut self, rows: isize, cols: isize, view: Rect) {
+        let (visible_rows, visible_cols) = self.visible(view);
+        let (drow, dcol) = (
+            rows * (visible_rows / 2).max(1) as isize,
+            cols * (visible_cols / 2).max(1) as isize,
+        );
+        self.origin = self.clamp(
+            self.origin.row as isize + drow,
+            self.origin.col as isize + dcol,
+        );
+        self.cursor = self.clamp(
+            self.cursor.row as isize + drow,
+            self.cursor.col as isize + dcol,
+        );
+    }
+
+    fn zoom(&mut self, delta: isize) {
+        self.level = (self.level as isize + delta).clamp(0, LEVELS.len() as isize - 1) as usize;
+    }
+
+    // Scroll just enough to keep the cursor on screen, with the origin on a
+    // block boundary so zoomed out characters always cover the same cells
+    fn follow(&mut self, view: Rect) {
+        let (rows, cols) = self.visible(view);
+        let block = self.block();
+        let scroll = |origin: usize, cursor: usize, span: usize| {
+            let origin = if cursor < origin {
+                cursor
+            } else if cursor >= origin + span {
+                cursor + block - span
+            } else {
+                origin
+            };
+            origin - origin % block
+        };
+        self.origin = Point::new(
+            scroll(self.origin.row, self.cursor.row, rows),
+            scroll(self.origin.col, self.cursor.col, cols),
+        );
+    }
+
+    // point the cursor at the cell under the mouse
+    fn hover(&mut self, column: u16, row: u16, view: Rect) {
+        let inside = column >= view.x
+            && column < view.x + view.width
+            && row >= view.y
+            && row < view.y + view.height;
+        if !inside {
+            return;
+        }
+        let block = self.block();
+        self.cursor = self.clamp(
+            (self.origin.row + (row - view.y) as usize * block) as isize,
+            (self.origin.col + (column - view.x) as usize / self.cell_width() * block) as isize,
+        );
+    }
+
+    // false once asked to quit
+    fn key(&mut self, key: KeyEvent, view: Rect) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.step(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => self.step(1, 0),
+            KeyCode::Left | KeyCode::Char('h') => self.step(0, -1),
+            KeyCode::Right | KeyCode::Char('l') => self.step(0, 1),
+            KeyCode::PageUp | KeyCode::Char('K') => self.pan(-1, 0, view),
+            KeyCode::PageDown | KeyCode::Char('J') => self.pan(1, 0, view),
+            KeyCode::Char('H') => self.pan(0, -1, view),
+            KeyCode::Char('L') => self.pan(0, 1, view),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(1),
+            KeyCode::Char('-') => self.zoom(-1),
+            _ => {}
+        }
+        true
+    }
+
+    fn map(&self, grid: &Grid<Glyph>, day: u32, theme: &Theme) -> Paragraph<'static> {
+        let block = self.block();
+        let cell_width = self.cell_width();
+        let lines: Vec<Line> = (self.origin.row..self.height)
+            .step_by(block)
+            .map(|row| {
+                let spans: Vec<Span> = (self.origin.col..self.width)
+                    .step_by(block)
+                    .map(|col| {
+                        let top = Point::new(row, col);
+                        let glyph = summarize(grid, top, block);
+                        let mut style = theme.tui(glyph.style);
+                        let under = |c: usize, p: usize| (c..c + block).contains(&p);
+                        if under(row, self.cursor.row) && under(col, self.cursor.col) {
+                            style = style.add_modifier(Modifier::REVERSED);
+                        }
+                        let text = format!("{:<width$}", glyph.ch, width = cell_width);
+                        Span::styled(text, style)
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(
+            " Day {} ({}x{}, zoom {}:{}) ",
+            day, self.width, self.height, cell_width, block
+        )))
+    }
+
+    fn panel(&self, grid: &Grid<Glyph>, cells: &dyn Inspect, theme: &Theme) -> Paragraph<'static> {
+        let glyph = grid.get(self.cursor).copied().unwrap_or_default();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("({}, {})  '{}'", self.cursor.row, self.cursor.col, glyph.ch),
+                theme.tui(glyph.style),
+            )),
+            Line::from(""),
+        ];
+        let details = cells.inspect(self.cursor);
+        if details.is_empty() {
+            lines.push(Line::from("nothing of note"));
+        }
+        for (name, value) in details {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", name),
+                    TuiStyle::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value),
+            ]));
+        }
+        lines.push(Line::from(""));
+        for help in [
+            "arrows/hjkl  move",
+            "HJKL/PgUp/PgDn  pan",
+            "+/-  zoom",
+            "mouse  inspect",
+            "q  quit",
+        ] {
+            lines.push(Line::from(Span::styled(help, theme.tui(Style::Dim))));
+        }
+
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Cell "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<Glyph> {
+        let mut grid = Grid::new(20, 10, Glyph::new('.', Style::Dim));
+        grid[Point::new(1, 1)] = Glyph::new('*', Style::Accent);
+        grid[Point::new(0, 0)] = Glyph::new('4', Style::Highlight);
+        grid
+    }
+
+    #[test]
+    fn test_summarize() {
+        let grid = grid();
+        assert_eq!(
+            summarize(&grid, Point::new(0, 0), 2),
+            Glyph::new('4', Style::Highlight)
+        );
+        assert_eq!(summarize(&grid, Point::new(1, 1), 1).ch, '*');
+        assert_eq!(summarize(&grid, Point::new(8, 18), 4).style, Style::Dim);
+    }
+
+    #[test]
+    fn test_follow_and_pan() {
+        let mut explorer = Explorer::new(&grid());
+        let view = Rect::new(1, 1, 8, 4);
+
+        // the cursor stops at the edges
+        explorer.step(-1, 30);
+        assert_eq!(explorer.cursor, Point::new(0, 19));
+        explorer.follow(view);
+        assert_eq!(explorer.origin, Point::new(0, 12));
+
+        explorer.pan(1, 0, view);
+        assert_eq!(explorer.cursor, Point::new(2, 19));
+        assert_eq!(explorer.origin, Point::new(2, 12));
+
+        explorer.step(-1, -19);
+        explorer.follow(view);
+        assert_eq!(explorer.origin, Point::new(1, 0));
+    }
+
+    #[test]
+    fn test_zoom_and_hover() {
+        let mut explorer = Explorer::new(&grid());
+        let view = Rect::new(1, 1, 8, 4);
+
+        explorer.zoom(-1);
+        assert_eq!((explorer.block(), explorer.cell_width()), (2, 1));
+        explorer.hover(3, 2, view);
+        assert_eq!(explorer.cursor, Point::new(2, 4));
+
+        explorer.zoom(10);
+        assert_eq!((explorer.block(), explorer.cell_width()), (1, 3));
+        explorer.hover(7, 1, view);
+        assert_eq!(explorer.cursor, Point::new(0, 2));
+        // outside the map
+        explorer.hover(0, 0, view);
+        assert_eq!(explorer.cursor, Point::new(0, 2));
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(feature = "export-arrow")]
+use aoc_common::table::Column;
+use aoc_common::table::Table;
+#[cfg(feature = "export-arrow")]
+use arrow::array::{ArrayRef, BooleanArray, StringArray, UInt64Array};
+#[cfg(feature = "export-arrow")]
+use arrow::datatypes::{DataType, Field, Schema};
+#[cfg(feature = "export-arrow")]
+use arrow::ipc::writer::FileWriter;
+#[cfg(feature = "export-arrow")]
+use arrow::record_batch::RecordBatch;
+use clap::{Args, ValueEnum};
+use color_eyre::eyre::{Result, WrapErr};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "export-arrow")]
+use std::sync::Arc;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Arrow IPC file, readable by pyarrow, polars and duckdb
+    #[cfg(feature = "export-arrow")]
+    Arrow,
+    /// Comma-separated values with a header row, for spreadsheets
+    Csv,
+    /// Parquet, compressed and columnar, for dumps too large to open as CSV
+    #[cfg(feature = "export-parquet")]
+    Parquet,
+}
+
+impl ExportFormat {
+    // Arrow when it's built in, since it keeps the column types
+    #[cfg(feature = "export-arrow")]
+    pub const DEFAULT: ExportFormat = ExportFormat::Arrow;
+    #[cfg(not(feature = "export-arrow"))]
+    pub const DEFAULT: ExportFormat = ExportFormat::Csv;
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "export-arrow")]
+            ExportFormat::Arrow => "arrow",
+            ExportFormat::Csv => "csv",
+            #[cfg(feature = "export-parquet")]
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Day whose parsed entities to export; the puzzle input is read from stdin
+    #[arg(long)]
+    pub day: u32,
+
+    /// File to write
+    #[arg(long)]
+    pub out: PathBuf,
+
+    #[arg(long, value_enum, default_value_t = ExportFormat::DEFAULT)]
+    pub format: ExportFormat,
+}
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .wrap_err("failed to read the puzzle input from stdin")?;
+
+    let table = aoc_solver::records::records(args.day, &input)?;
+    write(args.format, &table, &args.out)
+}
+
+/// Write `table` to the file at `path` in `format`.
+pub fn write(format: ExportFormat, table: &Table, path: &Path) -> Result<()> {
+    let file =
+        File::create(path).wrap_err_with(|| format!("failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+
+    match format {
+        #[cfg(feature = "export-arrow")]
+        ExportFormat::Arrow => write_arrow(&mut out, table)?,
+        ExportFormat::Csv => table.write_csv(&mut out)?,
+        #[cfg(feature = "export-parquet")]
+        ExportFormat::Parquet => write_parquet(&mut out, table)?,
+    }
+    out.flush()?;
+    info!(
+        "Wrote {} {} to {}",
+        table.rows(),
+        table.name(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "export-arrow")]
+pub fn record_batch(table: &Table) -> Result<RecordBatch> {
+    let mut fields = Vec::new();
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+
+    for (name, column) in table.columns() {
+        let (data_type, array): (DataType, ArrayRef) = match column {
+            Column::UInt(values) => (
+                DataType::UInt64,
+                Arc::new(UInt64Array::from(values.clone())),
+            ),
+            Column::Bool(values) => (
+                DataType::Boolean,
+                Arc::new(BooleanArray::from(values.clone())),
+            ),
+            Column::Str(values) => (DataType::Utf8, Arc::new(StringArray::from(values.clone()))),
+        };
+        fields.push(Field::new(name, data_type, false));
+        arrays.push(array);
+    }
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+#[cfg(feature = "export-arrow")]
+pub fn write_arrow(out: impl Write, table: &Table) -> Result<()> {
+    let batch = record_batch(table)?;
+    let mut writer = FileWriter::try_new(out, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "export-parquet")]
+pub fn write_parquet(out: impl Write + Send, table: &Table) -> Result<()> {
+    let batch = record_batch(table)?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(out, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "export-arrow")]
+    #[test]
+    fn test_write_arrow() {
+        use arrow::array::Array;
+        use arrow::ipc::reader::FileReader;
+        use std::io::Cursor;
+
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let table = aoc_solver::records::records(2, &input).unwrap();
+
+        let mut out = Vec::new();
+        write_arrow(&mut out, &table).unwrap();
+
+        let reader = FileReader::try_new(Cursor::new(out), None).unwrap();
+        let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 5);
+
+        let power = batches[0]
+            .column_by_name("power")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(power.len(), 5);
+        assert_eq!(power.value(2), 1560);
+    }
+
+    #[cfg(feature = "export-parquet")]
+    #[test]
+    fn test_write_parquet() {
+        use arrow::array::Array;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let table = aoc_solver::records::records(2, &input).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc-export-{}.parquet", std::process::id()));
+
+        write(ExportFormat::Parquet, &table, &path).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 5);
+
+        let power = batches[0]
+            .column_by_name("power")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(power.value(2), 1560);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let table = aoc_solver::records::records(2, &input).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc-export-{}.csv", std::process::id()));
+
+        write(ExportFormat::Csv, &table, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            "id,rounds,min_red,min_green,min_blue,feasible,power"
+        );
+        assert_eq!(lines[3], "3,3,20,13,6,false,1560");
+    }
+}
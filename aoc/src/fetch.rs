@@ -0,0 +1,270 @@
+use aoc_common::input::{
+    input_dir, inputs_path, resolve_year, unlock_in, workspace_root, Resolved, Source, YEAR,
+};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use reqwest::header::{COOKIE, USER_AGENT};
+use reqwest::StatusCode;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+// the adventofcode.com session cookie, from the browser
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SITE: &str = "https://adventofcode.com";
+// adventofcode.com asks automated clients to say where they come from
+pub const AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/snarkipus/AoC2023)"
+);
+// between the requests of one batch, so many missing days don't hammer the site
+const GAP: Duration = Duration::from_secs(2);
+
+/// The puzzle page of `day` of `year`; its input and answers are under it.
+pub fn puzzle_url(year: u32, day: u32) -> String {
+    format!("{}/{}/day/{}", SITE, year, day)
+}
+
+/// `day`'s input for `year`, downloaded first if the workspace doesn't have
+/// it yet and a session cookie is set.
+pub fn ensure(year: u32, day: u32) -> Result<Resolved> {
+    let missing = match resolve_year(year, day) {
+        Ok(resolved) => return Ok(resolved),
+        Err(missing) => missing,
+    };
+    if std::env::var_os(SESSION_ENV).is_none() {
+        bail!(
+            "{}; set {} to download it, or pipe it in",
+            missing,
+            SESSION_ENV
+        );
+    }
+    info!("Day {}: downloading its input with {}", day, SESSION_ENV);
+    Downloads::start(year, vec![day])?.next().map_or_else(
+        || Err(eyre!("the download of day {} stopped", day)),
+        |(_, stored)| stored,
+    )
+}
+
+/// `request` run to completion from synchronous code, on a runtime like the
+/// one [`Downloads`] fetches on.
+pub fn block_on<F: Future>(request: F) -> Result<F::Output> {
+    Ok(runtime()?.block_on(request))
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime> {
+    tokio::runtime::Runtime::new().wrap_err("cannot start the async runtime for requests")
+}
+
+/// Inputs downloading in the background, handed over as each one lands.
+pub struct Downloads {
+    // dropping the runtime would cancel the downloads still running
+    _runtime: Option<tokio::runtime::Runtime>,
+    finished: Receiver<(u32, Result<Resolved>)>,
+}
+
+impl Downloads {
+    /// Start downloading the input of every day in `days` of `year` at once,
+    /// on a runtime of its own so the caller can go on solving meanwhile.
+    pub fn start(year: u32, days: Vec<u32>) -> Result<Self> {
+        let (sender, finished) = mpsc::channel();
+        if days.is_empty() {
+            return Ok(Downloads {
+                _runtime: None,
+                finished,
+            });
+        }
+
+        let session = std::env::var(SESSION_ENV).wrap_err_with(|| {
+            format!(
+                "downloading inputs needs {} set to your adventofcode.com session cookie",
+                SESSION_ENV
+            )
+        })?;
+        let client = reqwest::Client::new();
+        let runtime = runtime()?;
+        for (i, day) in days.into_iter().enumerate() {
+            let (sender, client, session) = (sender.clone(), client.clone(), session.clone());
+            runtime.spawn(async move {
+                tokio::time::sleep(GAP * i as u32).await;
+                let url = format!("{}/input", puzzle_url(year, day));
+                let fetched = if unlocked(year, day, now()) {
+                    fetch(&client, &url, &session).await
+                } else {
+                    // asking early only earns a 404
+                    Err(eyre!("day {} isn't unlocked yet", day))
+                };
+                let stored = match fetched {
+                    Ok(input) => store(year, day, input).await,
+                    Err(err) => Err(err),
+                };
+                // the receiver only goes away when the run has failed anyway
+                let _ = sender.send((day, stored));
+            });
+        }
+        Ok(Downloads {
+            _runtime: Some(runtime),
+            finished,
+        })
+    }
+}
+
+impl Iterator for Downloads {
+    type Item = (u32, Result<Resolved>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.finished.recv().ok()
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64)
+}
+
+fn unlocked(year: u32, day: u32, now: i64) -> bool {
+    (1..=25).contains(&day) && now >= unlock_in(year, day)
+}
+
+async fn fetch(client: &reqwest::Client, url: &str, session: &str) -> Result<String> {
+    let response = client
+        .get(url)
+        .header(COOKIE, format!("session={}", session))
+        .header(USER_AGENT, AGENT)
+        .send()
+        .await
+        .wrap_err_with(|| format!("failed to fetch {}", url))?;
+    match response.status() {
+        StatusCode::NOT_FOUND => bail!("{} isn't there (404); is the day unlocked?", url),
+        // what adventofcode.com answers an expired or mistyped cookie with
+        StatusCode::BAD_REQUEST => bail!(
+            "adventofcode.com turned down the session in {} (400); copy a fresh one",
+            SESSION_ENV
+        ),
+        _ => {}
+    }
+    response
+        .error_for_status()
+        .wrap_err_with(|| format!("failed to fetch {}", url))?
+        .text()
+        .await
+        .wrap_err_with(|| format!("failed to read {}", url))
+}
+
+// Save `input` where the resolver looks first, $AOC_INPUT_DIR (or its
+// directory for an earlier year) when it's set and the ignored inputs/YYYY
+// otherwise
+async fn store(year: u32, day: u32, input: String) -> Result<Resolved> {
+    let name = format!("day{:02}.txt", day);
+    let (path, source): (PathBuf, Source) = match input_dir() {
+        Some(dir) if year == YEAR => (dir.join(name), Source::Env),
+        Some(dir) => (dir.join(year.to_string()).join(name), Source::Env),
+        None => (
+            workspace_root().join(inputs_path(year, day)),
+            Source::Inputs,
+        ),
+    };
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    tokio::fs::write(&path, input)
+        .await
+        .wrap_err_with(|| format!("cannot write {}", path.display()))?;
+    let resolved = Resolved { path, source };
+    info!("Day {}: downloaded {}", day, resolved);
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input::unlock;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_fetch() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // a one-request server standing in for adventofcode.com
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/2023/day/2/input", listener.local_addr().unwrap());
+            let server = tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let read = stream.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]).into_owned();
+                let body = "Game 1: 3 blue\n";
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                request
+            });
+
+            let input = fetch(&reqwest::Client::new(), &url, "53cr3t")
+                .await
+                .unwrap();
+            assert_eq!(input, "Game 1: 3 blue\n");
+            let request = server.await.unwrap().to_lowercase();
+            assert!(request.starts_with("get /2023/day/2/input http/1.1\r\n"));
+            assert!(request.contains("\r\ncookie: session=53cr3t\r\n"));
+            assert!(request.contains("\r\nuser-agent: aoc/"));
+        });
+    }
+
+    #[test]
+    fn test_fetch_not_found() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!(
+                "http://{}/2023/day/25/input",
+                listener.local_addr().unwrap()
+            );
+            tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let _ = stream.read(&mut request).await.unwrap();
+                let response =
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                stream.write_all(response.as_bytes()).await.unwrap();
+            });
+
+            let err = fetch(&reqwest::Client::new(), &url, "53cr3t")
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("(404)"));
+        });
+    }
+
+    #[test]
+    fn test_unlocked() {
+        assert!(!unlocked(YEAR, 1, unlock(1) - 1));
+        assert!(unlocked(YEAR, 1, unlock(1)));
+        assert!(unlocked(YEAR, 2, unlock(25)));
+        assert!(!unlocked(YEAR, 26, unlock(25) + 86_400));
+        assert!(!unlocked(YEAR, 0, unlock(25)));
+        // an earlier year's days are long open, a later one's not yet
+        assert!(unlocked(2022, 25, unlock(1)));
+        assert!(!unlocked(YEAR + 1, 1, unlock(25)));
+    }
+
+    #[test]
+    fn test_puzzle_url() {
+        assert_eq!(puzzle_url(2023, 7), "https://adventofcode.com/2023/day/7");
+        assert_eq!(puzzle_url(2022, 1), "https://adventofcode.com/2022/day/1");
+    }
+
+    #[test]
+    fn test_downloads_nothing() {
+        // nothing missing needs no session and no runtime
+        let downloads = Downloads::start(YEAR, Vec::new()).unwrap();
+        assert!(downloads._runtime.is_none());
+        assert_eq!(downloads.count(), 0);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::output::Solution;
+use crate::theme::{Theme, ThemeName};
+use aoc_common::visualize::Style;
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+// overrides the default database location
+pub const HISTORY_ENV: &str = "AOC_HISTORY_DB";
+const DEFAULT_PATH: &str = ".aoc/history.sqlite3";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id          INTEGER PRIMARY KEY,
+    recorded_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    git_commit  TEXT
+);
+CREATE TABLE IF NOT EXISTS results (
+    run_id       INTEGER NOT NULL REFERENCES runs(id),
+    day          INTEGER NOT NULL,
+    part         INTEGER NOT NULL,
+    answer       TEXT NOT NULL,
+    elapsed_us   INTEGER NOT NULL,
+    input_sha256 TEXT NOT NULL,
+    parse_us     INTEGER
+);
+CREATE INDEX IF NOT EXISTS results_day_part ON results (day, part);
+";
+
+// width of the longest bar in the history chart
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// Day to chart
+    #[arg(long)]
+    pub day: u32,
+
+    /// Only chart this part
+    #[arg(long)]
+    pub part: Option<u32>,
+}
+
+/// One solved part to store: the SHA-256 of its input goes alongside, and
+/// the time spent parsing that input when the day has a separate parse step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub solution: Solution,
+    pub input_sha256: String,
+    pub parse: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub run_id: i64,
+    pub recorded_at: String,
+    pub commit: Option<String>,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub input_sha256: String,
+    pub parse: Option<Duration>,
+}
+
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open_default() -> Result<Self> {
+        let path = std::env::var(HISTORY_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_PATH));
+        Self::open(&path)
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)
+            .wrap_err_with(|| format!("failed to open history database {}", path.display()))?;
+        Self::with_connection(conn)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        // databases from before parse timing lack the column
+        let has_parse = conn
+            .prepare("SELECT 1 FROM pragma_table_info('results') WHERE name = 'parse_us'")?
+            .exists([])?;
+        if !has_parse {
+            conn.execute("ALTER TABLE results ADD COLUMN parse_us INTEGER", [])?;
+        }
+        Ok(History { conn })
+    }
+
+    /// Store one run.
+    pub fn record(&mut self, commit: Option<&str>, records: &[Record]) -> Result<i64> {
+        let tx = self.conn.transaction()?;
+        tx.execute("INSERT INTO runs (git_commit) VALUES (?1)", params![commit])?;
+        let run_id = tx.last_insert_rowid();
+        for record in records {
+            let solution = &record.solution;
+            tx.execute(
+                "INSERT INTO results (run_id, day, part, answer, elapsed_us, input_sha256, parse_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    run_id,
+                    solution.day,
+                    solution.part,
+                    solution.answer,
+                    solution.elapsed_us as i64,
+                    record.input_sha256,
+                    record.parse.map(|parse| parse.as_micros() as i64)
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(run_id)
+    }
+
+    /// Every stored result for a day, oldest run first.
+    pub fn entries(&self, day: u32, part: Option<u32>) -> Result<Vec<Entry>> {
+        self.query(Some(day), part)
+    }
+
+    /// Every stored result, by day and then part, oldest run first.
+    pub fn all_entries(&self) -> Result<Vec<Entry>> {
+        self.query(None, None)
+    }
+
+    fn query(&self, day: Option<u32>, part: Option<u32>) -> Result<Vec<Entry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.id, r.recorded_at, r.git_commit, s.day, s.part, s.answer, s.elapsed_us,
+                    s.input_sha256, s.parse_us
+             FROM results s JOIN runs r ON r.id = s.run_id
+             WHERE (?1 IS NULL OR s.day = ?1) AND (?2 IS NULL OR s.part = ?2)
+             ORDER BY s.day, s.part, r.id",
+        )?;
+        let entries = stmt
+            .query_map(params![day, part], |row| {
+                Ok(Entry {
+                    run_id: row.get(0)?,
+                    recorded_at: row.get(1)?,
+                    commit: row.get(2)?,
+                    day: row.get(3)?,
+                    part: row.get(4)?,
+                    answer: row.get(5)?,
+                    elapsed: Duration::from_micros(row.get::<_, i64>(6)? as u64),
+                    input_sha256: row.get(7)?,
+                    parse: row
+                        .get::<_, Option<i64>>(8)?
+                        .map(|us| Duration::from_micros(us as u64)),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+}
+
+pub fn input_sha256(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn run(args: HistoryArgs, theme: Option<ThemeName>) -> Result<()> {
+    let history = History::open_default()?;
+    let entries = history.entries(args.day, args.part)?;
+    let theme = Theme::select(theme, std::io::stdout().is_terminal());
+    print!("{}", chart(args.day, &entries, &theme));
+    Ok(())
+}
+
+// One bar per stored result, grouped by part, scaled to the slowest run
+fn chart(day: u32, entries: &[Entry], theme: &Theme) -> String {
+    let mut out = String::new();
+    if entries.is_empty() {
+        let _ = writeln!(out, "No history for day {}", day);
+        return out;
+    }
+
+    let slowest = entries.iter().map(|e| e.elapsed).max().unwrap_or_default();
+    let mut current_part = None;
+    for entry in entries {
+        if current_part != Some(entry.part) {
+            current_part = Some(entry.part);
+            let title = format!("Day {} Part {}", day, entry.part);
+            let _ = writeln!(out, "{}", theme.paint(&title, Style::Accent));
+        }
+
+        let width = if slowest.is_zero() {
+            0
+        } else {
+            ((entry.elapsed.as_secs_f64() / slowest.as_secs_f64()) * BAR_WIDTH as f64).ceil()
+                as usize
+        };
+        let _ = writeln!(
+            out,
+            "  #{:<4} {}  {:<8} {:>10}  {}",
+            entry.run_id,
+            entry.recorded_at,
+            entry.commit.as_deref().unwrap_or("-"),
+            format!("{:.2?}", entry.elapsed),
+            theme.paint(&"█".repeat(width), Style::Highlight)
+        );
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u32, part: u32, answer: &str, elapsed_us: u64, input_sha256: &str) -> Record {
+        Record {
+            solution: Solution {
+                day,
+                part,
+                answer: answer.to_string(),
+                elapsed_us,
+            },
+            input_sha256: input_sha256.to_string(),
+            parse: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_query() {
+        let mut history = History::open_in_memory().unwrap();
+        let hash = input_sha256("467..114..");
+
+        let first = history
+            .record(
+                Some("abc1234"),
+                &[
+                    record(3, 1, "4361", 2000, &hash),
+                    record(2, 1, "8", 10, &input_sha256("Game 1")),
+                ],
+            )
+            .unwrap();
+        let second = history
+            .record(None, &[record(3, 1, "4361", 500, &hash)])
+            .unwrap();
+        assert!(second > first);
+
+        let entries = history.entries(3, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].run_id, first);
+        assert_eq!(entries[0].commit.as_deref(), Some("abc1234"));
+        assert_eq!(entries[0].elapsed, Duration::from_millis(2));
+        assert_eq!(entries[1].commit, None);
+        assert_eq!(entries[1].input_sha256, hash);
+
+        assert_eq!(history.entries(3, Some(2)).unwrap().len(), 0);
+        assert_eq!(history.entries(2, Some(1)).unwrap()[0].answer, "8");
+
+        let all = history.all_entries().unwrap();
+        assert_eq!(all.iter().map(|e| e.day).collect::<Vec<_>>(), vec![2, 3, 3]);
+    }
+
+    #[test]
+    fn test_parse_column_migration() {
+        // a database written before parse timing was recorded
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&SCHEMA.replace(",\n    parse_us     INTEGER", ""))
+            .unwrap();
+        let mut history = History::with_connection(conn).unwrap();
+
+        let mut parsed = record(2, 1, "8", 300, "");
+        parsed.parse = Some(Duration::from_micros(120));
+        history.record(None, &[parsed]).unwrap();
+        let entries = history.entries(2, None).unwrap();
+        assert_eq!(entries[0].parse, Some(Duration::from_micros(120)));
+    }
+
+    #[test]
+    fn test_input_sha256() {
+        assert_eq!(
+            input_sha256(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_chart() {
+        let mut history = History::open_in_memory().unwrap();
+        history
+            .record(Some("abc1234"), &[record(3, 1, "4361", 2000, "")])
+            .unwrap();
+        history
+            .record(Some("def5678"), &[record(3, 1, "4361", 1000, "")])
+            .unwrap();
+
+        let out = chart(3, &history.entries(3, None).unwrap(), &Theme::plain());
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "Day 3 Part 1");
+        assert!(lines[1].contains("abc1234"));
+        assert_eq!(lines[1].matches('█').count(), BAR_WIDTH);
+        assert_eq!(lines[2].matches('█').count(), BAR_WIDTH / 2);
+
+        assert_eq!(chart(4, &[], &Theme::plain()), "No history for day 4\n");
+    }
+}
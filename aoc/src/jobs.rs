@@ -0,0 +1,170 @@
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use tracing::warn;
+
+// how long an idle thread sleeps before looking for work again, unless new
+// work wakes it first
+const IDLE: Duration = Duration::from_millis(10);
+
+/// Threads solving days side by side for `run --all --jobs N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jobs {
+    /// How many threads solve at once; 0 counts as 1
+    pub threads: usize,
+    /// Keep each thread on a core of its own, so timings never include a
+    /// move between cores
+    pub pin_cores: bool,
+}
+
+impl Jobs {
+    /// Run `work` on every task, in no particular order, returning what each
+    /// returned. Tasks are handed out as `tasks` yields them, so a slow
+    /// iterator (downloads, say) overlaps with the work; each thread takes
+    /// from a queue of its own first and steals from the others when that
+    /// runs dry.
+    pub fn run<T, R>(
+        &self,
+        tasks: impl IntoIterator<Item = T>,
+        work: impl Fn(T) -> R + Sync,
+    ) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+    {
+        let threads = self.threads.max(1);
+        let cores = if self.pin_cores {
+            cores(threads)
+        } else {
+            Vec::new()
+        };
+        let injector = Injector::new();
+        let workers: Vec<Worker<T>> = (0..threads).map(|_| Worker::new_fifo()).collect();
+        let stealers: Vec<Stealer<T>> = workers.iter().map(Worker::stealer).collect();
+        let fed = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = workers
+                .into_iter()
+                .enumerate()
+                .map(|(i, local)| {
+                    let (injector, stealers, fed, work) = (&injector, &stealers, &fed, &work);
+                    let core = cores.get(i).copied();
+                    scope.spawn(move || {
+                        if let Some(core) = core {
+                            if !core_affinity::set_for_current(core) {
+                                warn!("Could not pin a solving thread to core {}", core.id);
+                            }
+                        }
+                        let mut done = Vec::new();
+                        loop {
+                            // read before looking, so an empty look after the
+                            // last task was queued really means there's none
+                            let finished = fed.load(Ordering::Acquire);
+                            match find(&local, injector, stealers) {
+                                Some(task) => done.push(work(task)),
+                                None if finished => break done,
+                                None => thread::park_timeout(IDLE),
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            for task in tasks {
+                injector.push(task);
+                for handle in &handles {
+                    handle.thread().unpark();
+                }
+            }
+            fed.store(true, Ordering::Release);
+            for handle in &handles {
+                handle.thread().unpark();
+            }
+
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+}
+
+// The next task for a thread: its own queue first, then a batch from the
+// shared one, then one stolen from another thread
+fn find<T>(local: &Worker<T>, injector: &Injector<T>, stealers: &[Stealer<T>]) -> Option<T> {
+    local.pop().or_else(|| {
+        std::iter::repeat_with(|| {
+            injector
+                .steal_batch_and_pop(local)
+                .or_else(|| stealers.iter().map(Stealer::steal).collect())
+        })
+        .find(|steal| !steal.is_retry())
+        .and_then(Steal::success)
+    })
+}
+
+// A core for each of `threads`, sharing cores round-robin when there are more
+// threads than cores; none when the platform can't say which cores there are
+fn cores(threads: usize) -> Vec<core_affinity::CoreId> {
+    let Some(cores) = core_affinity::get_core_ids().filter(|cores| !cores.is_empty()) else {
+        warn!("Cannot list the CPU cores here, so solving threads won't be pinned");
+        return Vec::new();
+    };
+    if threads > cores.len() {
+        warn!(
+            "{} solving threads share {} cores, so some timings will include waiting",
+            threads,
+            cores.len()
+        );
+    }
+    cores.iter().copied().cycle().take(threads).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_run() {
+        let jobs = Jobs {
+            threads: 4,
+            pin_cores: false,
+        };
+        let mut squares = jobs.run(1..=100u64, |n| n * n);
+        squares.sort_unstable();
+        assert_eq!(squares, (1..=100u64).map(|n| n * n).collect::<Vec<_>>());
+
+        // one slow task doesn't hold up the rest, which the other threads take
+        let threads = Mutex::new(HashSet::new());
+        let slow = jobs.run(0..8, |n| {
+            threads.lock().unwrap().insert(thread::current().id());
+            if n == 0 {
+                thread::sleep(Duration::from_millis(50));
+            }
+            n
+        });
+        assert_eq!(slow.len(), 8);
+        assert!(threads.into_inner().unwrap().len() > 1);
+
+        assert!(jobs.run(Vec::<u32>::new(), |n| n).is_empty());
+    }
+
+    #[test]
+    fn test_pinned() {
+        let jobs = Jobs {
+            threads: 2,
+            pin_cores: true,
+        };
+        let mut days = jobs.run([3, 1, 2], |day| day);
+        days.sort_unstable();
+        assert_eq!(days, [1, 2, 3]);
+    }
+}
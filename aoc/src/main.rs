@@ -0,0 +1,126 @@
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::Result;
+use std::str::FromStr;
+use tracing_subscriber::{filter::targets::Targets, layer::SubscriberExt, util::SubscriberInitExt};
+
+mod animate;
+mod anonymize;
+mod budget;
+mod bundle;
+mod chart;
+mod config;
+mod diff;
+#[cfg(feature = "tui")]
+mod explore;
+mod export;
+mod fetch;
+mod history;
+mod jobs;
+mod new_day;
+mod notify;
+mod output;
+#[cfg(feature = "render")]
+mod render;
+mod report;
+mod run;
+mod scratch;
+mod scrub;
+#[cfg(feature = "serve")]
+mod serve;
+mod step;
+mod submit;
+mod theme;
+mod trace;
+mod visualize;
+mod watch;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Colours for terminal output; by default only terminals get colour, and never with
+    /// NO_COLOR set
+    #[arg(long, global = true, value_enum)]
+    theme: Option<theme::ThemeName>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Rewrite a day's input into one that gives the same answers, to share in a bug report
+    Anonymize(anonymize::AnonymizeArgs),
+    /// Draw every recorded run as an SVG bar chart, parse and solve time stacked per day
+    Chart(chart::ChartArgs),
+    /// Compare the answers and timings of two runs saved with `aoc run --format json`
+    Diff(diff::DiffArgs),
+    /// Browse a grid day's parsed grid in the terminal, inspecting cells under the cursor
+    #[cfg(feature = "tui")]
+    Explore(explore::ExploreArgs),
+    /// Export a day's parsed entities (games, numbers, ...) as a table
+    Export(export::ExportArgs),
+    /// Pack the latest results, the run history, input hashes (never the inputs) and a
+    /// description of this machine into one archive to share
+    ExportBundle(bundle::BundleArgs),
+    /// Chart how a day's solve times evolved across recorded runs
+    History(history::HistoryArgs),
+    /// Start a day: a Day-N/NAME crate wired to the Solver trait, with its example's tests, in
+    /// the workspace
+    NewDay(new_day::NewDayArgs),
+    /// Summarize the latest recorded results, or write them up as one HTML page with --html
+    Report(report::ReportArgs),
+    /// Solve every implemented part of a day
+    Run(run::RunArgs),
+    /// Evaluate a Rust expression over a day's parsed input, without touching its solver
+    Scratch(scratch::ScratchArgs),
+    /// Move tracked puzzle inputs to the ignored inputs/2023 and rewrite references to them
+    Scrub(scrub::ScrubArgs),
+    /// Serve the solvers over HTTP: POST the raw input to /solve/{day}/{part}
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: std::net::SocketAddr,
+    },
+    /// Solve one part of a day and submit the answer to adventofcode.com (AOC_SESSION must hold
+    /// your session cookie)
+    Submit(submit::SubmitArgs),
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let config = config::Config::load()?;
+    config.apply();
+    install_tracing(config.log_level.as_deref().unwrap_or("info"))?;
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Anonymize(args) => anonymize::run(args),
+        Command::Chart(args) => chart::run(args),
+        Command::Diff(args) => diff::run(args, cli.theme),
+        #[cfg(feature = "tui")]
+        Command::Explore(args) => explore::run(args, cli.theme),
+        Command::Export(args) => export::run(args),
+        Command::ExportBundle(args) => bundle::run(args),
+        Command::History(args) => history::run(args, cli.theme),
+        Command::NewDay(args) => new_day::run(args),
+        Command::Report(args) => report::run(args),
+        Command::Run(args) => run::run(args, cli.theme),
+        Command::Scratch(args) => scratch::run(args),
+        Command::Scrub(args) => scrub::run(args),
+        #[cfg(feature = "serve")]
+        Command::Serve { addr } => serve::run(addr),
+        Command::Submit(args) => submit::run(args),
+    }
+}
+
+fn install_tracing(level: &str) -> Result<()> {
+    let filter_layer = Targets::from_str(std::env::var("RUST_LOG").as_deref().unwrap_or(level))?;
+    // stdout is reserved for results so the runner composes with pipelines
+    let format_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(format_layer)
+        .init();
+    Ok(())
+}
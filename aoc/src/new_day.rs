@@ -0,0 +1,267 @@
+use aoc_common::input::workspace_root;
+use clap::Args;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+// the new crate's files; {day}, {name}, {ident} and {Type} are filled in
+const MANIFEST: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[[bin]]
+name = "{name}"
+required-features = ["cli"]
+
+[features]
+default = ["cli"]
+# everything only the binary needs; use default-features = false for the bare library
+cli = ["aoc-common/timing"]
+
+[dependencies]
+aoc-common = { path = "../../aoc-common", default-features = false, features = ["registry"] }
+color-eyre = "0.6.2"
+tracing = "0.1.40"
+
+[dev-dependencies]
+test-case = "3.3.1"
+"#;
+
+const LIB: &str = r#"use aoc_common::registry::{aoc, Solver};
+use color_eyre::eyre::{bail, Result};
+
+/// The puzzle input, parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle(pub Vec<String>);
+
+impl Puzzle {
+    pub fn part1(&self) -> Result<u64> {
+        bail!("part 1 is not solved yet")
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        bail!("part 2 is not solved yet")
+    }
+}
+
+#[aoc(day = {day}, part = 1)]
+pub fn part1(lines: Vec<String>) -> Result<u64> {
+    parse_data(lines)?.part1()
+}
+
+#[aoc(day = {day}, part = 2)]
+pub fn part2(lines: Vec<String>) -> Result<u64> {
+    parse_data(lines)?.part2()
+}
+
+pub fn parse_data(lines: Vec<String>) -> Result<Puzzle> {
+    Ok(Puzzle(lines))
+}
+
+/// Day {day}'s parts behind one value, for code that takes any [`Solver`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct {Type};
+
+impl Solver for {Type} {
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(part1(input.lines().map(String::from).collect())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(part2(input.lines().map(String::from).collect())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // fill in the example's answers from the puzzle text, then drop the ignores
+    #[test]
+    #[ignore = "the example's answer isn't filled in yet"]
+    fn test_part1() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!({Type}.part1(&input).unwrap(), "0");
+    }
+
+    #[test]
+    #[ignore = "the example's answer isn't filled in yet"]
+    fn test_part2() {
+        let input = std::fs::read_to_string("../test-1.txt").unwrap();
+        assert_eq!({Type}.part2(&input).unwrap(), "0");
+    }
+}
+"#;
+
+const MAIN: &str = r#"aoc_common::aoc_main!(
+    day = {day},
+    parse = {ident}::parse_data,
+    part1 = {ident}::Puzzle::part1,
+    part2 = {ident}::Puzzle::part2
+);
+"#;
+
+#[derive(Debug, Args)]
+pub struct NewDayArgs {
+    /// Day to start
+    #[arg(long)]
+    pub day: u32,
+
+    /// Name of the day's crate, after the puzzle (e.g. scratchcards); it goes in Day-N/NAME
+    pub name: String,
+}
+
+pub fn run(args: NewDayArgs) -> Result<()> {
+    let root = workspace_root();
+    let member = scaffold(&root, args.day, &args.name)?;
+    println!("Created {}", member);
+    println!(
+        "Paste the example into Day-{}/test-1.txt and its answers into the tests; add {} to \
+         aoc-solver's dependencies for aoc run to find it",
+        args.day, args.name
+    );
+    Ok(())
+}
+
+// Write the day's crate and its example file, and add the crate to the
+// workspace; the member's path, relative to `root`
+fn scaffold(root: &Path, day: u32, name: &str) -> Result<String> {
+    if !(1..=25).contains(&day) {
+        bail!("there is no day {}; Advent of Code runs from 1 to 25", day);
+    }
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        bail!(
+            "{:?} can't name a crate here; use lowercase words joined by hyphens",
+            name
+        );
+    }
+    let member = format!("Day-{}/{}", day, name);
+    let dir = root.join(&member);
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+
+    let manifest = root.join("Cargo.toml");
+    let workspace = std::fs::read_to_string(&manifest)
+        .wrap_err_with(|| format!("cannot read {}", manifest.display()))?;
+    let workspace = add_member(&workspace, &member)
+        .ok_or_else(|| eyre!("{} has no workspace members to join", manifest.display()))?;
+
+    let fill = |template: &str| {
+        template
+            .replace("{day}", &day.to_string())
+            .replace("{name}", name)
+            .replace("{ident}", &name.replace('-', "_"))
+            .replace("{Type}", &type_name(name))
+    };
+    let files: [(PathBuf, String); 3] = [
+        (dir.join("Cargo.toml"), fill(MANIFEST)),
+        (dir.join("src").join("lib.rs"), fill(LIB)),
+        (dir.join("src").join("main.rs"), fill(MAIN)),
+    ];
+    std::fs::create_dir_all(dir.join("src"))?;
+    for (path, text) in files {
+        std::fs::write(&path, text).wrap_err_with(|| format!("cannot write {}", path.display()))?;
+        info!("Wrote {}", path.display());
+    }
+    let example = root.join(format!("Day-{}", day)).join("test-1.txt");
+    if !example.exists() {
+        std::fs::write(&example, "")?;
+    }
+    std::fs::write(&manifest, workspace)
+        .wrap_err_with(|| format!("cannot write {}", manifest.display()))?;
+    Ok(member)
+}
+
+// `manifest` with `member` listed after the last day of this year, or last
+// when there's none yet
+fn add_member(manifest: &str, member: &str) -> Option<String> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let start = lines.iter().position(|line| line.starts_with("members"))?;
+    let end = start + lines[start..].iter().position(|line| line.trim() == "]")?;
+    let at = (start + 1..end)
+        .rfind(|&i| lines[i].trim_start().starts_with("\"Day-"))
+        .map_or(end, |i| i + 1);
+
+    let entry = format!("    \"{}\",", member);
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(&entry);
+    out.extend(&lines[at..]);
+    Some(out.join("\n") + "\n")
+}
+
+// the crate's name as a type, e.g. bag-game to BagGame
+fn type_name(name: &str) -> String {
+    name.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \
+                             \"Day-1/trebuchet\",\n    \"2022/Day-1/calorie-counting\",\n    \
+                             \"xtask\",\n]\n";
+
+    #[test]
+    fn test_add_member() {
+        let manifest = add_member(WORKSPACE, "Day-4/scratchcards").unwrap();
+        assert!(manifest
+            .contains("    \"Day-1/trebuchet\",\n    \"Day-4/scratchcards\",\n    \"2022/Day-1"));
+        let manifest =
+            add_member("[workspace]\nmembers = [\n    \"aoc\",\n]\n", "Day-1/x").unwrap();
+        assert_eq!(
+            manifest,
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"Day-1/x\",\n]\n"
+        );
+        assert_eq!(add_member("[package]\n", "Day-1/x"), None);
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("bag-game"), "BagGame");
+        assert_eq!(type_name("scratchcards"), "Scratchcards");
+        assert_eq!(type_name("day-4b"), "Day4b");
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-new-day-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+
+        assert_eq!(
+            scaffold(&root, 4, "scratchcards").unwrap(),
+            "Day-4/scratchcards"
+        );
+        let lib = std::fs::read_to_string(root.join("Day-4/scratchcards/src/lib.rs")).unwrap();
+        assert!(lib.contains("#[aoc(day = 4, part = 2)]"));
+        assert!(lib.contains("impl Solver for Scratchcards {"));
+        let main = std::fs::read_to_string(root.join("Day-4/scratchcards/src/main.rs")).unwrap();
+        assert!(main.starts_with("aoc_common::aoc_main!(\n    day = 4,\n"));
+        assert!(main.contains("parse = scratchcards::parse_data,"));
+        assert!(main.contains("part2 = scratchcards::Puzzle::part2\n);"));
+        assert!(root.join("Day-4/test-1.txt").exists());
+        let workspace = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(workspace.contains("\"Day-4/scratchcards\","));
+
+        assert!(scaffold(&root, 4, "scratchcards").is_err());
+        assert!(scaffold(&root, 26, "later").is_err());
+        assert!(scaffold(&root, 5, "Bad_Name").is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
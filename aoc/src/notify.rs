@@ -0,0 +1,143 @@
+use crate::budget::Overrun;
+use aoc_common::cancel::Cancelled;
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+// Slack or Discord incoming webhook to notify when `run --all` finishes
+pub const WEBHOOK_ENV: &str = "AOC_WEBHOOK_URL";
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Summary {
+    pub days: usize,
+    pub solved: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub elapsed: Duration,
+    pub over_budget: Vec<Overrun>,
+    /// Set when the run stopped before every day was solved
+    pub cancelled: Option<Cancelled>,
+    /// Time spent reading and parsing inputs while days were solving
+    pub overlap: Duration,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AoC 2023 run finished: {} days, {} parts solved, {} days failed, {} skipped (no input) in {:.2?}",
+            self.days, self.solved, self.failed, self.skipped, self.elapsed
+        )?;
+        if !self.overlap.is_zero() {
+            write!(f, " (reading inputs ahead saved {:.2?})", self.overlap)?;
+        }
+        for overrun in &self.over_budget {
+            write!(f, "; {}", overrun)?;
+        }
+        if let Some(reason) = self.cancelled {
+            write!(f, "; {} before every day was solved", reason)?;
+        }
+        Ok(())
+    }
+}
+
+// Slack reads `text` and Discord reads `content`; each ignores the other,
+// so one payload works for both.
+#[derive(Debug, Serialize)]
+struct Payload {
+    text: String,
+    content: String,
+}
+
+impl From<&Summary> for Payload {
+    fn from(summary: &Summary) -> Self {
+        let message = summary.to_string();
+        Payload {
+            text: message.clone(),
+            content: message,
+        }
+    }
+}
+
+pub fn post(url: &str, summary: &Summary) -> Result<()> {
+    reqwest::blocking::Client::new()
+        .post(url)
+        .json(&Payload::from(summary))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .wrap_err("failed to post the run summary")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        let summary = Summary {
+            days: 2,
+            solved: 3,
+            failed: 0,
+            skipped: 1,
+            elapsed: Duration::from_millis(12),
+            over_budget: Vec::new(),
+            cancelled: None,
+            overlap: Duration::ZERO,
+        };
+        let json = serde_json::to_value(Payload::from(&summary)).unwrap();
+        let expected =
+            "AoC 2023 run finished: 2 days, 3 parts solved, 0 days failed, 1 skipped (no input) in 12.00ms";
+        assert_eq!(json["text"], expected);
+        assert_eq!(json["content"], expected);
+    }
+
+    #[test]
+    fn test_summary_over_budget() {
+        let summary = Summary {
+            days: 1,
+            solved: 2,
+            elapsed: Duration::from_millis(1500),
+            over_budget: vec![Overrun {
+                day: 3,
+                elapsed: Duration::from_millis(1400),
+                budget: Duration::from_secs(1),
+            }],
+            ..Summary::default()
+        };
+        assert!(summary
+            .to_string()
+            .ends_with("in 1.50s; day 3 took 1.40s, over its 1s budget"));
+    }
+
+    #[test]
+    fn test_summary_cancelled() {
+        let summary = Summary {
+            days: 1,
+            solved: 1,
+            elapsed: Duration::from_secs(30),
+            cancelled: Some(Cancelled::TimedOut),
+            ..Summary::default()
+        };
+        assert!(summary
+            .to_string()
+            .ends_with("in 30.00s; timed out before every day was solved"));
+    }
+
+    #[test]
+    fn test_summary_overlap() {
+        let summary = Summary {
+            days: 2,
+            solved: 4,
+            elapsed: Duration::from_millis(20),
+            overlap: Duration::from_micros(3500),
+            ..Summary::default()
+        };
+        assert!(summary
+            .to_string()
+            .ends_with("in 20.00ms (reading inputs ahead saved 3.50ms)"));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::theme::Theme;
+use aoc_common::pretty::{duration, Align, Pretty};
+use aoc_common::visualize::Style;
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One human readable line per part
+    Text,
+    /// A JSON array of solutions, nothing else on stdout
+    Json,
+    /// The same array as MessagePack (maps with field names)
+    Msgpack,
+    /// The same array as CBOR
+    Cbor,
+    /// The same array as a YAML sequence
+    Yaml,
+    /// A TOML document with one [[solutions]] table per part
+    Toml,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub elapsed_us: u64,
+}
+
+// TOML documents can't be a bare array and its integers stop at i64
+#[derive(Serialize)]
+struct TomlSolutions<'a> {
+    solutions: Vec<TomlSolution<'a>>,
+}
+
+#[derive(Serialize)]
+struct TomlSolution<'a> {
+    day: u32,
+    part: u32,
+    answer: &'a str,
+    elapsed_us: u64,
+}
+
+impl Solution {
+    pub fn new(day: u32, part: u32, answer: String, elapsed: Duration) -> Self {
+        Solution {
+            day,
+            part,
+            answer,
+            // only a run of over half a million years saturates
+            elapsed_us: elapsed.as_micros().try_into().unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Write `solutions` in `format`; only text output is styled, with `theme`.
+pub fn write_solutions(
+    out: &mut impl Write,
+    format: Format,
+    solutions: &[Solution],
+    theme: &Theme,
+) -> Result<()> {
+    match format {
+        Format::Text => {
+            for solution in solutions {
+                writeln!(
+                    out,
+                    "Day {} Part {}: {} ({:?})",
+                    solution.day,
+                    solution.part,
+                    theme.paint(&solution.answer, Style::Highlight),
+                    Duration::from_micros(solution.elapsed_us)
+                )?;
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer(&mut *out, solutions)?;
+            writeln!(out)?;
+        }
+        Format::Msgpack => rmp_serde::encode::write_named(out, solutions)?,
+        Format::Cbor => ciborium::into_writer(solutions, out)?,
+        Format::Yaml => serde_yaml::to_writer(out, solutions)?,
+        Format::Toml => {
+            let document = TomlSolutions {
+                solutions: solutions
+                    .iter()
+                    .map(|solution| TomlSolution {
+                        day: solution.day,
+                        part: solution.part,
+                        answer: &solution.answer,
+                        elapsed_us: solution.elapsed_us,
+                    })
+                    .collect(),
+            };
+            write!(out, "{}", toml::to_string(&document)?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Text output for many days at once: a table of every part and its time,
+/// with a total row, instead of a line per part.
+pub fn write_table(out: &mut impl Write, solutions: &[Solution], theme: &Theme) -> Result<()> {
+    let mut table = Pretty::new(&[
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        ("Answer", Align::Left),
+        ("Time", Align::Right),
+    ]);
+    for solution in solutions {
+        table.row(vec![
+            solution.day.to_string(),
+            solution.part.to_string(),
+            theme.paint(&solution.answer, Style::Highlight),
+            duration(Duration::from_micros(solution.elapsed_us)),
+        ]);
+    }
+    let total: u64 = solutions.iter().map(|solution| solution.elapsed_us).sum();
+    table.row(vec![
+        String::new(),
+        String::new(),
+        String::from("Total"),
+        duration(Duration::from_micros(total)),
+    ]);
+    write!(out, "{}", table)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solutions() -> Vec<Solution> {
+        vec![
+            Solution::new(2, 1, String::from("8"), Duration::from_micros(150)),
+            Solution::new(2, 2, String::from("2286"), Duration::from_millis(2)),
+        ]
+    }
+
+    #[test]
+    fn test_write_text() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Text, &solutions(), &Theme::plain()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Day 2 Part 1: 8 (150µs)\nDay 2 Part 2: 2286 (2ms)\n"
+        );
+    }
+
+    #[test]
+    fn test_write_table() {
+        let mut out = Vec::new();
+        write_table(&mut out, &solutions(), &Theme::plain()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[1], "│ Day ┆ Part ┆ Answer ┆   Time │");
+        assert_eq!(lines[3], "│   2 ┆    1 ┆ 8      ┆  150µs │");
+        assert_eq!(lines[4], "│   2 ┆    2 ┆ 2286   ┆ 2.00ms │");
+        assert_eq!(lines[5], "│     ┆      ┆ Total  ┆ 2.15ms │");
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Json, &solutions(), &Theme::plain()).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json[0]["answer"], "8");
+        assert_eq!(json[1]["part"], 2);
+        assert_eq!(json[1]["elapsed_us"], 2000);
+    }
+
+    #[test]
+    fn test_write_msgpack() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Msgpack, &solutions(), &Theme::plain()).unwrap();
+        let value: serde_json::Value = rmp_serde::from_slice(&out).unwrap();
+        assert_eq!(value[0]["answer"], "8");
+        assert_eq!(value[1]["elapsed_us"], 2000);
+    }
+
+    #[test]
+    fn test_write_cbor() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Cbor, &solutions(), &Theme::plain()).unwrap();
+        let value: serde_json::Value = ciborium::from_reader(out.as_slice()).unwrap();
+        assert_eq!(value[0]["answer"], "8");
+        assert_eq!(value[1]["elapsed_us"], 2000);
+    }
+
+    #[test]
+    fn test_write_yaml() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Yaml, &solutions(), &Theme::plain()).unwrap();
+        let value: serde_json::Value = serde_yaml::from_slice(&out).unwrap();
+        assert_eq!(value[0]["answer"], "8");
+        assert_eq!(value[1]["elapsed_us"], 2000);
+    }
+
+    #[test]
+    fn test_write_toml() {
+        let mut out = Vec::new();
+        write_solutions(&mut out, Format::Toml, &solutions(), &Theme::plain()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("[[solutions]]\nday = 2\npart = 1\nanswer = \"8\"\n"));
+        let value: toml::Value = toml::from_str(&text).unwrap();
+        assert_eq!(value["solutions"][0]["answer"].as_str(), Some("8"));
+        assert_eq!(value["solutions"][1]["elapsed_us"].as_integer(), Some(2000));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::animate::{within_budget, Playback};
+use aoc_common::grid::Grid;
+use aoc_common::raster;
+use aoc_common::visualize::{Glyph, Scene};
+use color_eyre::eyre::{bail, ensure, Result, WrapErr};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use tracing::info;
+
+// pixels per grid cell
+const CELL: u32 = 8;
+
+/// Save frames as an animated GIF when `path` ends in `.gif`, otherwise as
+/// numbered PNGs in the directory `path`.
+pub fn save(frames: &[Scene], playback: Playback, path: &Path) -> Result<()> {
+    ensure!(playback.fps > 0.0, "the frame rate must be positive");
+    let grids = within_budget(frames.len(), playback.budget)
+        .into_iter()
+        .map(|i| match &frames[i] {
+            Scene::Grid(grid) => Ok(grid),
+            _ => bail!("only grid scenes can be rendered to images"),
+        })
+        .collect::<Result<Vec<&Grid<Glyph>>>>()?;
+
+    if path.extension().is_some_and(|ext| ext == "gif") {
+        let file =
+            File::create(path).wrap_err_with(|| format!("failed to create {}", path.display()))?;
+        let delay_ms = (1000.0 / playback.fps).round() as u32;
+        raster::write_gif(BufWriter::new(file), grids.iter().copied(), CELL, delay_ms)?;
+    } else {
+        std::fs::create_dir_all(path)
+            .wrap_err_with(|| format!("failed to create {}", path.display()))?;
+        for (i, grid) in grids.iter().enumerate() {
+            raster::save_png(grid, CELL, path.join(format!("frame-{:04}.png", i + 1)))?;
+        }
+    }
+
+    info!("Rendered {} frames to {}", grids.len(), path.display());
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use crate::chart;
+use crate::history::{Entry, History};
+use aoc_common::visualize::{Scene, Style};
+use clap::Args;
+use color_eyre::eyre::{Result, WrapErr};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use tracing::info;
+
+// size of one grid cell, and of one line of a list, in SVG units
+const CELL: usize = 14;
+const LINE: usize = 18;
+// at most this many scenes per day; Day 2 has one per game
+const MAX_SCENES: usize = 12;
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Write a self-contained HTML report (results, timing chart and each day's visuals, drawn
+    /// from its input) instead of printing the latest results
+    #[arg(long)]
+    pub html: Option<PathBuf>,
+}
+
+pub fn run(args: ReportArgs) -> Result<()> {
+    let entries = History::open_default()?.all_entries()?;
+    match args.html {
+        Some(path) => {
+            std::fs::write(&path, html(&entries, &visuals()))
+                .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+            info!("Wrote the report to {}", path.display());
+        }
+        None => print!("{}", summary(&latest(&entries))),
+    }
+    Ok(())
+}
+
+// The most recent result for every day and part
+pub fn latest(entries: &[Entry]) -> Vec<&Entry> {
+    let mut latest: BTreeMap<(u32, u32), &Entry> = BTreeMap::new();
+    for entry in entries {
+        let known = latest.entry((entry.day, entry.part)).or_insert(entry);
+        if entry.run_id > known.run_id {
+            *known = entry;
+        }
+    }
+    latest.into_values().collect()
+}
+
+fn summary(latest: &[&Entry]) -> String {
+    if latest.is_empty() {
+        return String::from("No recorded runs\n");
+    }
+    let mut out = String::new();
+    for entry in latest {
+        let _ = writeln!(
+            out,
+            "Day {} Part {}: {} ({:.2?}, {})",
+            entry.day,
+            entry.part,
+            entry.answer,
+            entry.elapsed,
+            entry.commit.as_deref().unwrap_or("-")
+        );
+    }
+    out
+}
+
+// An SVG per visual of every day with an input to draw from: the day's own
+// rendering when it has one, its scenes otherwise
+fn visuals() -> Vec<(u32, Vec<String>)> {
+    (1..=25)
+        .filter_map(|day| {
+            let path = aoc_common::input::resolve(day).ok()?.path;
+            let input = std::fs::read_to_string(path).ok()?;
+            if let Ok(svg) = aoc_solver::visuals::svg(day, &input) {
+                return Some((day, vec![svg]));
+            }
+            let scenes = aoc_solver::visuals::scenes(day, &input).ok()?;
+            let svgs = scenes
+                .iter()
+                .take(MAX_SCENES)
+                .map(|(title, scene)| scene_svg(title, scene))
+                .collect();
+            Some((day, svgs))
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn fill(style: Style) -> &'static str {
+    match style {
+        Style::Plain => "#333333",
+        Style::Dim => "#bbbbbb",
+        Style::Highlight => "#2a9d4b",
+        Style::Accent => "#d4a017",
+        Style::Alert => "#d33333",
+    }
+}
+
+/// Any scene as a small SVG: grids cell by cell, lists and graphs as lines
+/// of text, everything coloured by style.
+pub fn scene_svg(title: &str, scene: &Scene) -> String {
+    let mut body = String::new();
+    let (width, height) = match scene {
+        Scene::Grid(grid) => {
+            for point in grid.points() {
+                let glyph = grid[point];
+                let _ = writeln!(
+                    body,
+                    r#"<text x="{}" y="{}" text-anchor="middle" fill="{}">{}</text>"#,
+                    point.col * CELL + CELL / 2,
+                    LINE + point.row * CELL + CELL * 3 / 4,
+                    fill(glyph.style),
+                    escape(&glyph.ch.to_string())
+                );
+            }
+            (grid.width() * CELL, LINE + grid.height() * CELL)
+        }
+        Scene::List(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let _ = writeln!(
+                    body,
+                    r#"<text x="0" y="{}" fill="{}">{}</text>"#,
+                    LINE * (i + 2),
+                    fill(item.style),
+                    escape(&item.text)
+                );
+            }
+            let widest = items.iter().map(|item| item.text.len()).max().unwrap_or(0);
+            (widest * CELL * 2 / 3, LINE * (items.len() + 1) + LINE / 2)
+        }
+        Scene::Graph { nodes, edges } => {
+            let lines: Vec<(String, Style)> = nodes
+                .iter()
+                .enumerate()
+                .map(|(i, node)| (format!("{:>3} {}", i, node.text), node.style))
+                .chain(edges.iter().map(|edge| {
+                    let label = edge.label.as_deref().unwrap_or_default();
+                    (
+                        format!("{} -> {} {}", edge.from, edge.to, label),
+                        Style::Dim,
+                    )
+                }))
+                .collect();
+            for (i, (text, style)) in lines.iter().enumerate() {
+                let _ = writeln!(
+                    body,
+                    r#"<text x="0" y="{}" fill="{}" xml:space="preserve">{}</text>"#,
+                    LINE * (i + 2),
+                    fill(*style),
+                    escape(text)
+                );
+            }
+            let widest = lines.iter().map(|(text, _)| text.len()).max().unwrap_or(0);
+            (widest * CELL * 2 / 3, LINE * (lines.len() + 1) + LINE / 2)
+        }
+    };
+
+    let width = width.max(title.len() * CELL * 2 / 3);
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="monospace" font-size="12" text-anchor="start">"#,
+        w = width,
+        h = height
+    );
+    svg.push('\n');
+    let _ = writeln!(
+        svg,
+        r#"<text x="0" y="{}" font-weight="bold">{}</text>"#,
+        LINE * 3 / 4,
+        escape(title)
+    );
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// One page with the latest results, the timing chart and each day's
+/// visuals inline, so it opens anywhere without other files.
+fn html(entries: &[Entry], visuals: &[(u32, Vec<String>)]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2023</title>\n<style>\n\
+         body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222; }\n\
+         table { border-collapse: collapse; }\n\
+         th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; text-align: left; }\n\
+         td.answer { font-family: monospace; }\n\
+         .visuals { display: flex; flex-wrap: wrap; gap: 1.5em; align-items: flex-start; }\n\
+         </style>\n</head>\n<body>\n<h1>Advent of Code 2023</h1>\n",
+    );
+
+    out.push_str("<h2>Results</h2>\n");
+    let latest = latest(entries);
+    if latest.is_empty() {
+        out.push_str("<p>No recorded runs.</p>\n");
+    } else {
+        out.push_str(
+            "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th>\
+             <th>Commit</th><th>Recorded</th></tr>\n",
+        );
+        for entry in latest {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"answer\">{}</td><td>{:.2?}</td>\
+                 <td>{}</td><td>{}</td></tr>",
+                entry.day,
+                entry.part,
+                escape(&entry.answer),
+                entry.elapsed,
+                escape(entry.commit.as_deref().unwrap_or("-")),
+                escape(&entry.recorded_at)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("<h2>Timings</h2>\n");
+    out.push_str(&chart::svg(entries));
+
+    for (day, svgs) in visuals {
+        let _ = writeln!(out, "<h2>Day {}</h2>\n<div class=\"visuals\">", day);
+        for svg in svgs {
+            out.push_str(svg);
+        }
+        out.push_str("</div>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::grid::Grid;
+    use aoc_common::visualize::{Glyph, Item};
+    use std::time::Duration;
+
+    fn entry(run_id: i64, day: u32, part: u32, answer: &str) -> Entry {
+        Entry {
+            run_id,
+            recorded_at: String::from("2023-12-03 10:00:00"),
+            commit: None,
+            day,
+            part,
+            answer: answer.to_string(),
+            elapsed: Duration::from_micros(150),
+            input_sha256: String::new(),
+            parse: None,
+        }
+    }
+
+    #[test]
+    fn test_latest() {
+        let entries = [
+            entry(1, 2, 1, "7"),
+            entry(2, 2, 1, "8"),
+            entry(1, 2, 2, "2286"),
+            entry(1, 1, 1, "142"),
+        ];
+        let latest = latest(&entries);
+        let answers: Vec<&str> = latest.iter().map(|e| e.answer.as_str()).collect();
+        assert_eq!(answers, vec!["142", "8", "2286"]);
+        assert_eq!(
+            summary(&latest).lines().nth(1),
+            Some("Day 2 Part 1: 8 (150.00µs, -)")
+        );
+        assert_eq!(summary(&[]), "No recorded runs\n");
+    }
+
+    #[test]
+    fn test_scene_svg() {
+        let grid = Grid::from_rows(vec![vec![
+            Glyph::new('<', Style::Accent),
+            Glyph::new('4', Style::Highlight),
+        ]]);
+        let svg = scene_svg("Schematic", &Scene::Grid(grid));
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"fill="#d4a017">&lt;</text>"##));
+        assert_eq!(svg.matches("<text").count(), 3);
+
+        let svg = scene_svg(
+            "Game 1",
+            &Scene::List(vec![Item::new("3 blue, 4 red", Style::Plain)]),
+        );
+        assert!(svg.contains(">3 blue, 4 red</text>"));
+    }
+
+    #[test]
+    fn test_html() {
+        let entries = [entry(1, 2, 1, "<8>")];
+        let visuals = [(3, vec![String::from("<svg id=\"day3\"></svg>\n")])];
+        let page = html(&entries, &visuals);
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<td class=\"answer\">&lt;8&gt;</td>"));
+        assert!(page.contains("<h2>Day 3</h2>"));
+        assert!(page.contains("<svg id=\"day3\">"));
+        // the timing chart is inline too
+        assert_eq!(page.matches("<svg").count(), 2);
+        assert!(page.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,886 @@
+use crate::animate::{self, Playback};
+use crate::budget::{self, parse_duration, Overrun};
+use crate::export::{self, ExportFormat};
+use crate::fetch::{self, Downloads};
+use crate::history::{self, History, Record};
+use crate::jobs::Jobs;
+use crate::notify::{self, Summary};
+use crate::output::{write_solutions, write_table, Format, Solution};
+use crate::step::TerminalStepper;
+use crate::theme::{Theme, ThemeName};
+use aoc_common::cancel::{CancelToken, Cancelled};
+use aoc_common::input::{Resolved, YEAR};
+use aoc_common::parse::ParseMode;
+use aoc_common::progress::Progress;
+use aoc_solver::{Empty, Options};
+use clap::Args;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use std::io::{IsTerminal, Read};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+// how often a brute-force search logs how far along it is
+const REPORT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Day to solve; the puzzle input is read from --input or piped stdin, and otherwise is the
+    /// day's own, downloaded the first time when AOC_SESSION is set
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    pub day: Option<u32>,
+
+    /// Solve --day of this earlier year (e.g. 2022) instead, by default AOC_YEAR or the config
+    /// file's year; its answers are only printed, so the extras built on this year's days
+    /// (--visualize, --brute-force, ...) don't apply
+    #[arg(
+        long,
+        requires = "day",
+        conflicts_with_all = [
+            "all", "brute_force", "visualize", "explain", "dump", "dot", "trace_algo", "step",
+            "animate", "watch"
+        ]
+    )]
+    pub year: Option<u32>,
+
+    /// Solve only this part of --day
+    #[arg(long, requires = "day")]
+    pub part: Option<u32>,
+
+    /// File holding the puzzle input, read instead of stdin
+    #[arg(long, requires = "day")]
+    pub input: Option<PathBuf>,
+
+    /// Solve every implemented day, reading each input from AOC_INPUT_DIR, inputs/2023 or Day-N
+    #[arg(long)]
+    pub all: bool,
+
+    /// With --all, download the inputs no day has yet (AOC_SESSION must hold your
+    /// adventofcode.com session cookie) while the days that have one solve
+    #[arg(long, requires = "all")]
+    pub fetch_missing: bool,
+
+    /// With --all, solve this many days at once; a thread out of days steals queued ones from
+    /// the others
+    #[arg(long, requires = "all", default_value_t = 1)]
+    pub jobs: usize,
+
+    /// With --all, keep each solving thread on a CPU core of its own, so timings don't include
+    /// the thread moving between cores
+    #[arg(long, requires = "all")]
+    pub pin_cores: bool,
+
+    /// Answer 0 for an empty input instead of failing
+    #[arg(long)]
+    pub allow_empty: bool,
+
+    /// Skip malformed input lines, reporting them at the end, instead of stopping at the first
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Fail when a day takes longer than the budget in its answers.toml, not just warn
+    #[arg(long)]
+    pub strict_budget: bool,
+
+    /// Stop solving after this long (e.g. 30s or 500ms) and print the parts finished so far;
+    /// Ctrl-C does the same at any time, and a second Ctrl-C quits at once
+    #[arg(long, value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Also solve the day's parts that have no clever solution yet, with their naive search
+    /// spread over every core, logging throughput and time left as it goes
+    #[arg(long, conflicts_with_all = ["all", "step", "animate", "watch"])]
+    pub brute_force: bool,
+
+    /// Which implementation solves the day; gpu runs its compute shaders, checking every answer
+    /// against the cpu implementation
+    #[cfg(feature = "gpu")]
+    #[arg(
+        long = "impl",
+        value_enum,
+        default_value_t = Impl::Cpu,
+        conflicts_with_all = ["all", "year", "step", "animate", "watch", "brute_force"]
+    )]
+    pub implementation: Impl,
+
+    /// Output format; only the results are written to stdout (binary formats as raw bytes)
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Also draw the day's parsed state on stderr
+    #[arg(long, conflicts_with = "all")]
+    pub visualize: bool,
+
+    /// Also narrate the day's intermediate values (each game's minimal bag, each number's
+    /// neighbouring symbols, ...) on stderr
+    #[arg(long, conflicts_with = "all")]
+    pub explain: bool,
+
+    /// Also write the day's parsed entities and their metrics (one row per game, number, ...)
+    /// to day<N>-<entities>.<format> in the current directory
+    #[arg(long, value_enum, conflicts_with = "all")]
+    pub dump: Option<ExportFormat>,
+
+    /// Write the day's graph (Day 3: numbers joined to the symbols they touch) as Graphviz DOT
+    #[arg(long, conflicts_with = "all")]
+    pub dot: Option<PathBuf>,
+
+    /// Write each step of the day's algorithm (Day 2: every game's verdict and running total)
+    /// to this file as JSON lines, one event per line, for visualizers and teaching material
+    #[arg(long, value_name = "OUT.jsonl", conflicts_with = "all")]
+    pub trace_algo: Option<PathBuf>,
+
+    /// Pause at every checkpoint of a cooperating solver (Day 2), drawing its state on stderr;
+    /// enter steps, c runs to the end, q stops drawing
+    #[arg(long, conflicts_with = "all")]
+    pub step: bool,
+
+    /// Play the day's solving progress on stderr (space pauses, +/- change the speed, q quits)
+    #[arg(long, conflicts_with = "all")]
+    pub animate: bool,
+
+    /// Rebuild and rerun the day whenever a source file changes, showing how the answers and
+    /// timings moved
+    #[arg(long, conflicts_with_all = ["all", "step", "animate"])]
+    pub watch: bool,
+
+    /// Frames per second for --animate
+    #[arg(long, default_value_t = 8.0)]
+    pub fps: f64,
+
+    /// Show at most this many frames of --animate, spread evenly over the run
+    #[arg(long)]
+    pub max_frames: Option<usize>,
+
+    /// Save the animation instead of playing it: an animated GIF for a .gif path, otherwise a
+    /// directory of PNG frames
+    #[cfg(feature = "render")]
+    #[arg(long, conflicts_with_all = ["all", "year"])]
+    pub render: Option<PathBuf>,
+}
+
+#[cfg(feature = "gpu")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Impl {
+    /// The day's own solver
+    Cpu,
+    /// Compute shaders through wgpu, for the parts that have them
+    Gpu,
+}
+
+pub fn run(args: RunArgs, theme: Option<ThemeName>) -> Result<()> {
+    // results and visuals may go to different places, so each gets its own theme
+    let results = Theme::select(theme, std::io::stdout().is_terminal());
+    let visuals = Theme::select(theme, std::io::stderr().is_terminal());
+    let options = Options {
+        empty: if args.allow_empty {
+            Empty::Lenient
+        } else {
+            Empty::Reject
+        },
+        mode: if args.lenient {
+            ParseMode::Lenient
+        } else {
+            ParseMode::Strict
+        },
+    };
+    let cancel = args
+        .timeout
+        .map_or_else(CancelToken::new, CancelToken::with_timeout);
+    let Some(day) = args.day else {
+        return run_all(
+            args.format,
+            options,
+            &results,
+            args.strict_budget,
+            args.fetch_missing,
+            Jobs {
+                threads: args.jobs,
+                pin_cores: args.pin_cores,
+            },
+            &cancel,
+        );
+    };
+
+    // without --year, the one the config file or AOC_YEAR sets
+    let year = match args.year {
+        Some(year) => Some(year),
+        None => crate::config::year()?,
+    };
+    if let Some(year) = year.filter(|&year| year != YEAR) {
+        return run_year(year, day, &args, options, &results, &cancel);
+    }
+
+    let mut parts = aoc_solver::parts(day);
+    if args.brute_force {
+        parts.extend(aoc_solver::brute_force_parts(day));
+        parts.sort_unstable();
+    }
+    if let Some(part) = args.part {
+        parts.retain(|&p| p == part);
+        if parts.is_empty() {
+            bail!("day {} part {} is not implemented", day, part);
+        }
+    }
+    if parts.is_empty() {
+        bail!("day {} is not implemented", day);
+    }
+
+    let input = day_input(YEAR, day, args.input.as_deref())?;
+
+    if args.watch {
+        return crate::watch::run(day, &input, options, &results);
+    }
+
+    if args.step {
+        // waiting on keypresses makes the timings meaningless, so nothing is recorded
+        let solutions = step_all(day, &parts, &input, visuals)?;
+        return write_solutions(
+            &mut std::io::stdout().lock(),
+            args.format,
+            &solutions,
+            &results,
+        );
+    }
+
+    check_input(day, &input);
+    #[cfg(feature = "gpu")]
+    if args.implementation == Impl::Gpu {
+        // GPU timings aren't comparable with the rest, so nothing is recorded
+        let solutions = solve_gpu(day, &parts, &input, options)?;
+        return write_solutions(
+            &mut std::io::stdout().lock(),
+            args.format,
+            &solutions,
+            &results,
+        );
+    }
+    cancel_on_ctrl_c(&cancel)?;
+    let solutions = solve_all(day, &parts, &input, None, options, &cancel)?;
+    write_solutions(
+        &mut std::io::stdout().lock(),
+        args.format,
+        &solutions,
+        &results,
+    )?;
+    if solutions.len() < parts.len() {
+        if let Some(reason) = cancel.reason() {
+            bail!(
+                "day {} {} after {} of {} parts",
+                day,
+                reason,
+                solutions.len(),
+                parts.len()
+            );
+        }
+    }
+    if args.visualize {
+        visualize(day, &input, &visuals)?;
+    }
+    if args.explain {
+        eprint!("{}", aoc_solver::explain(day, &input)?);
+    }
+    if let Some(path) = &args.dot {
+        write_dot(day, &input, path)?;
+    }
+    if let Some(path) = &args.trace_algo {
+        write_trace(day, &parts, &input, path)?;
+    }
+    if let Some(format) = args.dump {
+        let table = aoc_solver::records::records(day, &input)?;
+        let path = format!("day{}-{}.{}", day, table.name(), format.extension());
+        export::write(format, &table, Path::new(&path))?;
+    }
+    let playback = Playback {
+        fps: args.fps,
+        budget: args.max_frames,
+    };
+    #[cfg(feature = "render")]
+    if let Some(path) = &args.render {
+        crate::render::save(&aoc_solver::visuals::frames(day, &input)?, playback, path)?;
+    }
+    if args.animate {
+        animate::play(
+            &aoc_solver::visuals::frames(day, &input)?,
+            playback,
+            &visuals,
+        )?;
+    }
+
+    let overrun = over_budget(YEAR, day, &solutions);
+    let hash = history::input_sha256(&input);
+    let parse = time_parse(day, &input);
+    record(
+        solutions
+            .into_iter()
+            .map(|solution| Record {
+                solution,
+                input_sha256: hash.clone(),
+                parse,
+            })
+            .collect(),
+    );
+    match overrun {
+        Some(overrun) if args.strict_budget => bail!("{}", overrun),
+        _ => Ok(()),
+    }
+}
+
+// Solve a day of an earlier year and print its answers; the
+// history and the other extras are keyed by this year's days, so they're left
+// out, but its budget still holds
+fn run_year(
+    year: u32,
+    day: u32,
+    args: &RunArgs,
+    options: Options,
+    theme: &Theme,
+    cancel: &CancelToken,
+) -> Result<()> {
+    let mut parts = aoc_solver::parts_in(year, day);
+    if let Some(part) = args.part {
+        parts.retain(|&p| p == part);
+        if parts.is_empty() {
+            bail!("{} day {} part {} is not implemented", year, day, part);
+        }
+    }
+    if parts.is_empty() {
+        bail!("{} day {} is not implemented", year, day);
+    }
+    let input = day_input(year, day, args.input.as_deref())?;
+
+    cancel_on_ctrl_c(cancel)?;
+    let mut solutions = Vec::new();
+    for part in parts {
+        let start = Instant::now();
+        let answer = aoc_solver::solve_in(year, day, part, &input, options, cancel);
+        if answer.is_err() && cancel.is_cancelled() {
+            break;
+        }
+        let answer =
+            answer.wrap_err_with(|| format!("{} day {} part {} failed", year, day, part))?;
+        let elapsed = start.elapsed();
+        info!(year, day, part, ?elapsed, "Solved");
+        solutions.push(Solution::new(day, part, answer, elapsed));
+    }
+    write_solutions(
+        &mut std::io::stdout().lock(),
+        args.format,
+        &solutions,
+        theme,
+    )?;
+    match over_budget(year, day, &solutions) {
+        Some(overrun) if args.strict_budget => bail!("{}", overrun),
+        _ => Ok(()),
+    }
+}
+
+fn run_all(
+    format: Format,
+    options: Options,
+    theme: &Theme,
+    strict_budget: bool,
+    fetch_missing: bool,
+    jobs: Jobs,
+    cancel: &CancelToken,
+) -> Result<()> {
+    cancel_on_ctrl_c(cancel)?;
+    let start = Instant::now();
+    let mut summary = Summary::default();
+    let mut solutions = Vec::new();
+    let mut records = Vec::new();
+
+    let mut available = Vec::new();
+    let mut missing = Vec::new();
+    for day in aoc_solver::days() {
+        match aoc_common::input::resolve(day) {
+            Ok(resolved) => available.push((day, Ok(resolved))),
+            Err(_) if fetch_missing => missing.push(day),
+            Err(err) => available.push((day, Err(err.into()))),
+        }
+    }
+    if !missing.is_empty() {
+        info!(
+            "Downloading the inputs of {} days with {}",
+            missing.len(),
+            fetch::SESSION_ENV
+        );
+    }
+
+    // downloads land on their own threads while the jobs solve, each day as
+    // soon as its input is there; this thread reads, checks and parses the
+    // inputs ahead of the jobs, so they only ever solve
+    let downloads = Downloads::start(YEAR, missing)?;
+    let mut prepping = Vec::new();
+    let days = available
+        .into_iter()
+        .chain(downloads)
+        .map(|(day, resolved)| {
+            let began = Instant::now();
+            let prepared = prepare(day, resolved, options);
+            prepping.push(began..Instant::now());
+            (day, prepared)
+        });
+    let (outcomes, solving): (Vec<Outcome>, Vec<Range<Instant>>) = jobs
+        .run(days, |(day, prepared)| {
+            let began = Instant::now();
+            let outcome = solve_day(day, prepared, options, cancel);
+            (outcome, began..Instant::now())
+        })
+        .into_iter()
+        .unzip();
+    summary.overlap = overlap(&prepping, &solving);
+    for outcome in outcomes {
+        match outcome {
+            Outcome::NotStarted(reason) => summary.cancelled = Some(reason),
+            Outcome::Skipped => summary.skipped += 1,
+            Outcome::Failed => {
+                summary.days += 1;
+                summary.failed += 1;
+            }
+            Outcome::Solved {
+                solutions: mut day_solutions,
+                records: mut day_records,
+                over_budget,
+                cut_short,
+            } => {
+                summary.days += 1;
+                summary.cancelled = summary.cancelled.or(cut_short);
+                summary.solved += day_solutions.len();
+                summary.over_budget.extend(over_budget);
+                solutions.append(&mut day_solutions);
+                records.append(&mut day_records);
+            }
+        }
+    }
+    // downloaded days, and days solved side by side, finish in any order
+    solutions.sort_by_key(|solution| (solution.day, solution.part));
+    records.sort_by_key(|record| (record.solution.day, record.solution.part));
+    summary.elapsed = start.elapsed();
+    // a day over its budget, or a run cut short, makes the whole summary a
+    // warning, so it stands out
+    if summary.over_budget.is_empty() && summary.cancelled.is_none() {
+        info!("{}", summary);
+    } else {
+        warn!("{}", summary);
+    }
+
+    let mut out = std::io::stdout().lock();
+    match format {
+        Format::Text => write_table(&mut out, &solutions, theme)?,
+        format => write_solutions(&mut out, format, &solutions, theme)?,
+    }
+    record(records);
+
+    if let Ok(url) = std::env::var(notify::WEBHOOK_ENV) {
+        // a failed notification shouldn't fail a finished run
+        if let Err(err) = notify::post(&url, &summary) {
+            warn!("Webhook notification failed: {:?}", err);
+        }
+    }
+
+    if let Some(reason) = summary.cancelled {
+        bail!("the run {} before every day was solved", reason);
+    }
+    if strict_budget && !summary.over_budget.is_empty() {
+        bail!(
+            "{} days over their timing budget",
+            summary.over_budget.len()
+        );
+    }
+    Ok(())
+}
+
+// What became of one day of `run --all`
+enum Outcome {
+    // cancelled before the day began
+    NotStarted(Cancelled),
+    // no input to solve
+    Skipped,
+    Failed,
+    Solved {
+        solutions: Vec<Solution>,
+        records: Vec<Record>,
+        over_budget: Option<Overrun>,
+        // set when the day stopped before its last part
+        cut_short: Option<Cancelled>,
+    },
+}
+
+// A day's input read, checked, hashed and parsed before any job starts on
+// it, so the job answers each part from the parse. A day whose parts each
+// parse the input their own way, or whose parse failed, keeps only the text:
+// its solvers parse that, and report the failure as the day's.
+struct Prepared {
+    input: String,
+    sha256: String,
+    parsed: Option<aoc_solver::Parsed>,
+    parse: Option<Duration>,
+}
+
+fn prepare(day: u32, resolved: Result<Resolved>, options: Options) -> Result<Prepared> {
+    let resolved = resolved?;
+    info!("Day {}: reading {}", day, resolved);
+    let input = std::fs::read_to_string(&resolved.path)
+        .wrap_err_with(|| format!("cannot read {}", resolved))?;
+    check_input(day, &input);
+
+    let start = Instant::now();
+    let parsed = aoc_solver::parse_once(day, &input, options).ok().flatten();
+    let parse = parsed.as_ref().map(|_| start.elapsed());
+    Ok(Prepared {
+        sha256: history::input_sha256(&input),
+        input,
+        parsed,
+        parse,
+    })
+}
+
+// Solve one prepared day, on whichever job thread picked it up
+fn solve_day(
+    day: u32,
+    prepared: Result<Prepared>,
+    options: Options,
+    cancel: &CancelToken,
+) -> Outcome {
+    if let Some(reason) = cancel.reason() {
+        return Outcome::NotStarted(reason);
+    }
+    let prepared = match prepared {
+        Ok(prepared) => prepared,
+        Err(err) => {
+            warn!("Skipping day {}: {:#}", day, err);
+            return Outcome::Skipped;
+        }
+    };
+
+    let parts = aoc_solver::parts(day);
+    match solve_all(
+        day,
+        &parts,
+        &prepared.input,
+        prepared.parsed.as_ref(),
+        options,
+        cancel,
+    ) {
+        Ok(solutions) => {
+            let records = solutions
+                .iter()
+                .map(|solution| Record {
+                    solution: solution.clone(),
+                    input_sha256: prepared.sha256.clone(),
+                    parse: prepared.parse,
+                })
+                .collect();
+            Outcome::Solved {
+                cut_short: if solutions.len() < parts.len() {
+                    cancel.reason()
+                } else {
+                    None
+                },
+                over_budget: over_budget(YEAR, day, &solutions),
+                records,
+                solutions,
+            }
+        }
+        Err(err) => {
+            warn!("{:?}", err);
+            Outcome::Failed
+        }
+    }
+}
+
+// How much of the `prepping` spans ran while some job was solving, which is
+// the time reading ahead took off the run; the `solving` spans come from
+// several threads, so they may overlap each other
+fn overlap(prepping: &[Range<Instant>], solving: &[Range<Instant>]) -> Duration {
+    let mut solving = solving.to_vec();
+    solving.sort_by_key(|span| span.start);
+    let mut busy: Vec<Range<Instant>> = Vec::new();
+    for span in solving {
+        match busy.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => busy.push(span),
+        }
+    }
+    prepping
+        .iter()
+        .flat_map(|prep| {
+            busy.iter().map(move |busy| {
+                prep.end
+                    .min(busy.end)
+                    .saturating_duration_since(prep.start.max(busy.start))
+            })
+        })
+        .sum()
+}
+
+// the input at `path`, else piped stdin, else `year`'s own input for the day,
+// downloaded when it's missing
+fn day_input(year: u32, day: u32, path: Option<&Path>) -> Result<String> {
+    if path.is_some() || !std::io::stdin().is_terminal() {
+        return read_input(path);
+    }
+    let resolved = fetch::ensure(year, day)?;
+    info!("Day {}: reading {}", day, resolved);
+    std::fs::read_to_string(&resolved.path).wrap_err_with(|| format!("cannot read {}", resolved))
+}
+
+// the puzzle input, from `path` if one was given and stdin otherwise
+fn read_input(path: Option<&Path>) -> Result<String> {
+    let Some(path) = path else {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("failed to read the puzzle input from stdin")?;
+        return Ok(input);
+    };
+    std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read the puzzle input from {}", path.display()))
+}
+
+// Report structural problems up front, so a truncated paste is obvious even
+// when the solver goes on to produce an answer
+fn check_input(day: u32, input: &str) {
+    for finding in aoc_solver::validate(day, input) {
+        warn!("Day {} input {}", day, finding);
+    }
+}
+
+// Warn about a day whose parts took longer than the budget it declares; an
+// unreadable budget only warns too
+fn over_budget(year: u32, day: u32, solutions: &[Solution]) -> Option<Overrun> {
+    let budget = match budget::budget(year, day) {
+        Ok(budget) => budget?,
+        Err(err) => {
+            warn!("{:#}", err);
+            return None;
+        }
+    };
+    let overrun = budget::check(day, solutions, budget)?;
+    warn!("{}", overrun);
+    Some(overrun)
+}
+
+// Keep every run in the history database; like the webhook, a failure here
+// only warns
+fn record(records: Vec<Record>) {
+    let recorded = History::open_default()
+        .and_then(|mut db| db.record(history::current_commit().as_deref(), &records));
+    if let Err(err) = recorded {
+        warn!("Failed to record run history: {:?}", err);
+    }
+}
+
+// How long the day's parse step takes on its own, for charting parse against
+// solve time; None when the day has no separate step
+fn time_parse(day: u32, input: &str) -> Option<Duration> {
+    let start = Instant::now();
+    aoc_solver::parse(day, input).ok()?;
+    Some(start.elapsed())
+}
+
+// stderr, like the logs, so visualizing never disturbs piped results
+fn visualize(day: u32, input: &str, theme: &Theme) -> Result<()> {
+    let mut out = std::io::stderr().lock();
+    for (title, scene) in aoc_solver::visuals::scenes(day, input)? {
+        crate::visualize::render(&mut out, &title, &scene, theme)?;
+    }
+    Ok(())
+}
+
+fn write_dot(day: u32, input: &str, path: &Path) -> Result<()> {
+    let (graph, labels) = aoc_solver::visuals::graph(day, input)?;
+    let dot = graph.to_dot(&format!("day{}", day), |node| labels[node].clone());
+    std::fs::write(path, dot).wrap_err_with(|| format!("failed to write {}", path.display()))?;
+    info!("Wrote the day {} graph to {}", day, path.display());
+    Ok(())
+}
+
+// Solve every part again with its steps streamed to `path`, so a trace stays
+// out of the timings above
+fn write_trace(day: u32, parts: &[u32], input: &str, path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .wrap_err_with(|| format!("failed to create {}", path.display()))?;
+    let mut trace = crate::trace::JsonLines::new(std::io::BufWriter::new(file));
+    for &part in parts {
+        trace.part(part);
+        aoc_solver::visuals::traced(day, part, input, &mut trace)?;
+    }
+    let events = trace.written();
+    trace
+        .finish()
+        .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+    info!("Wrote {} day {} events to {}", events, day, path.display());
+    Ok(())
+}
+
+// The parts solved before `cancel` stopped the day, which is all of them
+// unless it did; from `parsed` when the input was parsed ahead
+fn solve_all(
+    day: u32,
+    parts: &[u32],
+    input: &str,
+    parsed: Option<&aoc_solver::Parsed>,
+    options: Options,
+    cancel: &CancelToken,
+) -> Result<Vec<Solution>> {
+    let mut solutions = Vec::new();
+    for &part in parts {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let start = Instant::now();
+        let answer = if aoc_solver::brute_force_parts(day).contains(&part) {
+            brute_force(day, part, input, cancel)
+        } else if let Some(parsed) = parsed {
+            aoc_solver::solve_parsed(parsed, part, cancel)
+        } else {
+            aoc_solver::solve_cancellable(day, part, input, options, cancel)
+        };
+        // a part cut short has no answer, but the ones before it stand
+        if answer.is_err() && cancel.is_cancelled() {
+            break;
+        }
+        let answer = answer.wrap_err_with(|| format!("day {} part {} failed", day, part))?;
+        let elapsed = start.elapsed();
+        info!(day, part, ?elapsed, "Solved");
+        solutions.push(Solution::new(day, part, answer, elapsed));
+    }
+
+    Ok(solutions)
+}
+
+// Solve each part with its compute shader, then again on the CPU, failing
+// when the two disagree; the times are the shaders' own, with the GPU set up
+// beforehand
+#[cfg(feature = "gpu")]
+fn solve_gpu(day: u32, parts: &[u32], input: &str, options: Options) -> Result<Vec<Solution>> {
+    let gpu = aoc_solver::gpu::Gpu::new()?;
+    info!("Solving day {} on {}", day, gpu);
+    let mut solutions = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let answer = aoc_solver::gpu::solve(&gpu, day, part, input, options.mode)
+            .wrap_err_with(|| format!("day {} part {} failed on the GPU", day, part))?;
+        let elapsed = start.elapsed();
+        let expected = aoc_solver::solve_with(day, part, input, options)
+            .wrap_err_with(|| format!("day {} part {} failed", day, part))?;
+        if !aoc_common::answer::matches(&expected, &answer, Default::default()) {
+            bail!(
+                "day {} part {}: the GPU answered {} but the CPU {}",
+                day,
+                part,
+                answer,
+                expected
+            );
+        }
+        info!(day, part, ?elapsed, "Solved on the GPU");
+        solutions.push(Solution::new(day, part, answer, elapsed));
+    }
+    Ok(solutions)
+}
+
+// Solve a part with its brute-force search, logging its progress every REPORT
+// until it's done
+fn brute_force(day: u32, part: u32, input: &str, cancel: &CancelToken) -> Result<String> {
+    let progress = Progress::new(aoc_solver::search_space(day, part, input)?);
+    warn!(
+        "Day {} part {} has no clever solution yet, trying all {} candidates",
+        day,
+        part,
+        progress.total()
+    );
+    let (finished, reporting) = mpsc::channel::<()>();
+    std::thread::scope(|scope| {
+        let progress = &progress;
+        scope.spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = reporting.recv_timeout(REPORT) {
+                info!("Day {} part {} brute force: {}", day, part, progress);
+            }
+        });
+        let answer = aoc_solver::brute_force(day, part, input, progress, cancel);
+        drop(finished);
+        answer
+    })
+}
+
+// The first Ctrl-C cancels the run, which stops at the solvers' next poll and
+// prints what it has; a second one quits on the spot
+fn cancel_on_ctrl_c(cancel: &CancelToken) -> Result<()> {
+    let cancel = cancel.clone();
+    ctrlc::set_handler(move || {
+        if cancel.reason() == Some(Cancelled::Interrupted) {
+            std::process::exit(130);
+        }
+        cancel.cancel();
+    })
+    .wrap_err("failed to handle Ctrl-C")
+}
+
+fn step_all(day: u32, parts: &[u32], input: &str, theme: Theme) -> Result<Vec<Solution>> {
+    let mut stepper = TerminalStepper::new(theme);
+    let mut solutions = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let answer = aoc_solver::visuals::stepped(day, part, input, &mut stepper)
+            .wrap_err_with(|| format!("day {} part {} failed", day, part))?;
+        solutions.push(Solution::new(day, part, answer, start.elapsed()));
+    }
+
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_all() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let cancel = CancelToken::new();
+        let solutions = solve_all(2, &[1, 2], &input, None, Options::default(), &cancel).unwrap();
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions[0].answer, "8");
+        assert_eq!(solutions[1].answer, "2286");
+
+        // parsed ahead, as run --all does
+        let parsed = aoc_solver::parse_once(2, &input, Options::default()).unwrap();
+        let solutions = solve_all(
+            2,
+            &[1, 2],
+            &input,
+            parsed.as_ref(),
+            Options::default(),
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(solutions[1].answer, "2286");
+
+        // a cancelled day stops with the parts it has
+        cancel.cancel();
+        let solutions = solve_all(2, &[1, 2], &input, None, Options::default(), &cancel).unwrap();
+        assert!(solutions.is_empty());
+    }
+
+    #[test]
+    fn test_read_input() {
+        let input = read_input(Some(Path::new("../Day-2/test-1.txt"))).unwrap();
+        assert!(input.starts_with("Game 1:"));
+        assert!(read_input(Some(Path::new("../Day-2/missing.txt"))).is_err());
+    }
+
+    #[test]
+    fn test_overlap() {
+        let t0 = Instant::now();
+        let span =
+            |from: u64, to: u64| t0 + Duration::from_millis(from)..t0 + Duration::from_millis(to);
+        // two jobs busy over 0-40ms with a gap at 40-50ms, then 50-60ms
+        let solving = [span(10, 40), span(0, 20), span(50, 60)];
+        let prepping = [span(0, 5), span(35, 55), span(70, 80)];
+        assert_eq!(overlap(&prepping, &solving), Duration::from_millis(15));
+        assert_eq!(overlap(&prepping, &[]), Duration::ZERO);
+    }
+}
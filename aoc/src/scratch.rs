@@ -0,0 +1,190 @@
+use aoc_common::input::{resolve, workspace_root};
+use clap::Args;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+// each day's library: its package, its directory under the workspace root,
+// and how it turns the input's `lines` into the `data` an expression sees
+const DAYS: &[(u32, &str, &str, &str)] = &[
+    (
+        1,
+        "trebuchet",
+        "Day-1/trebuchet",
+        "trebuchet::parse_data(lines.clone())?",
+    ),
+    (
+        2,
+        "bag-game",
+        "Day-2/bag-game",
+        "bag_game::parse_data(lines.clone())?",
+    ),
+    (
+        3,
+        "map-reader",
+        "Day-3/map-reader",
+        "lines.join(\"\\n\").parse::<map_reader::Schematic>()?",
+    ),
+    (
+        4,
+        "scratchcards",
+        "Day-4/scratchcards",
+        "scratchcards::parse_data(lines.clone())?",
+    ),
+    (
+        5,
+        "fertilizer",
+        "Day-5/fertilizer",
+        "lines.join(\"\\n\").parse::<fertilizer::Almanac>()?",
+    ),
+    (
+        7,
+        "camel-cards",
+        "Day-7/camel-cards",
+        "camel_cards::parse_data(lines.clone())?",
+    ),
+];
+
+#[derive(Debug, Args)]
+pub struct ScratchArgs {
+    /// Day whose parsed input to experiment on
+    #[arg(long)]
+    pub day: u32,
+
+    /// A file holding one Rust expression, or a `{ ... }` block, over `data` (the parsed input)
+    /// and `lines`; its value is printed with {:#?}. Everything the day's library exports is in
+    /// scope
+    #[arg(long)]
+    pub expr_file: PathBuf,
+}
+
+// Build a throwaway crate under .aoc/scratch that parses the day's input with
+// its own library and evaluates the expression, so trying something out never
+// means editing a solver's main
+pub fn run(args: ScratchArgs) -> Result<()> {
+    let root = workspace_root();
+    let Some(&(day, package, dir, parse)) = DAYS.iter().find(|(day, ..)| *day == args.day) else {
+        bail!("day {} has no library to experiment on", args.day);
+    };
+    let expr = std::fs::canonicalize(&args.expr_file)
+        .wrap_err_with(|| format!("cannot find {}", args.expr_file.display()))?;
+    let input = resolve(day)?;
+    info!("Reading {}", input);
+
+    let scratch = root
+        .join(".aoc")
+        .join("scratch")
+        .join(format!("day{}", day));
+    std::fs::create_dir_all(scratch.join("src"))?;
+    std::fs::write(
+        scratch.join("Cargo.toml"),
+        manifest(day, package, &root.join(dir)),
+    )?;
+    std::fs::write(
+        scratch.join("src").join("main.rs"),
+        main(package, parse, &input.path, &expr),
+    )?;
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(scratch.join("Cargo.toml"))
+        // the day's library is already built there
+        .env("CARGO_TARGET_DIR", root.join("target"))
+        .status()
+        .wrap_err("failed to run cargo")?;
+    if !status.success() {
+        return Err(eyre!("the scratch expression failed"));
+    }
+    Ok(())
+}
+
+// Paths go in as Debug-formatted string literals, which escapes them for TOML
+// and Rust alike
+fn manifest(day: u32, package: &str, dir: &Path) -> String {
+    format!(
+        "[package]\n\
+         name = \"scratch-day{}\"\n\
+         version = \"0.1.0\"\n\
+         edition = \"2021\"\n\
+         \n\
+         # not part of the workspace it sits in\n\
+         [workspace]\n\
+         \n\
+         [dependencies]\n\
+         {} = {{ path = {:?}, default-features = false }}\n\
+         color-eyre = \"0.6.2\"\n",
+        day,
+        package,
+        dir.to_string_lossy()
+    )
+}
+
+fn main(package: &str, parse: &str, input: &Path, expr: &Path) -> String {
+    format!(
+        "#[allow(unused_imports)]\n\
+         use {}::*;\n\
+         \n\
+         fn main() -> color_eyre::eyre::Result<()> {{\n    \
+             color_eyre::install()?;\n    \
+             let input = std::fs::read_to_string({:?})?;\n    \
+             let lines: Vec<String> = input.lines().map(String::from).collect();\n    \
+             #[allow(unused_variables)]\n    \
+             let data = {};\n    \
+             let value = include!({:?});\n    \
+             println!(\"{{:#?}}\", value);\n    \
+             Ok(())\n\
+         }}\n",
+        package.replace('-', "_"),
+        input.to_string_lossy(),
+        parse,
+        expr.to_string_lossy()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest() {
+        let manifest = manifest(2, "bag-game", Path::new("/aoc/Day-2/bag-game"));
+        assert!(manifest.starts_with("[package]\nname = \"scratch-day2\"\n"));
+        assert!(manifest.contains("\n[workspace]\n"));
+        assert!(manifest
+            .contains("bag-game = { path = \"/aoc/Day-2/bag-game\", default-features = false }\n"));
+    }
+
+    #[test]
+    fn test_main() {
+        let main = main(
+            "bag-game",
+            "bag_game::parse_data(lines.clone())?",
+            Path::new("/aoc/Day-2/input.txt"),
+            Path::new("/tmp/scratch.rs"),
+        );
+        assert!(main.starts_with("#[allow(unused_imports)]\nuse bag_game::*;\n"));
+        assert!(
+            main.contains("    let input = std::fs::read_to_string(\"/aoc/Day-2/input.txt\")?;\n")
+        );
+        assert!(main.contains("    let data = bag_game::parse_data(lines.clone())?;\n"));
+        assert!(main.contains("    let value = include!(\"/tmp/scratch.rs\");\n"));
+        assert!(main.ends_with("    Ok(())\n}\n"));
+    }
+
+    #[test]
+    fn test_days() {
+        // every solved day can be experimented on
+        for day in aoc_solver::days() {
+            assert!(DAYS.iter().any(|(d, ..)| *d == day), "day {}", day);
+        }
+        for (day, _, dir, _) in DAYS {
+            assert!(
+                Path::new("..").join(dir).join("Cargo.toml").is_file(),
+                "day {}",
+                day
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use aoc_common::input::{inputs_path, workspace_root, YEAR};
+use clap::Args;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use std::path::Path;
+use std::process::Command;
+use tracing::{info, warn};
+
+// what keeps the moved inputs out of version control
+const IGNORE: &str = "/inputs/";
+
+#[derive(Debug, Args)]
+pub struct ScrubArgs {
+    /// Only list the inputs that would be moved and the files that would be rewritten
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// A tracked puzzle input and where it goes instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Move {
+    from: String,
+    to: String,
+}
+
+// Move every tracked Day-N/input.txt to the ignored inputs/2023/dayNN.txt, and
+// every earlier year's YYYY/Day-N/input.txt to inputs/YYYY/dayNN.txt, which
+// the input resolver reads first, and point references at the new path
+pub fn run(args: ScrubArgs) -> Result<()> {
+    let root = workspace_root();
+    let files = git(&root, &["ls-files", "-z"])?;
+    let files: Vec<&str> = files.split('\0').filter(|f| !f.is_empty()).collect();
+    let moves = tracked_inputs(&files);
+    if moves.is_empty() {
+        info!("No puzzle inputs are tracked");
+        return Ok(());
+    }
+
+    for Move { from, to } in &moves {
+        info!("Moving {} to {}", from, to);
+        if args.dry_run {
+            continue;
+        }
+        let (source, target) = (root.join(from), root.join(to));
+        let input = std::fs::read(&source).wrap_err_with(|| format!("cannot read {}", from))?;
+        match std::fs::read(&target) {
+            Ok(existing) if existing != input => {
+                bail!("{} already exists and differs from {}", to, from)
+            }
+            Ok(_) => {}
+            Err(_) => {
+                if let Some(dir) = target.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&target, &input).wrap_err_with(|| format!("cannot write {}", to))?;
+            }
+        }
+        git(&root, &["rm", "--cached", "--quiet", from])?;
+        std::fs::remove_file(&source)?;
+    }
+
+    for file in files
+        .iter()
+        .filter(|f| !moves.iter().any(|m| m.from == **f))
+    {
+        // binary files can't mention a path worth rewriting
+        let Ok(text) = std::fs::read_to_string(root.join(file)) else {
+            continue;
+        };
+        if let Some(text) = rewrite(&text, &moves) {
+            info!("Rewriting input paths in {}", file);
+            if !args.dry_run {
+                std::fs::write(root.join(file), text)?;
+            }
+        }
+    }
+
+    let gitignore = root.join(".gitignore");
+    let text = std::fs::read_to_string(&gitignore).unwrap_or_default();
+    if let Some(text) = ignore(&text) {
+        info!("Adding {} to .gitignore", IGNORE);
+        if !args.dry_run {
+            std::fs::write(&gitignore, text)?;
+        }
+    }
+
+    warn!("The inputs are still in the git history; rewriting it is left to you");
+    Ok(())
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .wrap_err("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// The personal inputs among `files`, as git lists them; the examples
+// (test-N.txt) come from the puzzle text and may stay
+fn tracked_inputs(files: &[&str]) -> Vec<Move> {
+    files
+        .iter()
+        .filter_map(|file| {
+            let (year, path) = match file.split_once('/')? {
+                (year, path) if year.starts_with(|c: char| c.is_ascii_digit()) => {
+                    (year.parse().ok()?, path)
+                }
+                _ => (YEAR, *file),
+            };
+            let (dir, name) = path.split_once('/')?;
+            let day: u32 = dir.strip_prefix("Day-")?.parse().ok()?;
+            (name == "input.txt").then(|| Move {
+                from: file.to_string(),
+                to: inputs_path(year, day).to_string_lossy().replace('\\', "/"),
+            })
+        })
+        .collect()
+}
+
+// `text` with every moved path replaced, or None when it mentions none; the
+// longest paths go first, so 2022/Day-1/input.txt isn't taken for Day-1's
+fn rewrite(text: &str, moves: &[Move]) -> Option<String> {
+    let mut moves: Vec<&Move> = moves.iter().filter(|m| text.contains(&m.from)).collect();
+    moves.sort_by_key(|m| std::cmp::Reverse(m.from.len()));
+    (!moves.is_empty()).then(|| {
+        moves
+            .iter()
+            .fold(text.to_string(), |text, m| text.replace(&m.from, &m.to))
+    })
+}
+
+// `gitignore` with the inputs directory added, or None when it's there already
+fn ignore(gitignore: &str) -> Option<String> {
+    if gitignore.lines().any(|line| line.trim() == IGNORE) {
+        return None;
+    }
+    let mut text = gitignore.to_string();
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(IGNORE);
+    text.push('\n');
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moved(from: &str, to: &str) -> Move {
+        Move {
+            from: String::from(from),
+            to: String::from(to),
+        }
+    }
+
+    #[test]
+    fn test_tracked_inputs() {
+        let files = [
+            "Cargo.toml",
+            "Day-1/input.txt",
+            "Day-1/test-1.txt",
+            "Day-12/input.txt",
+            "Day-x/input.txt",
+            "aoc/Day-2/input.txt",
+            "2022/Day-1/input.txt",
+            "2022/Day-1/test-1.txt",
+        ];
+        assert_eq!(
+            tracked_inputs(&files),
+            [
+                moved("Day-1/input.txt", "inputs/2023/day01.txt"),
+                moved("Day-12/input.txt", "inputs/2023/day12.txt"),
+                moved("2022/Day-1/input.txt", "inputs/2022/day01.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let moves = [
+            moved("Day-1/input.txt", "inputs/2023/day01.txt"),
+            moved("Day-2/input.txt", "inputs/2023/day02.txt"),
+        ];
+        assert_eq!(
+            rewrite("open(\"Day-2/input.txt\") and Day-1/input.txt", &moves).unwrap(),
+            "open(\"inputs/2023/day02.txt\") and inputs/2023/day01.txt"
+        );
+        assert_eq!(rewrite("Day-3/input.txt", &moves), None);
+
+        let moves = [
+            moved("Day-1/input.txt", "inputs/2023/day01.txt"),
+            moved("2022/Day-1/input.txt", "inputs/2022/day01.txt"),
+        ];
+        assert_eq!(
+            rewrite("2022/Day-1/input.txt", &moves).unwrap(),
+            "inputs/2022/day01.txt"
+        );
+    }
+
+    #[test]
+    fn test_ignore() {
+        assert_eq!(ignore("target/").unwrap(), "target/\n/inputs/\n");
+        assert_eq!(ignore("").unwrap(), "/inputs/\n");
+        assert_eq!(ignore("target/\n/inputs/\n"), None);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::output::Solution;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use color_eyre::eyre::Result;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::time::Instant;
+use tracing::{info, warn};
+
+#[derive(Debug, Serialize)]
+struct Failure {
+    day: u32,
+    part: u32,
+    error: String,
+}
+
+pub fn run(addr: SocketAddr) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!("Listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, router()).await?;
+        Ok(())
+    })
+}
+
+fn router() -> Router {
+    Router::new().route("/solve/:day/:part", post(solve))
+}
+
+#[tracing::instrument(skip(input))]
+async fn solve(Path((day, part)): Path<(u32, u32)>, input: String) -> Response {
+    // solvers are CPU bound, keep them off the async workers
+    let result = tokio::task::spawn_blocking(move || {
+        let start = Instant::now();
+        let answer = aoc_solver::solve(day, part, &input);
+        (answer, start.elapsed())
+    })
+    .await;
+
+    match result {
+        Ok((Ok(answer), elapsed)) => {
+            info!(%answer, ?elapsed, "Solved");
+            Json(Solution::new(day, part, answer, elapsed)).into_response()
+        }
+        Ok((Err(err), _)) => {
+            warn!(%err, "Solve failed");
+            let failure = Failure {
+                day,
+                part,
+                error: err.to_string(),
+            };
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(failure)).into_response()
+        }
+        Err(err) => {
+            warn!(%err, "Solver panicked");
+            let failure = Failure {
+                day,
+                part,
+                error: String::from("solver panicked"),
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(failure)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn post_solve(uri: &str, input: String) -> (StatusCode, serde_json::Value) {
+        let request = Request::post(uri).body(Body::from(input)).unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_solve() {
+        let input = std::fs::read_to_string("../Day-2/test-1.txt").unwrap();
+        let (status, json) = post_solve("/solve/2/2", input).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["day"], 2);
+        assert_eq!(json["part"], 2);
+        assert_eq!(json["answer"], "2286");
+        assert!(json["elapsed_us"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_solve_unknown_day() {
+        let (status, json) = post_solve("/solve/25/1", String::new()).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(json["error"].is_string());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::theme::Theme;
+use crate::visualize::{header, render};
+use aoc_common::visualize::{Checkpoint, Stepper};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    // wait for a key at every checkpoint
+    Pause,
+    // keep drawing checkpoints without waiting
+    Run,
+    // stop drawing them at all
+    Quiet,
+}
+
+/// Draws each checkpoint on stderr and waits for a key: enter or space
+/// steps on, `c` runs to the end still drawing, and `q` stops drawing.
+pub struct TerminalStepper {
+    mode: Mode,
+    reached: usize,
+    theme: Theme,
+}
+
+impl TerminalStepper {
+    pub fn new(theme: Theme) -> Self {
+        TerminalStepper {
+            mode: Mode::Pause,
+            reached: 0,
+            theme,
+        }
+    }
+}
+
+impl Stepper for TerminalStepper {
+    fn checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.reached += 1;
+        if self.mode == Mode::Quiet {
+            return;
+        }
+
+        let mut out = io::stderr().lock();
+        let _ = draw(&mut out, self.reached, &checkpoint, &self.theme);
+        if self.mode == Mode::Pause {
+            let _ = write!(out, "[enter] next  [c] continue  [q] quiet ");
+            let _ = out.flush();
+            // without a terminal to read keys from, just run on
+            self.mode = wait().unwrap_or(Mode::Run);
+            let _ = writeln!(out);
+        }
+    }
+}
+
+fn draw(out: &mut impl Write, n: usize, checkpoint: &Checkpoint, theme: &Theme) -> io::Result<()> {
+    let title = format!("#{} {}", n, checkpoint.label);
+    match &checkpoint.scene {
+        Some(scene) => render(out, &title, scene, theme)?,
+        None => header(out, &title, theme)?,
+    }
+    for (name, value) in &checkpoint.variables {
+        writeln!(out, "  {} = {}", name, value)?;
+    }
+    Ok(())
+}
+
+fn mode(key: KeyEvent) -> Option<Mode> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('n') => Some(Mode::Pause),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Mode::Quiet),
+        KeyCode::Char('c') => Some(Mode::Run),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Mode::Quiet),
+        _ => None,
+    }
+}
+
+// Block until a key picks the next mode; raw mode only lasts for the wait
+fn wait() -> io::Result<Mode> {
+    terminal::enable_raw_mode()?;
+    let next = loop {
+        match event::read() {
+            Ok(Event::Key(key)) => {
+                if let Some(next) = mode(key) {
+                    break Ok(next);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => break Err(err),
+        }
+    };
+    terminal::disable_raw_mode()?;
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::visualize::{Item, Scene, Style};
+
+    #[test]
+    fn test_draw() {
+        let mut out = Vec::new();
+        let checkpoint = Checkpoint::new("Game 3")
+            .scene(Scene::List(vec![Item::new("20 red", Style::Alert)]))
+            .var("total", 3);
+        draw(&mut out, 3, &checkpoint, &Theme::plain()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "== #3 Game 3 ==\n  20 red\n  total = 3\n"
+        );
+
+        let mut out = Vec::new();
+        draw(&mut out, 1, &Checkpoint::new("start"), &Theme::plain()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "== #1 start ==\n");
+    }
+
+    #[test]
+    fn test_keys() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(mode(key(KeyCode::Enter)), Some(Mode::Pause));
+        assert_eq!(mode(key(KeyCode::Char('c'))), Some(Mode::Run));
+        assert_eq!(mode(key(KeyCode::Char('q'))), Some(Mode::Quiet));
+        assert_eq!(
+            mode(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Mode::Quiet)
+        );
+        assert_eq!(mode(key(KeyCode::Char('x'))), None);
+    }
+}
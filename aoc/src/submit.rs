@@ -0,0 +1,240 @@
+use crate::fetch::{self, puzzle_url, AGENT, SESSION_ENV};
+use aoc_common::cancel::CancelToken;
+use aoc_common::input::YEAR;
+use aoc_solver::Options;
+use clap::Args;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use reqwest::header::{COOKIE, USER_AGENT};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::info;
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// Day whose answer to submit
+    #[arg(long)]
+    pub day: u32,
+
+    /// Part whose answer to submit
+    #[arg(long)]
+    pub part: u32,
+
+    /// File holding the puzzle input; by default the day's own, downloaded when missing
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// Year of the day, by default AOC_YEAR or the config file's year, and otherwise 2023
+    #[arg(long)]
+    pub year: Option<u32>,
+}
+
+/// What adventofcode.com made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Wrong,
+    /// The last answer was too recent; how long is left, as the site puts it
+    Wait(String),
+    /// The part already has its star
+    Solved,
+}
+
+impl Verdict {
+    /// Read the verdict out of the page adventofcode.com answers a submission
+    /// with, if it says one.
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Right)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            let left = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("a little", |(left, _)| left);
+            Some(Verdict::Wait(left.to_string()))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::Solved)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "the right answer"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "not the right answer"),
+            Verdict::Wait(left) => write!(f, "too soon after the last one; {} left to wait", left),
+            Verdict::Solved => write!(f, "not needed; the part is already solved"),
+        }
+    }
+}
+
+pub fn run(args: SubmitArgs) -> Result<()> {
+    let session = std::env::var(SESSION_ENV).wrap_err_with(|| {
+        format!(
+            "submitting needs {} set to your adventofcode.com session cookie",
+            SESSION_ENV
+        )
+    })?;
+    let year = match args.year {
+        Some(year) => year,
+        None => crate::config::year()?.unwrap_or(YEAR),
+    };
+    let path = match args.input {
+        Some(path) => path,
+        None => fetch::ensure(year, args.day)?.path,
+    };
+    let input = std::fs::read_to_string(&path)
+        .wrap_err_with(|| format!("cannot read {}", path.display()))?;
+    let answer = aoc_solver::solve_in(
+        year,
+        args.day,
+        args.part,
+        &input,
+        Options::default(),
+        &CancelToken::new(),
+    )?;
+
+    info!(
+        "{} day {} part {}: submitting {}",
+        year, args.day, args.part, answer
+    );
+    let url = format!("{}/answer", puzzle_url(year, args.day));
+    let page = fetch::block_on(post(
+        &reqwest::Client::new(),
+        &url,
+        args.part,
+        &answer,
+        &session,
+    ))??;
+    let verdict = Verdict::parse(&page)
+        .ok_or_else(|| eyre!("adventofcode.com's reply doesn't say how the answer did"))?;
+    match verdict {
+        Verdict::Right | Verdict::Solved => {
+            println!(
+                "Day {} part {}: {} is {}",
+                args.day, args.part, answer, verdict
+            );
+            Ok(())
+        }
+        _ => bail!(
+            "day {} part {}: {} is {}",
+            args.day,
+            args.part,
+            answer,
+            verdict
+        ),
+    }
+}
+
+// the page adventofcode.com answers the submission with
+async fn post(
+    client: &reqwest::Client,
+    url: &str,
+    part: u32,
+    answer: &str,
+    session: &str,
+) -> Result<String> {
+    client
+        .post(url)
+        .header(COOKIE, format!("session={}", session))
+        .header(USER_AGENT, AGENT)
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .wrap_err_with(|| format!("failed to post to {}", url))?
+        .text()
+        .await
+        .wrap_err_with(|| format!("failed to read the reply from {}", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_post() {
+        let page = fetch::block_on(async {
+            // a one-request server standing in for adventofcode.com
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!(
+                "http://{}/2023/day/2/answer",
+                listener.local_addr().unwrap()
+            );
+            let server = tokio::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                // the form may arrive after the headers
+                let mut request = Vec::new();
+                let mut chunk = vec![0; 4096];
+                while !String::from_utf8_lossy(&request).contains("answer=") {
+                    let read = stream.read(&mut chunk).await.unwrap();
+                    request.extend_from_slice(&chunk[..read]);
+                }
+                let body = "<article><p>That's the right answer!</p></article>";
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                String::from_utf8_lossy(&request).into_owned()
+            });
+
+            let page = post(&reqwest::Client::new(), &url, 1, "8", "53cr3t")
+                .await
+                .unwrap();
+            let request = server.await.unwrap().to_lowercase();
+            assert!(request.starts_with("post /2023/day/2/answer http/1.1\r\n"));
+            assert!(request.contains("\r\ncookie: session=53cr3t\r\n"));
+            assert!(request.ends_with("level=1&answer=8"));
+            page
+        })
+        .unwrap();
+        assert_eq!(Verdict::parse(&page), Some(Verdict::Right));
+    }
+
+    #[test]
+    fn test_verdict() {
+        let page = "<article><p>That's the right answer! You are <em>one gold star</em> closer \
+                    to restoring snow operations.</p></article>";
+        assert_eq!(Verdict::parse(page), Some(Verdict::Right));
+        let page = "<article><p>That's not the right answer; your answer is too high.  If \
+                    you're stuck, make sure you're using the full input data</p></article>";
+        assert_eq!(Verdict::parse(page), Some(Verdict::TooHigh));
+        let page = "<p>That's not the right answer; your answer is too low.</p>";
+        assert_eq!(Verdict::parse(page), Some(Verdict::TooLow));
+        let page = "<p>That's not the right answer.  If you're stuck, ...</p>";
+        assert_eq!(Verdict::parse(page), Some(Verdict::Wrong));
+        let page = "<article><p>You gave an answer too recently; you have to wait after \
+                    submitting an answer before trying again.  You have 38s left to wait. \
+                    </p></article>";
+        let verdict = Verdict::parse(page).unwrap();
+        assert_eq!(verdict, Verdict::Wait(String::from("38s")));
+        assert_eq!(
+            verdict.to_string(),
+            "too soon after the last one; 38s left to wait"
+        );
+        let page = "<p>You don't seem to be solving the right level.  Did you already \
+                    complete it?</p>";
+        assert_eq!(Verdict::parse(page), Some(Verdict::Solved));
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+}
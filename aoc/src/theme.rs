@@ -0,0 +1,169 @@
+use aoc_common::visualize::Style;
+use clap::ValueEnum;
+#[cfg(feature = "tui")]
+use ratatui::style::{Color, Modifier, Style as TuiStyle};
+use std::io::{self, Write};
+
+// any non-empty value turns colour off, see https://no-color.org
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ThemeName {
+    /// Green, yellow and red on the terminal's own background
+    Default,
+    /// Bold text on solid backgrounds
+    HighContrast,
+    /// Blue, orange and vermillion from the Okabe-Ito palette, with alerts underlined
+    Colorblind,
+    /// No escape codes at all
+    NoColor,
+}
+
+/// How every styled piece of terminal output is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    name: ThemeName,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        Theme { name }
+    }
+
+    #[cfg(test)]
+    pub fn plain() -> Self {
+        Theme::new(ThemeName::NoColor)
+    }
+
+    /// The theme for one output stream: an explicit `--theme` wins, then
+    /// `NO_COLOR`, and otherwise only a terminal gets colour.
+    pub fn select(requested: Option<ThemeName>, terminal: bool) -> Self {
+        let no_color = std::env::var_os(NO_COLOR_ENV).is_some_and(|value| !value.is_empty());
+        Theme::new(choose(requested, terminal, no_color))
+    }
+
+    #[cfg(test)]
+    pub fn name(&self) -> ThemeName {
+        self.name
+    }
+
+    #[cfg(test)]
+    pub fn is_colored(&self) -> bool {
+        self.name != ThemeName::NoColor
+    }
+
+    // the SGR parameters for `style`, empty for none
+    fn code(&self, style: Style) -> &'static str {
+        match (self.name, style) {
+            (ThemeName::NoColor, _) | (_, Style::Plain) => "",
+            (ThemeName::Default, Style::Dim) => "2",
+            (ThemeName::Default, Style::Highlight) => "1;32",
+            (ThemeName::Default, Style::Accent) => "1;33",
+            (ThemeName::Default, Style::Alert) => "1;31",
+            (ThemeName::HighContrast, Style::Dim) => "37",
+            (ThemeName::HighContrast, Style::Highlight) => "1;30;102",
+            (ThemeName::HighContrast, Style::Accent) => "1;30;103",
+            (ThemeName::HighContrast, Style::Alert) => "1;97;41",
+            (ThemeName::Colorblind, Style::Dim) => "2",
+            (ThemeName::Colorblind, Style::Highlight) => "1;38;5;32",
+            (ThemeName::Colorblind, Style::Accent) => "1;38;5;214",
+            (ThemeName::Colorblind, Style::Alert) => "1;4;38;5;166",
+        }
+    }
+
+    /// `text` wrapped in the escape codes for `style`, if it has any.
+    pub fn paint(&self, text: &str, style: Style) -> String {
+        match self.code(style) {
+            "" => text.to_string(),
+            code => format!("\x1b[{}m{}{}", code, text, RESET),
+        }
+    }
+
+    /// The same look for the explorer's widgets.
+    #[cfg(feature = "tui")]
+    pub fn tui(&self, style: Style) -> TuiStyle {
+        let plain = TuiStyle::default();
+        let bold = plain.add_modifier(Modifier::BOLD);
+        match (self.name, style) {
+            (_, Style::Plain) => plain,
+            (ThemeName::Default, Style::Dim) => plain.fg(Color::DarkGray),
+            (ThemeName::Default, Style::Highlight) => bold.fg(Color::Green),
+            (ThemeName::Default, Style::Accent) => bold.fg(Color::Yellow),
+            (ThemeName::Default, Style::Alert) => bold.fg(Color::Red),
+            (ThemeName::HighContrast, Style::Dim) => plain.fg(Color::Gray),
+            (ThemeName::HighContrast, Style::Highlight) => {
+                bold.fg(Color::Black).bg(Color::LightGreen)
+            }
+            (ThemeName::HighContrast, Style::Accent) => {
+                bold.fg(Color::Black).bg(Color::LightYellow)
+            }
+            (ThemeName::HighContrast, Style::Alert) => bold.fg(Color::White).bg(Color::Red),
+            (ThemeName::Colorblind, Style::Dim) => plain.fg(Color::DarkGray),
+            (ThemeName::Colorblind, Style::Highlight) => bold.fg(Color::Indexed(32)),
+            (ThemeName::Colorblind, Style::Accent) => bold.fg(Color::Indexed(214)),
+            (ThemeName::Colorblind, Style::Alert) => bold
+                .fg(Color::Indexed(166))
+                .add_modifier(Modifier::UNDERLINED),
+            // no colour, but a full-screen view still needs some emphasis
+            (ThemeName::NoColor, Style::Dim) => plain.add_modifier(Modifier::DIM),
+            (ThemeName::NoColor, Style::Highlight | Style::Accent) => bold,
+            (ThemeName::NoColor, Style::Alert) => bold.add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    pub fn write(&self, out: &mut impl Write, text: &str, style: Style) -> io::Result<()> {
+        write!(out, "{}", self.paint(text, style))
+    }
+}
+
+fn choose(requested: Option<ThemeName>, terminal: bool, no_color: bool) -> ThemeName {
+    match requested {
+        Some(name) => name,
+        None if no_color || !terminal => ThemeName::NoColor,
+        None => ThemeName::Default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose() {
+        assert_eq!(choose(None, true, false), ThemeName::Default);
+        assert_eq!(choose(None, false, false), ThemeName::NoColor);
+        assert_eq!(choose(None, true, true), ThemeName::NoColor);
+        // asking for a theme outranks NO_COLOR and pipes
+        assert_eq!(
+            choose(Some(ThemeName::Colorblind), false, true),
+            ThemeName::Colorblind
+        );
+        assert_eq!(
+            Theme::select(Some(ThemeName::HighContrast), false).name(),
+            ThemeName::HighContrast
+        );
+    }
+
+    #[test]
+    fn test_paint() {
+        let default = Theme::new(ThemeName::Default);
+        assert_eq!(default.paint("4", Style::Highlight), "\x1b[1;32m4\x1b[0m");
+        assert_eq!(default.paint(".", Style::Plain), ".");
+        assert_eq!(Theme::plain().paint("4", Style::Alert), "4");
+        assert!(!Theme::plain().is_colored());
+
+        // every coloured theme sets each emphasis apart
+        for name in [
+            ThemeName::Default,
+            ThemeName::HighContrast,
+            ThemeName::Colorblind,
+        ] {
+            let theme = Theme::new(name);
+            let styles = [Style::Dim, Style::Highlight, Style::Accent, Style::Alert];
+            let codes: std::collections::HashSet<_> =
+                styles.iter().map(|&style| theme.code(style)).collect();
+            assert_eq!(codes.len(), styles.len());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use aoc_common::trace::{Event, Tracer};
+use color_eyre::eyre::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+// one line of the trace: where it falls, then the event's own fields
+#[derive(Serialize)]
+struct Line<'a> {
+    seq: u64,
+    part: u32,
+    #[serde(flatten)]
+    event: Fields<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Fields<'a> {
+    Push {
+        queue: &'a str,
+        item: &'a str,
+        priority: u64,
+    },
+    Pop {
+        queue: &'a str,
+        item: &'a str,
+        priority: u64,
+    },
+    Split {
+        start: i64,
+        end: i64,
+        at: i64,
+    },
+    Cycle {
+        start: usize,
+        length: usize,
+    },
+    Update {
+        label: &'a str,
+        variables: BTreeMap<&'a str, &'a str>,
+    },
+}
+
+impl<'a> From<&'a Event> for Fields<'a> {
+    fn from(event: &'a Event) -> Self {
+        match event {
+            Event::Push {
+                queue,
+                item,
+                priority,
+            } => Fields::Push {
+                queue,
+                item,
+                priority: *priority,
+            },
+            Event::Pop {
+                queue,
+                item,
+                priority,
+            } => Fields::Pop {
+                queue,
+                item,
+                priority: *priority,
+            },
+            Event::Split { start, end, at } => Fields::Split {
+                start: *start,
+                end: *end,
+                at: *at,
+            },
+            Event::Cycle { start, length } => Fields::Cycle {
+                start: *start,
+                length: *length,
+            },
+            Event::Update { label, variables } => Fields::Update {
+                label,
+                variables: variables
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Writes every event as a line of JSON the moment it happens, numbered from
+/// 0 across the whole trace and tagged with the part it came from, so a
+/// trace cut short still reads.
+pub struct JsonLines<W: Write> {
+    out: W,
+    part: u32,
+    seq: u64,
+    // the first write that failed; a solver can't be told mid-step
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLines<W> {
+    pub fn new(out: W) -> Self {
+        JsonLines {
+            out,
+            part: 0,
+            seq: 0,
+            error: None,
+        }
+    }
+
+    /// Tag the events from here on with `part`.
+    pub fn part(&mut self, part: u32) {
+        self.part = part;
+    }
+
+    /// How many events were written.
+    pub fn written(&self) -> u64 {
+        self.seq
+    }
+
+    /// Flush the trace, failing if any line couldn't be written.
+    pub fn finish(mut self) -> Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err.into());
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> Tracer for JsonLines<W> {
+    fn event(&mut self, event: Event) {
+        if self.error.is_some() {
+            return;
+        }
+        let line = Line {
+            seq: self.seq,
+            part: self.part,
+            event: Fields::from(&event),
+        };
+        let written = serde_json::to_writer(&mut self.out, &line)
+            .map_err(io::Error::from)
+            .and_then(|()| self.out.write_all(b"\n"));
+        match written {
+            Ok(()) => self.seq += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines() {
+        let mut trace = JsonLines::new(Vec::new());
+        trace.part(1);
+        trace.event(Event::Cycle {
+            start: 2,
+            length: 3,
+        });
+        trace.part(2);
+        trace.event(Event::Update {
+            label: String::from("Game 1"),
+            variables: vec![(String::from("total"), String::from("48"))],
+        });
+        assert_eq!(trace.written(), 2);
+        let out = String::from_utf8(trace.finish().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "{\"seq\":0,\"part\":1,\"event\":\"cycle\",\"start\":2,\"length\":3}\n\
+             {\"seq\":1,\"part\":2,\"event\":\"update\",\"label\":\"Game 1\",\
+             \"variables\":{\"total\":\"48\"}}\n"
+        );
+    }
+}
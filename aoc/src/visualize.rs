@@ -0,0 +1,100 @@
+use crate::theme::Theme;
+use aoc_common::visualize::{Scene, Style};
+use std::io::{self, Write};
+
+/// The `== title ==` line that starts every scene.
+pub fn header(out: &mut impl Write, title: &str, theme: &Theme) -> io::Result<()> {
+    theme.write(out, &format!("== {} ==", title), Style::Accent)?;
+    writeln!(out)
+}
+
+/// Draw a titled scene for a terminal.
+pub fn render(out: &mut impl Write, title: &str, scene: &Scene, theme: &Theme) -> io::Result<()> {
+    header(out, title, theme)?;
+
+    match scene {
+        Scene::Grid(grid) => {
+            for row in grid.rows() {
+                for glyph in row {
+                    theme.write(out, &glyph.ch.to_string(), glyph.style)?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Scene::Graph { nodes, edges } => {
+            for (i, node) in nodes.iter().enumerate() {
+                write!(out, "{:>4} ", i)?;
+                theme.write(out, &node.text, node.style)?;
+                writeln!(out)?;
+            }
+            for edge in edges {
+                write!(out, "{:>4} -> {}", edge.from, edge.to)?;
+                if let Some(label) = &edge.label {
+                    write!(out, " ({})", label)?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Scene::List(items) => {
+            for item in items {
+                write!(out, "  ")?;
+                theme.write(out, &item.text, item.style)?;
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ThemeName;
+    use aoc_common::grid::Grid;
+    use aoc_common::visualize::{Edge, Glyph, Item};
+
+    fn draw(scene: &Scene, color: bool) -> String {
+        let theme = if color {
+            Theme::new(ThemeName::Default)
+        } else {
+            Theme::plain()
+        };
+        let mut out = Vec::new();
+        render(&mut out, "Test", scene, &theme).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let grid = Grid::from_rows(vec![vec![
+            Glyph::new('4', Style::Highlight),
+            Glyph::new('.', Style::Plain),
+        ]]);
+        let scene = Scene::Grid(grid);
+        assert_eq!(draw(&scene, false), "== Test ==\n4.\n");
+        assert_eq!(
+            draw(&scene, true),
+            "\x1b[1;33m== Test ==\x1b[0m\n\x1b[1;32m4\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_render_graph_and_list() {
+        let scene = Scene::Graph {
+            nodes: vec![Item::new("a", Style::Plain), Item::new("b", Style::Plain)],
+            edges: vec![Edge {
+                from: 0,
+                to: 1,
+                label: Some(String::from("3")),
+            }],
+        };
+        assert_eq!(
+            draw(&scene, false),
+            "== Test ==\n   0 a\n   1 b\n   0 -> 1 (3)\n"
+        );
+
+        let scene = Scene::List(vec![Item::new("3 blue, 4 red", Style::Alert)]);
+        assert_eq!(draw(&scene, false), "== Test ==\n  3 blue, 4 red\n");
+    }
+}
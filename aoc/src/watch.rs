@@ -0,0 +1,227 @@
+use crate::output::Solution;
+use crate::theme::Theme;
+use aoc_common::input::workspace_root;
+use aoc_common::parse::ParseMode;
+use aoc_common::visualize::Style;
+use aoc_solver::{Empty, Options};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+// how often the sources are checked for changes
+const POLL: Duration = Duration::from_millis(500);
+
+// Rebuild and rerun `day` on `input` whenever a source file changes, printing
+// how the answers and timings moved since the previous run
+pub fn run(day: u32, input: &str, options: Options, theme: &Theme) -> Result<()> {
+    let root = workspace_root();
+    let mut previous: Vec<Solution> = Vec::new();
+    loop {
+        let changed = latest_change(&root);
+        match build(&root).and_then(|exe| solve(&exe, day, input, options)) {
+            Ok(solutions) => {
+                let mut out = std::io::stdout().lock();
+                for line in compare(&previous, &solutions, theme) {
+                    writeln!(out, "{}", line)?;
+                }
+                previous = solutions;
+            }
+            Err(err) => warn!("{:#}", err),
+        }
+
+        info!("Watching for changes...");
+        while latest_change(&root) == changed {
+            std::thread::sleep(POLL);
+        }
+    }
+}
+
+// The newest modification time of any Rust source or manifest under `dir`,
+// leaving out build output and hidden directories
+fn latest_change(dir: &Path) -> Option<SystemTime> {
+    let mut latest = None;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let modified = if path.is_dir() {
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            latest_change(&path)
+        } else if name.ends_with(".rs") || name.ends_with(".toml") {
+            entry.metadata().and_then(|m| m.modified()).ok()
+        } else {
+            continue;
+        };
+        latest = latest.max(modified);
+    }
+    latest
+}
+
+// Rebuild the runner if anything changed, returning the binary to run; the
+// same profile as this one, so a release watch stays fast
+fn build(root: &Path) -> Result<PathBuf> {
+    let exe = std::env::current_exe().wrap_err("cannot find the running aoc binary")?;
+    let release = exe
+        .parent()
+        .is_some_and(|dir| dir.file_name().is_some_and(|name| name == "release"));
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let mut command = Command::new(cargo);
+    command.args(["build", "--quiet", "--package", "aoc"]);
+    if release {
+        command.arg("--release");
+    }
+    let status = command
+        .current_dir(root)
+        .status()
+        .wrap_err("failed to run cargo build")?;
+    if !status.success() {
+        bail!("cargo build failed");
+    }
+    Ok(exe)
+}
+
+// One run of the freshly built binary, with its results read back as JSON
+fn solve(exe: &Path, day: u32, input: &str, options: Options) -> Result<Vec<Solution>> {
+    let mut child = Command::new(exe)
+        .args(child_args(day, options))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .wrap_err("failed to run aoc")?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| eyre!("aoc has no stdin"))?
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("day {} failed", day);
+    }
+    serde_json::from_slice(&output.stdout).wrap_err("aoc printed something other than JSON results")
+}
+
+fn child_args(day: u32, options: Options) -> Vec<String> {
+    let mut args = vec![
+        String::from("run"),
+        String::from("--day"),
+        day.to_string(),
+        String::from("--format"),
+        String::from("json"),
+    ];
+    if options.empty == Empty::Lenient {
+        args.push(String::from("--allow-empty"));
+    }
+    if options.mode == ParseMode::Lenient {
+        args.push(String::from("--lenient"));
+    }
+    args
+}
+
+// A line per part: its answer, what it was if that changed, and its time
+// against the previous run's
+fn compare(previous: &[Solution], current: &[Solution], theme: &Theme) -> Vec<String> {
+    current
+        .iter()
+        .map(|solution| {
+            let before = previous
+                .iter()
+                .find(|p| p.day == solution.day && p.part == solution.part);
+            let answer = match before {
+                Some(before) if before.answer != solution.answer => format!(
+                    "{} -> {}",
+                    before.answer,
+                    theme.paint(&solution.answer, Style::Alert)
+                ),
+                _ => theme.paint(&solution.answer, Style::Highlight),
+            };
+            let elapsed = format!("{:?}", Duration::from_micros(solution.elapsed_us));
+            let change = match before {
+                Some(before) if before.elapsed_us > 0 => {
+                    let percent = (solution.elapsed_us as f64 - before.elapsed_us as f64)
+                        / before.elapsed_us as f64
+                        * 100.0;
+                    format!(" ({:+.1}%)", percent)
+                }
+                _ => String::new(),
+            };
+            format!(
+                "Day {} Part {}: {}  {}{}",
+                solution.day, solution.part, answer, elapsed, change
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ThemeName;
+
+    fn solution(part: u32, answer: &str, elapsed_us: u64) -> Solution {
+        Solution {
+            day: 2,
+            part,
+            answer: String::from(answer),
+            elapsed_us,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let theme = Theme::new(ThemeName::NoColor);
+        let first = [solution(1, "8", 200), solution(2, "2286", 400)];
+        assert_eq!(
+            compare(&[], &first, &theme),
+            ["Day 2 Part 1: 8  200µs", "Day 2 Part 2: 2286  400µs"]
+        );
+
+        let second = [solution(1, "8", 150), solution(2, "2287", 400)];
+        assert_eq!(
+            compare(&first, &second, &theme),
+            [
+                "Day 2 Part 1: 8  150µs (-25.0%)",
+                "Day 2 Part 2: 2286 -> 2287  400µs (+0.0%)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_child_args() {
+        assert_eq!(
+            child_args(3, Options::default()).join(" "),
+            "run --day 3 --format json"
+        );
+        let options = Options {
+            empty: Empty::Lenient,
+            mode: ParseMode::Lenient,
+        };
+        assert_eq!(
+            child_args(3, options).join(" "),
+            "run --day 3 --format json --allow-empty --lenient"
+        );
+    }
+
+    #[test]
+    fn test_latest_change() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        assert_eq!(latest_change(&root), None);
+
+        std::fs::write(root.join("target").join("built.rs"), "").unwrap();
+        std::fs::write(root.join("notes.txt"), "").unwrap();
+        assert_eq!(latest_change(&root), None);
+
+        std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+        let modified = std::fs::metadata(root.join("src").join("lib.rs"))
+            .and_then(|m| m.modified())
+            .unwrap();
+        assert_eq!(latest_change(&root), Some(modified));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+// Python bindings for the solvers.
+//
+// Build and install into the active virtualenv with:
+//   cd pyaoc && maturin develop --release
+//
+// >>> import pyaoc
+// >>> pyaoc.solve(2, 1, open("Day-2/input.txt").read())
+// >>> [g.power for g in pyaoc.parse_games(open("Day-2/input.txt").read())]
+use std::collections::BTreeMap;
+
+use bag_game::Color;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+fn to_py_err(err: color_eyre::Report) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+#[pyfunction]
+fn solve(day: u32, part: u32, input: &str) -> PyResult<String> {
+    aoc_solver::solve(day, part, input).map_err(to_py_err)
+}
+
+// Day 1: the calibration value of every line
+#[pyfunction]
+fn calibration_values(input: &str) -> PyResult<Vec<usize>> {
+    trebuchet::parse_data(lines(input)).map_err(to_py_err)
+}
+
+// Day 2: a parsed game, with each round as a {color: count} dict
+#[pyclass(name = "Game", get_all)]
+#[derive(Clone)]
+struct PyGame {
+    id: usize,
+    rounds: Vec<BTreeMap<String, usize>>,
+    feasible: bool,
+    // None when the power doesn't fit in a u64
+    power: Option<u64>,
+}
+
+#[pymethods]
+impl PyGame {
+    fn __repr__(&self) -> String {
+        format!(
+            "Game(id={}, rounds={}, feasible={}, power={})",
+            self.id,
+            self.rounds.len(),
+            if self.feasible { "True" } else { "False" },
+            self.power
+                .map_or(String::from("None"), |power| power.to_string())
+        )
+    }
+}
+
+impl From<&bag_game::Game> for PyGame {
+    fn from(game: &bag_game::Game) -> Self {
+        let rounds = game
+            .rounds
+            .iter()
+            .map(|round| {
+                let mut counts = BTreeMap::new();
+                round.0.iter().for_each(|color_count| {
+                    let color = match color_count.color {
+                        Color::Blue => "blue",
+                        Color::Green => "green",
+                        Color::Red => "red",
+                    };
+                    *counts.entry(color.to_string()).or_insert(0) += color_count.count;
+                });
+                counts
+            })
+            .collect();
+
+        PyGame {
+            id: game.id,
+            rounds,
+            feasible: bag_game::is_feasible(game),
+            power: bag_game::get_power(game),
+        }
+    }
+}
+
+#[pyfunction]
+fn parse_games(input: &str) -> PyResult<Vec<PyGame>> {
+    let games = bag_game::parse_data(lines(input)).map_err(to_py_err)?;
+    Ok(games.iter().map(PyGame::from).collect())
+}
+
+#[pymodule]
+fn pyaoc(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(calibration_values, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_games, m)?)?;
+    m.add_class::<PyGame>()?;
+    Ok(())
+}
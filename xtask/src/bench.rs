@@ -0,0 +1,209 @@
+use crate::input_path;
+use aoc_common::input::YEAR;
+use aoc_common::pretty::{duration, Align, Pretty};
+use clap::Args;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+pub struct BenchCompareArgs {
+    /// The baseline revision (anything git rev-parse understands)
+    pub base: String,
+
+    /// The revision to compare with the baseline
+    pub head: String,
+
+    /// Runs per revision; each part's fastest run is the one compared
+    #[arg(long, default_value_t = 5)]
+    pub runs: usize,
+}
+
+// one part of `aoc run --all --format json`
+#[derive(Debug, Deserialize)]
+struct Timing {
+    day: u32,
+    part: u32,
+    elapsed_us: u128,
+}
+
+// the fastest time for each (day, part), in microseconds
+type Times = BTreeMap<(u32, u32), u128>;
+
+pub fn run(root: &Path, args: BenchCompareArgs) -> Result<()> {
+    let scratch = std::env::temp_dir().join(format!("xtask-bench-{}", std::process::id()));
+    let times = bench(root, &scratch, "base", &args.base, args.runs).and_then(|base| {
+        let head = bench(root, &scratch, "head", &args.head, args.runs)?;
+        Ok((base, head))
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+
+    let (base, head) = times?;
+    print!("{}", table(&args.base, &base, &args.head, &head));
+    Ok(())
+}
+
+// Check `rev` out into a temporary worktree, build the runner there and time
+// every day on this tree's inputs, so both revisions solve the same ones
+fn bench(root: &Path, scratch: &Path, label: &str, rev: &str, runs: usize) -> Result<Times> {
+    let worktree = scratch.join(label);
+    let path = worktree.to_string_lossy();
+    git(root, &["worktree", "add", "--detach", &path, rev])?;
+    let times = build_and_time(root, scratch, &worktree, runs)
+        .wrap_err_with(|| format!("failed to benchmark {}", rev));
+    git(root, &["worktree", "remove", "--force", &path])?;
+    times
+}
+
+fn build_and_time(root: &Path, scratch: &Path, worktree: &Path, runs: usize) -> Result<Times> {
+    // both revisions share a target directory, so the second reuses the dependencies
+    let target = scratch.join("target");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .args(["build", "--release", "--package", "aoc"])
+        .current_dir(worktree)
+        .env("CARGO_TARGET_DIR", &target)
+        .status()
+        .wrap_err("failed to run cargo build")?;
+    if !status.success() {
+        bail!("cargo build failed");
+    }
+
+    // every revision reads Day-N/input.txt, even ones older than the input resolver
+    for day in 1..=25 {
+        let dir = worktree.join(format!("Day-{}", day));
+        if let Some(input) = input_path(root, YEAR, day).filter(|_| dir.exists()) {
+            std::fs::copy(&input, dir.join("input.txt"))?;
+        }
+    }
+
+    let mut times = Vec::new();
+    for _ in 0..runs {
+        let output = Command::new(target.join("release").join("aoc"))
+            .args(["run", "--all", "--format", "json"])
+            .current_dir(worktree)
+            // keep benchmark runs out of the real history, and quiet
+            .env("AOC_HISTORY_DB", scratch.join("history.db"))
+            .env_remove("AOC_WEBHOOK_URL")
+            .env_remove(aoc_common::input::INPUT_DIR_ENV)
+            .output()
+            .wrap_err("failed to run aoc")?;
+        if !output.status.success() {
+            bail!(
+                "aoc run --all failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        let run: Vec<Timing> = serde_json::from_slice(&output.stdout)
+            .wrap_err("aoc run --all printed something other than JSON results")?;
+        times.push(run);
+    }
+    Ok(fastest(times))
+}
+
+fn fastest(runs: Vec<Vec<Timing>>) -> Times {
+    let mut times = Times::new();
+    for timing in runs.into_iter().flatten() {
+        let best = times
+            .entry((timing.day, timing.part))
+            .or_insert(timing.elapsed_us);
+        *best = (*best).min(timing.elapsed_us);
+    }
+    times
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .wrap_err("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn micros(us: Option<&u128>) -> String {
+    us.map_or(String::from("-"), |&us| {
+        duration(Duration::from_micros(us as u64))
+    })
+}
+
+// A row per part either revision solved, with the change from base to head
+fn table(base_name: &str, base: &Times, head_name: &str, head: &Times) -> String {
+    let mut keys: Vec<(u32, u32)> = base.keys().chain(head.keys()).copied().collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut table = Pretty::new(&[
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        (base_name, Align::Right),
+        (head_name, Align::Right),
+        ("Change", Align::Right),
+    ]);
+    for key in keys {
+        let (before, after) = (base.get(&key), head.get(&key));
+        let change = match (before, after) {
+            (Some(&before), Some(&after)) if before > 0 => {
+                let percent = (after as f64 - before as f64) / before as f64 * 100.0;
+                format!("{:+.1}%", percent)
+            }
+            _ => String::from("-"),
+        };
+        table.row(vec![
+            key.0.to_string(),
+            key.1.to_string(),
+            micros(before),
+            micros(after),
+            change,
+        ]);
+    }
+    table.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u32, part: u32, elapsed_us: u128) -> Timing {
+        Timing {
+            day,
+            part,
+            elapsed_us,
+        }
+    }
+
+    #[test]
+    fn test_fastest() {
+        let runs = vec![
+            vec![timing(1, 1, 120), timing(1, 2, 300)],
+            vec![timing(1, 1, 100), timing(1, 2, 310)],
+        ];
+        let times = fastest(runs);
+        assert_eq!(times[&(1, 1)], 100);
+        assert_eq!(times[&(1, 2)], 300);
+        assert!(fastest(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_table() {
+        let base = Times::from([((1, 1), 200), ((1, 2), 400)]);
+        let head = Times::from([((1, 1), 150), ((2, 1), 90_000)]);
+        let table = table("main", &base, "HEAD", &head);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[1], "│ Day ┆ Part ┆  main ┆    HEAD ┆ Change │");
+        assert_eq!(lines[3], "│   1 ┆    1 ┆ 200µs ┆   150µs ┆ -25.0% │");
+        assert_eq!(lines[4], "│   1 ┆    2 ┆ 400µs ┆       - ┆      - │");
+        assert_eq!(lines[5], "│   2 ┆    1 ┆     - ┆ 90.00ms ┆      - │");
+    }
+}
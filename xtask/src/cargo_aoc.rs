@@ -0,0 +1,42 @@
+use crate::input_path;
+use aoc_common::input::YEAR;
+use color_eyre::eyre::{Result, WrapErr};
+use std::path::{Path, PathBuf};
+
+/// Copy each implemented day's input to where cargo-aoc reads it in
+/// `aoc-cargo`, so its runs and benchmarks see the same inputs as `aoc run`.
+pub fn run(root: &Path) -> Result<()> {
+    for day in aoc_solver::days_in(YEAR) {
+        let Some(from) = input_path(root, YEAR, day) else {
+            println!("day {}: no input, skipped", day);
+            continue;
+        };
+        let to = target(root, day);
+        std::fs::create_dir_all(to.parent().unwrap_or(root))?;
+        std::fs::copy(&from, &to)
+            .wrap_err_with(|| format!("failed to copy {} to {}", from.display(), to.display()))?;
+        println!("day {}: {}", day, to.display());
+    }
+    Ok(())
+}
+
+// cargo-aoc's own layout, relative to the crate it runs in
+fn target(root: &Path, day: u32) -> PathBuf {
+    root.join("aoc-cargo")
+        .join("input")
+        .join(YEAR.to_string())
+        .join(format!("day{}.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target() {
+        assert_eq!(
+            target(Path::new("/ws"), 2),
+            Path::new("/ws/aoc-cargo/input/2023/day2.txt")
+        );
+    }
+}
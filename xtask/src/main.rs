@@ -0,0 +1,64 @@
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::Result;
+use std::path::{Path, PathBuf};
+
+mod bench;
+mod cargo_aoc;
+mod stats;
+mod verify;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "xtask",
+    about = "Maintenance tasks for the workspace, run with `cargo xtask`"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Build two revisions in temporary worktrees and compare each day's solve times
+    BenchCompare(bench::BenchCompareArgs),
+    /// Copy the inputs to aoc-cargo/input, where cargo-aoc reads them
+    CargoAocInputs,
+    /// Show how long after unlocking each star was earned (from a private leaderboard, with
+    /// AOC_SESSION set) next to how long each part takes to run
+    Stats(stats::StatsArgs),
+    /// Solve every day against its stored answers and print each season's star matrix
+    Verify,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::BenchCompare(args) => bench::run(&root(), args),
+        Command::CargoAocInputs => cargo_aoc::run(&root()),
+        Command::Stats(args) => stats::run(&root(), args),
+        Command::Verify => verify::run(&root()),
+    }
+}
+
+// the workspace root, wherever cargo was run from
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+// where `day`'s input is under `root`, honouring AOC_INPUT_DIR like the day binaries
+fn input_path(root: &Path, year: u32, day: u32) -> Option<PathBuf> {
+    let dir = aoc_common::input::input_dir();
+    Some(
+        aoc_common::input::resolve_in(root, dir.as_deref(), year, day)
+            .ok()?
+            .path,
+    )
+}
+
+fn read_input(root: &Path, year: u32, day: u32) -> Option<String> {
+    std::fs::read_to_string(input_path(root, year, day)?).ok()
+}
@@ -0,0 +1,210 @@
+use crate::read_input;
+use aoc_common::input::{unlock, YEAR};
+use clap::Args;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// the adventofcode.com `session` cookie, copied from a logged-in browser
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const LEADERBOARD_ENV: &str = "AOC_LEADERBOARD";
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Private leaderboard to read star times from; everyone's own leaderboard has their user
+    /// id. Defaults to AOC_LEADERBOARD
+    #[arg(long)]
+    pub leaderboard: Option<u64>,
+
+    /// Whose stars to show; defaults to the leaderboard's owner
+    #[arg(long)]
+    pub member: Option<u64>,
+}
+
+// the parts of adventofcode.com's private leaderboard JSON used here
+#[derive(Debug, Deserialize)]
+struct Leaderboard {
+    owner_id: u64,
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    // keyed by day, then part
+    completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+pub fn run(root: &Path, args: StatsArgs) -> Result<()> {
+    let session = std::env::var(SESSION_ENV).wrap_err_with(|| {
+        format!(
+            "set {} to your adventofcode.com session cookie",
+            SESSION_ENV
+        )
+    })?;
+    let leaderboard = match args.leaderboard {
+        Some(id) => id,
+        None => std::env::var(LEADERBOARD_ENV)
+            .wrap_err_with(|| format!("pass --leaderboard or set {}", LEADERBOARD_ENV))?
+            .parse()
+            .wrap_err_with(|| format!("{} is not a leaderboard id", LEADERBOARD_ENV))?,
+    };
+
+    let leaderboard = fetch(&session, leaderboard)?;
+    let member = args.member.unwrap_or(leaderboard.owner_id);
+    let member = leaderboard
+        .members
+        .values()
+        .find(|m| m.id == member)
+        .ok_or_else(|| eyre!("member {} is not on the leaderboard", member))?;
+
+    print!("{}", table(&star_times(member), &runtimes(root)));
+    Ok(())
+}
+
+fn fetch(session: &str, leaderboard: u64) -> Result<Leaderboard> {
+    let url = format!(
+        "https://adventofcode.com/2023/leaderboard/private/view/{}.json",
+        leaderboard
+    );
+    reqwest::blocking::Client::new()
+        .get(&url)
+        .header(reqwest::header::COOKIE, format!("session={}", session))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json())
+        .wrap_err_with(|| format!("failed to fetch {}", url))
+}
+
+// When each (day, part) star was earned, as a Unix timestamp
+fn star_times(member: &Member) -> BTreeMap<(u32, u32), i64> {
+    member
+        .completion_day_level
+        .iter()
+        .flat_map(|(day, parts)| {
+            parts.iter().filter_map(move |(part, star)| {
+                Some(((day.parse().ok()?, part.parse().ok()?), star.get_star_ts))
+            })
+        })
+        .collect()
+}
+
+// How long each implemented part takes to solve from its input
+fn runtimes(root: &Path) -> BTreeMap<(u32, u32), Duration> {
+    let mut runtimes = BTreeMap::new();
+    for day in aoc_solver::days() {
+        let Some(input) = read_input(root, YEAR, day) else {
+            continue;
+        };
+        for part in aoc_solver::parts(day) {
+            let start = Instant::now();
+            if aoc_solver::solve(day, part, &input).is_ok() {
+                runtimes.insert((day, part), start.elapsed());
+            }
+        }
+    }
+    runtimes
+}
+
+// h:mm:ss from the puzzle unlocking to the star, or days past 24 hours
+fn since_unlock(day: u32, timestamp: i64) -> String {
+    let seconds = (timestamp - unlock(day)).max(0);
+    if seconds >= DAY {
+        return format!("{}d {}h", seconds / DAY, seconds % DAY / 3600);
+    }
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+// One row per part with a star or a local solution
+fn table(stars: &BTreeMap<(u32, u32), i64>, runtimes: &BTreeMap<(u32, u32), Duration>) -> String {
+    let mut keys: Vec<(u32, u32)> = stars.keys().chain(runtimes.keys()).copied().collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut out = format!(
+        "{:>3} {:>4}  {:>10}  {:>10}\n",
+        "Day", "Part", "Star", "Runtime"
+    );
+    for (day, part) in keys {
+        let star = stars
+            .get(&(day, part))
+            .map_or(String::from("-"), |&ts| since_unlock(day, ts));
+        let runtime = runtimes
+            .get(&(day, part))
+            .map_or(String::from("-"), |elapsed| format!("{:.2?}", elapsed));
+        let _ = writeln!(out, "{:>3} {:>4}  {:>10}  {:>10}", day, part, star, runtime);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 42,
+        "members": {
+            "42": {
+                "id": 42,
+                "name": "me",
+                "stars": 3,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407405, "star_index": 0},
+                        "2": {"get_star_ts": 1701410400, "star_index": 1}
+                    },
+                    "2": {"1": {"get_star_ts": 1701590400, "star_index": 2}}
+                }
+            },
+            "7": {"id": 7, "name": null, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn test_star_times() {
+        let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+        let owner = &leaderboard.members["42"];
+        assert_eq!(owner.id, leaderboard.owner_id);
+        let stars = star_times(owner);
+        assert_eq!(stars.len(), 3);
+        assert_eq!(stars[&(2, 1)], 1701590400);
+        assert!(star_times(&leaderboard.members["7"]).is_empty());
+    }
+
+    #[test]
+    fn test_since_unlock() {
+        assert_eq!(since_unlock(1, 1701407405), "0:10:05");
+        assert_eq!(since_unlock(1, 1701410400), "1:00:00");
+        // day 2 unlocked 2023-12-02T05:00:00Z, 27 hours earlier
+        assert_eq!(since_unlock(2, 1701590400), "1d 3h");
+    }
+
+    #[test]
+    fn test_table() {
+        let stars = BTreeMap::from([((1, 1), 1701407405), ((2, 1), 1701590400)]);
+        let runtimes = BTreeMap::from([((1, 1), Duration::from_micros(1500))]);
+        let table = table(&stars, &runtimes);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "Day Part        Star     Runtime");
+        assert_eq!(lines[1], "  1    1     0:10:05      1.50ms");
+        assert_eq!(lines[2], "  2    1       1d 3h           -");
+    }
+}
@@ -0,0 +1,240 @@
+use crate::read_input;
+use aoc_common::answer::{matches, Normalize, Value};
+use aoc_common::cancel::CancelToken;
+use aoc_common::input::{day_dir, YEAR};
+use color_eyre::eyre::{bail, Result};
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+const DAYS: u32 = 25;
+const PARTS: u32 = 2;
+
+/// Where one part of one day stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the stored one
+    Verified,
+    /// Answered, but there is no stored answer to check it against
+    Solved,
+    /// The solver failed, or disagrees with the stored answer
+    Failing(String),
+    /// Implemented, but there's no input to read
+    MissingInput,
+    /// Not implemented yet
+    Missing,
+}
+
+// what the day's answers.toml says about checking its answers
+#[derive(Debug, Default, Deserialize)]
+struct DayFile {
+    // answers that don't fit on one line of answers.txt, such as grids; they
+    // take precedence over it
+    part1: Option<String>,
+    part2: Option<String>,
+    // compare answers case-insensitively
+    #[serde(default)]
+    uppercase: bool,
+}
+
+impl Status {
+    fn symbol(&self) -> char {
+        match self {
+            Status::Verified => '*',
+            Status::Solved => '+',
+            Status::Failing(_) => 'x',
+            Status::MissingInput => '?',
+            Status::Missing => '.',
+        }
+    }
+}
+
+pub fn run(root: &Path) -> Result<()> {
+    // this year's matrix even before it has a day, then every earlier year
+    // with one, each under its year once there's more than one
+    let mut years = aoc_solver::years();
+    if !years.contains(&YEAR) {
+        years.push(YEAR);
+        years.sort_unstable();
+    }
+    let mut failing = 0;
+    for (i, &year) in years.iter().enumerate() {
+        let statuses: Vec<Vec<Status>> = (1..=DAYS)
+            .map(|day| {
+                (1..=PARTS)
+                    .map(|part| status(root, year, day, part))
+                    .collect()
+            })
+            .collect();
+        if years.len() > 1 {
+            println!("{}{}\n", if i > 0 { "\n" } else { "" }, year);
+        }
+        print!("{}", matrix(&statuses));
+        failing += statuses
+            .iter()
+            .flatten()
+            .filter(|status| matches!(status, Status::Failing(_)))
+            .count();
+    }
+    if failing > 0 {
+        bail!("{} parts failing", failing);
+    }
+    Ok(())
+}
+
+// Solve one part from the day's input and check it against the stored answer
+// in answers.toml or answers.txt in the day's directory, both in canonical form
+fn status(root: &Path, year: u32, day: u32, part: u32) -> Status {
+    if !aoc_solver::parts_in(year, day).contains(&part) {
+        return Status::Missing;
+    }
+    let dir = root.join(day_dir(year, day));
+    let Some(input) = read_input(root, year, day) else {
+        return Status::MissingInput;
+    };
+    let options = aoc_solver::Options::default();
+    let cancel = CancelToken::new();
+    let answer = match aoc_solver::solve_in(year, day, part, &input, options, &cancel) {
+        Ok(answer) => answer,
+        Err(err) => return Status::Failing(format!("{:#}", err)),
+    };
+    let file = std::fs::read_to_string(dir.join("answers.toml")).unwrap_or_default();
+    let file: DayFile = match toml::from_str(&file) {
+        Ok(file) => file,
+        Err(err) => return Status::Failing(format!("invalid answers.toml: {}", err)),
+    };
+    let answers = std::fs::read_to_string(dir.join("answers.txt")).unwrap_or_default();
+    let stored = match part {
+        1 => file.part1.as_deref(),
+        _ => file.part2.as_deref(),
+    };
+    let normalize = Normalize {
+        uppercase: file.uppercase,
+    };
+    match stored.or_else(|| stored_answer(&answers, part)) {
+        None => Status::Solved,
+        Some(expected) if matches(expected, &answer, normalize) => Status::Verified,
+        Some(expected) => Status::Failing(format!(
+            "expected {}, got {}",
+            Value::parse(expected, normalize),
+            Value::parse(&answer, normalize)
+        )),
+    }
+}
+
+// answers.txt holds one accepted answer per line, part 1 first; a blank line
+// leaves that part unchecked
+fn stored_answer(answers: &str, part: u32) -> Option<&str> {
+    answers
+        .lines()
+        .nth(part as usize - 1)
+        .map(str::trim)
+        .filter(|answer| !answer.is_empty())
+}
+
+// A row per day and a column per part, then a legend, the star count and
+// what went wrong with each failing part
+fn matrix(statuses: &[Vec<Status>]) -> String {
+    let mut out = String::from("Day  1 2\n");
+    for (day, parts) in (1..).zip(statuses) {
+        let symbols: Vec<String> = parts.iter().map(|s| s.symbol().to_string()).collect();
+        let _ = writeln!(out, "{:>3}  {}", day, symbols.join(" "));
+    }
+    out.push_str(
+        "\n* verified  + solved, no stored answer  x failing  ? missing input  . missing\n",
+    );
+
+    let verified = statuses
+        .iter()
+        .flatten()
+        .filter(|s| **s == Status::Verified)
+        .count();
+    let solved = statuses
+        .iter()
+        .flatten()
+        .filter(|s| **s == Status::Solved)
+        .count();
+    let _ = writeln!(
+        out,
+        "{}/{} stars verified, {} more solved",
+        verified,
+        statuses.len() * PARTS as usize,
+        solved
+    );
+    for (day, parts) in (1..).zip(statuses) {
+        for (part, status) in (1..).zip(parts) {
+            if let Status::Failing(reason) = status {
+                let _ = writeln!(out, "Day {} part {}: {}", day, part, reason);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_answer() {
+        assert_eq!(stored_answer("142\n281\n", 1), Some("142"));
+        assert_eq!(stored_answer("142\n281\n", 2), Some("281"));
+        assert_eq!(stored_answer("\n281\n", 1), None);
+        assert_eq!(stored_answer("142", 2), None);
+    }
+
+    #[test]
+    fn test_status() {
+        let root = std::env::temp_dir().join(format!("xtask-verify-{}", std::process::id()));
+        let day = root.join("Day-2");
+        std::fs::create_dir_all(&day).unwrap();
+        assert_eq!(status(&root, YEAR, 2, 1), Status::MissingInput);
+        assert_eq!(status(&root, YEAR, 25, 1), Status::Missing);
+
+        std::fs::copy("../Day-2/test-1.txt", day.join("input.txt")).unwrap();
+        assert_eq!(status(&root, YEAR, 2, 1), Status::Solved);
+        std::fs::write(day.join("answers.txt"), "8\n2287\n").unwrap();
+        assert_eq!(status(&root, YEAR, 2, 1), Status::Verified);
+        assert_eq!(
+            status(&root, YEAR, 2, 2),
+            Status::Failing(String::from("expected 2287, got 2286"))
+        );
+
+        // answers.toml wins over answers.txt
+        std::fs::write(day.join("answers.toml"), "part2 = \"2286\"\n").unwrap();
+        assert_eq!(status(&root, YEAR, 2, 2), Status::Verified);
+        std::fs::write(day.join("answers.toml"), "part2 = 2286\n").unwrap();
+        assert!(matches!(status(&root, YEAR, 2, 2), Status::Failing(_)));
+        std::fs::remove_file(day.join("answers.toml")).unwrap();
+
+        std::fs::write(day.join("input.txt"), "Game one").unwrap();
+        assert!(matches!(status(&root, YEAR, 2, 1), Status::Failing(_)));
+
+        // an earlier year's day keeps its input and answers in its own directory
+        let day = root.join("2022").join("Day-1");
+        std::fs::create_dir_all(&day).unwrap();
+        assert_eq!(status(&root, 2022, 1, 1), Status::MissingInput);
+        std::fs::copy("../2022/Day-1/test-1.txt", day.join("input.txt")).unwrap();
+        std::fs::write(day.join("answers.txt"), "24000\n45000\n").unwrap();
+        assert_eq!(status(&root, 2022, 1, 2), Status::Verified);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_matrix() {
+        let mut statuses = vec![vec![Status::Missing, Status::Missing]; 25];
+        statuses[0] = vec![Status::Verified, Status::Solved];
+        statuses[1] = vec![
+            Status::MissingInput,
+            Status::Failing(String::from("expected 1, got 2")),
+        ];
+        let matrix = matrix(&statuses);
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines[0], "Day  1 2");
+        assert_eq!(lines[1], "  1  * +");
+        assert_eq!(lines[2], "  2  ? x");
+        assert_eq!(lines[25], " 25  . .");
+        assert!(matrix.contains("1/50 stars verified, 1 more solved\n"));
+        assert!(matrix.ends_with("Day 2 part 2: expected 1, got 2\n"));
+    }
+}